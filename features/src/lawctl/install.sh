@@ -0,0 +1,14 @@
+#!/bin/sh
+# Dev container feature: installs the latest lawctl release.
+# Runs as root at image build time, so binaries land in /usr/local/bin.
+#
+# Hook registration and policy checks happen later, in the
+# postCreateCommand/postStartCommand that `lawctl init --devcontainer` adds.
+
+set -e
+
+if ! command -v curl >/dev/null 2>&1; then
+    apt-get update && apt-get install -y --no-install-recommends curl ca-certificates
+fi
+
+curl -fsSL https://lawctl.dev/install | sh
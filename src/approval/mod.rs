@@ -1,3 +1,5 @@
+pub mod plan;
+pub mod reviewer;
 pub mod terminal;
 pub mod types;
 
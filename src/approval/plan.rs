@@ -0,0 +1,206 @@
+//! Grouped approval of a whole plan.
+//!
+//! When Claude Code leaves plan mode, the hook pulls the files and shell
+//! commands out of the plan and asks the human about them once. An approved
+//! plan is logged to the session's audit log as a `plan_approval` entry, and
+//! for the rest of the session `require_approval` actions it covers go ahead
+//! without another prompt. Only what the plan names is covered: a file it
+//! lists, or a command exactly as it was written. Denials still apply.
+//! Under a lawctl gateway the hook hands the plan to the gateway, which
+//! asks its own approval handler and keeps the plan for the session.
+//!
+//! Files are the inline code spans that look like paths (`src/main.rs`);
+//! commands are the lines of shell code blocks.
+
+use crate::audit::LogEntry;
+use crate::policy::types::Action;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Who `approved_by` names on a plan's entry and the actions it covers.
+pub const APPROVED_BY: &str = "plan";
+
+/// How many files or commands the approval prompt lists before summing up.
+const SHOWN_ITEMS: usize = 15;
+
+/// Code block languages whose lines are commands.
+const SHELL_FENCES: &[&str] = &["", "bash", "sh", "shell", "zsh", "console"];
+
+/// The files and commands a plan says it will touch and run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Plan {
+    pub files: BTreeSet<String>,
+    pub commands: BTreeSet<String>,
+}
+
+impl Plan {
+    /// Pull the files and commands out of a plan written in markdown.
+    pub fn parse(markdown: &str) -> Self {
+        let mut plan = Self::default();
+        // Inside a code block: whether its lines are shell commands
+        let mut fence: Option<bool> = None;
+        for line in markdown.lines() {
+            let trimmed = line.trim();
+            if let Some(info) = trimmed.strip_prefix("```") {
+                fence = match fence {
+                    Some(_) => None,
+                    None => Some(SHELL_FENCES.contains(&info.trim())),
+                };
+                continue;
+            }
+            match fence {
+                Some(true) => {
+                    let command = trimmed.strip_prefix("$ ").unwrap_or(trimmed);
+                    if !command.is_empty() && !command.starts_with('#') {
+                        plan.commands.insert(normalize_command(command));
+                    }
+                }
+                Some(false) => {}
+                None => {
+                    for span in line.split('`').skip(1).step_by(2) {
+                        if looks_like_path(span) {
+                            plan.files.insert(span.trim_start_matches("./").to_string());
+                        }
+                    }
+                }
+            }
+        }
+        plan
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.commands.is_empty()
+    }
+
+    /// What the approval prompt shows.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.files.is_empty() {
+            parts.push(format!("Files: {}", listed(&self.files, ", ")));
+        }
+        if !self.commands.is_empty() {
+            parts.push(format!("Commands: {}", listed(&self.commands, "; ")));
+        }
+        parts.join("\n")
+    }
+
+    /// Whether the plan runs exactly this command.
+    pub fn covers_command(&self, command: &str) -> bool {
+        self.commands.contains(&normalize_command(command))
+    }
+
+    /// Whether the plan lists every one of these files. Paths are compared
+    /// relative to `workspace`.
+    pub fn covers_files<'a>(
+        &self,
+        mut targets: impl Iterator<Item = &'a str>,
+        workspace: &Path,
+    ) -> bool {
+        let relative = |path: &'a str| {
+            Path::new(path)
+                .strip_prefix(workspace)
+                .map(|p| p.to_string_lossy().into_owned())
+                .unwrap_or_else(|_| path.trim_start_matches("./").to_string())
+        };
+        let mut any = false;
+        let all = targets.all(|target| {
+            any = true;
+            self.files.contains(&relative(target))
+        });
+        any && all
+    }
+
+    /// The plan most recently approved in a session, from its audit log.
+    pub fn approved_in(entries: &[LogEntry]) -> Option<Self> {
+        entries
+            .iter()
+            .rev()
+            .filter(|entry| {
+                entry.action == Action::PlanApproval
+                    && entry.approved_by.as_deref() == Some(APPROVED_BY)
+            })
+            .find_map(|entry| serde_json::from_str(entry.diff.as_deref()?).ok())
+    }
+}
+
+/// Commands compare with runs of whitespace collapsed.
+fn normalize_command(command: &str) -> String {
+    command.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// An inline code span naming a file: one word of path characters with a
+/// directory or an extension, e.g. `src/main.rs` or `Cargo.toml`.
+fn looks_like_path(span: &str) -> bool {
+    let valid = !span.is_empty()
+        && !span.starts_with('-')
+        && span
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '.' | '_' | '-'));
+    let name = span.rsplit('/').next().unwrap_or(span);
+    let has_extension = name.rsplit_once('.').is_some_and(|(stem, ext)| {
+        !stem.is_empty() && ext.chars().any(|c| c.is_ascii_alphabetic())
+    });
+    valid && (span.contains('/') || has_extension)
+}
+
+/// The first few items, and how many more there are.
+fn listed(items: &BTreeSet<String>, separator: &str) -> String {
+    let shown: Vec<&str> = items.iter().take(SHOWN_ITEMS).map(String::as_str).collect();
+    let mut text = shown.join(separator);
+    if items.len() > SHOWN_ITEMS {
+        text.push_str(&format!(" and {} more", items.len() - SHOWN_ITEMS));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAN: &str = r#"
+## Plan
+
+1. Add the parser in `src/plan.rs` and register it in `./src/lib.rs`
+2. Document it in `README.md`; call `Plan::parse()` from `main`
+3. Run the tests:
+
+```bash
+$ cargo test   --workspace
+# then
+cargo fmt
+```
+
+```rust
+let x = foo.bar();
+```
+"#;
+
+    #[test]
+    fn test_parse_plan() {
+        let plan = Plan::parse(PLAN);
+        assert_eq!(
+            plan.files.iter().collect::<Vec<_>>(),
+            ["README.md", "src/lib.rs", "src/plan.rs"]
+        );
+        assert_eq!(
+            plan.commands.iter().collect::<Vec<_>>(),
+            ["cargo fmt", "cargo test --workspace"]
+        );
+        assert!(Plan::parse("Just think about it.").is_empty());
+    }
+
+    #[test]
+    fn test_plan_covers_only_what_it_names() {
+        let plan = Plan::parse(PLAN);
+        let workspace = Path::new("/work");
+        assert!(plan.covers_files(["/work/src/plan.rs"].into_iter(), workspace));
+        assert!(plan.covers_files(["README.md", "src/lib.rs"].into_iter(), workspace));
+        assert!(!plan.covers_files(["/work/src/plan.rs", "/work/.env"].into_iter(), workspace));
+        assert!(!plan.covers_files(std::iter::empty(), workspace));
+
+        assert!(plan.covers_command("cargo test --workspace"));
+        assert!(!plan.covers_command("cargo test --workspace && git push"));
+        assert!(!plan.covers_command("cargo"));
+    }
+}
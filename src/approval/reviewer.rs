@@ -0,0 +1,222 @@
+//! An optional second opinion on actions waiting for approval.
+//!
+//! With a `reviewer:` in the policy, the command or diff behind each
+//! approval prompt is sent — redacted — to an LLM endpoint speaking the
+//! OpenAI chat-completions format, and its risk assessment is shown in the
+//! prompt. It only informs: the human still decides, and a reviewer that
+//! fails or is slow just leaves a note.
+//!
+//! ```yaml
+//! reviewer:
+//!   url: https://api.openai.com/v1/chat/completions
+//!   model: gpt-4o-mini
+//!   api_key_env: OPENAI_API_KEY
+//! ```
+//!
+//! Requests go through `curl`, with the key passed on stdin rather than
+//! the command line.
+
+use crate::policy::types::Action;
+use crate::utils::redact::redact;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// How long a review may take when the policy doesn't say.
+pub const DEFAULT_TIMEOUT_MS: u64 = 15_000;
+
+/// Longest payload sent for review; the rest is cut off.
+const MAX_PAYLOAD_CHARS: usize = 20_000;
+
+/// Longest assessment shown in the prompt.
+const MAX_REVIEW_CHARS: usize = 600;
+
+const INSTRUCTIONS: &str = "You review actions an AI coding agent wants to take, \
+for the human who must approve or deny them. Start with one word, LOW, MEDIUM or HIGH, \
+for the risk, then explain in at most three short sentences what the action does and \
+anything that looks dangerous. Don't recommend approving or denying.";
+
+/// Where approval prompts get their second opinion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Reviewer {
+    /// Chat-completions endpoint
+    pub url: String,
+    /// Model to ask, for endpoints that need one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Environment variable holding the API key, sent as a bearer token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// Milliseconds to wait for an answer; defaults to `DEFAULT_TIMEOUT_MS`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+impl Reviewer {
+    /// Ask for an assessment of an action waiting for approval.
+    pub fn review(
+        &self,
+        action: &Action,
+        target: &str,
+        payload: Option<&str>,
+        reason: &str,
+    ) -> Result<String> {
+        let key = match &self.api_key_env {
+            Some(var) => Some(
+                std::env::var(var)
+                    .with_context(|| format!("Reviewer API key variable {} isn't set", var))?,
+            ),
+            None => None,
+        };
+        let body = self.request_body(&prompt(action, target, payload, reason));
+        let config = curl_config(&self.url, key.as_deref(), &body.to_string());
+
+        let timeout = self.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS);
+        let mut child = Command::new("curl")
+            .args(["-sS", "--fail", "--max-time"])
+            .arg(format!("{:.3}", timeout as f64 / 1000.0))
+            .args(["-K", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run curl for the reviewer — is it installed?")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(config.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            bail!(
+                "Reviewer request failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        parse_response(&output.stdout)
+    }
+
+    fn request_body(&self, prompt: &str) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "messages": [
+                { "role": "system", "content": INSTRUCTIONS },
+                { "role": "user", "content": prompt },
+            ],
+        });
+        if let Some(model) = &self.model {
+            body["model"] = model.clone().into();
+        }
+        body
+    }
+}
+
+/// What the reviewer is told about the action, secrets masked.
+fn prompt(action: &Action, target: &str, payload: Option<&str>, reason: &str) -> String {
+    let mut prompt = format!(
+        "Action: {}\nTarget: {}\nWhy approval is needed: {}\n",
+        action, target, reason
+    );
+    if let Some(payload) = payload {
+        let total = payload.chars().count();
+        let kept: String = payload.chars().take(MAX_PAYLOAD_CHARS).collect();
+        prompt.push_str("\nPayload (command or diff):\n");
+        prompt.push_str(&kept);
+        if total > MAX_PAYLOAD_CHARS {
+            prompt.push_str(&format!("\n... ({} chars total, cut off)", total));
+        }
+    }
+    redact(&prompt)
+}
+
+/// A curl config (read with `-K -`) posting `body` to `url`.
+fn curl_config(url: &str, key: Option<&str>, body: &str) -> String {
+    let mut config = format!(
+        "url = {}\nheader = \"Content-Type: application/json\"\n",
+        quote(url)
+    );
+    if let Some(key) = key {
+        config.push_str(&format!(
+            "header = {}\n",
+            quote(&format!("Authorization: Bearer {}", key))
+        ));
+    }
+    config.push_str(&format!("data-binary = {}\n", quote(body)));
+    config
+}
+
+/// A double-quoted curl config value.
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// The assessment in a chat-completions response, tidied for the prompt.
+fn parse_response(body: &[u8]) -> Result<String> {
+    let response: serde_json::Value =
+        serde_json::from_slice(body).context("Reviewer sent invalid JSON")?;
+    let content = response["choices"][0]["message"]["content"]
+        .as_str()
+        .context("Reviewer response has no message")?;
+    let content = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if content.is_empty() {
+        bail!("Reviewer gave an empty answer");
+    }
+    if content.chars().count() <= MAX_REVIEW_CHARS {
+        return Ok(content);
+    }
+    let kept: String = content.chars().take(MAX_REVIEW_CHARS - 3).collect();
+    Ok(format!("{}...", kept))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_is_redacted_and_capped() {
+        let payload = format!(
+            "export API_KEY=sk-abcdefghijklmnopqrstuvwx\n{}",
+            "x".repeat(MAX_PAYLOAD_CHARS)
+        );
+        let prompt = prompt(&Action::RunCmd, "shell", Some(&payload), "Needs review");
+        assert!(prompt.contains("Action: run_cmd"));
+        assert!(!prompt.contains("sk-abcdefghijklmnopqrstuvwx"));
+        assert!(prompt.contains("cut off"));
+    }
+
+    #[test]
+    fn test_curl_config() {
+        let config = curl_config(
+            "https://llm.example/v1/chat/completions",
+            Some("k3y"),
+            r#"{"content":"a \"quoted\"\nline"}"#,
+        );
+        assert!(config.contains("url = \"https://llm.example/v1/chat/completions\"\n"));
+        assert!(config.contains("header = \"Authorization: Bearer k3y\"\n"));
+        assert!(config.contains(r#"data-binary = "{\"content\":\"a \\\"quoted\\\"\\nline\"}""#));
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let body =
+            br#"{"choices":[{"message":{"content":"HIGH\n\nPipes a remote script to sh."}}]}"#;
+        assert_eq!(
+            parse_response(body).unwrap(),
+            "HIGH Pipes a remote script to sh."
+        );
+        assert!(parse_response(br#"{"error":"quota"}"#).is_err());
+        assert!(parse_response(b"not json").is_err());
+    }
+}
@@ -9,6 +9,8 @@
 
 use crate::approval::types::{ApprovalRequest, ApprovalResponse};
 use crate::approval::ApprovalHandler;
+use crate::i18n::t;
+use crate::utils::term;
 use anyhow::Result;
 use async_trait::async_trait;
 use crossterm::{
@@ -17,8 +19,10 @@ use crossterm::{
     style::{Color, Print, ResetColor, SetForegroundColor},
     terminal,
 };
-use std::io::Write;
-use std::time::Duration;
+use std::io::{BufRead, Write};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 /// Terminal-based approval handler.
 /// Shows a prompt in the terminal and waits for the user to press A/D.
@@ -59,15 +63,28 @@ impl ApprovalHandler for TerminalApproval {
 
 /// Display the approval prompt and wait for user input.
 fn show_approval_prompt(request: &ApprovalRequest, timeout: Duration) -> Result<ApprovalResponse> {
+    if term::is_plain() {
+        return show_plain_prompt(request, timeout);
+    }
     let mut stdout = std::io::stdout();
 
     // Draw the approval box
+    let (approve, deny, view) = (
+        t("approval.approve"),
+        t("approval.deny"),
+        t("approval.view"),
+    );
+    let buttons = format!("  {}    {}    {}", approve, deny, view);
     execute!(
         stdout,
         Print("\n"),
         SetForegroundColor(Color::Yellow),
         Print("╔══════════════════════════════════════════════════════════╗\n"),
-        Print("║              ⚠  APPROVAL REQUIRED                      ║\n"),
+        Print(row(&format!(
+            "{:^width$}",
+            format!("⚠  {}", t("approval.title")),
+            width = BOX_WIDTH
+        ))),
         Print("╠══════════════════════════════════════════════════════════╣\n"),
         ResetColor,
     )?;
@@ -75,27 +92,18 @@ fn show_approval_prompt(request: &ApprovalRequest, timeout: Duration) -> Result<
     execute!(
         stdout,
         SetForegroundColor(Color::White),
-        Print(format!(
-            "║  Action:  {:<47}║\n",
-            format!("{}", request.action)
-        )),
-        Print(format!(
-            "║  Target:  {:<47}║\n",
-            truncate(&request.target, 47)
-        )),
+        Print(field(t("approval.action"), &request.action.to_string())),
+        Print(field(t("approval.target"), &request.target)),
     )?;
 
     if let Some(ref preview) = request.payload_preview {
         // Show first few lines of the payload
-        execute!(
-            stdout,
-            Print("║  Preview:                                                ║\n"),
-        )?;
+        execute!(stdout, Print(row(&format!("  {}", t("approval.preview")))))?;
         for line in preview.lines().take(5) {
             execute!(
                 stdout,
                 SetForegroundColor(Color::DarkGrey),
-                Print(format!("║    {:<54}║\n", truncate(line, 54))),
+                Print(row(&format!("    {}", truncate(line, BOX_WIDTH - 4)))),
             )?;
         }
     }
@@ -103,20 +111,29 @@ fn show_approval_prompt(request: &ApprovalRequest, timeout: Duration) -> Result<
     execute!(
         stdout,
         SetForegroundColor(Color::Yellow),
-        Print("║                                                          ║\n"),
-        Print(format!(
-            "║  Reason: {:<48}║\n",
-            truncate(&request.reason, 48)
-        )),
-        Print("║                                                          ║\n"),
+        Print(row("")),
+        Print(field(t("approval.reason"), &request.reason)),
+    )?;
+    if let Some(ref review) = request.review {
+        execute!(stdout, Print(row(&format!("  {}", t("approval.review")))))?;
+        for line in wrap(review, BOX_WIDTH - 4).iter().take(REVIEW_ROWS) {
+            execute!(stdout, Print(row(&format!("    {}", line))))?;
+        }
+    }
+    execute!(
+        stdout,
+        Print(row("")),
         SetForegroundColor(Color::Green),
-        Print("║  [A] Approve    "),
+        Print(format!("║  {}    ", approve)),
         SetForegroundColor(Color::Red),
-        Print("[D] Deny    "),
+        Print(format!("{}    ", deny)),
         SetForegroundColor(Color::Blue),
-        Print("[V] View full payload         "),
+        Print(view),
         SetForegroundColor(Color::Yellow),
-        Print("║\n"),
+        Print(format!(
+            "{}║\n",
+            " ".repeat(BOX_WIDTH.saturating_sub(buttons.chars().count()))
+        )),
         Print("╚══════════════════════════════════════════════════════════╝\n"),
         ResetColor,
     )?;
@@ -148,13 +165,13 @@ fn show_approval_prompt(request: &ApprovalRequest, timeout: Duration) -> Result<
                             execute!(
                                 stdout,
                                 SetForegroundColor(Color::DarkGrey),
-                                Print("\n--- Full payload ---\n"),
+                                Print(format!("\n{}\n", t("approval.payload_start"))),
                                 Print(preview),
-                                Print("\n--- End payload ---\n"),
+                                Print(format!("\n{}\n", t("approval.payload_end"))),
                                 ResetColor,
                             )?;
                         } else {
-                            execute!(stdout, Print("\n(no payload)\n"))?;
+                            execute!(stdout, Print(format!("\n{}\n", t("approval.no_payload"))))?;
                         }
                         stdout.flush()?;
                         terminal::enable_raw_mode()?;
@@ -179,14 +196,14 @@ fn show_approval_prompt(request: &ApprovalRequest, timeout: Duration) -> Result<
         execute!(
             stdout,
             SetForegroundColor(Color::Green),
-            Print("\n  ✓ Approved\n\n"),
+            Print(format!("\n  ✓ {}\n\n", t("approval.approved"))),
             ResetColor,
         )?;
     } else {
         execute!(
             stdout,
             SetForegroundColor(Color::Red),
-            Print("\n  ✗ Denied\n\n"),
+            Print(format!("\n  ✗ {}\n\n", t("approval.denied"))),
             ResetColor,
         )?;
     }
@@ -195,11 +212,152 @@ fn show_approval_prompt(request: &ApprovalRequest, timeout: Duration) -> Result<
     Ok(result)
 }
 
+/// The approval prompt for screen readers and dumb terminals: plain lines,
+/// no colour or box drawing, and a typed answer instead of a raw keypress.
+fn show_plain_prompt(request: &ApprovalRequest, timeout: Duration) -> Result<ApprovalResponse> {
+    let mut stdout = std::io::stdout();
+    writeln!(stdout)?;
+    writeln!(stdout, "{}", t("approval.title"))?;
+    writeln!(stdout, "{} {}", t("approval.action"), request.action)?;
+    writeln!(stdout, "{} {}", t("approval.target"), request.target)?;
+    if let Some(ref preview) = request.payload_preview {
+        writeln!(stdout, "{}", t("approval.preview"))?;
+        for line in preview.lines().take(5) {
+            writeln!(stdout, "  {}", line)?;
+        }
+    }
+    writeln!(stdout, "{} {}", t("approval.reason"), request.reason)?;
+    if let Some(ref review) = request.review {
+        writeln!(stdout, "{} {}", t("approval.review"), review)?;
+    }
+
+    let deadline = Instant::now() + timeout;
+    let approved = loop {
+        write!(stdout, "{} ", t("approval.plain_question"))?;
+        stdout.flush()?;
+        let Some(answer) = read_line_until(deadline) else {
+            // Timeout or end of input — deny by default
+            writeln!(stdout)?;
+            break false;
+        };
+        match parse_answer(&answer) {
+            Some(Answer::Approve) => break true,
+            Some(Answer::Deny) => break false,
+            Some(Answer::View) => {
+                writeln!(stdout, "{}", t("approval.payload_start"))?;
+                match request.payload_preview {
+                    Some(ref preview) => writeln!(stdout, "{}", preview)?,
+                    None => writeln!(stdout, "{}", t("approval.no_payload"))?,
+                }
+                writeln!(stdout, "{}", t("approval.payload_end"))?;
+            }
+            None => writeln!(stdout, "{}", t("approval.plain_retry"))?,
+        }
+    };
+
+    let decision = if approved {
+        t("approval.approved")
+    } else {
+        t("approval.denied")
+    };
+    writeln!(stdout, "{}", decision)?;
+    writeln!(stdout)?;
+    stdout.flush()?;
+
+    Ok(ApprovalResponse {
+        approved,
+        approved_by: approved.then(|| "terminal".to_string()),
+    })
+}
+
+#[derive(Debug, PartialEq)]
+enum Answer {
+    Approve,
+    Deny,
+    View,
+}
+
+/// A typed answer. Empty means no, matching the `[y/N]` default; the
+/// single-key letters (A/D/V) work too.
+fn parse_answer(input: &str) -> Option<Answer> {
+    match input.trim().to_lowercase().as_str() {
+        "y" | "yes" | "s" | "si" | "sí" | "a" | "approve" => Some(Answer::Approve),
+        "" | "n" | "no" | "d" | "deny" => Some(Answer::Deny),
+        "v" | "view" => Some(Answer::View),
+        _ => None,
+    }
+}
+
+/// Next line from stdin, or None on timeout or end of input.
+///
+/// Stdin is read on one long-lived thread: a read that outlives a timed-out
+/// prompt then answers the next prompt instead of being lost.
+fn read_line_until(deadline: Instant) -> Option<String> {
+    static LINES: OnceLock<Mutex<Receiver<String>>> = OnceLock::new();
+    let lines = LINES.get_or_init(|| {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in std::io::stdin().lock().lines() {
+                let Ok(line) = line else { break };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Mutex::new(rx)
+    });
+    let lines = lines.lock().ok()?;
+    let wait = deadline.saturating_duration_since(Instant::now());
+    lines.recv_timeout(wait).ok()
+}
+
+/// Inner width of the approval box, in characters.
+const BOX_WIDTH: usize = 58;
+
+/// Most lines of a reviewer's assessment shown in the box.
+const REVIEW_ROWS: usize = 6;
+
+/// One line of the box, padded to its width.
+fn row(content: &str) -> String {
+    let padding = BOX_WIDTH.saturating_sub(content.chars().count());
+    format!("║{}{}║\n", content, " ".repeat(padding))
+}
+
+/// A `Label:  value` line. Labels are translated, so the value column
+/// follows the label rather than sitting at a fixed offset.
+fn field(label: &str, value: &str) -> String {
+    let label = format!("  {:<9}", label);
+    let room = BOX_WIDTH.saturating_sub(label.chars().count() + 1);
+    row(&format!("{} {}", label, truncate(value, room)))
+}
+
+/// `text` broken into lines of at most `width` characters at spaces;
+/// longer words are cut.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let word = truncate(word, width);
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
 fn truncate(s: &str, max: usize) -> String {
-    if s.len() <= max {
+    if s.chars().count() <= max {
         s.to_string()
     } else {
-        format!("{}...", &s[..max.saturating_sub(3)])
+        let kept: String = s.chars().take(max.saturating_sub(3)).collect();
+        format!("{}...", kept)
     }
 }
 
@@ -230,3 +388,37 @@ impl ApprovalHandler for AutoDeny {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answer() {
+        assert_eq!(parse_answer("y\n"), Some(Answer::Approve));
+        assert_eq!(parse_answer(" Yes "), Some(Answer::Approve));
+        assert_eq!(parse_answer("sí"), Some(Answer::Approve));
+        assert_eq!(parse_answer(""), Some(Answer::Deny));
+        assert_eq!(parse_answer("N"), Some(Answer::Deny));
+        assert_eq!(parse_answer("v"), Some(Answer::View));
+        assert_eq!(parse_answer("maybe"), None);
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            wrap("HIGH  pipes a script to sh", 12),
+            ["HIGH pipes a", "script to sh"]
+        );
+        assert_eq!(wrap(&"y".repeat(20), 10), ["yyyyyyy..."]);
+        assert!(wrap("", 10).is_empty());
+    }
+
+    #[test]
+    fn test_box_rows_keep_their_width() {
+        let long = "x".repeat(200);
+        for line in [row(""), field("Target:", &long), field("Destino:", "é")] {
+            assert_eq!(line.trim_end().chars().count(), BOX_WIDTH + 2);
+        }
+    }
+}
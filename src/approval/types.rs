@@ -13,6 +13,8 @@ pub struct ApprovalRequest {
     pub payload_preview: Option<String>,
     /// Why approval is needed (from the policy rule)
     pub reason: String,
+    /// The policy's reviewer's risk assessment, when it has one
+    pub review: Option<String>,
 }
 
 /// Response from the human reviewer.
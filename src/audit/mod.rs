@@ -1,7 +1,11 @@
+pub mod baseline;
 pub mod logger;
+pub mod projects;
 pub mod reader;
 pub mod types;
+pub mod watch;
 
 pub use logger::AuditLogger;
+pub use projects::ProjectIndex;
 pub use reader::AuditReader;
 pub use types::*;
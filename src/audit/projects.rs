@@ -0,0 +1,103 @@
+//! Which project each log directory belongs to.
+//!
+//! Session logs are kept per project, in `logs/<key>/` where the key is a
+//! hash of the workspace path ([`locations::workspace_key`]), so nothing
+//! about the project leaks into directory names. `logs/projects.json` maps
+//! the keys back to the workspaces. Logs from before namespacing sit
+//! directly in `logs/` and belong to no project.
+
+use crate::utils::locations;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The index file, in the log directory.
+pub const INDEX_FILE: &str = "projects.json";
+
+/// Project key → workspace path.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectIndex {
+    pub projects: BTreeMap<String, PathBuf>,
+}
+
+impl ProjectIndex {
+    /// The index in `log_dir`; empty if there isn't one (or it's unreadable).
+    pub fn load(log_dir: &Path) -> Self {
+        std::fs::read_to_string(log_dir.join(INDEX_FILE))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Record `workspace` under `key`, unless it already is.
+    pub fn register(log_dir: &Path, key: &str, workspace: &Path) -> Result<()> {
+        let mut index = Self::load(log_dir);
+        let workspace = workspace
+            .canonicalize()
+            .unwrap_or_else(|_| workspace.to_path_buf());
+        if index.projects.get(key) == Some(&workspace) {
+            return Ok(());
+        }
+        index.projects.insert(key.to_string(), workspace);
+        // Write-then-rename so a concurrent reader never sees half a file
+        let path = log_dir.join(INDEX_FILE);
+        let tmp = log_dir.join(format!("{}.{}.tmp", INDEX_FILE, std::process::id()));
+        std::fs::write(&tmp, serde_json::to_string_pretty(&index)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &path).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// The workspace a project key belongs to.
+    pub fn workspace(&self, key: &str) -> Option<&Path> {
+        self.projects.get(key).map(PathBuf::as_path)
+    }
+}
+
+/// The log directory for `workspace` under the log root.
+pub fn project_log_dir(log_dir: &Path, workspace: &Path) -> PathBuf {
+    log_dir.join(locations::workspace_key(workspace))
+}
+
+/// The project a command run from `dir` is about: the nearest directory
+/// with a policy file, or `dir` itself.
+pub fn project_root(dir: &Path) -> PathBuf {
+    dir.ancestors()
+        .find(|d| crate::policy::parser::policy_file_in(d).is_some())
+        .unwrap_or(dir)
+        .to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_and_load() {
+        let logs = tempfile::TempDir::new().unwrap();
+        let workspace = tempfile::TempDir::new().unwrap();
+        let key = locations::workspace_key(workspace.path());
+
+        assert!(ProjectIndex::load(logs.path()).projects.is_empty());
+        ProjectIndex::register(logs.path(), &key, workspace.path()).unwrap();
+        ProjectIndex::register(logs.path(), &key, workspace.path()).unwrap();
+
+        let index = ProjectIndex::load(logs.path());
+        assert_eq!(index.projects.len(), 1);
+        assert_eq!(
+            index.workspace(&key),
+            Some(workspace.path().canonicalize().unwrap().as_path())
+        );
+    }
+
+    #[test]
+    fn test_project_root() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        let nested = workspace.path().join("src/deep");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(project_root(&nested), nested);
+
+        std::fs::write(workspace.path().join(".lawctl.yaml"), "").unwrap();
+        assert_eq!(project_root(&nested), workspace.path());
+    }
+}
@@ -4,37 +4,103 @@
 //! and pretty-printing for the `lawctl log` command.
 
 use crate::audit::types::*;
+use crate::utils::domain::host_and_port;
+use crate::utils::locations;
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 /// Reads and queries audit log files.
+///
+/// Listing (`list_sessions`, the latest session) covers every project, or
+/// one project for a reader made with [`AuditReader::for_workspace`].
+/// Sessions are always found by ID wherever they are.
 pub struct AuditReader {
     log_dir: PathBuf,
+    /// Only list this project's sessions
+    project: Option<String>,
 }
 
 impl AuditReader {
-    /// Create a reader using the default log directory.
+    /// Create a reader using the default log directory, across all projects.
     pub fn new() -> Result<Self> {
-        let log_dir = crate::audit::logger::AuditLogger::log_directory()?;
-        Ok(Self { log_dir })
+        Ok(Self::with_dir(
+            crate::audit::logger::AuditLogger::log_directory()?,
+        ))
+    }
+
+    /// Create a reader for the sessions of one workspace.
+    pub fn for_workspace(workspace: &Path) -> Result<Self> {
+        Ok(Self::new()?.scoped_to(workspace))
     }
 
     /// Create a reader for a specific directory (for testing).
     pub fn with_dir(dir: impl AsRef<Path>) -> Self {
         Self {
             log_dir: dir.as_ref().to_path_buf(),
+            project: None,
+        }
+    }
+
+    /// Limit listing to the sessions of `workspace`.
+    pub fn scoped_to(mut self, workspace: &Path) -> Self {
+        self.project = Some(locations::workspace_key(workspace));
+        self
+    }
+
+    /// The log file of a session, in whichever project it belongs to.
+    fn session_path(&self, session_id: &str) -> PathBuf {
+        let name = format!("{}.jsonl", session_id);
+        let flat = self.log_dir.join(&name);
+        if flat.exists() {
+            return flat;
         }
+        self.project_dirs()
+            .into_iter()
+            .map(|dir| dir.join(&name))
+            .find(|path| path.exists())
+            .unwrap_or(flat)
+    }
+
+    /// Per-project log directories.
+    fn project_dirs(&self) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(&self.log_dir) else {
+            return Vec::new();
+        };
+        let mut dirs: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+            .map(|e| e.path())
+            .collect();
+        dirs.sort();
+        dirs
+    }
+
+    /// Every session log file in scope.
+    fn session_files(&self) -> Vec<PathBuf> {
+        let dirs = match &self.project {
+            Some(key) => vec![self.log_dir.join(key)],
+            None => std::iter::once(self.log_dir.clone())
+                .chain(self.project_dirs())
+                .collect(),
+        };
+        dirs.iter()
+            .filter_map(|dir| fs::read_dir(dir).ok())
+            .flatten()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "jsonl"))
+            .collect()
     }
 
     /// Read all entries from a session log file.
     pub fn read_session(&self, session_id: &str) -> Result<Vec<LogEntry>> {
-        let path = self.log_dir.join(format!("{}.jsonl", session_id));
+        let path = self.session_path(session_id);
         self.read_file(&path)
     }
 
-    /// Read entries from a specific log file.
+    /// Read entries from a specific log file. Session records are skipped.
     fn read_file(&self, path: &Path) -> Result<Vec<LogEntry>> {
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read log file: {}", path.display()))?;
@@ -43,6 +109,7 @@ impl AuditReader {
             .lines()
             .filter(|line| !line.trim().is_empty())
             .enumerate()
+            .filter(|(_, line)| !is_session_record(line))
             .map(|(i, line)| {
                 serde_json::from_str(line)
                     .with_context(|| format!("Failed to parse log entry at line {}", i + 1))
@@ -50,6 +117,24 @@ impl AuditReader {
             .collect()
     }
 
+    /// The session start/end records in a session's log.
+    pub fn read_session_records(&self, session_id: &str) -> Result<Vec<SessionRecord>> {
+        let path = self.session_path(session_id);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read log file: {}", path.display()))?;
+        Ok(content
+            .lines()
+            .filter(|line| is_session_record(line))
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// How a session ended, going by its records.
+    pub fn session_status(&self, session_id: &str) -> Result<SessionStatus> {
+        let records = self.read_session_records(session_id)?;
+        Ok(session_status(&records, process_alive))
+    }
+
     /// Read entries from the most recent session.
     pub fn read_latest_session(&self) -> Result<Vec<LogEntry>> {
         let latest = self.find_latest_session()?;
@@ -61,40 +146,35 @@ impl AuditReader {
 
     /// Find the most recent session log file.
     fn find_latest_session(&self) -> Result<Option<PathBuf>> {
-        if !self.log_dir.exists() {
-            return Ok(None);
-        }
+        Ok(self.files_by_recency().into_iter().next())
+    }
 
-        let mut entries: Vec<PathBuf> = fs::read_dir(&self.log_dir)?
-            .filter_map(|e| e.ok())
-            .map(|e| e.path())
-            .filter(|p| p.extension().is_some_and(|e| e == "jsonl"))
-            .collect();
+    /// The IDs of the `limit` most recently written sessions, newest first.
+    pub fn recent_sessions(&self, limit: usize) -> Vec<String> {
+        self.files_by_recency()
+            .iter()
+            .take(limit)
+            .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .collect()
+    }
 
-        // Sort by modification time, most recent first
+    /// Session log files by modification time, most recent first.
+    fn files_by_recency(&self) -> Vec<PathBuf> {
+        let mut entries = self.session_files();
         entries.sort_by(|a, b| {
             let a_time = fs::metadata(a).and_then(|m| m.modified()).ok();
             let b_time = fs::metadata(b).and_then(|m| m.modified()).ok();
             b_time.cmp(&a_time)
         });
-
-        Ok(entries.into_iter().next())
+        entries
     }
 
     /// List all available session IDs.
     pub fn list_sessions(&self) -> Result<Vec<String>> {
-        if !self.log_dir.exists() {
-            return Ok(Vec::new());
-        }
-
-        let mut sessions: Vec<String> = fs::read_dir(&self.log_dir)?
-            .filter_map(|e| e.ok())
-            .filter(|e| e.path().extension().is_some_and(|ext| ext == "jsonl"))
-            .filter_map(|e| {
-                e.path()
-                    .file_stem()
-                    .map(|s| s.to_string_lossy().to_string())
-            })
+        let mut sessions: Vec<String> = self
+            .session_files()
+            .iter()
+            .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
             .collect();
 
         sessions.sort();
@@ -155,10 +235,38 @@ impl AuditReader {
             summary.end_time = Some(last.timestamp);
         }
 
-        summary.total_actions = entries.len();
         for entry in entries {
+            let network = entry.network.as_ref();
+            if let Some(bytes) = network.and_then(|n| n.upload_bytes) {
+                let sent = match &entry.decision {
+                    crate::policy::Decision::Allowed { .. }
+                    | crate::policy::Decision::Logged { .. } => true,
+                    crate::policy::Decision::RequiresApproval { .. } => entry.approved_by.is_some(),
+                    crate::policy::Decision::Denied { .. } => false,
+                };
+                if let (true, Some((host, _))) = (sent, host_and_port(&entry.target)) {
+                    *summary.uploads.entry(host).or_default() += bytes;
+                }
+            }
+            if network.is_some_and(|n| n.transfer_record) {
+                continue;
+            }
+            if entry.action == crate::policy::Action::Rejected {
+                summary.rejected += 1;
+                continue;
+            }
+            if entry.is_human() {
+                summary.human_changes += 1;
+                continue;
+            }
+
+            summary.total_actions += 1;
             match &entry.decision {
                 crate::policy::Decision::Allowed { .. } => summary.allowed += 1,
+                crate::policy::Decision::Logged { .. } => {
+                    summary.allowed += 1;
+                    summary.would_deny += 1;
+                }
                 crate::policy::Decision::Denied { .. } => summary.denied += 1,
                 crate::policy::Decision::RequiresApproval { .. } => {
                     if entry.approved_by.is_some() {
@@ -173,11 +281,51 @@ impl AuditReader {
         summary
     }
 
+    /// Aggregate allowed writes and deletes per file, in first-touched order.
+    pub fn file_changes(entries: &[LogEntry]) -> Vec<FileChange> {
+        let mut changes: Vec<FileChange> = Vec::new();
+        for entry in entries {
+            if entry.is_human() || (!entry.decision.is_allowed() && entry.approved_by.is_none()) {
+                continue;
+            }
+            let paths: Vec<&str> = match entry.action {
+                crate::policy::Action::Write => vec![entry.target.as_str()],
+                // Batched deletes are logged as "a, b, c"
+                crate::policy::Action::Delete => entry.target.split(", ").collect(),
+                _ => continue,
+            };
+            for path in paths {
+                let index = match changes.iter().position(|c| c.path == path) {
+                    Some(i) => i,
+                    None => {
+                        changes.push(FileChange {
+                            path: path.to_string(),
+                            ..Default::default()
+                        });
+                        changes.len() - 1
+                    }
+                };
+                let change = &mut changes[index];
+                if entry.action == crate::policy::Action::Delete {
+                    change.deleted = true;
+                    continue;
+                }
+                change.writes += 1;
+                let (added, removed) = diff_stats(entry.diff.as_deref().unwrap_or(""));
+                change.lines_added += added;
+                change.lines_removed += removed;
+            }
+        }
+        changes
+    }
+
     /// Pretty-print a log entry for terminal display.
     pub fn format_entry(entry: &LogEntry) -> String {
         let timestamp = entry.timestamp.format("%H:%M:%S").to_string();
         let decision_str = match &entry.decision {
+            _ if entry.is_human() => "HUMAN".cyan().to_string(),
             crate::policy::Decision::Allowed { .. } => "ALLOWED".green().to_string(),
+            crate::policy::Decision::Logged { .. } => "WOULD DENY".magenta().to_string(),
             crate::policy::Decision::Denied { .. } => "DENIED".red().to_string(),
             crate::policy::Decision::RequiresApproval { .. } => {
                 if entry.approved_by.is_some() {
@@ -200,7 +348,239 @@ impl AuditReader {
         if let Some(ref rule) = entry.policy_rule {
             line.push_str(&format!(" ({})", rule.dimmed()));
         }
+        if !entry.threats.is_empty() {
+            let threats: Vec<String> = entry.threats.iter().map(|t| t.to_string()).collect();
+            line.push_str(&format!(
+                " {}",
+                format!("HIGH RISK: {}", threats.join(", ")).red().bold()
+            ));
+        }
 
         line
     }
 }
+
+/// Whether a log line is a session record rather than an action entry.
+fn is_session_record(line: &str) -> bool {
+    #[derive(serde::Deserialize)]
+    struct Probe {
+        record: Option<String>,
+    }
+    serde_json::from_str::<Probe>(line).is_ok_and(|p| p.record.is_some())
+}
+
+/// A session's status from its records. The last end record wins; a start
+/// with no end is running if its lawctl process still is.
+fn session_status(records: &[SessionRecord], alive: impl Fn(u32) -> bool) -> SessionStatus {
+    if let Some(end) = records
+        .iter()
+        .rev()
+        .find(|r| matches!(r, SessionRecord::SessionEnd { .. }))
+    {
+        return SessionStatus::Ended(end.clone());
+    }
+    match records.iter().rev().find_map(|r| match r {
+        SessionRecord::SessionStart { pid, .. } => Some(*pid),
+        _ => None,
+    }) {
+        Some(pid) if alive(pid) => SessionStatus::Running,
+        Some(_) => SessionStatus::Crashed,
+        None => SessionStatus::Unknown,
+    }
+}
+
+/// Whether a process is still running.
+fn process_alive(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// Lines added/removed by a write. A unified diff is counted by its +/-
+/// lines; anything else is whole new content, so every line counts as added.
+fn diff_stats(diff: &str) -> (usize, usize) {
+    let is_unified = diff.lines().any(|l| l.starts_with("@@ "));
+    if !is_unified {
+        return (diff.lines().count(), 0);
+    }
+    let mut added = 0;
+    let mut removed = 0;
+    for line in diff.lines() {
+        if line.starts_with('+') && !line.starts_with("+++") {
+            added += 1;
+        } else if line.starts_with('-') && !line.starts_with("---") {
+            removed += 1;
+        }
+    }
+    (added, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn start(pid: u32) -> SessionRecord {
+        SessionRecord::SessionStart {
+            timestamp: Utc::now(),
+            session_id: "s".to_string(),
+            agent: "claude".to_string(),
+            pid,
+            lawctl_version: "0.1.0".to_string(),
+        }
+    }
+
+    fn end(reason: EndReason, exit_code: Option<i32>) -> SessionRecord {
+        SessionRecord::SessionEnd {
+            timestamp: Utc::now(),
+            session_id: "s".to_string(),
+            reason,
+            exit_code,
+            signal: None,
+            duration_ms: 5,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_session_status() {
+        let alive = |pid: u32| pid == 1;
+        assert_eq!(session_status(&[], alive), SessionStatus::Unknown);
+        assert_eq!(session_status(&[start(1)], alive), SessionStatus::Running);
+        let crashed = session_status(&[start(2)], alive);
+        assert_eq!(crashed, SessionStatus::Crashed);
+        assert!(crashed.is_abnormal());
+
+        let clean = session_status(&[start(2), end(EndReason::Exited, Some(0))], alive);
+        assert!(!clean.is_abnormal());
+        let failed = session_status(&[start(2), end(EndReason::Failed, Some(3))], alive);
+        assert!(failed.is_abnormal());
+        assert_eq!(
+            failed.describe().as_deref(),
+            Some("agent exited with code 3")
+        );
+    }
+
+    #[test]
+    fn test_records_are_kept_out_of_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut logger = crate::audit::AuditLogger::with_path(tmp.path().join("s.jsonl")).unwrap();
+        logger.log_record(&start(2)).unwrap();
+        logger
+            .log(&LogEntry {
+                timestamp: Utc::now(),
+                session_id: "s".to_string(),
+                agent: "claude".to_string(),
+                action: crate::policy::Action::Write,
+                target: "src/main.rs".to_string(),
+                policy_rule: None,
+                decision: crate::policy::Decision::Allowed { matched_rule: None },
+                diff: None,
+                approved_by: None,
+                eval_duration_us: None,
+                network: None,
+                warning: None,
+                output: None,
+                threats: Vec::new(),
+                actor: Actor::Agent,
+                process_chain: Vec::new(),
+            })
+            .unwrap();
+        logger
+            .log_record(&SessionRecord::Upload {
+                timestamp: Utc::now(),
+                session_id: "s".to_string(),
+                host: "example.com".to_string(),
+                bytes: 4096,
+            })
+            .unwrap();
+        logger.log_record(&end(EndReason::Exited, Some(0))).unwrap();
+
+        let reader = AuditReader::with_dir(tmp.path());
+        let entries = reader.read_session("s").unwrap();
+        assert_eq!(entries.len(), 1);
+        let records = reader.read_session_records("s").unwrap();
+        assert_eq!(records.len(), 3);
+        assert!(matches!(
+            reader.session_status("s").unwrap(),
+            SessionStatus::Ended(_)
+        ));
+
+        // Upload bytes count towards the totals, but not as an action
+        let mut summary = AuditReader::summarize(&entries);
+        summary.add_uploads(&records);
+        assert_eq!(summary.total_actions, 1);
+        assert_eq!(summary.uploads.get("example.com"), Some(&4096));
+    }
+
+    #[test]
+    fn test_summary_counts_rejected_and_log_only() {
+        let entry = |action: crate::policy::Action, decision| LogEntry {
+            timestamp: Utc::now(),
+            session_id: "s".to_string(),
+            agent: "claude".to_string(),
+            action,
+            target: "x".to_string(),
+            policy_rule: None,
+            decision,
+            diff: None,
+            approved_by: None,
+            eval_duration_us: None,
+            network: None,
+            warning: None,
+            output: None,
+            threats: Vec::new(),
+            actor: Actor::Agent,
+            process_chain: Vec::new(),
+        };
+        let denied = || crate::policy::Decision::Denied {
+            reason: "no".to_string(),
+            matched_rule: None,
+        };
+        let summary = AuditReader::summarize(&[
+            entry(
+                crate::policy::Action::Write,
+                crate::policy::Decision::Allowed { matched_rule: None },
+            ),
+            entry(crate::policy::Action::Rejected, denied()),
+            entry(crate::policy::Action::Rejected, denied()),
+            entry(
+                crate::policy::Action::Write,
+                crate::policy::Decision::Logged {
+                    reason: "strict".to_string(),
+                    matched_rule: None,
+                },
+            ),
+        ]);
+        assert_eq!((summary.total_actions, summary.denied), (2, 0));
+        assert_eq!((summary.allowed, summary.would_deny), (2, 1));
+        assert_eq!(summary.rejected, 2);
+        assert!(summary
+            .one_line()
+            .ends_with("| 1 would deny | 2 rejected requests"));
+    }
+
+    #[test]
+    fn test_sessions_are_listed_per_project() {
+        let logs = tempfile::TempDir::new().unwrap();
+        let project_a = tempfile::TempDir::new().unwrap();
+        let project_b = tempfile::TempDir::new().unwrap();
+        for (workspace, session) in [(&project_a, "a1"), (&project_a, "a2"), (&project_b, "b1")] {
+            let dir = crate::audit::projects::project_log_dir(logs.path(), workspace.path());
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join(format!("{}.jsonl", session)), "").unwrap();
+        }
+        // A log from before namespacing
+        std::fs::write(logs.path().join("old.jsonl"), "").unwrap();
+
+        let all = AuditReader::with_dir(logs.path());
+        assert_eq!(all.list_sessions().unwrap(), ["a1", "a2", "b1", "old"]);
+        let a = AuditReader::with_dir(logs.path()).scoped_to(project_a.path());
+        assert_eq!(a.list_sessions().unwrap(), ["a1", "a2"]);
+        // Any session can still be read by ID
+        assert!(a.read_session("b1").unwrap().is_empty());
+        assert!(a.read_session("old").unwrap().is_empty());
+    }
+}
@@ -3,9 +3,12 @@
 //! Every action an agent attempts gets logged — allowed, denied, or approved.
 //! The audit log is the product's superpower: full visibility into what happened.
 
-use crate::policy::types::{Action, Decision};
+use crate::policy::types::{Action, ActionContext, Decision};
+use crate::utils::process::ProcessInfo;
+use crate::utils::threats::{self, Threat};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// A single entry in the audit log.
 /// One entry per agent action attempt.
@@ -44,6 +47,268 @@ pub struct LogEntry {
     /// How long the policy evaluation took (microseconds)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub eval_duration_us: Option<u64>,
+
+    /// For network actions: scheme, method and response cap
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<NetworkDetails>,
+
+    /// Something about the session to flag alongside this action, e.g.
+    /// version skew between lawctl's components
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+
+    /// For run_cmd actions the gateway executed: how much output there was,
+    /// and where the full output went if it was cut off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<CommandOutput>,
+
+    /// For commands: high-risk patterns the built-in detection found in it
+    /// (miners, reverse shells, resource abuse), whatever the decision
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub threats: Vec<Threat>,
+
+    /// Who made the change. Everything is the agent's except what
+    /// `--watch-workspace` saw change without an entry to account for it.
+    #[serde(default, skip_serializing_if = "Actor::is_agent")]
+    pub actor: Actor,
+
+    /// For gateway requests: the process that sent it, then its parent and
+    /// so on up to the agent, when the gateway could tell
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub process_chain: Vec<ProcessInfo>,
+}
+
+/// Who a log entry is about.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Actor {
+    /// The agent, through lawctl
+    #[default]
+    Agent,
+    /// Someone changing the workspace outside the agent
+    Human,
+}
+
+impl Actor {
+    pub fn is_agent(&self) -> bool {
+        *self == Actor::Agent
+    }
+}
+
+impl LogEntry {
+    /// The process a request is put down to: the client's parent when
+    /// there is one — the client is usually a one-off shim — otherwise
+    /// the client itself.
+    pub fn requester(&self) -> Option<&ProcessInfo> {
+        self.process_chain.get(1).or(self.process_chain.first())
+    }
+
+    /// Whether this is a change the watcher saw someone else make, rather
+    /// than something the agent did.
+    pub fn is_human(&self) -> bool {
+        self.actor == Actor::Human
+    }
+}
+
+/// Request details recorded for network actions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkDetails {
+    /// URL scheme (e.g. "https")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<String>,
+
+    /// HTTP method, when known (HTTPS tunnels don't reveal it)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+
+    /// Response size cap from the deciding rule's `max_response_mb`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_mb: Option<u64>,
+
+    /// Set when the proxy cut the response off at the cap
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub response_truncated: bool,
+
+    /// Bytes this request sent to the host, as far as known when logged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_bytes: Option<u64>,
+
+    /// Set on entries older versions wrote after a connection closed,
+    /// carrying upload bytes that weren't known at decision time. Not an
+    /// action — summaries only add its bytes. Such bytes now go in a
+    /// [`SessionRecord::Upload`].
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub transfer_record: bool,
+}
+
+/// What an executed command produced. The output itself isn't logged.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandOutput {
+    pub exit_code: i32,
+
+    /// Full size of each stream, before any truncation
+    pub stdout_bytes: u64,
+    pub stderr_bytes: u64,
+
+    /// Set when the agent got less than the full output
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+
+    /// Where the complete output of truncated streams was kept, when
+    /// `spill_output` is on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spill_files: Vec<std::path::PathBuf>,
+}
+
+impl NetworkDetails {
+    /// Details for a network context, or None if there's nothing to record.
+    pub fn from_context(
+        context: &ActionContext,
+        max_response_mb: Option<u64>,
+        upload_bytes: Option<u64>,
+    ) -> Option<Self> {
+        if context.scheme.is_none()
+            && context.method.is_none()
+            && max_response_mb.is_none()
+            && upload_bytes.is_none()
+        {
+            return None;
+        }
+        Some(Self {
+            scheme: context.scheme.clone(),
+            method: context.method.clone(),
+            max_response_mb,
+            upload_bytes,
+            ..Default::default()
+        })
+    }
+}
+
+/// The threats to flag on a command's log entry.
+pub fn command_threats(action: &Action, context: &ActionContext) -> Vec<Threat> {
+    match (action, &context.command) {
+        (Action::RunCmd | Action::RemoteExec, Some(command)) => threats::detect(command),
+        _ => Vec::new(),
+    }
+}
+
+/// A marker line in a session log, alongside the action entries: written
+/// when `lawctl run` starts the agent and when the session ends. A log with
+/// a start and no end was cut off — lawctl itself died. Upload records
+/// carry bytes the proxy only counted once a connection closed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+pub enum SessionRecord {
+    SessionStart {
+        timestamp: DateTime<Utc>,
+        session_id: String,
+        agent: String,
+        /// The lawctl process running the session
+        pid: u32,
+        lawctl_version: String,
+    },
+    SessionEnd {
+        timestamp: DateTime<Utc>,
+        session_id: String,
+        reason: EndReason,
+        /// The agent's exit code, when it exited on its own
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        exit_code: Option<i32>,
+        /// The signal that killed the agent, or for `reason: interrupted`
+        /// the one lawctl received
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signal: Option<i32>,
+        duration_ms: u64,
+        /// For `reason: error`: what went wrong
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    Upload {
+        timestamp: DateTime<Utc>,
+        session_id: String,
+        host: String,
+        /// Bytes sent that the decision for the request didn't know of —
+        /// a tunnel's traffic, or a body without Content-Length
+        bytes: u64,
+    },
+}
+
+/// Why a session ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndReason {
+    /// The agent exited with code 0
+    Exited,
+    /// The agent exited with a non-zero code
+    Failed,
+    /// The agent was killed by a signal
+    Killed,
+    /// lawctl hit an error running the session
+    Error,
+    /// lawctl was told to stop (SIGINT/SIGTERM) and shut the agent down
+    Interrupted,
+}
+
+/// How a session ended, going by its start and end records.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionStatus {
+    /// No records — a hook-only session, or one from before they existed
+    Unknown,
+    /// Started, and the lawctl running it is still alive
+    Running,
+    /// Started and never ended: lawctl was killed or crashed
+    Crashed,
+    /// Ended with this record
+    Ended(SessionRecord),
+}
+
+impl SessionStatus {
+    /// Whether the session ended some way other than the agent exiting
+    /// cleanly.
+    pub fn is_abnormal(&self) -> bool {
+        match self {
+            SessionStatus::Crashed => true,
+            SessionStatus::Ended(SessionRecord::SessionEnd { reason, .. }) => {
+                *reason != EndReason::Exited
+            }
+            _ => false,
+        }
+    }
+
+    /// A few words on how the session ended, for `lawctl log --list`.
+    pub fn describe(&self) -> Option<String> {
+        match self {
+            SessionStatus::Unknown => None,
+            SessionStatus::Running => Some("running".to_string()),
+            SessionStatus::Crashed => {
+                Some("no end record — lawctl stopped unexpectedly".to_string())
+            }
+            SessionStatus::Ended(SessionRecord::SessionEnd {
+                reason,
+                exit_code,
+                signal,
+                error,
+                ..
+            }) => Some(match reason {
+                EndReason::Exited => "ended normally".to_string(),
+                EndReason::Failed => format!("agent exited with code {}", exit_code.unwrap_or(-1)),
+                EndReason::Killed => match signal {
+                    Some(signal) => format!("agent killed by signal {}", signal),
+                    None => "agent killed".to_string(),
+                },
+                EndReason::Interrupted => match signal {
+                    Some(signal) => format!("interrupted by signal {}", signal),
+                    None => "interrupted".to_string(),
+                },
+                EndReason::Error => {
+                    format!("lawctl error: {}", error.as_deref().unwrap_or("unknown"))
+                }
+            }),
+            SessionStatus::Ended(
+                SessionRecord::SessionStart { .. } | SessionRecord::Upload { .. },
+            ) => None,
+        }
+    }
 }
 
 /// Summary statistics for a session's audit log.
@@ -55,18 +320,94 @@ pub struct SessionSummary {
     pub allowed: usize,
     pub denied: usize,
     pub approved: usize,
+    /// Gateway requests turned away before evaluation (malformed JSON,
+    /// unknown actions). Not counted in the totals above.
+    #[serde(default)]
+    pub rejected: usize,
+    /// Allowed actions a `log_only` rule says strict mode would deny
+    /// (counted in `allowed` too)
+    #[serde(default)]
+    pub would_deny: usize,
+    /// Workspace changes someone other than the agent made, seen by
+    /// `--watch-workspace`. Not counted in the totals above.
+    #[serde(default)]
+    pub human_changes: usize,
     pub start_time: Option<DateTime<Utc>>,
     pub end_time: Option<DateTime<Utc>>,
+    /// Bytes sent per host by allowed network actions
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub uploads: BTreeMap<String, u64>,
 }
 
 impl SessionSummary {
     /// Format as a human-readable one-liner for terminal output.
     pub fn one_line(&self) -> String {
-        format!(
+        let line = format!(
             "{} actions | {} allowed | {} denied | {} approved",
             self.total_actions, self.allowed, self.denied, self.approved
-        )
+        );
+        let line = match self.would_deny {
+            0 => line,
+            n => format!("{} | {} would deny", line, n),
+        };
+        let line = match self.rejected {
+            0 => line,
+            n => format!("{} | {} rejected requests", line, n),
+        };
+        match self.human_changes {
+            0 => line,
+            n => format!("{} | {} human changes", line, n),
+        }
     }
+
+    /// Add the bytes of a session's upload records to its totals.
+    pub fn add_uploads(&mut self, records: &[SessionRecord]) {
+        for record in records {
+            if let SessionRecord::Upload { host, bytes, .. } = record {
+                *self.uploads.entry(host.clone()).or_default() += bytes;
+            }
+        }
+    }
+
+    /// Per-host upload totals, largest first, or None if nothing was sent.
+    pub fn uploads_line(&self) -> Option<String> {
+        if self.uploads.is_empty() {
+            return None;
+        }
+        let mut hosts: Vec<_> = self.uploads.iter().collect();
+        hosts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let parts: Vec<String> = hosts
+            .iter()
+            .map(|(host, bytes)| format!("{} {}", host, format_bytes(**bytes)))
+            .collect();
+        Some(format!("Uploaded: {}", parts.join(", ")))
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = 1024 * KB;
+    if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+/// What a session did to one file, aggregated from its write/delete entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: String,
+    /// Number of allowed writes
+    pub writes: usize,
+    /// Lines added across those writes (whole-content writes count every line)
+    pub lines_added: usize,
+    /// Lines removed, when a write carried a unified diff
+    pub lines_removed: usize,
+    /// Whether the file was deleted at some point
+    pub deleted: bool,
 }
 
 /// Filter criteria for querying audit logs.
@@ -0,0 +1,291 @@
+//! Workspace watch mode (`lawctl run --watch-workspace`).
+//!
+//! While the agent works, lawctl also looks at the workspace itself every
+//! few seconds. A file that appears, changes or disappears with no agent
+//! action to account for it was changed by someone else — usually the human
+//! at the keyboard — and is logged as an `actor: human` entry, so the session
+//! log is a timeline of everything that happened to the workspace.
+//!
+//! A change is the agent's when a recent entry wrote, deleted, moved or
+//! chmodded that file, or ran something (a command, an install, an unknown
+//! tool) that could have touched any file. Attribution is by timing, so a
+//! human edit made while an agent command runs is put down to the agent.
+
+use crate::audit::{Actor, AuditLogger, AuditReader, LogEntry};
+use crate::policy::types::{Action, Decision};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// How often the workspace is looked at.
+pub const WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// How far before a look agent entries still count towards it. The hook
+/// logs a write just before it happens, and approvals take time.
+const ATTRIBUTION_SLACK: chrono::Duration = chrono::Duration::seconds(10);
+
+/// Directories not watched: version control and build output.
+const SKIPPED_DIRS: &[&str] = &[".git", ".hg", ".svn", "target", "node_modules"];
+
+/// Most files looked at; the rest of a huge workspace isn't watched.
+const MAX_FILES: usize = 50_000;
+
+/// Modification time and size of every file in the workspace, by
+/// workspace-relative path.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Scan(BTreeMap<String, (SystemTime, u64)>);
+
+impl Scan {
+    /// Look at every file under `workspace`.
+    pub fn take(workspace: &Path) -> Self {
+        let mut files = BTreeMap::new();
+        let mut dirs = vec![workspace.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.filter_map(|e| e.ok()) {
+                let Ok(kind) = entry.file_type() else {
+                    continue;
+                };
+                let path = entry.path();
+                if kind.is_dir() {
+                    if !SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()) {
+                        dirs.push(path);
+                    }
+                    continue;
+                }
+                if !kind.is_file() || files.len() >= MAX_FILES {
+                    continue;
+                }
+                let (Ok(metadata), Ok(relative)) = (entry.metadata(), path.strip_prefix(workspace))
+                else {
+                    continue;
+                };
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                files.insert(
+                    relative.to_string_lossy().into_owned(),
+                    (modified, metadata.len()),
+                );
+            }
+        }
+        Self(files)
+    }
+
+    /// The files written (created or changed) and deleted since `self`.
+    pub fn changes(&self, later: &Scan) -> Vec<(Action, String)> {
+        let written = later
+            .0
+            .iter()
+            .filter(|(path, seen)| self.0.get(*path) != Some(seen))
+            .map(|(path, _)| (Action::Write, path.clone()));
+        let deleted = self
+            .0
+            .keys()
+            .filter(|path| !later.0.contains_key(*path))
+            .map(|path| (Action::Delete, path.clone()));
+        written.chain(deleted).collect()
+    }
+}
+
+/// The `changes` no agent entry since `since` accounts for.
+pub fn unaccounted(
+    changes: Vec<(Action, String)>,
+    entries: &[LogEntry],
+    since: DateTime<Utc>,
+    workspace: &Path,
+) -> Vec<(Action, String)> {
+    let recent: Vec<&LogEntry> = entries
+        .iter()
+        .filter(|entry| entry.timestamp >= since && !entry.is_human())
+        .filter(|entry| !entry.decision.is_denied())
+        .collect();
+    let ran_something = recent.iter().any(|entry| {
+        matches!(
+            entry.action,
+            Action::RunCmd | Action::PackageInstall | Action::RemoteCopy | Action::UnknownTool
+        )
+    });
+    if ran_something {
+        return Vec::new();
+    }
+    let touched: HashSet<String> = recent
+        .iter()
+        .filter(|entry| {
+            matches!(
+                entry.action,
+                Action::Write | Action::Delete | Action::Move | Action::Chmod
+            )
+        })
+        // Several targets are logged as "a, b, c"
+        .flat_map(|entry| entry.target.split(", "))
+        .map(|target| {
+            let path = Path::new(target);
+            let path = path.strip_prefix(workspace).unwrap_or(path);
+            let path = path.strip_prefix("./").unwrap_or(path);
+            path.to_string_lossy().into_owned()
+        })
+        .collect();
+    changes
+        .into_iter()
+        .filter(|(_, path)| !touched.contains(path))
+        .collect()
+}
+
+/// The log entry for a change someone else made to `path`.
+pub fn human_entry(session_id: &str, agent: &str, action: Action, path: String) -> LogEntry {
+    LogEntry {
+        timestamp: Utc::now(),
+        session_id: session_id.to_string(),
+        agent: agent.to_string(),
+        action,
+        target: path,
+        policy_rule: None,
+        decision: Decision::Allowed { matched_rule: None },
+        diff: None,
+        approved_by: None,
+        eval_duration_us: None,
+        network: None,
+        warning: None,
+        output: None,
+        threats: Vec::new(),
+        actor: Actor::Human,
+        process_chain: Vec::new(),
+    }
+}
+
+/// Look at the workspace every `every` while a session runs, logging the
+/// changes the agent didn't make to the session's log.
+pub async fn watch(
+    workspace: PathBuf,
+    session_id: String,
+    agent: String,
+    every: std::time::Duration,
+) {
+    let mut logger = match AuditLogger::new(&session_id, &workspace) {
+        Ok(logger) => logger,
+        Err(e) => {
+            tracing::warn!("Workspace watch is off: {:#}", e);
+            return;
+        }
+    };
+    let reader = AuditReader::for_workspace(&workspace);
+    let take = |workspace: PathBuf| async move {
+        tokio::task::spawn_blocking(move || Scan::take(&workspace))
+            .await
+            .unwrap_or_default()
+    };
+    let mut scan = take(workspace.clone()).await;
+    let mut looked = Utc::now();
+    let mut interval = tokio::time::interval(every);
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        let now = Utc::now();
+        let next = take(workspace.clone()).await;
+        let changes = scan.changes(&next);
+        // Without the agent's entries there's no telling whose a change is
+        let entries = match &reader {
+            Ok(reader) if !changes.is_empty() => reader.read_session(&session_id).ok(),
+            _ => None,
+        };
+        if let Some(entries) = entries {
+            let since = looked - ATTRIBUTION_SLACK;
+            for (action, path) in unaccounted(changes, &entries, since, &workspace) {
+                let entry = human_entry(&session_id, &agent, action, path);
+                if let Err(e) = logger.log(&entry) {
+                    tracing::warn!("Failed to log a workspace change: {:#}", e);
+                }
+            }
+        }
+        scan = next;
+        looked = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(action: Action, target: &str) -> LogEntry {
+        LogEntry {
+            timestamp: Utc::now(),
+            session_id: "s".to_string(),
+            agent: "a".to_string(),
+            action,
+            target: target.to_string(),
+            policy_rule: None,
+            decision: Decision::Allowed { matched_rule: None },
+            diff: None,
+            approved_by: None,
+            eval_duration_us: None,
+            network: None,
+            warning: None,
+            output: None,
+            threats: Vec::new(),
+            actor: Actor::Agent,
+            process_chain: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_scan_changes() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join("src")).unwrap();
+        std::fs::create_dir_all(tmp.path().join(".git")).unwrap();
+        std::fs::write(tmp.path().join("src/lib.rs"), "one").unwrap();
+        std::fs::write(tmp.path().join("old.txt"), "old").unwrap();
+        let before = Scan::take(tmp.path());
+
+        std::fs::write(tmp.path().join("src/lib.rs"), "one two").unwrap();
+        std::fs::write(tmp.path().join("new.txt"), "new").unwrap();
+        std::fs::write(tmp.path().join(".git/HEAD"), "ref").unwrap();
+        std::fs::remove_file(tmp.path().join("old.txt")).unwrap();
+        let mut changes = before.changes(&Scan::take(tmp.path()));
+        changes.sort_by(|a, b| a.1.cmp(&b.1));
+        assert_eq!(
+            changes,
+            [
+                (Action::Write, "new.txt".to_string()),
+                (Action::Delete, "old.txt".to_string()),
+                (Action::Write, "src/lib.rs".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_only_unaccounted_changes_are_human() {
+        let workspace = Path::new("/work");
+        let since = Utc::now() - chrono::Duration::minutes(1);
+        let changes = || {
+            vec![
+                (Action::Write, "src/main.rs".to_string()),
+                (Action::Write, "notes.md".to_string()),
+            ]
+        };
+        let entries = [entry(Action::Write, "/work/src/main.rs")];
+
+        assert_eq!(
+            unaccounted(changes(), &entries, since, workspace),
+            [(Action::Write, "notes.md".to_string())]
+        );
+
+        // Too long ago to account for anything
+        let later = Utc::now() + chrono::Duration::minutes(1);
+        assert_eq!(unaccounted(changes(), &entries, later, workspace).len(), 2);
+
+        // A command could have changed anything
+        let entries = [entry(Action::RunCmd, "cargo fmt")];
+        assert!(unaccounted(changes(), &entries, since, workspace).is_empty());
+
+        let human = human_entry("s", "a", Action::Delete, "notes.md".to_string());
+        assert!(human.is_human());
+        let json = serde_json::to_string(&human).unwrap();
+        assert!(json.contains(r#""actor":"human""#));
+        assert!(!serde_json::to_string(&entries[0])
+            .unwrap()
+            .contains("actor"));
+    }
+}
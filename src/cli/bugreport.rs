@@ -0,0 +1,224 @@
+//! `lawctl bugreport` — one file to attach to an issue.
+//!
+//! Bundles environment info, the `lawctl doctor` checks, a summary of the
+//! session and its debug trace (from `lawctl go --debug`) into a Markdown
+//! file. The trace is redacted when it's captured and the whole report is
+//! redacted again on the way out; file contents and diffs from the audit
+//! log are never included.
+
+use crate::audit::AuditReader;
+use crate::cli::{debug, doctor, go, setup, update};
+use crate::integrity::Status;
+use crate::utils::redact::redact;
+use crate::utils::term::glyph;
+use crate::utils::version::VERSION;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Run `lawctl bugreport`, for `session` or the most recent debug trace.
+pub fn run_bugreport(session: Option<&str>, output: Option<&Path>) -> Result<()> {
+    let trace = match session {
+        Some(id) => {
+            let path = debug::debug_log_path(id)?;
+            if !path.exists() {
+                anyhow::bail!(
+                    "No debug trace for session {} — run `lawctl go --debug` to capture one",
+                    id
+                );
+            }
+            Some(path)
+        }
+        None => latest_trace(&debug::debug_directory()?),
+    };
+    let session_id = session.map(str::to_string).or_else(|| {
+        trace
+            .as_ref()
+            .and_then(|p| p.file_stem())
+            .map(|s| s.to_string_lossy().into_owned())
+    });
+
+    let report = redact(&build_report(session_id.as_deref(), trace.as_deref()));
+    let path = output.map(Path::to_path_buf).unwrap_or_else(|| {
+        PathBuf::from(format!(
+            "lawctl-bugreport-{}.md",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ))
+    });
+    std::fs::write(&path, report).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    println!();
+    println!(
+        "  {} Bug report written to {}",
+        glyph("✓").green().bold(),
+        path.display().to_string().bold()
+    );
+    if trace.is_none() {
+        println!(
+            "  {} No debug trace found — reproduce with {} for a more useful report.",
+            glyph("ℹ").blue(),
+            "lawctl go --debug".bold()
+        );
+    }
+    println!(
+        "  {}",
+        "Secrets are masked, but read it through before attaching it to an issue.".dimmed()
+    );
+    println!();
+    Ok(())
+}
+
+/// The newest trace in `dir`.
+fn latest_trace(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "log"))
+        .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+        .map(|entry| entry.path())
+}
+
+fn build_report(session_id: Option<&str>, trace: Option<&Path>) -> String {
+    let mut md = String::new();
+    let _ = writeln!(md, "# lawctl bug report\n");
+
+    let _ = writeln!(md, "## Environment\n");
+    let install_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.canonicalize().ok())
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    match &install_dir {
+        Some(dir) => {
+            for (binary, version) in update::component_versions(dir) {
+                let version = version.unwrap_or_else(|| "not found".to_string());
+                let _ = writeln!(md, "- {}: {}", binary, version);
+            }
+        }
+        None => {
+            let _ = writeln!(md, "- lawctl: {}", VERSION);
+        }
+    }
+    let _ = writeln!(
+        md,
+        "- OS: {} {} ({})",
+        std::env::consts::OS,
+        command_output("uname", &["-r"]).unwrap_or_default(),
+        std::env::consts::ARCH
+    );
+    for var in ["SHELL", "TERM", "LANG"] {
+        let value = std::env::var(var).unwrap_or_else(|_| "unset".to_string());
+        let _ = writeln!(md, "- {}: {}", var, value);
+    }
+    let docker = command_output("docker", &["--version"]);
+    let _ = writeln!(md, "- Docker: {}", docker.as_deref().unwrap_or("not found"));
+    let agents = setup::detected_agents();
+    let _ = writeln!(
+        md,
+        "- Agents on PATH: {}",
+        if agents.is_empty() {
+            "none".to_string()
+        } else {
+            agents.join(", ")
+        }
+    );
+
+    let _ = writeln!(md, "\n## Checks\n");
+    match go::find_policy_file() {
+        Some(policy_path) => {
+            let _ = writeln!(md, "Policy: `{}`\n", policy_path.display());
+            for check in doctor::run_checks(&policy_path) {
+                let status = match check.status {
+                    Status::Ok => "ok",
+                    Status::Warning => "warning",
+                    Status::Failed => "FAILED",
+                };
+                let _ = writeln!(md, "- [{}] {}: {}", status, check.name, check.detail);
+            }
+        }
+        None => {
+            let _ = writeln!(md, "No .lawctl.yaml found from the current directory.");
+        }
+    }
+
+    let _ = writeln!(md, "\n## Session\n");
+    let entries = AuditReader::new().and_then(|reader| match session_id {
+        Some(id) => reader.read_session(id),
+        None => reader.read_latest_session(),
+    });
+    match entries {
+        Ok(entries) if !entries.is_empty() => {
+            let summary = AuditReader::summarize(&entries);
+            let _ = writeln!(md, "- Session: {}", summary.session_id);
+            let _ = writeln!(md, "- Agent: {}", summary.agent);
+            let _ = writeln!(
+                md,
+                "- Actions: {} ({} allowed, {} denied, {} approved)",
+                summary.total_actions, summary.allowed, summary.denied, summary.approved
+            );
+            for warning in doctor::logged_warnings(&entries) {
+                let _ = writeln!(md, "- Warning: {}", warning);
+            }
+        }
+        _ => {
+            let _ = writeln!(md, "No audit log for this session.");
+        }
+    }
+
+    let _ = writeln!(md, "\n## Debug trace\n");
+    match trace.map(|p| (p, std::fs::read_to_string(p))) {
+        Some((path, Ok(contents))) => {
+            let _ = writeln!(md, "From `{}`:\n", path.display());
+            let _ = writeln!(md, "```\n{}```", contents);
+        }
+        Some((path, Err(e))) => {
+            let _ = writeln!(md, "Couldn't read `{}`: {}", path.display(), e);
+        }
+        None => {
+            let _ = writeln!(
+                md,
+                "No debug trace. Reproduce the problem with `lawctl go --debug`, then run `lawctl bugreport` again."
+            );
+        }
+    }
+    md
+}
+
+/// First line of a command's stdout, if it ran.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|out| out.status.success())?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_trace() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(latest_trace(dir.path()), None);
+        std::fs::write(dir.path().join("old.log"), "").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        std::fs::write(dir.path().join("new.log"), "").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+        assert_eq!(latest_trace(dir.path()), Some(dir.path().join("new.log")));
+    }
+
+    #[test]
+    fn test_report_includes_trace() {
+        let dir = tempfile::tempdir().unwrap();
+        let trace = dir.path().join("abc.log");
+        std::fs::write(&trace, "DEBUG gateway request action=write\n").unwrap();
+        let report = build_report(Some("abc"), Some(&trace));
+        assert!(report.contains("## Environment"));
+        assert!(report.contains("```\nDEBUG gateway request action=write\n```"));
+    }
+}
@@ -0,0 +1,112 @@
+//! `lawctl completions` and `lawctl man` — shell completion and manpages,
+//! both generated from the clap definitions so they never drift from
+//! `--help`.
+//!
+//! Completion is dynamic: the registration script printed by `lawctl
+//! completions <shell>` calls back into lawctl on every TAB (with
+//! `LAWCTL_COMPLETE` set), which is how `lawctl log --session <TAB>` can
+//! offer the sessions actually on disk and `lawctl init --template <TAB>`
+//! the built-in templates.
+
+use crate::audit::AuditReader;
+use crate::policy::defaults;
+use anyhow::{Context, Result};
+use clap::Command;
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::Shells;
+use std::ffi::OsStr;
+use std::io::Write;
+use std::path::Path;
+
+/// Environment variable that switches lawctl into completion mode.
+pub const COMPLETE_VAR: &str = "LAWCTL_COMPLETE";
+
+/// Print the registration script for `shell`, to be sourced from the
+/// shell's startup file.
+pub fn print_completions(shell: &str) -> Result<()> {
+    let shells = Shells::builtins();
+    let completer = shells.completer(shell).with_context(|| {
+        format!(
+            "Unsupported shell '{}' — use one of: {}",
+            shell,
+            shells.names().collect::<Vec<_>>().join(", ")
+        )
+    })?;
+    let mut stdout = std::io::stdout().lock();
+    completer
+        .write_registration(COMPLETE_VAR, "lawctl", "lawctl", "lawctl", &mut stdout)
+        .context("Failed to write completion script")?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Print the manpage to stdout, or with `out_dir` write `lawctl.1` and one
+/// `lawctl-<command>.1` per visible subcommand there.
+pub fn print_man(cmd: Command, out_dir: Option<&Path>) -> Result<()> {
+    let Some(dir) = out_dir else {
+        let mut stdout = std::io::stdout().lock();
+        clap_mangen::Man::new(cmd).render(&mut stdout)?;
+        return Ok(());
+    };
+
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let mut written = vec![write_page(cmd.clone(), dir, "lawctl")?];
+    for sub in cmd.get_subcommands().filter(|s| !s.is_hide_set()) {
+        let name = format!("lawctl-{}", sub.get_name());
+        // The page title and synopsis should read `lawctl-log`, not `log`
+        let sub = sub
+            .clone()
+            .display_name(name.clone())
+            .bin_name(name.clone())
+            .version(env!("CARGO_PKG_VERSION"));
+        written.push(write_page(sub, dir, &name)?);
+    }
+    for page in written {
+        println!("  {}", page.display());
+    }
+    Ok(())
+}
+
+fn write_page(cmd: Command, dir: &Path, name: &str) -> Result<std::path::PathBuf> {
+    let path = dir.join(format!("{}.1", name));
+    let mut buf = Vec::new();
+    clap_mangen::Man::new(cmd).render(&mut buf)?;
+    std::fs::write(&path, buf).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Recorded session IDs starting with what's been typed.
+pub fn session_candidates(current: &OsStr) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    AuditReader::new()
+        .and_then(|reader| reader.list_sessions())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|id| id.starts_with(current.as_ref()))
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// Built-in policy templates, with their descriptions.
+pub fn template_candidates(current: &OsStr) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    defaults::available_templates()
+        .into_iter()
+        .filter(|(name, _)| name.starts_with(current.as_ref()))
+        .map(|(name, description)| CompletionCandidate::new(name).help(Some(description.into())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_candidates() {
+        let names: Vec<String> = template_candidates(OsStr::new("safe"))
+            .iter()
+            .map(|c| c.get_value().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["safe-dev", "safe-ci"]);
+    }
+}
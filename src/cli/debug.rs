@@ -0,0 +1,129 @@
+//! `lawctl go --debug` — a trace of the gateway's internals for bug reports.
+//!
+//! Everything lawctl traces at debug level (requests, decisions, timings,
+//! errors) is written to `debug/<session>.log` in lawctl's data directory, one line per
+//! event, with each line passed through [`redact`] first. Payloads
+//! themselves — file contents, diffs — are never traced, only their size.
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+use crate::utils::locations;
+use crate::utils::redact::redact;
+
+/// Where debug traces are kept (`debug/` in lawctl's data directory).
+pub fn debug_directory() -> Result<PathBuf> {
+    Ok(locations::data_dir()?.join("debug"))
+}
+
+/// The trace file for a session.
+pub fn debug_log_path(session_id: &str) -> Result<PathBuf> {
+    Ok(debug_directory()?.join(format!("{}.log", session_id)))
+}
+
+/// Set up tracing: warnings on stderr as usual (or whatever `RUST_LOG`
+/// asks for), plus a redacted debug-level trace in `capture` if given.
+pub fn init_tracing(capture: Option<&Path>) -> Result<()> {
+    let stderr = tracing_subscriber::fmt::layer()
+        .with_target(false)
+        .without_time()
+        .with_writer(std::io::stderr)
+        .with_filter(EnvFilter::from_default_env().add_directive("lawctl=warn".parse()?));
+
+    let file = match capture {
+        Some(path) => {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)
+                    .with_context(|| format!("Failed to create {}", dir.display()))?;
+            }
+            let file = File::create(path)
+                .with_context(|| format!("Failed to create debug log {}", path.display()))?;
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(RedactingWriter(Arc::new(Mutex::new(file))))
+                    .with_filter(EnvFilter::new("lawctl=debug,lawctl_shim=debug")),
+            )
+        }
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(stderr)
+        .with(file)
+        .try_init()
+        .context("Failed to set up tracing")
+}
+
+/// Writes each trace line to the capture file with secrets masked.
+#[derive(Clone)]
+struct RedactingWriter(Arc<Mutex<File>>);
+
+/// One event's worth of output, redacted and written when dropped.
+struct RedactedLine {
+    file: Arc<Mutex<File>>,
+    buf: Vec<u8>,
+}
+
+impl<'a> MakeWriter<'a> for RedactingWriter {
+    type Writer = RedactedLine;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactedLine {
+            file: self.0.clone(),
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl Write for RedactedLine {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for RedactedLine {
+    fn drop(&mut self) {
+        if self.buf.is_empty() {
+            return;
+        }
+        let line = redact(&String::from_utf8_lossy(&self.buf));
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = file.write_all(line.as_bytes());
+        let _ = file.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_are_redacted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trace.log");
+        let writer = RedactingWriter(Arc::new(Mutex::new(File::create(&path).unwrap())));
+        {
+            let mut line = writer.make_writer();
+            write!(line, "request target=\"curl https://bob:pw@x\" ").unwrap();
+            writeln!(line, "token=abc123").unwrap();
+        }
+        let trace = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            trace,
+            "request target=\"curl https://[REDACTED]@x\" token=[REDACTED]\n"
+        );
+    }
+}
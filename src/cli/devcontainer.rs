@@ -0,0 +1,306 @@
+//! `lawctl init --devcontainer` — protection by default in dev containers
+//! and Codespaces.
+//!
+//! Creates or updates `.devcontainer/devcontainer.json` so every container
+//! built from it comes with lawctl:
+//!   - the lawctl feature installs the binaries at image build time
+//!   - `postCreateCommand` registers the Claude Code hook in the container
+//!   - `postStartCommand` validates the policy on every start
+//!
+//! The gateway isn't a daemon — `lawctl go` starts one per agent session —
+//! so there is nothing long-running to launch.
+//!
+//! Existing files are merged, not replaced: other features and lifecycle
+//! commands are kept, and the original is backed up. devcontainer.json is
+//! JSON with comments; comments don't survive the rewrite (the backup
+//! keeps them).
+
+use crate::utils::term::glyph;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+
+/// The published lawctl dev container feature (see `features/src/lawctl`).
+const FEATURE_ID: &str = "ghcr.io/abcxz/lawctl/lawctl:1";
+
+/// Registers the hook inside the container (the policy comes from the repo).
+const POST_CREATE: &str = "lawctl setup --yes --agent claude-code";
+
+/// Fails the start loudly if the policy no longer parses.
+const POST_START: &str = "lawctl check";
+
+/// Base image for a brand new devcontainer.json.
+const DEFAULT_IMAGE: &str = "mcr.microsoft.com/devcontainers/base:ubuntu";
+
+/// Run `lawctl init --devcontainer`: the policy file (if missing), then
+/// the dev container config.
+pub fn run_devcontainer_init(template: Option<&str>, output_path: Option<&str>) -> Result<()> {
+    let cwd = std::env::current_dir().context("Failed to get current directory")?;
+
+    let policy_file = output_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| cwd.join(".lawctl.yaml"));
+    if !policy_file.exists() {
+        crate::cli::init::run_init(template, output_path)?;
+    }
+
+    let config_path = find_config(&cwd);
+    let changed = update_config(&config_path, &cwd)?;
+
+    println!();
+    if changed {
+        println!(
+            "  {} Dev container set up: {}",
+            glyph("✓").green().bold(),
+            config_path.display().to_string().bold()
+        );
+        println!("    {} feature {}", glyph("•"), FEATURE_ID.cyan());
+        println!(
+            "    {} postCreateCommand: {}",
+            glyph("•"),
+            POST_CREATE.dimmed()
+        );
+        println!(
+            "    {} postStartCommand:  {}",
+            glyph("•"),
+            POST_START.dimmed()
+        );
+    } else {
+        println!(
+            "  {} {} already includes lawctl",
+            glyph("✓").green().bold(),
+            config_path.display()
+        );
+    }
+    println!();
+    println!(
+        "  Commit {} and {} — every Codespace and dev container built from",
+        ".devcontainer/".bold(),
+        ".lawctl.yaml".bold()
+    );
+    println!("  this repo is protected from the first command.");
+    println!();
+    Ok(())
+}
+
+/// The config the dev container tooling would pick up: `.devcontainer.json`
+/// at the root if that's what the project uses, otherwise the usual place.
+fn find_config(root: &Path) -> PathBuf {
+    let root_level = root.join(".devcontainer.json");
+    if root_level.exists() {
+        root_level
+    } else {
+        root.join(".devcontainer").join("devcontainer.json")
+    }
+}
+
+/// Create or merge the config file. Returns false if nothing changed.
+fn update_config(path: &Path, project_root: &Path) -> Result<bool> {
+    let existed = path.exists();
+    let mut config = if existed {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&strip_jsonc(&content))
+            .with_context(|| format!("Failed to parse {}", path.display()))?
+    } else {
+        let name = project_root
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "workspace".to_string());
+        json!({ "name": name, "image": DEFAULT_IMAGE })
+    };
+
+    if !merge_lawctl(&mut config)
+        .with_context(|| format!("Unexpected layout in {}", path.display()))?
+    {
+        return Ok(false);
+    }
+
+    if existed {
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(".lawctl-backup");
+        std::fs::copy(path, PathBuf::from(backup))
+            .with_context(|| format!("Failed to back up {}", path.display()))?;
+    } else if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let content = serde_json::to_string_pretty(&config)? + "\n";
+    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+/// Add the lawctl feature and lifecycle commands. Returns false if they
+/// were all there already.
+fn merge_lawctl(config: &mut Value) -> Result<bool> {
+    let config = config
+        .as_object_mut()
+        .context("devcontainer.json is not a JSON object")?;
+
+    let features = config
+        .entry("features")
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .context("\"features\" is not an object")?;
+    let mut changed = false;
+    if !features.keys().any(|id| id.contains("/lawctl/lawctl")) {
+        features.insert(FEATURE_ID.to_string(), json!({}));
+        changed = true;
+    }
+
+    changed |= merge_command(config, "postCreateCommand", POST_CREATE)?;
+    changed |= merge_command(config, "postStartCommand", POST_START)?;
+    Ok(changed)
+}
+
+/// Add a command to a lifecycle hook, whichever of its three forms (string,
+/// argv array, or named parallel commands) the config uses.
+fn merge_command(config: &mut Map<String, Value>, key: &str, command: &str) -> Result<bool> {
+    let Some(existing) = config.get_mut(key) else {
+        config.insert(key.to_string(), json!(command));
+        return Ok(true);
+    };
+    if existing.to_string().contains(command) {
+        return Ok(false);
+    }
+    match existing {
+        Value::String(current) if current.trim().is_empty() => *current = command.to_string(),
+        Value::String(current) => *current = format!("{} && {}", current, command),
+        // An argv array can't be chained — run it alongside as a named command
+        Value::Array(_) => *existing = json!({ "project": existing.clone(), "lawctl": command }),
+        Value::Object(commands) => {
+            commands.insert("lawctl".to_string(), json!(command));
+        }
+        _ => anyhow::bail!("\"{}\" is not a string, array or object", key),
+    }
+    Ok(true)
+}
+
+/// Turn JSON-with-comments into plain JSON: drop `//` and `/* */` comments
+/// and trailing commas, leaving string contents alone.
+fn strip_jsonc(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            (',', _) => {
+                // Trailing comma: the next significant character closes
+                let rest: String = chars.clone().collect();
+                let next = strip_leading_comments(&rest);
+                if !next.starts_with('}') && !next.starts_with(']') {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Skip whitespace and comments at the start of `s`.
+fn strip_leading_comments(mut s: &str) -> &str {
+    loop {
+        s = s.trim_start();
+        if let Some(rest) = s.strip_prefix("//") {
+            s = rest.split_once('\n').map_or("", |(_, after)| after);
+        } else if let Some(rest) = s.strip_prefix("/*") {
+            s = rest.split_once("*/").map_or("", |(_, after)| after);
+        } else {
+            return s;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_jsonc() {
+        let input = r#"{
+            // The image
+            "image": "x", /* inline */
+            "url": "https://example.com/a//b",
+            "list": [1, 2,],
+        }"#;
+        let value: Value = serde_json::from_str(&strip_jsonc(input)).unwrap();
+        assert_eq!(value["url"], "https://example.com/a//b");
+        assert_eq!(value["list"], json!([1, 2]));
+    }
+
+    #[test]
+    fn test_merge_keeps_existing_config() {
+        let mut config = json!({
+            "image": "x",
+            "features": { "ghcr.io/devcontainers/features/node:1": {} },
+            "postCreateCommand": "npm ci",
+            "postStartCommand": ["make", "dev"],
+        });
+        assert!(merge_lawctl(&mut config).unwrap());
+        assert_eq!(config["features"].as_object().unwrap().len(), 2);
+        assert_eq!(
+            config["postCreateCommand"],
+            format!("npm ci && {}", POST_CREATE)
+        );
+        assert_eq!(
+            config["postStartCommand"]["project"],
+            json!(["make", "dev"])
+        );
+        assert_eq!(config["postStartCommand"]["lawctl"], POST_START);
+
+        // Running it again changes nothing
+        assert!(!merge_lawctl(&mut config).unwrap());
+    }
+
+    #[test]
+    fn test_update_config_creates_and_backs_up() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join(".devcontainer/devcontainer.json");
+        assert!(update_config(&path, tmp.path()).unwrap());
+        let created: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(created["image"], DEFAULT_IMAGE);
+        assert!(!update_config(&path, tmp.path()).unwrap());
+
+        std::fs::write(&path, "{ // mine\n \"image\": \"custom\" }").unwrap();
+        assert!(update_config(&path, tmp.path()).unwrap());
+        assert!(tmp
+            .path()
+            .join(".devcontainer/devcontainer.json.lawctl-backup")
+            .exists());
+    }
+}
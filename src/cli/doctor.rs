@@ -0,0 +1,145 @@
+//! `lawctl doctor` — everything that could quietly stop lawctl protecting you.
+//!
+//! Runs the same checks as the session-start integrity check, plus the ones
+//! that only matter when something looks off: whether lawctl, lawctl-hook
+//! and lawctl-shim are the same version, whether the policy expects a newer
+//! lawctl, whether Claude Code's permissions agree with the policy, and any
+//! warnings the last session logged.
+
+use crate::audit::{AuditReader, LogEntry};
+use crate::cli::{export, gc, update};
+use crate::integrity::{self, Check, Expectations, IntegrityPaths, Status};
+use crate::policy::{parser, Policy, PolicyEngine};
+use crate::utils::term::glyph;
+use crate::utils::version::{self, VERSION};
+use anyhow::Result;
+use colored::Colorize;
+use std::path::Path;
+
+/// Run `lawctl doctor` for the policy at `policy_path`.
+pub async fn run_doctor(policy_path: &Path) -> Result<()> {
+    println!();
+    println!("  {}  {}", "lawctl doctor".bold(), VERSION.dimmed());
+    println!();
+
+    let mut checks = run_checks(policy_path);
+    let collected = gc::collect_garbage().await;
+    checks.push(if collected.is_empty() {
+        Check::new(
+            "Cleanup",
+            Status::Ok,
+            "nothing left over from earlier sessions",
+        )
+    } else {
+        Check::new(
+            "Cleanup",
+            Status::Ok,
+            format!("removed {}", collected.describe().join(", ")),
+        )
+    });
+    for check in &checks {
+        println!("{}", check.display());
+    }
+    println!();
+
+    let problems = checks.iter().filter(|c| c.status != Status::Ok).count();
+    if problems == 0 {
+        println!("  {} No problems found.", glyph("✓").green().bold());
+        println!();
+        return Ok(());
+    }
+    if checks.iter().any(|c| c.status == Status::Failed) {
+        anyhow::bail!(
+            "lawctl doctor found {} problem{}",
+            problems,
+            if problems == 1 { "" } else { "s" }
+        );
+    }
+    println!(
+        "  {} {} warning{} — lawctl is still protecting you.",
+        glyph("⚠").yellow(),
+        problems,
+        if problems == 1 { "" } else { "s" }
+    );
+    println!();
+    Ok(())
+}
+
+/// Every check `lawctl doctor` runs, for the policy at `policy_path`.
+pub fn run_checks(policy_path: &Path) -> Vec<Check> {
+    let mut checks = Vec::new();
+    match parser::parse_policy_file(policy_path)
+        .and_then(|p| Ok((PolicyEngine::new(p.clone())?, p)))
+    {
+        Ok((engine, policy)) => {
+            checks.push(Check::new(
+                "Policy",
+                Status::Ok,
+                format!("{} ({} rules)", policy.law, policy.rules.len()),
+            ));
+            let workspace = policy_path.parent().unwrap_or(Path::new("."));
+            let expectations = Expectations {
+                policy_path: policy_path.to_path_buf(),
+                policy_hash: engine.policy_hash(),
+                workspace: workspace.to_path_buf(),
+                requires_hook: false,
+            };
+            checks.extend(integrity::verify(&IntegrityPaths::default(), &expectations).checks);
+            checks.extend(export::sync_check(&policy, workspace));
+            checks.extend(version_checks(Some(&policy)));
+        }
+        Err(e) => {
+            checks.push(Check::new(
+                "Policy",
+                Status::Failed,
+                format!("{}: {:#}", policy_path.display(), e),
+            ));
+            checks.extend(version_checks(None));
+        }
+    }
+    if let Ok(entries) = AuditReader::new().and_then(|r| r.read_latest_session()) {
+        for warning in logged_warnings(&entries) {
+            checks.push(Check::new("Last session", Status::Warning, warning));
+        }
+    }
+    checks
+}
+
+/// Whether lawctl's binaries agree on a version, and whether the policy
+/// asks for a newer one.
+pub fn version_checks(policy: Option<&Policy>) -> Vec<Check> {
+    let mut checks = Vec::new();
+    let install_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.canonicalize().ok())
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    if let Some(dir) = install_dir {
+        checks.push(match update::skew_warning(&dir, VERSION) {
+            Some(warning) => Check::new("Versions", Status::Warning, warning),
+            None => Check::new(
+                "Versions",
+                Status::Ok,
+                format!("lawctl, lawctl-hook and lawctl-shim are all {}", VERSION),
+            ),
+        });
+    }
+    if let Some(warning) =
+        policy.and_then(|p| version::policy_requirement(p.requires_lawctl.as_deref()))
+    {
+        checks.push(Check::new("Versions", Status::Warning, warning));
+    }
+    checks
+}
+
+/// Distinct warnings logged in a session, oldest first.
+pub fn logged_warnings(entries: &[LogEntry]) -> Vec<String> {
+    let mut warnings: Vec<String> = Vec::new();
+    for entry in entries {
+        if let Some(warning) = &entry.warning {
+            if !warnings.contains(warning) {
+                warnings.push(warning.clone());
+            }
+        }
+    }
+    warnings
+}
@@ -0,0 +1,161 @@
+//! `lawctl explain` — why would the policy decide that?
+//!
+//! `lawctl explain write .env` runs one hypothetical action through the
+//! engine and shows its working: each rule for the action in order, which
+//! condition made it skip or which exception applied, the rule (or
+//! default) that decided, and any built-in protection that overrode the
+//! rules. Nothing is executed or logged.
+
+use crate::policy::engine::{Explanation, StepOutcome, TargetTrace};
+use crate::policy::testkit::ActionSpec;
+use crate::policy::{parser, Action, Decision, PolicyEngine};
+use crate::utils::term::glyph;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::path::Path;
+
+/// Fill in what a bare target means for the action: the command line for
+/// command actions, the URL for network.
+fn spec_from_args(mut spec: ActionSpec) -> ActionSpec {
+    let command_action = matches!(
+        Action::from_str_loose(&spec.action),
+        Some(Action::RunCmd | Action::K8s | Action::Cloud)
+    );
+    if command_action && spec.command.is_none() {
+        spec.command = spec.target.take();
+    }
+    let network = Action::from_str_loose(&spec.action) == Some(Action::Network);
+    if network && spec.url.is_none() && spec.target.as_deref().is_some_and(|t| t.contains("://")) {
+        spec.url = spec.target.take();
+    }
+    spec
+}
+
+fn decision_line(decision: &Decision) -> String {
+    match decision {
+        Decision::Allowed { .. } => "allowed".green().bold().to_string(),
+        Decision::Denied { reason, .. } => {
+            format!("{} — {}", "denied".red().bold(), reason)
+        }
+        Decision::RequiresApproval { reason, .. } => {
+            format!("{} — {}", "requires approval".yellow().bold(), reason)
+        }
+        Decision::Logged { reason, .. } => {
+            format!(
+                "{} — would be denied: {}",
+                "allowed (log only)".magenta().bold(),
+                reason
+            )
+        }
+    }
+}
+
+fn print_target(trace: &TargetTrace, show_name: bool) {
+    if show_name {
+        println!("  {}", trace.target.bold());
+    }
+    if trace.decided_by == "cedar" {
+        println!(
+            "    {} Cedar policies decided before any rule was checked",
+            glyph("•")
+        );
+    }
+    for step in &trace.steps {
+        let (icon, note) = match &step.outcome {
+            StepOutcome::Matched { conditions } if conditions.is_empty() => (
+                glyph("✓").green().bold().to_string(),
+                "matched (no conditions)".to_string(),
+            ),
+            StepOutcome::Matched { conditions } => (
+                glyph("✓").green().bold().to_string(),
+                format!("matched: {}", conditions.join(", ")),
+            ),
+            StepOutcome::Exception { condition } => (
+                glyph("↷").cyan().to_string(),
+                format!("exception: {} applies", condition),
+            ),
+            StepOutcome::Missed { condition } => (
+                glyph("✗").dimmed().to_string(),
+                format!("skipped: {} doesn't match", condition),
+            ),
+        };
+        println!(
+            "    {} {:>2}. {}  {}",
+            icon,
+            step.number,
+            step.rule,
+            note.dimmed()
+        );
+    }
+    let by = match trace.decided_by {
+        "default" if trace.steps.is_empty() => "no rules for this action — the default",
+        "default" => "no rule matched — the default",
+        "cedar" => "Cedar",
+        "exception" => "the deny rule's exception",
+        _ => "the matched rule",
+    };
+    println!(
+        "    {} {} ({})",
+        glyph("→"),
+        decision_line(&trace.decision),
+        by.dimmed()
+    );
+}
+
+fn print_explanation(spec: &ActionSpec, explanation: &Explanation) {
+    let multi = explanation.targets.len() > 1;
+    println!();
+    println!("  Explaining {}", spec.describe().bold());
+    println!();
+    for trace in &explanation.targets {
+        print_target(trace, multi);
+        if multi {
+            println!();
+        }
+    }
+    if let Some(check) = explanation.built_in {
+        println!();
+        println!(
+            "  {} A built-in check decided, not the rules: {}",
+            glyph("⚠").yellow(),
+            check
+        );
+    }
+    println!();
+    println!("  Final decision: {}", decision_line(&explanation.decision));
+    if explanation.other_rules > 0 {
+        println!(
+            "  {}",
+            format!(
+                "{} rule{} for other actions not consulted",
+                explanation.other_rules,
+                if explanation.other_rules == 1 {
+                    ""
+                } else {
+                    "s"
+                }
+            )
+            .dimmed()
+        );
+    }
+    println!();
+}
+
+/// Run `lawctl explain`.
+pub fn run_explain(spec: ActionSpec, policy_path: &Path, json: bool) -> Result<()> {
+    let spec = spec_from_args(spec);
+    let (action, context) = spec.action_and_context()?;
+    let policy = parser::parse_policy_tree(policy_path)?;
+    let workspace = policy_path.parent().unwrap_or(Path::new("."));
+    let engine = PolicyEngine::new(policy)
+        .context("Policy parsed but has invalid glob patterns")?
+        .with_workspace(workspace)
+        .with_policy_file(policy_path);
+    let explanation = engine.explain(&action, &context);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&explanation)?);
+    } else {
+        print_explanation(&spec, &explanation);
+    }
+    Ok(())
+}
@@ -0,0 +1,433 @@
+//! `lawctl export claude-permissions` — Claude Code permission entries that
+//! agree with the policy.
+//!
+//! With lawctl's hook installed, an action Claude Code doesn't already allow
+//! is asked about twice: once by Claude Code, once (or never) by lawctl.
+//! Exporting the policy's allow rules into `permissions.allow` leaves the
+//! deciding to lawctl, and its deny rules into `permissions.deny` stops
+//! Claude Code from offering to run what lawctl would block anyway.
+//!
+//! Only rules whose only condition is a command or path pattern translate
+//! (`if_matches` → `Bash(...)`, `if_path_matches` → `Edit(...)` or
+//! `Read(...)`); a
+//! `deny: network` with `unless_domain` exports its domains as allowed
+//! `WebFetch(domain:...)`. `require_approval` rules aren't exported:
+//! lawctl does the asking for those.
+
+use crate::cli::import;
+use crate::integrity::{Check, Status};
+use crate::policy::parser;
+use crate::policy::types::{Action, Conditions, Policy, Rule};
+use crate::utils::paths::{EXCLUDE_PREFIX, REGEX_PREFIX};
+use crate::utils::term::glyph;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Permission entries for Claude Code's settings.
+#[derive(Debug, Default, PartialEq)]
+pub struct ClaudePermissions {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+    /// Rules that couldn't be exported, and why
+    pub skipped: Vec<(String, String)>,
+}
+
+/// A lawctl command glob as a Claude Code `Bash(...)` specifier.
+fn bash_specifier(pattern: &str) -> Option<String> {
+    if pattern.starts_with(REGEX_PREFIX) {
+        return None;
+    }
+    let prefix = pattern
+        .strip_suffix(" *")
+        .or_else(|| pattern.strip_suffix('*'));
+    match prefix {
+        Some(prefix) if !prefix.contains('*') && !prefix.is_empty() => {
+            Some(format!("Bash({}:*)", prefix.trim_end()))
+        }
+        None if !pattern.contains('*') => Some(format!("Bash({})", pattern)),
+        _ => None,
+    }
+}
+
+/// A lawctl path pattern as a Claude Code `Edit(...)` or `Read(...)`
+/// specifier; absolute paths are written `//path` there.
+fn path_specifier(tool: &str, pattern: &str) -> String {
+    if pattern.starts_with('/') {
+        format!("{}(/{})", tool, pattern)
+    } else {
+        format!("{}({})", tool, pattern)
+    }
+}
+
+/// Whether `conditions` has nothing set but what `keep` leaves.
+fn only_condition(conditions: &Conditions, keep: impl FnOnce(&mut Conditions)) -> bool {
+    let mut rest = conditions.clone();
+    keep(&mut rest);
+    serde_json::to_value(&rest).ok() == serde_json::to_value(Conditions::default()).ok()
+}
+
+impl ClaudePermissions {
+    /// The entries for every rule of `policy` that translates.
+    pub fn from_policy(policy: &Policy) -> Self {
+        let mut out = Self::default();
+        for rule in &policy.rules {
+            let (list, conditions) = match rule {
+                Rule::Deny { conditions, .. } => (&mut out.deny, conditions),
+                Rule::Allow { conditions, .. } => (&mut out.allow, conditions),
+                Rule::RequireApproval { .. } | Rule::LogOnly { .. } => continue,
+            };
+            let is_deny = matches!(rule, Rule::Deny { .. });
+            let skip = |why: &str| (rule.describe(), why.to_string());
+            if conditions.expires.is_some() {
+                out.skipped
+                    .push(skip("Claude Code permissions have no expiry date"));
+                continue;
+            }
+            let entries: Result<Vec<String>, (String, String)> = match rule.action() {
+                Action::RunCmd
+                    if only_condition(conditions, |c| c.if_matches.clear()) || !is_deny =>
+                {
+                    if conditions.if_matches.is_empty() {
+                        Ok(vec!["Bash".to_string()])
+                    } else {
+                        conditions
+                            .if_matches
+                            .iter()
+                            .map(|p| bash_specifier(p))
+                            .collect::<Option<Vec<_>>>()
+                            .ok_or_else(|| {
+                                skip("only plain commands and trailing wildcards translate")
+                            })
+                    }
+                }
+                Action::Write | Action::Read
+                    if only_condition(conditions, |c| c.if_path_matches.clear()) || !is_deny =>
+                {
+                    let tool = if *rule.action() == Action::Write {
+                        "Edit"
+                    } else {
+                        "Read"
+                    };
+                    if conditions.if_path_matches.is_empty() {
+                        Ok(vec![tool.to_string()])
+                    } else if conditions
+                        .if_path_matches
+                        .iter()
+                        .any(|p| p.starts_with(EXCLUDE_PREFIX))
+                    {
+                        Err(skip("'!' exclusions have no Claude Code equivalent"))
+                    } else {
+                        Ok(conditions
+                            .if_path_matches
+                            .iter()
+                            .map(|p| path_specifier(tool, p))
+                            .collect())
+                    }
+                }
+                Action::Network
+                    if is_deny
+                        && !conditions.unless_domain.is_empty()
+                        && only_condition(conditions, |c| c.unless_domain.clear()) =>
+                {
+                    // The exceptions are what lawctl lets through
+                    for domain in &conditions.unless_domain {
+                        let entry = format!("WebFetch(domain:{})", domain);
+                        if !out.allow.contains(&entry) {
+                            out.allow.push(entry);
+                        }
+                    }
+                    continue;
+                }
+                Action::Network if only_condition(conditions, |_| {}) => {
+                    Ok(vec!["WebFetch".to_string()])
+                }
+                Action::RunCmd | Action::Write | Action::Read | Action::Network => {
+                    Err(skip("its conditions have no Claude Code equivalent"))
+                }
+                _ => Err(skip("Claude Code has no permission for this action")),
+            };
+            match entries {
+                Ok(entries) => {
+                    for entry in entries {
+                        if !list.contains(&entry) {
+                            list.push(entry);
+                        }
+                    }
+                }
+                Err(skipped) => out.skipped.push(skipped),
+            }
+        }
+        out
+    }
+
+    /// As a settings.json fragment.
+    pub fn to_json(&self) -> Value {
+        serde_json::json!({
+            "permissions": {
+                "allow": self.allow,
+                "deny": self.deny,
+            }
+        })
+    }
+
+    /// Add any missing entries to `settings`. Returns how many were added.
+    pub fn merge_into(&self, settings: &mut Value) -> Result<usize> {
+        let permissions = settings
+            .as_object_mut()
+            .context("settings is not an object")?
+            .entry("permissions")
+            .or_insert(serde_json::json!({}))
+            .as_object_mut()
+            .context("permissions is not an object")?;
+        let mut added = 0;
+        for (name, entries) in [("allow", &self.allow), ("deny", &self.deny)] {
+            let list = permissions
+                .entry(name)
+                .or_insert(serde_json::json!([]))
+                .as_array_mut()
+                .with_context(|| format!("permissions.{} is not an array", name))?;
+            for entry in entries {
+                if !list.iter().any(|e| e.as_str() == Some(entry)) {
+                    list.push(Value::String(entry.clone()));
+                    added += 1;
+                }
+            }
+        }
+        Ok(added)
+    }
+}
+
+/// Claude Code's `allow` and `deny` lists, merged across its settings files.
+fn claude_lists(sources: &[PathBuf]) -> (Vec<String>, Vec<String>) {
+    let mut allow = Vec::new();
+    let mut deny = Vec::new();
+    for source in sources {
+        let Some(settings) = std::fs::read_to_string(source)
+            .ok()
+            .and_then(|c| serde_json::from_str::<Value>(&c).ok())
+        else {
+            continue;
+        };
+        for (name, list) in [("allow", &mut allow), ("deny", &mut deny)] {
+            if let Some(entries) = settings
+                .pointer(&format!("/permissions/{}", name))
+                .and_then(Value::as_array)
+            {
+                list.extend(entries.iter().filter_map(Value::as_str).map(str::to_string));
+            }
+        }
+    }
+    (allow, deny)
+}
+
+/// `lawctl doctor`'s check that Claude Code's permissions agree with the
+/// policy. None when the project has no Claude Code settings at all.
+pub fn sync_check(policy: &Policy, workspace: &Path) -> Option<Check> {
+    const NAME: &str = "Claude permissions";
+    let sources: Vec<PathBuf> = import::default_sources(workspace)
+        .into_iter()
+        .filter(|p| p.exists())
+        .collect();
+    if sources.is_empty() {
+        return None;
+    }
+    let (allow, deny) = claude_lists(&sources);
+    let expected = ClaudePermissions::from_policy(policy);
+
+    let conflicts: Vec<&String> = deny.iter().filter(|e| expected.allow.contains(e)).collect();
+    if let Some(entry) = conflicts.first() {
+        return Some(Check::new(
+            NAME,
+            Status::Warning,
+            format!(
+                "Claude Code denies {}, which the policy allows{}",
+                entry,
+                if conflicts.len() > 1 {
+                    format!(" (and {} more)", conflicts.len() - 1)
+                } else {
+                    String::new()
+                }
+            ),
+        ));
+    }
+    let missing = expected
+        .allow
+        .iter()
+        .filter(|e| !allow.contains(e))
+        .chain(expected.deny.iter().filter(|e| !deny.contains(e)))
+        .count();
+    Some(if missing == 0 {
+        Check::new(NAME, Status::Ok, "in sync with the policy")
+    } else {
+        Check::new(
+            NAME,
+            Status::Warning,
+            format!(
+                "{} entr{} from the policy missing, so Claude Code asks first \
+                 (run `lawctl export claude-permissions --write`)",
+                missing,
+                if missing == 1 { "y" } else { "ies" }
+            ),
+        )
+    })
+}
+
+/// Run `lawctl export claude-permissions`.
+pub fn run_export_claude_permissions(policy_path: &Path, write: bool) -> Result<()> {
+    let policy = parser::parse_policy_file(policy_path)?;
+    let permissions = ClaudePermissions::from_policy(&policy);
+
+    if !write {
+        println!("{}", serde_json::to_string_pretty(&permissions.to_json())?);
+    } else {
+        let workspace = policy_path.parent().unwrap_or(Path::new("."));
+        let settings_path = workspace.join(".claude").join("settings.json");
+        let mut settings: Value = if settings_path.exists() {
+            let content = std::fs::read_to_string(&settings_path)
+                .with_context(|| format!("Failed to read {}", settings_path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", settings_path.display()))?
+        } else {
+            serde_json::json!({})
+        };
+        let added = permissions
+            .merge_into(&mut settings)
+            .with_context(|| format!("Unexpected layout in {}", settings_path.display()))?;
+        if added > 0 {
+            if settings_path.exists() {
+                let mut backup = settings_path.as_os_str().to_owned();
+                backup.push(".lawctl-backup");
+                std::fs::copy(&settings_path, PathBuf::from(backup))
+                    .with_context(|| format!("Failed to back up {}", settings_path.display()))?;
+            } else if let Some(parent) = settings_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&settings_path, serde_json::to_string_pretty(&settings)?)
+                .with_context(|| format!("Failed to write {}", settings_path.display()))?;
+        }
+        println!();
+        if added > 0 {
+            println!(
+                "  {} Added {} permission entr{} to {}",
+                glyph("✓").green().bold(),
+                added,
+                if added == 1 { "y" } else { "ies" },
+                settings_path.display().to_string().bold()
+            );
+        } else {
+            println!(
+                "  {} {} already has every entry from the policy",
+                glyph("✓").green().bold(),
+                settings_path.display().to_string().bold()
+            );
+        }
+    }
+
+    if !permissions.skipped.is_empty() {
+        eprintln!();
+        eprintln!(
+            "  {} {} rule{} not exported:",
+            glyph("⚠").yellow(),
+            permissions.skipped.len(),
+            if permissions.skipped.len() == 1 {
+                ""
+            } else {
+                "s"
+            }
+        );
+        for (rule, why) in &permissions.skipped {
+            eprintln!("    {} {} — {}", glyph("•"), rule, why.dimmed());
+        }
+        eprintln!();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_policy() {
+        let policy = parser::parse_policy_str(
+            r#"
+law: test
+rules:
+  - deny: run_cmd
+    if_matches: ["rm -rf *", "curl * | bash", "shutdown"]
+  - deny: write
+    if_path_matches: ["*.env", "/etc/**"]
+  - deny: write
+    if_path_matches: ["src/**"]
+    max_diff_lines: 10
+  - deny: read
+    if_path_matches: ["*.env"]
+  - deny: network
+    unless_domain: ["github.com", "docs.rs"]
+  - require_approval: git_push
+  - allow: run_cmd
+    if_matches: ["cargo *", "git status*"]
+  - allow: write
+    if_path_matches: ["src/**"]
+    max_diff_lines: 500
+  - allow: delete
+"#,
+        )
+        .unwrap();
+        let exported = ClaudePermissions::from_policy(&policy);
+        assert_eq!(
+            exported.allow,
+            [
+                "WebFetch(domain:github.com)",
+                "WebFetch(domain:docs.rs)",
+                "Bash(cargo:*)",
+                "Bash(git status:*)",
+                "Edit(src/**)"
+            ]
+        );
+        assert_eq!(
+            exported.deny,
+            ["Edit(*.env)", "Edit(//etc/**)", "Read(*.env)"]
+        );
+        assert_eq!(exported.skipped.len(), 3);
+    }
+
+    #[test]
+    fn test_merge_is_idempotent() {
+        let exported = ClaudePermissions {
+            allow: vec!["Bash(cargo:*)".to_string()],
+            deny: vec!["Edit(*.env)".to_string()],
+            skipped: Vec::new(),
+        };
+        let mut settings = serde_json::json!({
+            "permissions": { "allow": ["Read(**)"] },
+            "hooks": {}
+        });
+        assert_eq!(exported.merge_into(&mut settings).unwrap(), 2);
+        assert_eq!(exported.merge_into(&mut settings).unwrap(), 0);
+        assert_eq!(
+            settings["permissions"]["allow"],
+            serde_json::json!(["Read(**)", "Bash(cargo:*)"])
+        );
+        assert!(settings.get("hooks").is_some());
+    }
+
+    #[test]
+    fn test_round_trip_through_import() {
+        let policy = parser::parse_policy_str(
+            "law: test\nrules:\n  - allow: run_cmd\n    if_matches: [\"npm test *\"]\n",
+        )
+        .unwrap();
+        let mut translation = import::Translation::default();
+        translation.add_settings(&ClaudePermissions::from_policy(&policy).to_json());
+        assert_eq!(
+            translation.rules[0].condition,
+            Some((
+                "if_matches",
+                vec!["npm test".to_string(), "npm test *".to_string()]
+            ))
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Cleaning up after sessions that couldn't clean up after themselves.
+//!
+//! A lawctl that crashed or was `kill -9`ed leaves its gateway socket in
+//! the socket directory and, in Docker mode, its sandbox container behind.
+//! `lawctl go` and `lawctl doctor` sweep these up before doing anything
+//! else. A socket counts as dead when nothing accepts a connection on it;
+//! a container when the session it's labelled with isn't running.
+
+use crate::audit::{AuditReader, SessionStatus};
+use crate::sandbox::docker;
+use crate::utils::locations;
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+
+/// What a sweep removed.
+#[derive(Debug, Default)]
+pub struct Collected {
+    pub sockets: Vec<PathBuf>,
+    pub containers: Vec<String>,
+}
+
+impl Collected {
+    pub fn is_empty(&self) -> bool {
+        self.sockets.is_empty() && self.containers.is_empty()
+    }
+
+    /// One line per kind of thing removed, e.g. "2 dead gateway sockets".
+    pub fn describe(&self) -> Vec<String> {
+        let plural = |n: usize| if n == 1 { "" } else { "s" };
+        let mut lines = Vec::new();
+        if !self.sockets.is_empty() {
+            lines.push(format!(
+                "{} dead gateway socket{}",
+                self.sockets.len(),
+                plural(self.sockets.len())
+            ));
+        }
+        if !self.containers.is_empty() {
+            lines.push(format!(
+                "{} orphaned sandbox container{} ({})",
+                self.containers.len(),
+                plural(self.containers.len()),
+                self.containers.join(", ")
+            ));
+        }
+        lines
+    }
+}
+
+/// Gateway sockets in `dir` that nothing is listening on any more.
+pub fn dead_sockets(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let name = e.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("lawctl-") && name.ends_with(".sock")
+        })
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_socket()))
+        .map(|e| e.path())
+        .filter(|path| {
+            UnixStream::connect(path)
+                .is_err_and(|e| e.kind() == std::io::ErrorKind::ConnectionRefused)
+        })
+        .collect()
+}
+
+/// Remove dead sockets and orphaned containers. Best-effort: anything that
+/// can't be removed (or a Docker that isn't there) is only traced.
+pub async fn collect_garbage() -> Collected {
+    let sockets = locations::socket_dirs()
+        .iter()
+        .flat_map(|dir| dead_sockets(dir))
+        .filter(|path| match std::fs::remove_file(path) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::debug!("Couldn't remove {}: {}", path.display(), e);
+                false
+            }
+        })
+        .collect();
+
+    let reader = AuditReader::new().ok();
+    let running = |session: &str| {
+        reader
+            .as_ref()
+            .and_then(|r| r.session_status(session).ok())
+            .is_some_and(|status| matches!(status, SessionStatus::Running))
+    };
+    let containers = docker::remove_orphans(running).await.unwrap_or_else(|e| {
+        tracing::debug!("Skipped container cleanup: {:#}", e);
+        Vec::new()
+    });
+
+    Collected {
+        sockets,
+        containers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixListener;
+
+    #[test]
+    fn test_dead_sockets() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let _live = UnixListener::bind(dir.path().join("lawctl-live.sock")).unwrap();
+        drop(UnixListener::bind(dir.path().join("lawctl-dead.sock")).unwrap());
+        drop(UnixListener::bind(dir.path().join("other.sock")).unwrap());
+        std::fs::write(dir.path().join("lawctl-file.sock"), "").unwrap();
+
+        assert_eq!(
+            dead_sockets(dir.path()),
+            [dir.path().join("lawctl-dead.sock")]
+        );
+        assert_eq!(
+            crate::gateway::client::live_sockets(dir.path()),
+            [dir.path().join("lawctl-live.sock")]
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        let collected = Collected {
+            sockets: vec![PathBuf::from("/tmp/lawctl-a.sock")],
+            containers: vec!["lawctl-1234abcd".to_string(), "lawctl-5678abcd".to_string()],
+        };
+        assert_eq!(
+            collected.describe(),
+            [
+                "1 dead gateway socket",
+                "2 orphaned sandbox containers (lawctl-1234abcd, lawctl-5678abcd)"
+            ]
+        );
+    }
+}
@@ -6,7 +6,9 @@
 //! - If no policy exists, runs setup wizard first
 //! - No flags needed for the common case
 
-use crate::cli::run::RunOptions;
+use crate::cli::run::{OutputLevel, RunOptions};
+use crate::policy::parser;
+use crate::utils::term::glyph;
 use anyhow::Result;
 use colored::Colorize;
 use std::path::{Path, PathBuf};
@@ -39,12 +41,11 @@ fn which_exists(binary: &str) -> bool {
         .unwrap_or(false)
 }
 
-/// Find .lawctl.yaml by walking up from the current directory.
-fn find_policy_file() -> Option<PathBuf> {
+/// Find the policy file by walking up from the current directory.
+pub fn find_policy_file() -> Option<PathBuf> {
     let mut dir = std::env::current_dir().ok()?;
     loop {
-        let candidate = dir.join(".lawctl.yaml");
-        if candidate.exists() {
+        if let Some(candidate) = parser::policy_file_in(&dir) {
             return Some(candidate);
         }
         if !dir.pop() {
@@ -54,7 +55,9 @@ fn find_policy_file() -> Option<PathBuf> {
 }
 
 /// Run the `lawctl go` command.
-pub async fn run_go(explicit_command: Vec<String>) -> Result<()> {
+/// `options` carries the flags; the policy, command and agent name are
+/// filled in here.
+pub async fn run_go(explicit_command: Vec<String>, options: RunOptions) -> Result<()> {
     // Step 1: Find or create policy
     let policy_path = match find_policy_file() {
         Some(path) => path,
@@ -63,10 +66,10 @@ pub async fn run_go(explicit_command: Vec<String>) -> Result<()> {
             println!();
             println!(
                 "  {} No policy file found. Let's set one up first!",
-                "ℹ".blue()
+                glyph("ℹ").blue()
             );
             println!();
-            crate::cli::setup::run_setup()?;
+            crate::cli::setup::run_setup(&Default::default())?;
 
             // After setup, try to find the policy again
             find_policy_file().ok_or_else(|| {
@@ -77,6 +80,31 @@ pub async fn run_go(explicit_command: Vec<String>) -> Result<()> {
         }
     };
 
+    // Step 1b: Clear away whatever crashed sessions left behind
+    let collected = crate::cli::gc::collect_garbage().await;
+    if !collected.is_empty() && options.output != OutputLevel::Quiet {
+        println!();
+        println!(
+            "  {} Cleaned up after earlier sessions: {}",
+            glyph("ℹ").blue(),
+            collected.describe().join(", ").dimmed()
+        );
+    }
+
+    // Step 1c: A permissive policy that's had a few sessions could be tighter
+    if options.output != OutputLevel::Quiet {
+        let offer = crate::policy::parser::parse_policy_file(&policy_path)
+            .ok()
+            .and_then(|policy| {
+                let workspace = policy_path.parent().unwrap_or(Path::new("."));
+                crate::cli::tighten::offer(&policy, workspace)
+            });
+        if let Some(offer) = offer {
+            println!();
+            println!("  {} {}", glyph("ℹ").blue(), offer);
+        }
+    }
+
     // Step 2: Figure out what agent command to run
     let agent_command = if !explicit_command.is_empty() {
         explicit_command
@@ -84,8 +112,14 @@ pub async fn run_go(explicit_command: Vec<String>) -> Result<()> {
         // Try to auto-detect the agent
         match detect_agent() {
             Some((name, cmd)) => {
-                println!();
-                println!("  {} Detected {}, launching...", "▶".green(), name.bold());
+                if options.output != OutputLevel::Quiet {
+                    println!();
+                    println!(
+                        "  {} Detected {}, launching...",
+                        glyph("▶").green(),
+                        name.bold()
+                    );
+                }
                 cmd
             }
             None => {
@@ -125,7 +159,7 @@ pub async fn run_go(explicit_command: Vec<String>) -> Result<()> {
         policy_path,
         agent_command,
         agent_name,
-        ..Default::default()
+        ..options
     };
 
     crate::cli::run::run_agent(options).await
@@ -0,0 +1,107 @@
+//! `lawctl policy history` — every recorded version of the policy, newest
+//! first, with who changed it and the diff from the version before.
+//!
+//! Versions are recorded as lawctl loads the policy (see
+//! [`crate::policy::history`]); running this records the file as it is now,
+//! so an edit no session has loaded yet shows up too.
+
+use crate::policy::history::{self, Revision};
+use crate::utils::term::glyph;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use similar::{ChangeTag, TextDiff};
+use std::path::Path;
+
+/// Print the lines that changed between two versions, with a line of
+/// context around each change.
+fn print_diff(before: &str, after: &str) {
+    let diff = TextDiff::from_lines(before, after);
+    for (i, group) in diff.grouped_ops(1).iter().enumerate() {
+        if i > 0 {
+            println!("      {}", "…".dimmed());
+        }
+        for op in group {
+            for change in diff.iter_changes(op) {
+                let line = change.value().trim_end_matches('\n');
+                match change.tag() {
+                    ChangeTag::Delete => println!("      {}", format!("- {}", line).red()),
+                    ChangeTag::Insert => println!("      {}", format!("+ {}", line).green()),
+                    ChangeTag::Equal => println!("      {}", format!("  {}", line).dimmed()),
+                }
+            }
+        }
+    }
+}
+
+fn print_revision(revision: &Revision) {
+    let by = if revision.commit.is_some() {
+        revision.changed_by().normal()
+    } else {
+        revision.changed_by().yellow()
+    };
+    println!(
+        "  {} {}  {}  {}",
+        glyph("•"),
+        revision
+            .recorded_at
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+            .bold(),
+        revision.hash.dimmed(),
+        by
+    );
+    println!("    {}", format!("seen by {}", revision.via).dimmed());
+}
+
+/// Run `lawctl policy history`.
+pub fn run_policy_history(policy_path: &Path, limit: Option<usize>) -> Result<()> {
+    // Keyed by the project's full path, as sessions record it
+    let policy_path = &policy_path
+        .canonicalize()
+        .with_context(|| format!("Failed to read {}", policy_path.display()))?;
+    let workspace = policy_path.parent().unwrap_or(Path::new("."));
+    let dir = history::history_dir(workspace)?;
+    let commit = crate::policy::parser::parse_policy_file(policy_path)
+        .map(|policy| policy.commit_policy_history)
+        .unwrap_or(false);
+    history::record(&dir, policy_path, "lawctl policy history", commit)?;
+
+    let revisions = history::revisions(&dir);
+    let shown = limit.unwrap_or(revisions.len()).min(revisions.len());
+    println!();
+    println!(
+        "  {} version{} of {} recorded",
+        revisions.len(),
+        if revisions.len() == 1 { "" } else { "s" },
+        policy_path.display().to_string().bold()
+    );
+    println!();
+    for (i, revision) in revisions.iter().enumerate().rev().take(shown) {
+        print_revision(revision);
+        match i.checked_sub(1).map(|prev| &revisions[prev]) {
+            Some(previous) => match (
+                history::snapshot(&dir, previous),
+                history::snapshot(&dir, revision),
+            ) {
+                (Ok(before), Ok(after)) => print_diff(&before, &after),
+                (Err(e), _) | (_, Err(e)) => println!("      {}", e.to_string().dimmed()),
+            },
+            None => println!("      {}", "(first recorded version)".dimmed()),
+        }
+        println!();
+    }
+    if shown < revisions.len() {
+        println!(
+            "  {} older version{} not shown",
+            revisions.len() - shown,
+            if revisions.len() - shown == 1 {
+                ""
+            } else {
+                "s"
+            }
+        );
+        println!();
+    }
+    Ok(())
+}
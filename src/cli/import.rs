@@ -0,0 +1,505 @@
+//! `lawctl import claude-settings` — start a policy from Claude Code's
+//! permission lists.
+//!
+//! Claude Code keeps `permissions.allow`, `ask` and `deny` lists of
+//! `Tool(specifier)` entries in its settings files. The ones lawctl has an
+//! equivalent for become rules, in Claude Code's precedence (deny, then ask,
+//! then allow) since lawctl's first match wins:
+//!
+//! | Claude Code                  | lawctl                                    |
+//! |------------------------------|-------------------------------------------|
+//! | `Bash(npm run test:*)`       | `run_cmd`, `if_matches: ["npm run test", "npm run test *"]` |
+//! | `Bash(git status)`           | `run_cmd`, `if_matches: ["git status"]`   |
+//! | `Edit(src/**)`, `Write(...)` | `write`, `if_path_matches: ["src/**"]`    |
+//! | `Read(*.env)`                | `read`, `if_path_matches: ["*.env"]`      |
+//! | `WebFetch(domain:x.com)`     | allowed: a `network` approval `unless_domain` |
+//! | `WebFetch`                   | `network`                                 |
+//! | `additionalDirectories`      | `allowed_cwd` (absolute paths)            |
+//!
+//! Searches (`Glob`, `Grep`), web search, MCP tools and denied domains
+//! have no lawctl equivalent; they're listed in the report and as comments
+//! in the policy. Commands and edits nothing matches still need approval,
+//! as they do in Claude Code.
+
+use crate::policy::parser;
+use crate::utils::term::glyph;
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// A rule to write, as YAML fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedRule {
+    /// `deny`, `require_approval` or `allow`
+    pub kind: &'static str,
+    /// The lawctl action
+    pub action: &'static str,
+    /// Condition name and its patterns, if any
+    pub condition: Option<(&'static str, Vec<String>)>,
+    /// The Claude Code entries it came from
+    pub sources: Vec<String>,
+}
+
+/// The outcome of translating one or more settings files.
+#[derive(Debug, Default)]
+pub struct Translation {
+    pub rules: Vec<ImportedRule>,
+    pub allowed_cwd: Vec<String>,
+    /// Entries that weren't translated, and why
+    pub skipped: Vec<(String, String)>,
+    /// Whether file edits are approved up front (`acceptEdits` and up)
+    pub accept_edits: bool,
+}
+
+/// The settings files Claude Code reads for a project, broadest first.
+pub fn default_sources(project: &Path) -> Vec<PathBuf> {
+    let mut sources = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        sources.push(home.join(".claude").join("settings.json"));
+    }
+    sources.push(project.join(".claude").join("settings.json"));
+    sources.push(project.join(".claude").join("settings.local.json"));
+    sources
+}
+
+/// Split `Tool(specifier)` into its parts.
+fn split_entry(entry: &str) -> (&str, Option<&str>) {
+    match entry.split_once('(') {
+        Some((tool, rest)) => (tool.trim(), rest.strip_suffix(')')),
+        None => (entry.trim(), None),
+    }
+}
+
+/// `npm run test:*` → prefix patterns; anything else is used as given.
+fn command_patterns(spec: &str) -> Vec<String> {
+    match spec.strip_suffix(":*") {
+        Some(prefix) => vec![prefix.to_string(), format!("{} *", prefix)],
+        None => vec![spec.to_string()],
+    }
+}
+
+/// A Claude Code path pattern as a lawctl one. Claude's `/x` is relative to
+/// the settings file (the project), `//x` is absolute.
+fn path_pattern(spec: &str) -> Result<String, &'static str> {
+    if let Some(absolute) = spec.strip_prefix("//") {
+        return Ok(format!("/{}", absolute));
+    }
+    if let Some(rest) = spec.strip_prefix("~/") {
+        return dirs::home_dir()
+            .map(|home| home.join(rest).to_string_lossy().into_owned())
+            .ok_or("no home directory to expand ~ against");
+    }
+    let relative = spec
+        .strip_prefix("./")
+        .or_else(|| spec.strip_prefix('/'))
+        .unwrap_or(spec);
+    Ok(relative.to_string())
+}
+
+impl Translation {
+    /// Translate one settings file's `permissions`.
+    pub fn add_settings(&mut self, settings: &Value) {
+        let permissions = settings.get("permissions");
+        let list = |name: &str| -> Vec<String> {
+            permissions
+                .and_then(|p| p.get(name))
+                .and_then(Value::as_array)
+                .map(|a| {
+                    a.iter()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        for (list_name, kind) in [
+            ("deny", "deny"),
+            ("ask", "require_approval"),
+            ("allow", "allow"),
+        ] {
+            for entry in list(list_name) {
+                self.add_entry(&entry, kind);
+            }
+        }
+        for dir in list("additionalDirectories") {
+            if Path::new(&dir).is_absolute() {
+                if !self.allowed_cwd.contains(&dir) {
+                    self.allowed_cwd.push(dir);
+                }
+            } else {
+                self.skipped.push((
+                    format!("additionalDirectories: {}", dir),
+                    "allowed_cwd needs absolute paths".to_string(),
+                ));
+            }
+        }
+        if let Some(mode) = permissions
+            .and_then(|p| p.get("defaultMode"))
+            .and_then(Value::as_str)
+        {
+            self.accept_edits = matches!(mode, "acceptEdits" | "bypassPermissions");
+        }
+    }
+
+    fn add_entry(&mut self, entry: &str, kind: &'static str) {
+        // The same entry often sits in both the user and project settings
+        let seen = self
+            .rules
+            .iter()
+            .any(|r| r.sources.iter().any(|s| s == entry))
+            || self.skipped.iter().any(|(e, _)| e == entry);
+        if seen {
+            return;
+        }
+        let (tool, spec) = split_entry(entry);
+        let translated = match (tool, spec) {
+            ("Bash", None) | ("Bash", Some("*")) => Ok(("run_cmd", None)),
+            ("Bash", Some(spec)) => Ok(("run_cmd", Some(("if_matches", command_patterns(spec))))),
+            ("Edit" | "Write" | "MultiEdit" | "NotebookEdit", None) => Ok(("write", None)),
+            ("Edit" | "Write" | "MultiEdit" | "NotebookEdit", Some(spec)) => path_pattern(spec)
+                .map(|pattern| ("write", Some(("if_path_matches", vec![pattern])))),
+            ("Read", None) => Ok(("read", None)),
+            ("Read", Some(spec)) => {
+                path_pattern(spec).map(|pattern| ("read", Some(("if_path_matches", vec![pattern]))))
+            }
+            ("WebFetch", None) => Ok(("network", None)),
+            ("WebFetch", Some(spec)) => match (spec.strip_prefix("domain:"), kind) {
+                // Only the allowlist has a lawctl form: everything else asks
+                (Some(domain), "allow") => {
+                    self.add_allowed_domain(entry, domain);
+                    return;
+                }
+                (Some(_), _) => Err("lawctl can't single out a domain to deny or ask about"),
+                (None, _) => Err("only domain: specifiers can be translated"),
+            },
+            ("Glob" | "Grep" | "LS" | "NotebookRead", _) => {
+                Err("lawctl only sees reads through Read and the shell")
+            }
+            ("WebSearch", _) => Err("lawctl doesn't see web searches"),
+            (tool, _) if tool.starts_with("mcp__") => Err("lawctl doesn't see MCP tools"),
+            _ => Err("no lawctl equivalent"),
+        };
+        match translated {
+            Ok((action, condition)) => self.push_rule(kind, action, condition, entry),
+            Err(why) => self.skipped.push((entry.to_string(), why.to_string())),
+        }
+    }
+
+    /// Merge into an existing rule of the same kind, action and condition
+    /// name, so an allowlist of fifty commands stays one rule.
+    fn push_rule(
+        &mut self,
+        kind: &'static str,
+        action: &'static str,
+        condition: Option<(&'static str, Vec<String>)>,
+        source: &str,
+    ) {
+        let existing = self.rules.iter_mut().find(|r| {
+            r.kind == kind
+                && r.action == action
+                && r.condition.as_ref().map(|c| c.0) == condition.as_ref().map(|c| c.0)
+        });
+        match (existing, condition) {
+            (Some(rule), Some((_, patterns))) if rule.condition.is_some() => {
+                let (_, existing) = rule.condition.as_mut().unwrap();
+                for pattern in patterns {
+                    if !existing.contains(&pattern) {
+                        existing.push(pattern);
+                    }
+                }
+                rule.sources.push(source.to_string());
+            }
+            (Some(rule), None) => rule.sources.push(source.to_string()),
+            (_, condition) => self.rules.push(ImportedRule {
+                kind,
+                action,
+                condition,
+                sources: vec![source.to_string()],
+            }),
+        }
+    }
+
+    fn add_allowed_domain(&mut self, entry: &str, domain: &str) {
+        self.push_rule(
+            "require_approval",
+            "network",
+            Some(("unless_domain", vec![domain.to_string()])),
+            entry,
+        );
+    }
+
+    /// The rules in lawctl order: Claude Code's deny > ask > allow.
+    fn ordered_rules(&self) -> Vec<&ImportedRule> {
+        let rank = |rule: &ImportedRule| match (rule.kind, rule.condition.as_ref()) {
+            ("deny", _) => 0,
+            // The domain allowlist is only an approval for everything else
+            ("require_approval", Some(("unless_domain", _))) => 3,
+            ("require_approval", _) => 1,
+            _ => 2,
+        };
+        let mut rules: Vec<&ImportedRule> = self.rules.iter().collect();
+        rules.sort_by_key(|rule| rank(rule));
+        rules
+    }
+
+    /// The policy as YAML, with the sources and anything skipped as comments.
+    pub fn to_yaml(&self, sources: &[PathBuf]) -> String {
+        let quote = |s: &str| serde_json::to_string(s).unwrap_or_default();
+        let mut out = String::from("# Lawctl Policy: imported from Claude Code permissions\n");
+        for source in sources {
+            out.push_str(&format!("#   {}\n", source.display()));
+        }
+        out.push_str(
+            "# Deletes and git push follow lawctl's defaults (denied unless a rule\n\
+             # allows them); add rules for them below.\n",
+        );
+        if !self.skipped.is_empty() {
+            out.push_str("#\n# Not imported:\n");
+            for (entry, why) in &self.skipped {
+                out.push_str(&format!("#   {} — {}\n", entry, why));
+            }
+        }
+        out.push_str("\nlaw: claude-settings-import\n");
+        if !self.allowed_cwd.is_empty() {
+            let dirs: Vec<String> = self.allowed_cwd.iter().map(|d| quote(d)).collect();
+            out.push_str(&format!("\nallowed_cwd: [{}]\n", dirs.join(", ")));
+        }
+        out.push_str("\nrules:\n");
+        for rule in self.ordered_rules() {
+            out.push_str(&format!("  # {}\n", rule.sources.join(", ")));
+            out.push_str(&format!("  - {}: {}\n", rule.kind, rule.action));
+            if let Some((name, patterns)) = &rule.condition {
+                let patterns: Vec<String> = patterns.iter().map(|p| quote(p)).collect();
+                out.push_str(&format!("    {}: [{}]\n", name, patterns.join(", ")));
+            }
+        }
+        out.push_str(
+            "\n  # Anything not listed above asks first, as in Claude Code\n  \
+             - require_approval: run_cmd\n    \
+             prompt: \"This command isn't in your imported allowlist.\"\n",
+        );
+        if !self.accept_edits {
+            out.push_str(
+                "  - require_approval: write\n    \
+                 prompt: \"This edit isn't in your imported allowlist.\"\n",
+            );
+        }
+        out
+    }
+}
+
+/// Run `lawctl import claude-settings`.
+pub fn run_import_claude_settings(
+    from: &[PathBuf],
+    output: Option<&Path>,
+    force: bool,
+) -> Result<()> {
+    let cwd = std::env::current_dir().context("Failed to get current directory")?;
+    let mut sources: Vec<PathBuf> = if from.is_empty() {
+        default_sources(&cwd)
+            .into_iter()
+            .filter(|p| p.exists())
+            .collect()
+    } else {
+        from.to_vec()
+    };
+    // Run from $HOME, the user and project settings are the same file
+    let mut seen = std::collections::HashSet::new();
+    sources.retain(|p| seen.insert(p.canonicalize().unwrap_or_else(|_| p.clone())));
+    if sources.is_empty() {
+        bail!("No Claude Code settings found (looked in ~/.claude and ./.claude); pass --from <settings.json>");
+    }
+
+    let mut translation = Translation::default();
+    for source in &sources {
+        let content = std::fs::read_to_string(source)
+            .with_context(|| format!("Failed to read {}", source.display()))?;
+        let settings: Value = serde_json::from_str(&content)
+            .with_context(|| format!("{} isn't valid JSON", source.display()))?;
+        translation.add_settings(&settings);
+    }
+    let yaml = translation.to_yaml(&sources);
+    parser::parse_policy_str(&yaml).context("The imported policy doesn't validate")?;
+
+    let to_stdout = output.is_some_and(|p| p == Path::new("-"));
+    if to_stdout {
+        print!("{}", yaml);
+    } else {
+        let path = output
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| cwd.join(".lawctl.yaml"));
+        if path.exists() && !force {
+            bail!(
+                "{} already exists. Use --force to replace it, or --output - to print the policy",
+                path.display()
+            );
+        }
+        std::fs::write(&path, &yaml)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!();
+        println!(
+            "  {} Wrote {} ({} rules from {} settings file{})",
+            glyph("✓").green().bold(),
+            path.display().to_string().bold(),
+            translation.rules.len(),
+            sources.len(),
+            if sources.len() == 1 { "" } else { "s" }
+        );
+    }
+
+    // The report goes to stderr when the policy itself is on stdout
+    let report = |line: String| {
+        if to_stdout {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
+        }
+    };
+    if !translation.skipped.is_empty() {
+        report(String::new());
+        report(format!(
+            "  {} {} entr{} couldn't be translated:",
+            glyph("⚠").yellow(),
+            translation.skipped.len(),
+            if translation.skipped.len() == 1 {
+                "y"
+            } else {
+                "ies"
+            }
+        ));
+        for (entry, why) in &translation.skipped {
+            report(format!("    {} {} — {}", glyph("•"), entry, why.dimmed()));
+        }
+    }
+    report(String::new());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::types::Action;
+    use crate::policy::{ActionContext, Decision, PolicyEngine};
+
+    fn translate(settings: Value) -> Translation {
+        let mut translation = Translation::default();
+        translation.add_settings(&settings);
+        translation
+    }
+
+    #[test]
+    fn test_translate_entries() {
+        let t = translate(serde_json::json!({
+            "permissions": {
+                "allow": [
+                    "Bash(npm run test:*)",
+                    "Bash(git status)",
+                    "Edit(src/**)",
+                    "Write(./docs/*.md)",
+                    "WebFetch(domain:docs.rs)",
+                    "Read(~/.zshrc)",
+                    "mcp__github__create_issue"
+                ],
+                "deny": ["Bash(curl:*)", "Edit(//etc/**)", "WebFetch(domain:evil.com)"],
+                "additionalDirectories": ["/srv/shared", "../sibling"]
+            }
+        }));
+
+        let allow_cmd = t
+            .rules
+            .iter()
+            .find(|r| r.kind == "allow" && r.action == "run_cmd")
+            .unwrap();
+        assert_eq!(
+            allow_cmd.condition,
+            Some((
+                "if_matches",
+                vec![
+                    "npm run test".to_string(),
+                    "npm run test *".to_string(),
+                    "git status".to_string()
+                ]
+            ))
+        );
+        let allow_write = t
+            .rules
+            .iter()
+            .find(|r| r.kind == "allow" && r.action == "write")
+            .unwrap();
+        assert_eq!(
+            allow_write.condition,
+            Some((
+                "if_path_matches",
+                vec!["src/**".to_string(), "docs/*.md".to_string()]
+            ))
+        );
+        assert!(t.rules.iter().any(|r| r.kind == "deny"
+            && r.condition == Some(("if_path_matches", vec!["/etc/**".to_string()]))));
+        assert_eq!(t.allowed_cwd, ["/srv/shared"]);
+        let home = dirs::home_dir().unwrap();
+        assert!(t.rules.iter().any(|r| r.kind == "allow"
+            && r.action == "read"
+            && r.condition
+                == Some((
+                    "if_path_matches",
+                    vec![home.join(".zshrc").to_string_lossy().into_owned()]
+                ))));
+
+        let skipped: Vec<&str> = t.skipped.iter().map(|(e, _)| e.as_str()).collect();
+        assert_eq!(
+            skipped,
+            [
+                "WebFetch(domain:evil.com)",
+                "mcp__github__create_issue",
+                "additionalDirectories: ../sibling"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_imported_policy_behaves_like_claude_code() {
+        let t = translate(serde_json::json!({
+            "permissions": {
+                "allow": ["Bash(cargo:*)", "Edit(src/**)", "WebFetch(domain:docs.rs)"],
+                "ask": ["Bash(cargo publish:*)"],
+                "deny": ["Edit(src/secrets.rs)"]
+            }
+        }));
+        let yaml = t.to_yaml(&[PathBuf::from(".claude/settings.json")]);
+        let engine = PolicyEngine::new(parser::parse_policy_str(&yaml).unwrap()).unwrap();
+
+        let run = |cmd: &str| {
+            engine.evaluate(
+                &Action::RunCmd,
+                &ActionContext::new("shell").with_command(cmd),
+            )
+        };
+        assert!(matches!(run("cargo test"), Decision::Allowed { .. }));
+        assert!(matches!(
+            run("cargo publish"),
+            Decision::RequiresApproval { .. }
+        ));
+        assert!(matches!(run("make"), Decision::RequiresApproval { .. }));
+
+        let write = |path: &str| engine.evaluate(&Action::Write, &ActionContext::new(path));
+        assert!(matches!(write("src/main.rs"), Decision::Allowed { .. }));
+        assert!(write("src/secrets.rs").is_denied());
+        assert!(matches!(
+            write("README.md"),
+            Decision::RequiresApproval { .. }
+        ));
+
+        let fetch = |url: &str, host: &str| {
+            engine.evaluate(&Action::Network, &ActionContext::new(url).with_domain(host))
+        };
+        assert!(matches!(
+            fetch("https://docs.rs/serde", "docs.rs"),
+            Decision::Allowed { .. }
+        ));
+        assert!(matches!(
+            fetch("https://example.com", "example.com"),
+            Decision::RequiresApproval { .. }
+        ));
+    }
+}
@@ -5,6 +5,7 @@
 //! Designed to be the very first thing a new user runs.
 
 use crate::policy::defaults;
+use crate::utils::term::glyph;
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::path::{Path, PathBuf};
@@ -61,7 +62,7 @@ pub fn run_init(template: Option<&str>, output_path: Option<&str>) -> Result<()>
     if output_file.exists() {
         println!(
             "{} A policy file already exists at {}",
-            "⚠".yellow(),
+            glyph("⚠").yellow(),
             output_file.display()
         );
         println!("  Use --force to overwrite it, or edit it directly.");
@@ -90,39 +91,66 @@ pub fn run_init(template: Option<&str>, output_path: Option<&str>) -> Result<()>
     println!();
     println!(
         "  {} Created {}",
-        "✓".green().bold(),
+        glyph("✓").green().bold(),
         output_file.display().to_string().bold()
     );
     println!();
     println!("  Detected project type: {}", project_type.name().cyan());
     println!("  Template: {}", template_name.cyan());
     println!();
-    println!("  {} What this policy does:", "ℹ".blue());
+    println!("  {} What this policy does:", glyph("ℹ").blue());
 
     match template_name {
         "safe-dev" => {
-            println!("    • Blocks agents from touching your secrets (.env, .ssh, .pem files)");
-            println!("    • Prevents accidental file deletions (except /tmp and build dirs)");
-            println!("    • Blocks dangerous shell commands (rm -rf, curl|bash, etc.)");
-            println!("    • Requires YOUR approval before any git push");
-            println!("    • Allows writes to source and test directories");
+            println!(
+                "    {} Blocks agents from touching your secrets (.env, .ssh, .pem files)",
+                glyph("•")
+            );
+            println!(
+                "    {} Prevents accidental file deletions (except /tmp and build dirs)",
+                glyph("•")
+            );
+            println!(
+                "    {} Blocks dangerous shell commands (rm -rf, curl|bash, etc.)",
+                glyph("•")
+            );
+            println!(
+                "    {} Requires YOUR approval before any git push",
+                glyph("•")
+            );
+            println!(
+                "    {} Allows writes to source and test directories",
+                glyph("•")
+            );
         }
         "safe-ci" => {
-            println!("    • Blocks all git push operations");
-            println!("    • Only allows writes to build output directories");
-            println!("    • Restricts network to package registries only");
-            println!("    • Blocks all file deletions");
+            println!("    {} Blocks all git push operations", glyph("•"));
+            println!(
+                "    {} Only allows writes to build output directories",
+                glyph("•")
+            );
+            println!(
+                "    {} Restricts network to package registries only",
+                glyph("•")
+            );
+            println!("    {} Blocks all file deletions", glyph("•"));
         }
         "permissive" => {
-            println!("    • Allows all actions (except git push needs approval)");
-            println!("    • Logs everything the agent does");
-            println!("    • Good for testing — switch to safe-dev once comfortable");
+            println!(
+                "    {} Allows all actions (except git push needs approval)",
+                glyph("•")
+            );
+            println!("    {} Logs everything the agent does", glyph("•"));
+            println!(
+                "    {} Good for testing — switch to safe-dev once comfortable",
+                glyph("•")
+            );
         }
         _ => {}
     }
 
     println!();
-    println!("  {} Next steps:", "→".blue());
+    println!("  {} Next steps:", glyph("→").blue());
     println!(
         "    1. Review the policy: {}",
         format!("cat {}", output_file.display()).dimmed()
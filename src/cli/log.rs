@@ -2,13 +2,26 @@
 //!
 //! Shows what happened in a session: every action the agent attempted,
 //! what was allowed, what was blocked, and what required approval.
-//! This is the "what just happened?" command.
+//! This is the "what just happened?" command. It looks at the current
+//! project's sessions unless given `--all`.
 
-use crate::audit::{AuditReader, DecisionFilter, LogFilter};
+use crate::audit::projects::project_root;
+use crate::audit::{AuditReader, DecisionFilter, LogEntry, LogFilter, SessionStatus};
 use crate::policy::types::Action;
+use crate::utils::process::{self, ProcessInfo};
+use crate::utils::term::glyph;
 use anyhow::{Context, Result};
 use colored::Colorize;
 
+/// A reader for the current project's sessions, or every project's.
+fn reader(all: bool) -> Result<AuditReader> {
+    if all {
+        return AuditReader::new();
+    }
+    let cwd = std::env::current_dir().context("Failed to get current directory")?;
+    AuditReader::for_workspace(&project_root(&cwd))
+}
+
 /// Run the `lawctl log` command.
 pub fn run_log(
     session_id: Option<&str>,
@@ -16,8 +29,10 @@ pub fn run_log(
     decision_filter: Option<&str>,
     limit: Option<usize>,
     summary_only: bool,
+    all: bool,
+    by_process: bool,
 ) -> Result<()> {
-    let reader = AuditReader::new().context("Failed to initialize log reader")?;
+    let reader = reader(all).context("Failed to initialize log reader")?;
 
     // Read entries
     let entries = if let Some(sid) = session_id {
@@ -28,7 +43,15 @@ pub fn run_log(
         let entries = reader.read_latest_session()?;
         if entries.is_empty() {
             println!();
-            println!("  {} No audit logs found.", "ℹ".blue());
+            if all {
+                println!("  {} No audit logs found.", glyph("ℹ").blue());
+            } else {
+                println!(
+                    "  {} No audit logs found for this project.",
+                    glyph("ℹ").blue()
+                );
+                println!("  Other projects: {}", "lawctl log --all".dimmed());
+            }
             println!("  Run an agent through lawctl first:");
             println!("    {}", "lawctl run -- <your agent command>".dimmed());
             println!();
@@ -52,13 +75,19 @@ pub fn run_log(
 
     let filtered = AuditReader::filter_entries(&entries, &filter);
 
+    let uploads = entries
+        .first()
+        .and_then(|e| reader.read_session_records(&e.session_id).ok())
+        .unwrap_or_default();
+
     if summary_only {
         // Just show the summary
-        let summary = AuditReader::summarize(&entries);
+        let mut summary = AuditReader::summarize(&entries);
+        summary.add_uploads(&uploads);
         println!();
         println!(
             "  {} Session: {}",
-            "📋".to_string().bold(),
+            glyph("📋").bold(),
             summary.session_id.cyan()
         );
         println!("  Agent: {}", summary.agent);
@@ -70,6 +99,9 @@ pub fn run_log(
             summary.denied.to_string().red().bold(),
             summary.approved.to_string().yellow().bold(),
         );
+        if let Some(uploads) = summary.uploads_line() {
+            println!("  {}", uploads);
+        }
 
         if let (Some(start), Some(end)) = (summary.start_time, summary.end_time) {
             let duration = end - start;
@@ -88,16 +120,32 @@ pub fn run_log(
             println!();
         }
 
-        for entry in &filtered {
-            println!("  {}", AuditReader::format_entry(entry));
+        if by_process {
+            for (process, entries) in group_by_process(&filtered) {
+                println!(
+                    "  {} {} {}",
+                    glyph("▸").bold(),
+                    process.bold(),
+                    format!("— {} actions", entries.len()).dimmed()
+                );
+                for entry in entries {
+                    println!("    {}", AuditReader::format_entry(entry));
+                }
+                println!();
+            }
+        } else {
+            for entry in &filtered {
+                println!("  {}", AuditReader::format_entry(entry));
+            }
         }
 
         // Show summary at the bottom
-        let summary = AuditReader::summarize(&entries);
+        let mut summary = AuditReader::summarize(&entries);
+        summary.add_uploads(&uploads);
         println!();
         println!(
             "  {} {}",
-            "─".repeat(40).dimmed(),
+            glyph("─").repeat(40).dimmed(),
             summary.one_line().dimmed()
         );
         println!();
@@ -106,23 +154,69 @@ pub fn run_log(
     Ok(())
 }
 
+/// Entries grouped by the process they're put down to, each group labelled
+/// with that process and its ancestors, in order of first appearance.
+fn group_by_process(entries: &[LogEntry]) -> Vec<(String, Vec<&LogEntry>)> {
+    let mut groups: Vec<(Option<&ProcessInfo>, String, Vec<&LogEntry>)> = Vec::new();
+    for entry in entries {
+        let requester = entry.requester();
+        if let Some((_, _, group)) = groups.iter_mut().find(|(r, _, _)| *r == requester) {
+            group.push(entry);
+            continue;
+        }
+        let label = match requester {
+            Some(requester) => {
+                let from = entry
+                    .process_chain
+                    .iter()
+                    .position(|p| p == requester)
+                    .unwrap_or(0);
+                process::describe(&entry.process_chain[from..])
+            }
+            None => "unknown process".to_string(),
+        };
+        groups.push((requester, label, vec![entry]));
+    }
+    groups
+        .into_iter()
+        .map(|(_, label, entries)| (label, entries))
+        .collect()
+}
+
 /// List available sessions.
-pub fn run_log_list() -> Result<()> {
-    let reader = AuditReader::new()?;
+pub fn run_log_list(all: bool) -> Result<()> {
+    let reader = reader(all)?;
     let sessions = reader.list_sessions()?;
 
     if sessions.is_empty() {
         println!();
-        println!("  {} No sessions found.", "ℹ".blue());
+        println!("  {} No sessions found.", glyph("ℹ").blue());
         println!();
         return Ok(());
     }
 
     println!();
-    println!("  {} Available sessions:", "📋".to_string().bold());
+    println!("  {} Available sessions:", glyph("📋").bold());
     println!();
     for session in &sessions {
-        println!("  • {}", session);
+        let status = reader
+            .session_status(session)
+            .unwrap_or(SessionStatus::Unknown);
+        match status.describe() {
+            Some(note) if status.is_abnormal() => println!(
+                "  {} {}  {}",
+                glyph("⚠").yellow(),
+                session,
+                format!("({})", note).yellow()
+            ),
+            Some(note) => println!(
+                "  {} {}  {}",
+                glyph("•"),
+                session,
+                format!("({})", note).dimmed()
+            ),
+            None => println!("  {} {}", glyph("•"), session),
+        }
     }
     println!();
     println!("  View a session: {}", "lawctl log --session <id>".dimmed());
@@ -131,7 +225,7 @@ pub fn run_log_list() -> Result<()> {
     Ok(())
 }
 
-fn format_duration(seconds: i64) -> String {
+pub(crate) fn format_duration(seconds: i64) -> String {
     if seconds < 60 {
         format!("{}s", seconds)
     } else if seconds < 3600 {
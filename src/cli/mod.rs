@@ -1,5 +1,25 @@
+pub mod bugreport;
+pub mod completions;
+pub mod debug;
+pub mod devcontainer;
+pub mod doctor;
+pub mod explain;
+pub mod export;
+pub mod gc;
 pub mod go;
+pub mod history;
+pub mod import;
 pub mod init;
 pub mod log;
+pub mod policy_effect;
+pub mod policy_test;
+pub mod report;
 pub mod run;
+pub mod schema;
 pub mod setup;
+pub mod simulate;
+pub mod status;
+pub mod template;
+pub mod tighten;
+pub mod trap;
+pub mod update;
@@ -0,0 +1,252 @@
+//! `lawctl diff-policy-effect` — did a policy change do what you meant?
+//!
+//! Takes a session from before the change and one from after. The
+//! "before" session's actions are replayed through the current policy (or
+//! `--policy`) and compared with what was logged at the time: what's now
+//! denied, what now asks first, what's now let through. The two sessions'
+//! logged outcomes are shown side by side too, so the replay can be checked
+//! against how the new policy actually behaved.
+
+use crate::audit::{AuditReader, LogEntry, SessionSummary};
+use crate::cli::simulate::replay_context;
+use crate::policy::{parser, Action, Decision, PolicyEngine};
+use crate::utils::term::glyph;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::path::Path;
+
+/// Most changes listed per kind.
+const MAX_LISTED: usize = 10;
+
+/// How an action turned out, as far as the comparison cares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Allowed,
+    /// Asked a human (whatever they answered)
+    Asked,
+    Denied,
+}
+
+impl Outcome {
+    /// The outcome a policy decision leads to.
+    pub fn of(decision: &Decision) -> Self {
+        match decision {
+            Decision::Allowed { .. } | Decision::Logged { .. } => Outcome::Allowed,
+            Decision::RequiresApproval { .. } => Outcome::Asked,
+            Decision::Denied { .. } => Outcome::Denied,
+        }
+    }
+
+    /// The outcome recorded in a log entry. Approved actions are logged as
+    /// allowed, with who approved them.
+    pub fn logged(entry: &LogEntry) -> Self {
+        let approved = entry.approved_by.is_some()
+            || matches!(&entry.decision, Decision::Allowed { matched_rule: Some(rule) } if rule == "approved by human");
+        match Outcome::of(&entry.decision) {
+            Outcome::Allowed if approved => Outcome::Asked,
+            outcome => outcome,
+        }
+    }
+}
+
+/// An action whose outcome the policy would change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub action: Action,
+    /// The command for run_cmd, the targets otherwise
+    pub target: String,
+    pub was: Outcome,
+    pub now: Outcome,
+}
+
+/// Replay a session's entries through `engine` and keep those that come
+/// out differently than they were logged.
+pub fn replay_changes(engine: &PolicyEngine, entries: &[LogEntry]) -> Vec<Change> {
+    entries
+        .iter()
+        .filter(|entry| !entry.network.as_ref().is_some_and(|n| n.transfer_record))
+        .filter_map(|entry| {
+            let was = Outcome::logged(entry);
+            let now = Outcome::of(&engine.evaluate(&entry.action, &replay_context(entry)));
+            let target = match entry.action {
+                Action::RunCmd => entry.diff.clone().unwrap_or_else(|| entry.target.clone()),
+                _ => entry.target.clone(),
+            };
+            (was != now).then(|| Change {
+                action: entry.action.clone(),
+                target,
+                was,
+                now,
+            })
+        })
+        .collect()
+}
+
+fn percent(part: usize, whole: usize) -> String {
+    if whole == 0 {
+        return "—".to_string();
+    }
+    format!("{:.0}%", part as f64 * 100.0 / whole as f64)
+}
+
+fn print_session(label: &str, summary: &SessionSummary) {
+    println!(
+        "  {:<7} {}  {}",
+        label,
+        summary.session_id.dimmed(),
+        summary.one_line()
+    );
+    println!(
+        "          denied {}, approved {}",
+        percent(summary.denied, summary.total_actions),
+        percent(summary.approved, summary.total_actions)
+    );
+}
+
+fn print_changes(title: &str, changes: &[&Change], paint: fn(String) -> colored::ColoredString) {
+    if changes.is_empty() {
+        return;
+    }
+    println!();
+    println!("  {} ({}):", paint(title.to_string()).bold(), changes.len());
+    for change in changes.iter().take(MAX_LISTED) {
+        println!(
+            "    {} {:<10} {}",
+            glyph("•"),
+            change.action.to_string(),
+            change.target
+        );
+    }
+    if changes.len() > MAX_LISTED {
+        println!(
+            "    {}",
+            format!("… and {} more", changes.len() - MAX_LISTED).dimmed()
+        );
+    }
+}
+
+/// Run `lawctl diff-policy-effect`.
+pub fn run_diff_policy_effect(before: &str, after: &str, policy_path: &Path) -> Result<()> {
+    let policy = parser::parse_policy_file(policy_path)?;
+    let engine = PolicyEngine::new(policy).context("Failed to load policy")?;
+    let reader = AuditReader::new().context("Failed to initialize log reader")?;
+    let read = |id: &str| {
+        reader
+            .read_session(id)
+            .with_context(|| format!("Failed to read session: {}", id))
+    };
+    let (before_entries, after_entries) = (read(before)?, read(after)?);
+    let changes = replay_changes(&engine, &before_entries);
+
+    println!();
+    print_session("Before", &AuditReader::summarize(&before_entries));
+    print_session("After", &AuditReader::summarize(&after_entries));
+    println!();
+    println!(
+        "  Replaying the {} actions of {} under {}:",
+        before_entries.len(),
+        before.dimmed(),
+        policy_path.display().to_string().bold()
+    );
+
+    if changes.is_empty() {
+        println!();
+        println!(
+            "  {} No differences — this policy decides every one of them the same way.",
+            glyph("ℹ").blue()
+        );
+        println!();
+        return Ok(());
+    }
+    let of = |now: Outcome| changes.iter().filter(|c| c.now == now).collect::<Vec<_>>();
+    print_changes("Newly denied", &of(Outcome::Denied), |s| s.red());
+    print_changes("Approvals added", &of(Outcome::Asked), |s| s.yellow());
+    print_changes("Newly allowed", &of(Outcome::Allowed), |s| s.green());
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::Actor;
+    use chrono::Utc;
+
+    fn entry(action: Action, target: &str, diff: Option<&str>, decision: Decision) -> LogEntry {
+        LogEntry {
+            timestamp: Utc::now(),
+            session_id: "before".to_string(),
+            agent: "test".to_string(),
+            action,
+            target: target.to_string(),
+            policy_rule: None,
+            decision,
+            diff: diff.map(String::from),
+            approved_by: None,
+            eval_duration_us: None,
+            network: None,
+            warning: None,
+            output: None,
+            threats: Vec::new(),
+            actor: Actor::Agent,
+            process_chain: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_replay_changes() {
+        let allowed = Decision::Allowed { matched_rule: None };
+        let entries = vec![
+            entry(
+                Action::Write,
+                "src/main.rs",
+                Some("fn main() {}"),
+                allowed.clone(),
+            ),
+            entry(Action::Write, ".env", Some("KEY=1"), allowed.clone()),
+            entry(
+                Action::RunCmd,
+                "shell",
+                Some("npm install left-pad"),
+                allowed,
+            ),
+            entry(
+                Action::Delete,
+                "build/out.o",
+                None,
+                Decision::Denied {
+                    reason: "no".to_string(),
+                    matched_rule: None,
+                },
+            ),
+        ];
+        let policy = parser::parse_policy_str(
+            r#"
+law: after
+rules:
+  - deny: write
+    if_path_matches: ["*.env"]
+  - require_approval: run_cmd
+    if_matches: ["npm install*"]
+  - allow: delete
+    if_path_matches: ["build/**"]
+  - allow: write
+"#,
+        )
+        .unwrap();
+        let changes = replay_changes(&PolicyEngine::new(policy).unwrap(), &entries);
+
+        let summary: Vec<(&str, Outcome, Outcome)> = changes
+            .iter()
+            .map(|c| (c.target.as_str(), c.was, c.now))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (".env", Outcome::Allowed, Outcome::Denied),
+                ("npm install left-pad", Outcome::Allowed, Outcome::Asked),
+                ("build/out.o", Outcome::Denied, Outcome::Allowed),
+            ]
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! `lawctl test` — run a policy's unit tests (`.lawctl.tests.yaml`).
+//!
+//! Prints one line per case and a summary, and fails when any case does,
+//! so it can gate policy changes in CI. The test file format is described
+//! in [`crate::policy::testkit`].
+
+use crate::policy::testkit::{self, TestFile, TestResult};
+use crate::policy::{parser, Decision, PolicyEngine};
+use crate::utils::term::glyph;
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// The policy a test file runs against: `--policy` if given, then the
+/// file's own `policy:`, then the policy next to it.
+fn policy_for(tests_path: &Path, file: &TestFile, policy: Option<&Path>) -> PathBuf {
+    let dir = tests_path.parent().unwrap_or(Path::new("."));
+    match (policy, &file.policy) {
+        (Some(policy), _) => parser::locate_policy(policy),
+        (None, Some(relative)) => dir.join(relative),
+        (None, None) => parser::locate_policy(&dir.join(parser::POLICY_FILE)),
+    }
+}
+
+fn outcome(decision: &Decision) -> String {
+    match decision {
+        Decision::Allowed { .. } => "allowed".to_string(),
+        Decision::Denied { reason, .. } => format!("denied ({})", reason),
+        Decision::RequiresApproval { reason, .. } => format!("requires approval ({})", reason),
+        Decision::Logged { reason, .. } => format!("allowed, log only ({})", reason),
+    }
+}
+
+fn print_result(result: &TestResult) {
+    if result.passed() {
+        println!(
+            "  {} {} {}",
+            glyph("✓").green(),
+            result.name,
+            format!("→ {}", result.expected).dimmed()
+        );
+    } else {
+        println!("  {} {}", glyph("✗").red().bold(), result.name.bold());
+        println!(
+            "      expected {}, got {}",
+            result.expected.to_string().green(),
+            outcome(&result.decision).red()
+        );
+        if let Some(rule) = result.decision.matched_rule() {
+            println!("      {}", format!("rule: {}", rule).dimmed());
+        }
+    }
+}
+
+/// Run `lawctl test`.
+pub fn run_policy_tests(tests_path: &Path, policy: Option<&Path>) -> Result<()> {
+    let file = TestFile::load(tests_path)?;
+    let policy_path = policy_for(tests_path, &file, policy);
+    let parsed = parser::parse_policy_tree(&policy_path)?;
+    let engine =
+        PolicyEngine::new(parsed).context("Policy parsed but has invalid glob patterns")?;
+    let results = testkit::run(&engine, &file.tests)?;
+
+    println!();
+    println!(
+        "  Running {} test{} from {} against {}",
+        results.len(),
+        if results.len() == 1 { "" } else { "s" },
+        tests_path.display().to_string().bold(),
+        policy_path.display().to_string().bold()
+    );
+    println!();
+    for result in &results {
+        print_result(result);
+    }
+
+    let failed = results.iter().filter(|r| !r.passed()).count();
+    println!();
+    if failed == 0 {
+        println!(
+            "  {} {} passed",
+            glyph("✓").green().bold(),
+            results.len().to_string().green().bold()
+        );
+        println!();
+        return Ok(());
+    }
+    println!(
+        "  {} passed, {} failed",
+        (results.len() - failed).to_string().green().bold(),
+        failed.to_string().red().bold()
+    );
+    bail!(
+        "{} of {} policy test{} failed",
+        failed,
+        results.len(),
+        if results.len() == 1 { "" } else { "s" }
+    )
+}
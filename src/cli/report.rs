@@ -0,0 +1,316 @@
+//! `lawctl report` — session reports for sharing outside the terminal.
+//!
+//! `lawctl report md` renders a session's audit log as markdown meant to
+//! be pasted into a PR description: files changed, commands run, and
+//! anything a human had to weigh in on.
+
+use crate::audit::{AuditReader, LogEntry, SessionRecord};
+use crate::cli::log::format_duration;
+use crate::policy::types::{Action, Decision};
+use anyhow::{Context, Result};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Run `lawctl report md`: print (or write) a markdown session report.
+pub fn run_report_markdown(session_id: Option<&str>, output: Option<&Path>) -> Result<()> {
+    let reader = AuditReader::new().context("Failed to initialize log reader")?;
+    let entries = match session_id {
+        Some(sid) => reader
+            .read_session(sid)
+            .with_context(|| format!("Failed to read session: {}", sid))?,
+        None => reader.read_latest_session()?,
+    };
+    if entries.is_empty() {
+        anyhow::bail!("No audit logs found. Run an agent through lawctl first.");
+    }
+
+    let records = reader
+        .read_session_records(&entries[0].session_id)
+        .unwrap_or_default();
+    let markdown = render_markdown(&entries, &records);
+    match output {
+        Some(path) => std::fs::write(path, &markdown)
+            .with_context(|| format!("Failed to write report: {}", path.display()))?,
+        None => print!("{}", markdown),
+    }
+    Ok(())
+}
+
+/// Render a session's entries, and the upload records alongside them, as
+/// a markdown report.
+pub fn render_markdown(entries: &[LogEntry], records: &[SessionRecord]) -> String {
+    let mut summary = AuditReader::summarize(entries);
+    summary.add_uploads(records);
+    // Post-transfer accounting entries in older logs aren't actions
+    let actions: Vec<&LogEntry> = entries
+        .iter()
+        .filter(|e| !e.network.as_ref().is_some_and(|n| n.transfer_record))
+        .collect();
+
+    let mut md = String::new();
+    let _ = writeln!(md, "## Agent session report");
+    let _ = writeln!(md);
+
+    let short_id = summary.session_id.get(..8).unwrap_or(&summary.session_id);
+    let mut header = format!("**Session:** `{}` · **Agent:** {}", short_id, summary.agent);
+    if let (Some(start), Some(end)) = (summary.start_time, summary.end_time) {
+        let _ = write!(
+            header,
+            " · **Duration:** {}",
+            format_duration((end - start).num_seconds())
+        );
+    }
+    let _ = writeln!(md, "{}", header);
+    let _ = writeln!(md);
+    let _ = writeln!(
+        md,
+        "**{}** actions checked by policy — {} allowed, {} denied, {} approved.",
+        summary.total_actions, summary.allowed, summary.denied, summary.approved
+    );
+
+    // Files changed
+    let changes = AuditReader::file_changes(entries);
+    let _ = writeln!(md);
+    let _ = writeln!(md, "### Files changed");
+    let _ = writeln!(md);
+    if changes.is_empty() {
+        let _ = writeln!(md, "_None._");
+    } else {
+        let _ = writeln!(md, "| File | Writes | Lines |");
+        let _ = writeln!(md, "|------|-------:|-------|");
+        for change in &changes {
+            let lines = if change.deleted && change.writes == 0 {
+                "deleted".to_string()
+            } else {
+                let mut lines = format!("+{}", change.lines_added);
+                if change.lines_removed > 0 {
+                    let _ = write!(lines, " −{}", change.lines_removed);
+                }
+                if change.deleted {
+                    lines.push_str(", then deleted");
+                }
+                lines
+            };
+            let _ = writeln!(
+                md,
+                "| {} | {} | {} |",
+                code(&change.path).replace('|', "\\|"),
+                change.writes,
+                lines
+            );
+        }
+    }
+
+    // Commands run, repeats collapsed
+    let mut commands: Vec<(String, usize)> = Vec::new();
+    for entry in actions
+        .iter()
+        .filter(|e| e.action == Action::RunCmd && was_executed(e))
+    {
+        let command = subject(entry).to_string();
+        match commands.iter_mut().find(|(c, _)| *c == command) {
+            Some((_, count)) => *count += 1,
+            None => commands.push((command, 1)),
+        }
+    }
+    let _ = writeln!(md);
+    let _ = writeln!(md, "### Commands run");
+    let _ = writeln!(md);
+    if commands.is_empty() {
+        let _ = writeln!(md, "_None._");
+    }
+    for (command, count) in &commands {
+        match count {
+            1 => {
+                let _ = writeln!(md, "- {}", code(command));
+            }
+            n => {
+                let _ = writeln!(md, "- {} (×{})", code(command), n);
+            }
+        }
+    }
+
+    // Human decisions and blocks
+    let approved: Vec<&&LogEntry> = actions.iter().filter(|e| e.approved_by.is_some()).collect();
+    if !approved.is_empty() {
+        let _ = writeln!(md);
+        let _ = writeln!(md, "### Approved");
+        let _ = writeln!(md);
+        for entry in approved {
+            let _ = writeln!(
+                md,
+                "- **{}** {} — approved by {}",
+                entry.action,
+                code(subject(entry)),
+                entry.approved_by.as_deref().unwrap_or("unknown")
+            );
+        }
+    }
+
+    let denied: Vec<(&LogEntry, &str)> = actions
+        .iter()
+        .filter_map(|e| match &e.decision {
+            Decision::Denied { reason, .. } => Some((*e, reason.as_str())),
+            Decision::RequiresApproval { reason, .. } if e.approved_by.is_none() => {
+                Some((*e, reason.as_str()))
+            }
+            _ => None,
+        })
+        .collect();
+    if !denied.is_empty() {
+        let _ = writeln!(md);
+        let _ = writeln!(md, "### Denied");
+        let _ = writeln!(md);
+        for (entry, reason) in denied {
+            let _ = writeln!(
+                md,
+                "- **{}** {} — {}",
+                entry.action,
+                code(subject(entry)),
+                reason
+            );
+        }
+    }
+
+    if let Some(uploads) = summary.uploads_line() {
+        let _ = writeln!(md);
+        let _ = writeln!(md, "{}", uploads);
+    }
+
+    let _ = writeln!(md);
+    let _ = writeln!(
+        md,
+        "<sub>Generated by lawctl from the session audit log (`lawctl log --session {}`).</sub>",
+        summary.session_id
+    );
+    md
+}
+
+/// What to show for an entry: the command for run_cmd (whose target is
+/// just "shell"), otherwise the target.
+fn subject(entry: &LogEntry) -> &str {
+    match (&entry.action, &entry.diff) {
+        (Action::RunCmd, Some(command)) => command,
+        _ => &entry.target,
+    }
+}
+
+/// Whether an entry's action actually went ahead.
+fn was_executed(entry: &LogEntry) -> bool {
+    entry.decision.is_allowed() || entry.approved_by.is_some()
+}
+
+/// Format text as an inline code span, on one line, with enough backticks
+/// that any inside it don't end the span early.
+fn code(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut longest = 0;
+    let mut run = 0;
+    for c in text.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    let fence = "`".repeat(longest + 1);
+    if longest > 0 {
+        format!("{} {} {}", fence, text, fence)
+    } else {
+        format!("{}{}{}", fence, text, fence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::Actor;
+    use chrono::{Duration, Utc};
+
+    fn entry(action: Action, target: &str, decision: Decision) -> LogEntry {
+        LogEntry {
+            timestamp: Utc::now(),
+            session_id: "3f2a9c1e-session".to_string(),
+            agent: "claude-code".to_string(),
+            action,
+            target: target.to_string(),
+            policy_rule: None,
+            decision,
+            diff: None,
+            approved_by: None,
+            eval_duration_us: None,
+            network: None,
+            warning: None,
+            output: None,
+            threats: Vec::new(),
+            actor: Actor::Agent,
+            process_chain: Vec::new(),
+        }
+    }
+
+    fn allowed() -> Decision {
+        Decision::Allowed { matched_rule: None }
+    }
+
+    #[test]
+    fn test_markdown_report_sections() {
+        let start = Utc::now();
+        let mut entries = vec![
+            LogEntry {
+                timestamp: start,
+                diff: Some("fn main() {}\n// two\n".to_string()),
+                ..entry(Action::Write, "src/main.rs", allowed())
+            },
+            LogEntry {
+                diff: Some("@@ -1,2 +1,2 @@\n-old\n+new\n context\n".to_string()),
+                ..entry(Action::Write, "src/main.rs", allowed())
+            },
+            entry(Action::Delete, "old.txt, tmp|x.log", allowed()),
+            LogEntry {
+                diff: Some("cargo test".to_string()),
+                ..entry(Action::RunCmd, "shell", allowed())
+            },
+            LogEntry {
+                diff: Some("cargo test".to_string()),
+                ..entry(Action::RunCmd, "shell", allowed())
+            },
+            LogEntry {
+                diff: Some("rm -rf /".to_string()),
+                ..entry(
+                    Action::RunCmd,
+                    "shell",
+                    Decision::Denied {
+                        reason: "Dangerous command".to_string(),
+                        matched_rule: None,
+                    },
+                )
+            },
+            LogEntry {
+                approved_by: Some("terminal".to_string()),
+                ..entry(
+                    Action::GitPush,
+                    "main",
+                    Decision::Allowed {
+                        matched_rule: Some("approved by human".to_string()),
+                    },
+                )
+            },
+        ];
+        entries.last_mut().unwrap().timestamp = start + Duration::seconds(125);
+
+        let md = render_markdown(&entries, &[]);
+        assert!(
+            md.contains("**Session:** `3f2a9c1e` · **Agent:** claude-code · **Duration:** 2m 5s")
+        );
+        assert!(md.contains("| `src/main.rs` | 2 | +3 −1 |"));
+        assert!(md.contains("| `old.txt` | 0 | deleted |"));
+        assert!(md.contains("| `tmp\\|x.log` | 0 | deleted |"));
+        assert!(md.contains("- `cargo test` (×2)"));
+        assert!(md.contains("- **git_push** `main` — approved by terminal"));
+        assert!(md.contains("- **run_cmd** `rm -rf /` — Dangerous command"));
+    }
+
+    #[test]
+    fn test_code_spans_survive_backticks() {
+        assert_eq!(code("ls"), "`ls`");
+        assert_eq!(code("echo `date`"), "`` echo `date` ``");
+        assert_eq!(code("a\n  b"), "`a b`");
+    }
+}
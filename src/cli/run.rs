@@ -1,7 +1,7 @@
 //! `lawctl run` — the main command that wraps an agent in the sandbox.
 //!
 //! This is the core user flow:
-//! 1. Parse the policy file
+//! 1. Parse the policy file and run the integrity self-check
 //! 2. Start the gateway server (Unix socket)
 //! 3. Start the sandbox (Docker or direct mode for development)
 //! 4. Launch the agent command inside the sandbox
@@ -9,14 +9,31 @@
 //! 6. Print session summary
 
 use crate::approval::{AutoApproval, AutoDeny, TerminalApproval};
-use crate::audit::AuditLogger;
-use crate::gateway::GatewayServer;
-use crate::policy::{parser, PolicyEngine};
+use crate::audit::baseline::{AnomalyDetector, Baseline};
+use crate::audit::{watch, AuditLogger, AuditReader, EndReason, LogEntry, SessionRecord};
+use crate::gateway::{GatewayServer, LogCallback};
+use crate::i18n::{t, tf};
+use crate::integrity::{self, Expectations, IntegrityPaths};
+use crate::policy::{history, parser, traps, Decision, PolicyEngine};
+use crate::sandbox::{env, AgentUser};
+use crate::utils::locations;
+use crate::utils::session_lock::{self, LockHolder, WorkspaceLock};
+use crate::utils::signals;
+use crate::utils::term::glyph;
 use anyhow::{Context, Result};
 use colored::Colorize;
+use std::io::{IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Carries `--disable-rule` selectors, comma-separated, to the Claude hook.
+pub const DISABLED_RULES_ENV: &str = "LAWCTL_DISABLED_RULES";
+
+/// The gateway socket for a session, in `dir`.
+pub fn socket_path(dir: &Path, session_id: &str) -> PathBuf {
+    dir.join(format!("lawctl-{}.sock", &session_id[..8]))
+}
+
 /// Options for the `lawctl run` command.
 #[derive(Debug)]
 pub struct RunOptions {
@@ -34,6 +51,103 @@ pub struct RunOptions {
     pub session_id: Option<String>,
     /// Agent name for logging
     pub agent_name: String,
+    /// Route the agent's HTTP(S) traffic through a local policy-enforcing
+    /// proxy (direct mode only — Docker enforces at the network level)
+    pub network_proxy: bool,
+    /// How much to print while the session runs
+    pub output: OutputLevel,
+    /// Where `--debug` is capturing the internal trace
+    pub debug_log: Option<PathBuf>,
+    /// Start the agent as this unprivileged user (direct mode only)
+    pub as_user: Option<String>,
+    /// Stop a session already running in the workspace and take over
+    pub force: bool,
+    /// Rules switched off for this session, by id or `tag:NAME`
+    pub disabled_rules: Vec<String>,
+    /// Log workspace changes the agent didn't make
+    pub watch_workspace: bool,
+}
+
+/// How chatty a session is on the terminal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputLevel {
+    /// Only denials and the final summary
+    Quiet,
+    /// The session banner, denials and the final summary
+    #[default]
+    Normal,
+    /// Everything in normal, plus every evaluated action as it happens
+    Verbose,
+}
+
+impl OutputLevel {
+    pub fn from_flags(quiet: bool, verbose: bool) -> Self {
+        match (quiet, verbose) {
+            (true, _) => OutputLevel::Quiet,
+            (_, true) => OutputLevel::Verbose,
+            _ => OutputLevel::Normal,
+        }
+    }
+
+    /// Whether to print the banner and progress lines.
+    fn banner(self) -> bool {
+        self != OutputLevel::Quiet
+    }
+
+    /// Whether a logged action is shown live.
+    fn shows(self, entry: &LogEntry) -> bool {
+        self == OutputLevel::Verbose || matches!(entry.decision, Decision::Denied { .. })
+    }
+
+    /// The gateway log callback that prints actions at this level, and
+    /// (outside quiet mode) what `detector` finds unusual about them.
+    fn log_callback(self, detector: Option<AnomalyDetector>) -> LogCallback {
+        let detector = detector.map(std::sync::Mutex::new);
+        Arc::new(move |entry: &LogEntry| {
+            if let Some(detector) = detector.as_ref().filter(|_| self.banner()) {
+                let anomalies = match detector.lock() {
+                    Ok(mut detector) => detector.observe(entry),
+                    Err(_) => Vec::new(),
+                };
+                for anomaly in anomalies {
+                    eprintln!(
+                        "  {} {}",
+                        glyph("⚠").yellow(),
+                        tf("anomaly.live", &[("message", &anomaly)]).yellow()
+                    );
+                }
+            }
+            // A tripped honeytoken is announced at every level
+            if let Decision::Denied {
+                reason,
+                matched_rule: Some(rule),
+            } = &entry.decision
+            {
+                if rule == traps::RULE {
+                    eprintln!();
+                    eprintln!(
+                        "  {} {}",
+                        glyph("⚠").red().bold(),
+                        tf("trap.tripped", &[("reason", reason)]).red().bold()
+                    );
+                    eprintln!();
+                }
+            }
+            if !self.shows(entry) {
+                return;
+            }
+            // stderr, so an agent whose stdout is piped doesn't get these
+            let mut line = format!(
+                "  {} {}",
+                glyph("▶").dimmed(),
+                AuditReader::format_entry(entry)
+            );
+            if let Decision::Denied { reason, .. } = &entry.decision {
+                line.push_str(&format!(" — {}", reason));
+            }
+            eprintln!("{}", line);
+        })
+    }
 }
 
 impl Default for RunOptions {
@@ -46,6 +160,13 @@ impl Default for RunOptions {
             approval_mode: "terminal".to_string(),
             session_id: None,
             agent_name: "unknown-agent".to_string(),
+            network_proxy: false,
+            output: OutputLevel::Normal,
+            debug_log: None,
+            as_user: None,
+            force: false,
+            disabled_rules: vec![],
+            watch_workspace: false,
         }
     }
 }
@@ -58,42 +179,155 @@ pub async fn run_agent(options: RunOptions) -> Result<()> {
         .clone()
         .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
 
-    println!();
-    println!(
-        "  {} Lawctl v{}",
-        "⚖".to_string().bold(),
-        env!("CARGO_PKG_VERSION")
-    );
-    println!("  Session: {}", session_id[..8].cyan());
+    let banner = options.output.banner();
+    if banner {
+        println!();
+        println!(
+            "  {} Lawctl v{}",
+            glyph("⚖").to_string().bold(),
+            env!("CARGO_PKG_VERSION")
+        );
+        println!("  Session: {}", session_id[..8].cyan());
+    }
+
+    // One session per workspace
+    let lock = WorkspaceLock::acquire(
+        &session_lock::lock_directory()?,
+        &LockHolder {
+            pid: std::process::id(),
+            session_id: session_id.clone(),
+            agent: options.agent_name.clone(),
+            started: chrono::Utc::now(),
+            workspace: options.workspace.clone(),
+        },
+        options.force,
+    )?;
 
     // Step 1: Parse policy
-    let policy_path = if options.policy_path.is_absolute() {
+    let policy_path = parser::locate_policy(&if options.policy_path.is_absolute() {
         options.policy_path.clone()
     } else {
         options.workspace.join(&options.policy_path)
-    };
+    });
 
-    println!(
-        "  Policy:  {}",
-        policy_path
-            .file_name()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .cyan()
+    if banner {
+        println!(
+            "  Policy:  {}",
+            policy_path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .cyan()
+        );
+    }
+
+    let mut policy = parser::parse_policy_tree(&policy_path)?;
+    policy.disable_rules(&options.disabled_rules)?;
+    history::record_load(
+        policy_path.parent().unwrap_or(&options.workspace),
+        &policy_path,
+        "lawctl run",
+        policy.commit_policy_history,
     );
+    let engine = PolicyEngine::new(policy)?
+        .with_workspace(&options.workspace)
+        .with_policy_file(&policy_path)
+        .with_session(&session_id);
+
+    if banner {
+        println!("  Law:     {}", engine.policy_name().cyan());
+        println!("  Rules:   {}", engine.policy().rules.len());
+        if !options.disabled_rules.is_empty() {
+            println!("  Disabled: {}", options.disabled_rules.join(", ").yellow());
+        }
+    }
+    for skipped in &engine.policy().skipped_layers {
+        tracing::warn!("nested policy {}", skipped);
+        println!("  {} Nested policy {}", glyph("⚠").yellow(), skipped);
+    }
+
+    let agent_env = env::agent_env(&engine.policy().env_passthrough);
+    let agent_user = match options.as_user.as_deref() {
+        Some(_) if options.use_docker => {
+            anyhow::bail!(
+                "--as-user is for direct mode; the Docker sandbox already isolates the agent"
+            )
+        }
+        Some(name) => Some(AgentUser::resolve(name)?),
+        None => None,
+    };
+    if banner {
+        if let Some(ref user) = agent_user {
+            println!("  User:    {}", user.name.cyan());
+        }
+    }
 
-    let policy = parser::parse_policy_file(&policy_path)?;
-    let engine = PolicyEngine::new(policy)?;
+    tracing::debug!(
+        session_id = %session_id,
+        version = env!("CARGO_PKG_VERSION"),
+        policy = %policy_path.display(),
+        policy_hash = %engine.policy_hash(),
+        rules = engine.policy().rules.len(),
+        docker = options.use_docker,
+        network_proxy = options.network_proxy,
+        agent = %options.agent_name,
+        as_user = ?options.as_user,
+        "session start"
+    );
 
-    println!("  Law:     {}", engine.policy_name().cyan());
-    println!("  Rules:   {}", engine.policy().rules.len());
+    // Step 1b: Make sure lawctl is still wired up before trusting it
+    let integrity_paths = IntegrityPaths::default();
+    let expectations = Expectations {
+        policy_path: policy_path.clone(),
+        policy_hash: engine.policy_hash(),
+        workspace: options.workspace.clone(),
+        requires_hook: uses_claude_hook(&options.agent_command),
+    };
+    check_integrity(&integrity_paths, &expectations)?;
+    let integrity_watch = tokio::spawn(integrity::watch(
+        integrity_paths,
+        expectations,
+        INTEGRITY_RECHECK,
+    ));
 
     // Step 2: Set up audit logger
-    let logger = AuditLogger::new(&session_id)?;
-    println!(
-        "  Log:     {}",
-        logger.log_path().display().to_string().dimmed()
-    );
+    let mut logger = AuditLogger::new(&session_id, &options.workspace)?;
+    logger.log_record(&SessionRecord::SessionStart {
+        timestamp: chrono::Utc::now(),
+        session_id: session_id.clone(),
+        agent: options.agent_name.clone(),
+        pid: std::process::id(),
+        lawctl_version: env!("CARGO_PKG_VERSION").to_string(),
+    })?;
+    let started = std::time::Instant::now();
+    if banner {
+        println!(
+            "  Log:     {}",
+            logger.log_path().display().to_string().dimmed()
+        );
+    }
+
+    // Changes made around the agent, when asked to watch for them
+    let workspace_watch = options.watch_workspace.then(|| {
+        tokio::spawn(watch::watch(
+            options.workspace.clone(),
+            session_id.clone(),
+            options.agent_name.clone(),
+            watch::WATCH_INTERVAL,
+        ))
+    });
+    if banner && options.watch_workspace {
+        println!(
+            "  Watch:   {}",
+            "logging changes made outside the agent".dimmed()
+        );
+    }
+
+    // What this project's sessions usually do, to flag what's new
+    let baseline = Baseline::load(&options.workspace, &session_id).unwrap_or_else(|e| {
+        tracing::debug!("No session baseline: {:#}", e);
+        None
+    });
 
     // Step 3: Set up approval handler
     let approval_handler: Arc<dyn crate::approval::ApprovalHandler + Send + Sync> =
@@ -104,10 +338,19 @@ pub async fn run_agent(options: RunOptions) -> Result<()> {
         };
 
     // Step 4: Set up gateway socket
-    let socket_path = PathBuf::from(format!("/tmp/lawctl-{}.sock", &session_id[..8]));
+    // An agent running as another user can't reach a private runtime dir
+    let socket_dir = if agent_user.is_some() {
+        locations::shared_runtime_dir()
+    } else {
+        locations::runtime_dir()
+    };
+    locations::ensure_dir(&socket_dir)?;
+    let socket_path = socket_path(&socket_dir, &session_id);
 
-    println!("  Socket:  {}", socket_path.display().to_string().dimmed());
-    println!();
+    if banner {
+        println!("  Socket:  {}", socket_path.display().to_string().dimmed());
+        println!();
+    }
 
     let gateway = GatewayServer::new(
         &socket_path,
@@ -117,30 +360,214 @@ pub async fn run_agent(options: RunOptions) -> Result<()> {
         options.agent_name.clone(),
         logger,
         approval_handler,
+    )
+    .with_log_callback(
+        options.output.log_callback(
+            baseline
+                .clone()
+                .map(|b| AnomalyDetector::new(b, Some(&options.workspace))),
+        ),
     );
 
     // Step 5: Start gateway and agent
-    if options.use_docker {
-        println!("  {} Starting Docker sandbox...", "→".blue());
-        run_with_docker(gateway, &options, &socket_path, &session_id).await?;
+    let outcome = if options.use_docker {
+        if banner {
+            println!("  {} Starting Docker sandbox...", glyph("→").blue());
+        }
+        run_with_docker(gateway, &options, &socket_path, &session_id, &agent_env).await
     } else {
-        println!("  {} Running in direct mode (no sandbox)", "→".blue());
+        if banner {
+            match agent_user {
+                Some(ref user) => println!(
+                    "  {} Running in direct mode as {} (no sandbox)",
+                    glyph("→").blue(),
+                    user.name
+                ),
+                None => println!(
+                    "  {} Running in direct mode (no sandbox)",
+                    glyph("→").blue()
+                ),
+            }
+            println!(
+                "  {}",
+                "  For full isolation, use: lawctl run --docker -- <command>".dimmed()
+            );
+            println!();
+        }
+        run_direct(
+            gateway,
+            &options,
+            &socket_path,
+            &agent_env,
+            agent_user.as_ref(),
+        )
+        .await
+    };
+
+    integrity_watch.abort();
+    if let Some(workspace_watch) = workspace_watch {
+        workspace_watch.abort();
+    }
+    record_session_end(&session_id, &options.workspace, started.elapsed(), &outcome);
+    if socket_path.exists() {
+        let _ = std::fs::remove_file(&socket_path);
+    }
+    drop(lock);
+    let exit = outcome?;
+
+    // Step 6: Print summary
+    print_session_summary(&session_id, baseline, &options.workspace)?;
+    if let Some(ref debug_log) = options.debug_log {
         println!(
-            "  {}",
-            "  For full isolation, use: lawctl run --docker -- <command>".dimmed()
+            "  {} Debug trace: {}",
+            glyph("ℹ").blue(),
+            debug_log.display().to_string().dimmed()
+        );
+        println!(
+            "    Attach it to an issue with: {}",
+            "lawctl bugreport".bold()
         );
         println!();
-        run_direct(gateway, &options, &socket_path).await?;
     }
 
-    // Step 6: Print summary
-    print_session_summary(&session_id)?;
+    // Exit the way a shell expects of a process stopped by a signal
+    if let Some(signal) = exit.interrupted {
+        std::process::exit(128 + signal);
+    }
+    Ok(())
+}
 
-    // Cleanup socket
-    if socket_path.exists() {
-        let _ = std::fs::remove_file(&socket_path);
+/// How the agent process ended.
+#[derive(Debug, Clone, Copy, Default)]
+struct AgentExit {
+    code: Option<i32>,
+    signal: Option<i32>,
+    /// The signal lawctl received and shut the agent down for
+    interrupted: Option<i32>,
+}
+
+impl AgentExit {
+    /// Say how the agent ended, unless it exited cleanly.
+    fn report(&self) {
+        if let Some(signal) = self.interrupted {
+            println!(
+                "\n  {} Stopped the agent ({})",
+                glyph("⚠").yellow(),
+                signals::name(signal)
+            );
+        } else if self.code != Some(0) {
+            println!(
+                "\n  {} Agent exited with code: {}",
+                glyph("⚠").yellow(),
+                self.code.unwrap_or(-1)
+            );
+        }
     }
+}
 
+impl From<std::process::ExitStatus> for AgentExit {
+    fn from(status: std::process::ExitStatus) -> Self {
+        use std::os::unix::process::ExitStatusExt;
+        Self {
+            code: status.code(),
+            signal: status.signal(),
+            interrupted: None,
+        }
+    }
+}
+
+/// Append the session-end record. A failure here only costs the marker,
+/// so it's traced rather than returned.
+fn record_session_end(
+    session_id: &str,
+    workspace: &Path,
+    duration: std::time::Duration,
+    outcome: &Result<AgentExit>,
+) {
+    let record = session_end_record(session_id, duration, outcome);
+    if let Err(e) = AuditLogger::new(session_id, workspace).and_then(|mut l| l.log_record(&record))
+    {
+        tracing::warn!("Failed to record session end: {:#}", e);
+    }
+}
+
+fn session_end_record(
+    session_id: &str,
+    duration: std::time::Duration,
+    outcome: &Result<AgentExit>,
+) -> SessionRecord {
+    let (reason, exit, error) = match outcome {
+        Ok(exit) => {
+            let reason = match (exit.interrupted, exit.code) {
+                (Some(_), _) => EndReason::Interrupted,
+                (None, Some(0)) => EndReason::Exited,
+                (None, Some(_)) => EndReason::Failed,
+                (None, None) => EndReason::Killed,
+            };
+            (reason, *exit, None)
+        }
+        Err(e) => (
+            EndReason::Error,
+            AgentExit::default(),
+            Some(format!("{:#}", e)),
+        ),
+    };
+    SessionRecord::SessionEnd {
+        timestamp: chrono::Utc::now(),
+        session_id: session_id.to_string(),
+        reason,
+        exit_code: exit.code,
+        signal: exit.interrupted.or(exit.signal),
+        duration_ms: duration.as_millis() as u64,
+        error,
+    }
+}
+
+/// How often the integrity checks re-run during a session.
+const INTEGRITY_RECHECK: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Whether the agent is Claude Code, whose tools are policed by lawctl-hook.
+fn uses_claude_hook(agent_command: &[String]) -> bool {
+    agent_command
+        .first()
+        .and_then(|cmd| Path::new(cmd).file_name())
+        .is_some_and(|name| name == "claude")
+}
+
+/// Run the integrity checks before the session starts. A changed policy can
+/// be approved on the spot; anything else that fails aborts the session.
+fn check_integrity(paths: &IntegrityPaths, expect: &Expectations) -> Result<()> {
+    let report = integrity::verify(paths, expect);
+    let problems: Vec<_> = report.problems().collect();
+    if problems.is_empty() {
+        return Ok(());
+    }
+    println!();
+    for check in &problems {
+        println!("{}", check.display());
+    }
+    let other_failures = problems
+        .iter()
+        .any(|c| c.status == integrity::Status::Failed && c.name != "Policy");
+    if other_failures {
+        anyhow::bail!("Integrity check failed — lawctl can't protect this session as set up");
+    }
+    if report.policy_changed() {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!(
+                "The policy changed since it was last approved. Run lawctl interactively to approve it."
+            );
+        }
+        print!("  Approve the changed policy for this and future sessions? [y/N] ");
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !matches!(answer.trim(), "y" | "Y" | "yes") {
+            anyhow::bail!("Policy not approved — session not started");
+        }
+        integrity::approve_policy(&paths.approvals, &expect.policy_path, &expect.policy_hash)?;
+    }
+    println!();
     Ok(())
 }
 
@@ -150,7 +577,24 @@ async fn run_direct(
     gateway: GatewayServer,
     options: &RunOptions,
     socket_path: &Path,
-) -> Result<()> {
+    agent_env: &[(String, String)],
+    agent_user: Option<&AgentUser>,
+) -> Result<AgentExit> {
+    // The proxy shares the gateway's policy and logger, so start it first
+    let proxy = if options.network_proxy {
+        let proxy = gateway.start_network_proxy().await?;
+        if options.output.banner() {
+            println!(
+                "  {} Network proxy: {}",
+                glyph("→").blue(),
+                proxy.url().dimmed()
+            );
+        }
+        Some(proxy)
+    } else {
+        None
+    };
+
     // Start the gateway in the background
     let gateway_handle = tokio::spawn(async move {
         if let Err(e) = gateway.run().await {
@@ -160,39 +604,97 @@ async fn run_direct(
 
     // Wait a moment for the socket to be ready
     tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    let agent_user = agent_user.filter(|user| user.needs_switch());
+    if let Some(user) = agent_user {
+        user.grant_socket(socket_path)?;
+    }
 
     // Run the agent command
     let cmd = options.agent_command.join(" ");
-    println!("  {} Running: {}", "▶".green(), cmd.bold());
-    println!();
+    if options.output.banner() {
+        println!("  {} Running: {}", glyph("▶").green(), cmd.bold());
+        println!();
+    }
 
-    let mut child = tokio::process::Command::new("sh")
+    let mut command = tokio::process::Command::new("sh");
+    command
         .arg("-c")
         .arg(&cmd)
+        .env_clear()
+        .envs(agent_env.iter().cloned())
         .env("LAWCTL_SOCKET", socket_path.to_string_lossy().as_ref())
         .env(
             "LAWCTL_WORKSPACE",
             options.workspace.to_string_lossy().as_ref(),
         )
-        .current_dir(&options.workspace)
+        .current_dir(&options.workspace);
+    if !options.disabled_rules.is_empty() {
+        // The Claude hook loads the policy itself; tell it what's off
+        command.env(DISABLED_RULES_ENV, options.disabled_rules.join(","));
+    }
+    if let Some(ref proxy) = proxy {
+        command.envs(proxy.env_vars());
+    }
+    if let Some(user) = agent_user {
+        command.uid(user.uid).gid(user.gid).envs(user.env_vars());
+    }
+
+    // Interactive agents share lawctl's process group, so they stay in the
+    // terminal's foreground and Ctrl-C reaches them directly. Otherwise the
+    // agent gets a group of its own that signals are forwarded to.
+    let own_group = !std::io::stdin().is_terminal();
+    if own_group {
+        command.process_group(0);
+    }
+    let mut signals = signals::Signals::new()?;
+    let mut child = command
         .spawn()
         .with_context(|| format!("Failed to start agent: {}", cmd))?;
-
-    let status = child.wait().await?;
+    let pid = child
+        .id()
+        .context("Agent exited before it could be tracked")?;
+
+    let exit = loop {
+        tokio::select! {
+            status = child.wait() => break AgentExit::from(status?),
+            signal = signals.recv() => {
+                // The terminal already sent Ctrl-C to the agent; it decides
+                // what that means (interactive agents cancel, not quit)
+                if signal == signals::SIGINT && !own_group {
+                    continue;
+                }
+                tracing::debug!(signal = %signals::name(signal), "forwarding signal to agent");
+                let send = |sig| {
+                    if own_group {
+                        signals::send_group(pid, sig)
+                    } else {
+                        signals::send(pid, sig)
+                    }
+                };
+                send(signal);
+                let status = match tokio::time::timeout(signals::GRACE_PERIOD, child.wait()).await {
+                    Ok(status) => status?,
+                    Err(_) => {
+                        send(signals::SIGKILL);
+                        child.wait().await?
+                    }
+                };
+                break AgentExit {
+                    interrupted: Some(signal),
+                    ..status.into()
+                };
+            }
+        }
+    };
+    tracing::debug!(exit_code = ?exit.code, signal = ?exit.signal, "agent exited");
 
     // Give gateway a moment to finish processing
     tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
     gateway_handle.abort();
+    drop(proxy);
 
-    if !status.success() {
-        println!(
-            "\n  {} Agent exited with code: {}",
-            "⚠".yellow(),
-            status.code().unwrap_or(-1)
-        );
-    }
-
-    Ok(())
+    exit.report();
+    Ok(exit)
 }
 
 /// Run agent inside a Docker sandbox.
@@ -201,7 +703,8 @@ async fn run_with_docker(
     options: &RunOptions,
     socket_path: &Path,
     session_id: &str,
-) -> Result<()> {
+    agent_env: &[(String, String)],
+) -> Result<AgentExit> {
     use crate::sandbox::{DockerSandbox, SandboxConfig};
 
     let sandbox_config = SandboxConfig {
@@ -213,6 +716,8 @@ async fn run_with_docker(
             options.agent_command.join(" "),
         ],
         container_name: Some(format!("lawctl-{}", &session_id[..8])),
+        session_id: Some(session_id.to_string()),
+        env_vars: env::for_container(agent_env).into_iter().collect(),
         ..Default::default()
     };
 
@@ -229,51 +734,211 @@ async fn run_with_docker(
 
     // Start container
     let container_id = sandbox.start().await?;
-    println!("  Container: {}", container_id[..12].dimmed());
-    println!();
+    if options.output.banner() {
+        println!("  Container: {}", container_id[..12].dimmed());
+        println!();
+    }
 
-    // Wait for container to finish
-    let exit_code = sandbox.wait().await?;
+    // Wait for container to finish, or stop it if lawctl is told to stop
+    let mut signals = signals::Signals::new()?;
+    let exit = tokio::select! {
+        exit_code = sandbox.wait() => AgentExit {
+            code: Some(exit_code? as i32),
+            ..Default::default()
+        },
+        signal = signals.recv() => {
+            tracing::debug!(signal = %signals::name(signal), "stopping sandbox container");
+            sandbox.stop(signals::GRACE_PERIOD).await?;
+            AgentExit {
+                interrupted: Some(signal),
+                ..Default::default()
+            }
+        }
+    };
 
     // Cleanup
     sandbox.cleanup().await?;
     gateway_handle.abort();
 
-    if exit_code != 0 {
-        println!("\n  {} Agent exited with code: {}", "⚠".yellow(), exit_code);
-    }
-
-    Ok(())
+    exit.report();
+    Ok(exit)
 }
 
-/// Print the session summary after the agent finishes.
-fn print_session_summary(session_id: &str) -> Result<()> {
+/// Print the session summary after the agent finishes, with anything
+/// unusual against the project's `baseline`.
+fn print_session_summary(
+    session_id: &str,
+    baseline: Option<Baseline>,
+    workspace: &Path,
+) -> Result<()> {
     let reader = crate::audit::AuditReader::new()?;
     let entries = reader.read_session(session_id).unwrap_or_default();
 
     if entries.is_empty() {
-        println!("\n  {} No actions were logged this session.", "ℹ".blue());
+        println!("\n  {} {}", glyph("ℹ").blue(), t("summary.none"));
         return Ok(());
     }
 
-    let summary = crate::audit::AuditReader::summarize(&entries);
+    let mut summary = crate::audit::AuditReader::summarize(&entries);
+    summary.add_uploads(&reader.read_session_records(session_id).unwrap_or_default());
 
     println!();
-    println!("  {} Session complete", "─".repeat(40).dimmed());
+    println!(
+        "  {} {}",
+        glyph("─").repeat(40).dimmed(),
+        t("summary.complete")
+    );
     println!();
     println!(
-        "  {} actions | {} allowed | {} denied | {} approved",
-        summary.total_actions.to_string().bold(),
-        summary.allowed.to_string().green().bold(),
-        summary.denied.to_string().red().bold(),
-        summary.approved.to_string().yellow().bold(),
+        "  {}",
+        tf(
+            "summary.counts",
+            &[
+                ("total", &summary.total_actions.to_string().bold()),
+                ("allowed", &summary.allowed.to_string().green().bold()),
+                ("denied", &summary.denied.to_string().red().bold()),
+                ("approved", &summary.approved.to_string().yellow().bold()),
+            ]
+        )
     );
+    if summary.would_deny > 0 {
+        println!(
+            "  {} {}",
+            glyph("ℹ").blue(),
+            tf(
+                "summary.would_deny",
+                &[("count", &summary.would_deny.to_string().magenta().bold())]
+            )
+        );
+    }
+    if summary.rejected > 0 {
+        println!(
+            "  {} {}",
+            glyph("⚠").yellow(),
+            tf(
+                "summary.rejected",
+                &[("count", &summary.rejected.to_string().red().bold())]
+            )
+        );
+    }
+    if let Some(uploads) = summary.uploads_line() {
+        println!("  {}", uploads);
+    }
+    if let Some(baseline) = baseline {
+        let sessions = baseline.sessions;
+        let anomalies = AnomalyDetector::new(baseline, Some(workspace)).replay(&entries);
+        if !anomalies.is_empty() {
+            let score: u32 = anomalies.iter().map(|a| a.score).sum();
+            println!();
+            println!(
+                "  {} {}",
+                glyph("⚠").yellow(),
+                tf(
+                    "summary.anomalies",
+                    &[
+                        ("score", &score.to_string().bold()),
+                        ("sessions", &sessions)
+                    ]
+                )
+            );
+            for anomaly in &anomalies {
+                println!("    {} {}", glyph("•").dimmed(), anomaly);
+            }
+        }
+    }
     println!();
     println!(
-        "  View full log: {}",
-        format!("lawctl log --session {}", session_id).dimmed()
+        "  {}",
+        tf(
+            "summary.view_log",
+            &[(
+                "command",
+                &format!("lawctl log --session {}", session_id).dimmed()
+            )]
+        )
     );
     println!();
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::Actor;
+    use chrono::Utc;
+
+    fn entry(decision: Decision) -> LogEntry {
+        LogEntry {
+            timestamp: Utc::now(),
+            session_id: "s".to_string(),
+            agent: "test".to_string(),
+            action: crate::policy::Action::Write,
+            target: "src/main.rs".to_string(),
+            policy_rule: None,
+            decision,
+            diff: None,
+            approved_by: None,
+            eval_duration_us: None,
+            network: None,
+            warning: None,
+            output: None,
+            threats: Vec::new(),
+            actor: Actor::Agent,
+            process_chain: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_session_end_record() {
+        let second = std::time::Duration::from_secs(1);
+        let ended = |outcome: Result<AgentExit>| match session_end_record("s", second, &outcome) {
+            SessionRecord::SessionEnd {
+                reason,
+                duration_ms,
+                error,
+                ..
+            } => {
+                assert_eq!(duration_ms, 1000);
+                (reason, error)
+            }
+            other => panic!("not an end record: {:?}", other),
+        };
+        let exit = |code, signal| {
+            Ok(AgentExit {
+                code,
+                signal,
+                interrupted: None,
+            })
+        };
+        assert_eq!(ended(exit(Some(0), None)), (EndReason::Exited, None));
+        assert_eq!(ended(exit(Some(2), None)), (EndReason::Failed, None));
+        assert_eq!(ended(exit(None, Some(9))), (EndReason::Killed, None));
+        let interrupted = AgentExit {
+            interrupted: Some(signals::SIGTERM),
+            ..Default::default()
+        };
+        assert_eq!(ended(Ok(interrupted)), (EndReason::Interrupted, None));
+        assert_eq!(
+            ended(Err(anyhow::anyhow!("docker went away"))),
+            (EndReason::Error, Some("docker went away".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_output_levels() {
+        let allowed = entry(Decision::Allowed { matched_rule: None });
+        let denied = entry(Decision::Denied {
+            reason: "no".to_string(),
+            matched_rule: None,
+        });
+        for level in [OutputLevel::Quiet, OutputLevel::Normal] {
+            assert!(level.shows(&denied));
+            assert!(!level.shows(&allowed));
+        }
+        assert!(OutputLevel::Verbose.shows(&allowed));
+        assert!(!OutputLevel::Quiet.banner());
+        assert_eq!(OutputLevel::from_flags(false, true), OutputLevel::Verbose);
+        assert_eq!(OutputLevel::from_flags(false, false), OutputLevel::Normal);
+    }
+}
@@ -0,0 +1,20 @@
+//! `lawctl schema` — print the policy file format as a JSON Schema.
+//!
+//! Point an editor at it for completion and validation while writing a
+//! policy (the YAML language server reads a `# yaml-language-server:
+//! $schema=...` comment), or validate policies in CI with any JSON Schema
+//! tool.
+
+use crate::policy::parser;
+use anyhow::{bail, Result};
+
+/// Run `lawctl schema`.
+pub fn run_schema(format: &str) -> Result<()> {
+    match format {
+        "json-schema" | "jsonschema" => {
+            println!("{}", serde_json::to_string_pretty(&parser::json_schema())?);
+            Ok(())
+        }
+        other => bail!("Unknown schema format '{}' — use json-schema", other),
+    }
+}
@@ -4,13 +4,20 @@
 //! generates a policy file, and auto-installs agent hooks so protection
 //! is invisible from that point on.
 //!
+//! Every question can be answered up front for provisioning scripts:
+//! `lawctl setup --yes --level strict --agent claude-code`.
+//!
 //! Flow:
 //!   1. Welcome message explaining what lawctl does
-//!   2. "What agent are you using?" (auto-detect if possible)
+//!   2. "Which agents are you using?" (auto-detect if possible, pick several)
 //!   3. "How careful should we be?" (protection level)
-//!   4. Generate policy + install agent hook
+//!   4. Generate policy + install agent hooks (user and existing project
+//!      Claude Code settings, each backed up and reported)
 //!   5. Done — user just uses their agent normally
+//!   6. Optional test drive: fake actions through the new policy
 
+use crate::i18n::{t, tf};
+use crate::utils::term::glyph;
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::io::{self, Write};
@@ -25,6 +32,16 @@ enum ProtectionLevel {
 }
 
 impl ProtectionLevel {
+    /// Parse a `--level` value: the wizard's names or the template names.
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "standard" | "safe-dev" => Some(ProtectionLevel::Standard),
+            "strict" | "safe-ci" => Some(ProtectionLevel::Strict),
+            "relaxed" | "permissive" => Some(ProtectionLevel::Relaxed),
+            _ => None,
+        }
+    }
+
     fn template_name(&self) -> &str {
         match self {
             ProtectionLevel::Standard => "safe-dev",
@@ -34,87 +51,160 @@ impl ProtectionLevel {
     }
 }
 
-/// Run the interactive setup wizard.
-pub fn run_setup() -> Result<()> {
+/// Agent ids setup knows how to protect.
+const AGENTS: &[&str] = &["claude-code", "cursor", "codex", "aider"];
+
+/// Answers given up front, for scripted setup. Anything left unset is
+/// asked interactively — or, with `yes`, takes its default.
+#[derive(Debug, Default)]
+pub struct SetupOptions {
+    /// Don't prompt: detected agents, standard level, no test drive
+    pub yes: bool,
+    /// Protection level: standard, strict or relaxed
+    pub level: Option<String>,
+    /// Agents to protect (`none` for none, `auto` for whatever is installed)
+    pub agents: Vec<String>,
+}
+
+/// Run the setup wizard.
+pub fn run_setup(options: &SetupOptions) -> Result<()> {
+    // Validate flags before touching anything
+    let level_flag = options
+        .level
+        .as_deref()
+        .map(|name| {
+            ProtectionLevel::from_name(name).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown protection level '{}' — use standard, strict or relaxed",
+                    name
+                )
+            })
+        })
+        .transpose()?;
+    let agents_flag = resolve_agent_flags(&options.agents)?;
+
     let cwd = std::env::current_dir().context("Failed to get current directory")?;
 
     // Check if already set up
-    let policy_path = cwd.join(".lawctl.yaml");
-    if policy_path.exists() {
+    if let Some(policy_path) = crate::policy::parser::policy_file_in(&cwd) {
         println!();
-        println!("  {} You're already set up!", "✓".green().bold());
         println!(
-            "  Policy file: {}",
-            policy_path.display().to_string().dimmed()
+            "  {} {}",
+            glyph("✓").green().bold(),
+            t("setup.already.title")
+        );
+        println!(
+            "  {}",
+            tf(
+                "setup.already.policy",
+                &[("path", &policy_path.display().to_string().dimmed())]
+            )
         );
         println!();
-        println!("  Just use your agent normally — lawctl is watching.");
+        println!("  {}", t("setup.already.watching"));
         println!();
         println!(
-            "  To reconfigure, delete .lawctl.yaml and run {} again.",
-            "lawctl setup".bold()
+            "  {}",
+            tf(
+                "setup.already.reconfigure",
+                &[("command", &"lawctl setup".bold())]
+            )
         );
         println!();
+        // A committed policy still needs hooks on a fresh machine or container
+        if let Some(agents) = agents_flag {
+            if install_agent_hooks(&cwd, &agents) {
+                println!();
+            }
+        }
         return Ok(());
     }
 
     // ── Welcome ──
-    print_welcome();
+    if !options.yes {
+        print_welcome();
+    }
 
-    // ── Step 1: Detect / ask about agent ──
-    let agent = ask_agent()?;
+    // ── Step 1: Detect / ask about agents ──
+    let agents = match agents_flag {
+        Some(agents) => agents,
+        None if options.yes => detected_agents(),
+        None => ask_agents()?,
+    };
 
     // ── Step 2: Ask protection level ──
-    let level = ask_protection_level()?;
+    let level = match level_flag {
+        Some(level) => level,
+        None if options.yes => ProtectionLevel::Standard,
+        None => ask_protection_level()?,
+    };
 
     // ── Step 3: Generate policy ──
     let template_name = level.template_name();
     let yaml_content = crate::policy::defaults::get_default_policy(template_name)
         .expect("Built-in template should always exist");
 
+    let policy_path = cwd.join(".lawctl.yaml");
     std::fs::write(&policy_path, yaml_content)
         .with_context(|| format!("Failed to write {}", policy_path.display()))?;
 
-    // ── Step 4: Install agent hook ──
-    let hook_installed = match agent.as_deref() {
-        Some("claude-code") => match install_claude_code_hook() {
-            Ok(()) => true,
-            Err(e) => {
-                eprintln!(
-                    "  {} Couldn't auto-install Claude Code hook: {}",
-                    "⚠".yellow(),
-                    e
-                );
-                false
-            }
-        },
-        _ => false,
-    };
+    // ── Step 4: Install agent hooks ──
+    let hook_installed = install_agent_hooks(&cwd, &agents);
 
     // ── Step 5: Show what we did ──
-    print_setup_complete(&policy_path, level, agent.as_deref(), hook_installed);
+    print_setup_complete(&policy_path, level, &agents, hook_installed);
+
+    // ── Step 6: Optional test drive ──
+    if !options.yes && ask_test_drive()? {
+        crate::cli::simulate::run_test_drive(&policy_path)?;
+    }
 
     Ok(())
 }
 
+/// Install hooks for the agents that have them. Returns whether the
+/// Claude Code hook is in place.
+fn install_agent_hooks(project_root: &Path, agents: &[String]) -> bool {
+    if !agents.iter().any(|a| a == "claude-code") {
+        return false;
+    }
+    println!();
+    match install_claude_code_hooks(project_root) {
+        Ok(changes) => {
+            print_settings_changes(&changes);
+            true
+        }
+        Err(e) => {
+            eprintln!(
+                "  {} Couldn't auto-install Claude Code hook: {}",
+                glyph("⚠").yellow(),
+                e
+            );
+            false
+        }
+    }
+}
+
 /// Print the welcome banner.
 fn print_welcome() {
     println!();
-    println!("  {}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".dimmed());
     println!(
-        "  {}  {}",
-        "lawctl".bold(),
-        "— keep your AI agent from breaking things".dimmed()
+        "  {}",
+        glyph("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━").dimmed()
+    );
+    println!("  {}  {}", "lawctl".bold(), t("setup.tagline").dimmed());
+    println!(
+        "  {}",
+        glyph("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━").dimmed()
     );
-    println!("  {}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".dimmed());
     println!();
-    println!("  Lawctl watches what your AI agent does and stops it from:");
-    println!("    - Deleting important files");
-    println!("    - Leaking your secrets (.env, API keys, SSH keys)");
-    println!("    - Running dangerous commands");
-    println!("    - Pushing code without your OK");
+    println!("  {}", t("setup.welcome.intro"));
+    println!("    - {}", t("setup.welcome.deleting"));
+    println!("    - {}", t("setup.welcome.secrets"));
+    println!("    - {}", t("setup.welcome.commands"));
+    println!("    - {}", t("setup.welcome.pushing"));
     println!();
-    println!("  Let's set it up. This takes about 30 seconds.");
+    println!("  {}", t("setup.welcome.start"));
     println!();
 }
 
@@ -129,24 +219,71 @@ fn agent_is_installed(cmd: &str) -> bool {
         .unwrap_or(false)
 }
 
-/// Ask which agent they're using.
-fn ask_agent() -> Result<Option<String>> {
+/// Agents found on PATH, as setup agent ids.
+pub fn detected_agents() -> Vec<String> {
+    [
+        ("claude", "claude-code"),
+        ("cursor", "cursor"),
+        ("codex", "codex"),
+        ("aider", "aider"),
+    ]
+    .iter()
+    .filter(|(binary, _)| agent_is_installed(binary))
+    .map(|(_, id)| id.to_string())
+    .collect()
+}
+
+/// Turn `--agent` values into agent ids. None if none were given.
+fn resolve_agent_flags(flags: &[String]) -> Result<Option<Vec<String>>> {
+    if flags.is_empty() {
+        return Ok(None);
+    }
+    let mut agents: Vec<String> = Vec::new();
+    for flag in flags {
+        let flag = flag.trim().to_ascii_lowercase();
+        let found = match flag.as_str() {
+            "none" => Vec::new(),
+            "auto" => detected_agents(),
+            "claude" => vec!["claude-code".to_string()],
+            id if AGENTS.contains(&id) => vec![id.to_string()],
+            other => anyhow::bail!(
+                "Unknown agent '{}' — use one of: {}, auto, none",
+                other,
+                AGENTS.join(", ")
+            ),
+        };
+        for agent in found {
+            if !agents.contains(&agent) {
+                agents.push(agent);
+            }
+        }
+    }
+    Ok(Some(agents))
+}
+
+/// Ask which agents to protect. Several can be picked ("1 3" or "1,3").
+fn ask_agents() -> Result<Vec<String>> {
     // Show what we found
     let has_claude = agent_is_installed("claude");
     let has_cursor = agent_is_installed("cursor");
 
     if has_claude || has_cursor {
-        println!("  {} Found on your machine:", "?".cyan().bold());
+        println!("  {} {}", "?".cyan().bold(), t("setup.agents.found"));
         if has_claude {
-            println!("    {} Claude Code", "•".green());
+            println!("    {} Claude Code", glyph("•").green());
         }
         if has_cursor {
-            println!("    {} Cursor", "•".green());
+            println!("    {} Cursor", glyph("•").green());
         }
         println!();
     }
 
-    println!("  {} What AI agent are you using?", "1".cyan().bold());
+    println!(
+        "  {} {} {}",
+        "1".cyan().bold(),
+        t("setup.agents.question"),
+        t("setup.agents.hint").dimmed()
+    );
     println!();
 
     let options = [
@@ -154,7 +291,7 @@ fn ask_agent() -> Result<Option<String>> {
         ("Cursor", "cursor"),
         ("Codex (OpenAI)", "codex"),
         ("Aider", "aider"),
-        ("Something else / not sure", "other"),
+        (t("setup.agents.other"), "other"),
     ];
 
     for (i, (label, _)) in options.iter().enumerate() {
@@ -168,36 +305,46 @@ fn ask_agent() -> Result<Option<String>> {
     }
 
     println!();
-    let choice = read_number_choice(options.len())?;
-    let agent_name = options[choice].1;
-
-    if agent_name == "other" {
+    let agents: Vec<String> = read_number_choices(options.len())?
+        .into_iter()
+        .map(|choice| options[choice].1)
+        .filter(|agent| *agent != "other")
+        .map(String::from)
+        .collect();
+
+    if agents.is_empty() {
         println!();
-        println!("    No problem — lawctl works with any agent.");
-        Ok(None)
-    } else {
-        Ok(Some(agent_name.to_string()))
+        println!("    {}", t("setup.agents.none"));
     }
+    Ok(agents)
 }
 
 /// Ask protection level.
 fn ask_protection_level() -> Result<ProtectionLevel> {
     println!();
-    println!("  {} How careful should lawctl be?", "2".cyan().bold());
+    println!("  {} {}", "2".cyan().bold(), t("setup.level.question"));
     println!();
     println!(
         "    {} {}  {}",
         "1".cyan().bold(),
-        "Standard".bold(),
-        "(recommended)".dimmed()
+        t("setup.level.standard").bold(),
+        t("setup.level.recommended").dimmed()
     );
-    println!("      Blocks dangerous stuff, asks before git push");
+    println!("      {}", t("setup.level.standard.about"));
     println!();
-    println!("    {} {}", "2".cyan().bold(), "Strict".bold());
-    println!("      Locks things down more — for important projects");
+    println!(
+        "    {} {}",
+        "2".cyan().bold(),
+        t("setup.level.strict").bold()
+    );
+    println!("      {}", t("setup.level.strict.about"));
     println!();
-    println!("    {} {}", "3".cyan().bold(), "Relaxed".bold());
-    println!("      Logs everything but doesn't block much — for trying it out");
+    println!(
+        "    {} {}",
+        "3".cyan().bold(),
+        t("setup.level.relaxed").bold()
+    );
+    println!("      {}", t("setup.level.relaxed.about"));
     println!();
 
     let choice = read_number_choice(3)?;
@@ -213,7 +360,7 @@ fn ask_protection_level() -> Result<ProtectionLevel> {
 /// Read a 1-based number choice from the user.
 fn read_number_choice(max: usize) -> Result<usize> {
     loop {
-        print!("  {} ", "→".blue());
+        print!("  {} ", glyph("→").blue());
         io::stdout().flush()?;
 
         let mut input = String::new();
@@ -231,22 +378,124 @@ fn read_number_choice(max: usize) -> Result<usize> {
             }
         }
 
-        println!("    {} Pick a number 1-{}", "?".yellow(), max);
+        println!(
+            "    {} {}",
+            "?".yellow(),
+            tf("setup.pick.one", &[("max", &max)])
+        );
+    }
+}
+
+/// Read one or more 1-based number choices, separated by spaces or commas.
+/// Returns them 0-based, deduplicated, in the order given.
+fn read_number_choices(max: usize) -> Result<Vec<usize>> {
+    loop {
+        print!("  {} ", glyph("→").blue());
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        let bytes_read = io::stdin().read_line(&mut input)?;
+
+        if bytes_read == 0 {
+            println!("1");
+            return Ok(vec![0]);
+        }
+
+        if let Some(choices) = parse_number_choices(&input, max) {
+            return Ok(choices);
+        }
+
+        println!(
+            "    {} {}",
+            "?".yellow(),
+            tf("setup.pick.many", &[("max", &max)])
+        );
+    }
+}
+
+fn parse_number_choices(input: &str, max: usize) -> Option<Vec<usize>> {
+    let mut choices = Vec::new();
+    for part in input
+        .split([' ', ',', '\t'])
+        .filter(|p| !p.trim().is_empty())
+    {
+        let n: usize = part.trim().parse().ok()?;
+        if n < 1 || n > max {
+            return None;
+        }
+        if !choices.contains(&(n - 1)) {
+            choices.push(n - 1);
+        }
+    }
+    (!choices.is_empty()).then_some(choices)
+}
+
+/// Offer to show lawctl at work on a few fake actions. Defaults to yes;
+/// a closed stdin (non-interactive setup) skips it.
+fn ask_test_drive() -> Result<bool> {
+    println!("  {} {}", "?".cyan().bold(), t("setup.test_drive.question"));
+    print!("  {} ", glyph("→").blue());
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        println!();
+        return Ok(false);
     }
+    Ok(!matches!(input.trim(), "n" | "N" | "no"))
 }
 
 // ── Claude Code Hook Installation ──────────────────────────────────────
 
-/// Install lawctl-hook into Claude Code's user settings.
-fn install_claude_code_hook() -> Result<()> {
-    let settings_path = claude_code_settings_path()?;
+/// Command name the hook entry is recognised by.
+const HOOK_COMMAND: &str = "lawctl-hook";
+
+/// Tools the hook needs to see.
+const HOOK_MATCHER: &str = "Bash|Write|Edit|NotebookEdit";
 
-    // Read existing settings or start fresh
-    let mut settings: serde_json::Value = if settings_path.exists() {
-        let content = std::fs::read_to_string(&settings_path)
+/// What setup did to one Claude Code settings file.
+#[derive(Debug)]
+struct SettingsChange {
+    path: PathBuf,
+    outcome: SettingsOutcome,
+}
+
+#[derive(Debug, PartialEq)]
+enum SettingsOutcome {
+    /// The file didn't exist; it was created with just the hook
+    Created,
+    /// The hook was added; the original was copied to `backup`
+    Added { backup: PathBuf },
+    /// The hook was already there
+    AlreadyInstalled,
+}
+
+/// Install lawctl-hook into Claude Code's user settings, plus any project
+/// settings files (`.claude/settings.json`, `.claude/settings.local.json`)
+/// that already exist — project settings can carry their own hooks config.
+fn install_claude_code_hooks(project_root: &Path) -> Result<Vec<SettingsChange>> {
+    let hook_binary = find_hook_binary()?;
+    let mut targets = vec![claude_code_settings_path()?];
+    for name in ["settings.json", "settings.local.json"] {
+        let path = project_root.join(".claude").join(name);
+        if path.exists() {
+            targets.push(path);
+        }
+    }
+    targets
+        .into_iter()
+        .map(|path| install_hook_into(&path, &hook_binary))
+        .collect()
+}
+
+/// Merge the hook into one settings file, backing it up first.
+fn install_hook_into(settings_path: &Path, hook_binary: &Path) -> Result<SettingsChange> {
+    let existed = settings_path.exists();
+    let mut settings: serde_json::Value = if existed {
+        let content = std::fs::read_to_string(settings_path)
             .with_context(|| format!("Failed to read {}", settings_path.display()))?;
         serde_json::from_str(&content)
-            .with_context(|| "Failed to parse Claude Code settings.json")?
+            .with_context(|| format!("Failed to parse {}", settings_path.display()))?
     } else {
         // Create the directory if needed
         if let Some(parent) = settings_path.parent() {
@@ -255,24 +504,43 @@ fn install_claude_code_hook() -> Result<()> {
         serde_json::json!({})
     };
 
-    // Find the lawctl-hook binary
-    let hook_binary = find_hook_binary()?;
+    if !merge_hook(&mut settings, hook_binary)
+        .with_context(|| format!("Unexpected layout in {}", settings_path.display()))?
+    {
+        return Ok(SettingsChange {
+            path: settings_path.to_path_buf(),
+            outcome: SettingsOutcome::AlreadyInstalled,
+        });
+    }
 
-    // Build the hook config
-    let hook_entry = serde_json::json!({
-        "type": "command",
-        "command": hook_binary.to_string_lossy()
-    });
+    let outcome = if existed {
+        let mut backup = settings_path.as_os_str().to_owned();
+        backup.push(".lawctl-backup");
+        let backup = PathBuf::from(backup);
+        std::fs::copy(settings_path, &backup)
+            .with_context(|| format!("Failed to back up {}", settings_path.display()))?;
+        SettingsOutcome::Added { backup }
+    } else {
+        SettingsOutcome::Created
+    };
 
-    let pretool_rule = serde_json::json!({
-        "matcher": "Bash|Write|Edit|NotebookEdit",
-        "hooks": [hook_entry]
-    });
+    // Write back
+    let content = serde_json::to_string_pretty(&settings)?;
+    std::fs::write(settings_path, content)
+        .with_context(|| format!("Failed to write {}", settings_path.display()))?;
+
+    Ok(SettingsChange {
+        path: settings_path.to_path_buf(),
+        outcome,
+    })
+}
 
-    // Merge into settings — don't clobber existing hooks
+/// Add the lawctl PreToolUse hook to a settings object without clobbering
+/// existing hooks. Returns false if it was already there.
+fn merge_hook(settings: &mut serde_json::Value, hook_binary: &Path) -> Result<bool> {
     let hooks = settings
         .as_object_mut()
-        .ok_or_else(|| anyhow::anyhow!("settings.json is not an object"))?
+        .ok_or_else(|| anyhow::anyhow!("settings is not an object"))?
         .entry("hooks")
         .or_insert(serde_json::json!({}));
 
@@ -297,30 +565,55 @@ fn install_claude_code_hook() -> Result<()> {
                 hooks.iter().any(|h| {
                     h.get("command")
                         .and_then(|c| c.as_str())
-                        .map(|c| c.contains("lawctl-hook"))
+                        .map(|c| c.contains(HOOK_COMMAND))
                         .unwrap_or(false)
                 })
             })
             .unwrap_or(false)
     });
-
     if already_installed {
-        println!("  {} Claude Code hook already installed", "✓".green());
-        return Ok(());
+        return Ok(false);
     }
 
-    // Add our hook
-    pretool_arr.push(pretool_rule);
-
-    // Write back
-    let content = serde_json::to_string_pretty(&settings)?;
-    std::fs::write(&settings_path, content)
-        .with_context(|| format!("Failed to write {}", settings_path.display()))?;
-
-    println!("  {} Installed Claude Code hook", "✓".green());
-    println!("    {}", settings_path.display().to_string().dimmed());
+    pretool_arr.push(serde_json::json!({
+        "matcher": HOOK_MATCHER,
+        "hooks": [{
+            "type": "command",
+            "command": hook_binary.to_string_lossy()
+        }]
+    }));
+    Ok(true)
+}
 
-    Ok(())
+/// Tell the user exactly which settings files were touched.
+fn print_settings_changes(changes: &[SettingsChange]) {
+    for change in changes {
+        let path = change.path.display().to_string();
+        match &change.outcome {
+            SettingsOutcome::Created => {
+                println!("  {} Created {}", glyph("✓").green(), path.dimmed());
+                println!(
+                    "    added PreToolUse hook: {} ({})",
+                    HOOK_COMMAND, HOOK_MATCHER
+                );
+            }
+            SettingsOutcome::Added { backup } => {
+                println!("  {} Modified {}", glyph("✓").green(), path.dimmed());
+                println!(
+                    "    added PreToolUse hook: {} ({})",
+                    HOOK_COMMAND, HOOK_MATCHER
+                );
+                println!("    backup: {}", backup.display().to_string().dimmed());
+            }
+            SettingsOutcome::AlreadyInstalled => {
+                println!(
+                    "  {} Claude Code hook already installed in {}",
+                    glyph("✓").green(),
+                    path.dimmed()
+                );
+            }
+        }
+    }
 }
 
 /// Get the path to Claude Code's user settings.json
@@ -369,78 +662,192 @@ fn find_hook_binary() -> Result<PathBuf> {
 fn print_setup_complete(
     policy_path: &Path,
     level: ProtectionLevel,
-    agent: Option<&str>,
+    agents: &[String],
     hook_installed: bool,
 ) {
     println!();
-    println!("  {}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".dimmed());
-    println!("  {} You're protected!", "✓".green().bold());
-    println!("  {}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".dimmed());
+    println!(
+        "  {}",
+        glyph("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━").dimmed()
+    );
+    println!("  {} {}", glyph("✓").green().bold(), t("setup.done.title"));
+    println!(
+        "  {}",
+        glyph("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━").dimmed()
+    );
     println!();
 
     match level {
         ProtectionLevel::Standard => {
-            println!("  Lawctl will now:");
+            println!("  {}", t("setup.done.will"));
+            println!(
+                "    {} {}",
+                glyph("•").green(),
+                t("setup.done.block_secrets")
+            );
             println!(
-                "    {} Block agents from touching your secrets",
-                "•".green()
+                "    {} {}",
+                glyph("•").green(),
+                t("setup.done.prevent_deletes")
             );
-            println!("    {} Stop accidental file deletions", "•".green());
-            println!("    {} Block dangerous shell commands", "•".green());
-            println!("    {} Ask you before any git push", "•".yellow());
-            println!("    {} Log everything the agent does", "•".blue());
+            println!(
+                "    {} {}",
+                glyph("•").green(),
+                t("setup.done.block_commands")
+            );
+            println!("    {} {}", glyph("•").yellow(), t("setup.done.ask_push"));
+            println!("    {} {}", glyph("•").blue(), t("setup.done.log"));
         }
         ProtectionLevel::Strict => {
-            println!("  Lawctl will now:");
-            println!("    {} Block all git pushes", "•".red());
-            println!("    {} Only allow writes to build output dirs", "•".green());
-            println!("    {} Block dangerous shell commands", "•".green());
-            println!("    {} Restrict network to package registries", "•".green());
-            println!("    {} Log everything the agent does", "•".blue());
-        }
-        ProtectionLevel::Relaxed => {
-            println!("  Lawctl will now:");
-            println!("    {} Log everything the agent does", "•".blue());
-            println!("    {} Ask you before any git push", "•".yellow());
+            println!("  {}", t("setup.done.will"));
+            println!("    {} {}", glyph("•").red(), t("setup.done.block_push"));
+            println!("    {} {}", glyph("•").green(), t("setup.done.build_only"));
             println!(
-                "    {}",
-                "  Most actions are allowed — good for getting started.".dimmed()
+                "    {} {}",
+                glyph("•").green(),
+                t("setup.done.block_commands")
             );
+            println!("    {} {}", glyph("•").green(), t("setup.done.registries"));
+            println!("    {} {}", glyph("•").blue(), t("setup.done.log"));
+        }
+        ProtectionLevel::Relaxed => {
+            println!("  {}", t("setup.done.will"));
+            println!("    {} {}", glyph("•").blue(), t("setup.done.log"));
+            println!("    {} {}", glyph("•").yellow(), t("setup.done.ask_push"));
+            println!("    {}", t("setup.done.relaxed_note").dimmed());
         }
     }
 
     println!();
 
-    if hook_installed && agent == Some("claude-code") {
-        // The golden path — nothing more to do
-        println!("  {} That's it. Just use Claude Code normally.", "→".blue());
-        println!("    Lawctl runs in the background on every action.");
-        println!();
-        println!("  {} If lawctl blocks something, you'll see:", "ℹ".blue());
-        println!(
-            "    {}",
-            "[lawctl] BLOCKED: write '.env' — denied by policy".dimmed()
-        );
-    } else if agent == Some("cursor") {
-        println!("  {} To use with Cursor:", "→".blue());
-        println!("    Cursor extension coming soon. For now:");
-        println!("    {}", "lawctl go -- cursor".bold());
-    } else {
-        println!("  {} To run your agent with protection:", "→".blue());
-        println!();
-        match agent {
-            Some(name) => println!("    {}", format!("lawctl go -- {}", name).bold()),
-            None => println!("    {}", "lawctl go -- <your agent command>".bold()),
+    for (i, agent) in agents.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        match agent.as_str() {
+            "claude-code" if hook_installed => {
+                // The golden path — nothing more to do
+                println!("  {} {}", glyph("→").blue(), t("setup.done.claude_ready"));
+                println!("    {}", t("setup.done.background"));
+                println!();
+                println!(
+                    "  {} {}",
+                    glyph("ℹ").blue(),
+                    t("setup.done.blocked_example")
+                );
+                println!(
+                    "    {}",
+                    "[lawctl] BLOCKED: write '.env' — denied by policy".dimmed()
+                );
+            }
+            "claude-code" => {
+                println!("  {} {}", glyph("→").blue(), t("setup.done.run_claude"));
+                println!("    {}", "lawctl go -- claude".bold());
+            }
+            "cursor" => {
+                println!("  {} {}", glyph("→").blue(), t("setup.done.cursor"));
+                println!("    {}", t("setup.done.cursor_soon"));
+                println!("    {}", "lawctl go -- cursor".bold());
+            }
+            name => {
+                println!(
+                    "  {} {}",
+                    glyph("→").blue(),
+                    tf("setup.done.run_named", &[("agent", &name)])
+                );
+                println!("    {}", format!("lawctl go -- {}", name).bold());
+            }
         }
     }
+    if agents.is_empty() {
+        println!("  {} {}", glyph("→").blue(), t("setup.done.run_any"));
+        println!();
+        println!("    {}", "lawctl go -- <your agent command>".bold());
+    }
 
     println!();
-    println!("  {} To see what your agent did:", "→".blue());
+    println!("  {} {}", glyph("→").blue(), t("setup.done.see_log"));
     println!("    {}", "lawctl log".bold());
     println!();
     println!(
-        "  Policy saved to: {}",
-        policy_path.display().to_string().dimmed()
+        "  {}",
+        tf(
+            "setup.done.saved",
+            &[("path", &policy_path.display().to_string().dimmed())]
+        )
     );
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_agent_flags() {
+        assert_eq!(resolve_agent_flags(&[]).unwrap(), None);
+        assert_eq!(
+            resolve_agent_flags(&["claude".to_string(), "aider".to_string()]).unwrap(),
+            Some(vec!["claude-code".to_string(), "aider".to_string()])
+        );
+        assert_eq!(
+            resolve_agent_flags(&["none".to_string()]).unwrap(),
+            Some(vec![])
+        );
+        assert!(resolve_agent_flags(&["vim".to_string()]).is_err());
+        assert!(matches!(
+            ProtectionLevel::from_name("Strict"),
+            Some(ProtectionLevel::Strict)
+        ));
+    }
+
+    #[test]
+    fn test_parse_number_choices() {
+        assert_eq!(parse_number_choices("1 3\n", 5), Some(vec![0, 2]));
+        assert_eq!(parse_number_choices("2,1, 2", 5), Some(vec![1, 0]));
+        assert_eq!(parse_number_choices("6", 5), None);
+        assert_eq!(parse_number_choices("  \n", 5), None);
+    }
+
+    #[test]
+    fn test_hook_merged_with_backup() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("settings.local.json");
+        let original = r#"{"permissions": {"allow": ["Bash(ls)"]}, "hooks": {"PreToolUse": [
+            {"matcher": "Bash", "hooks": [{"type": "command", "command": "other-hook"}]}
+        ]}}"#;
+        std::fs::write(&path, original).unwrap();
+
+        let change = install_hook_into(&path, Path::new("/usr/local/bin/lawctl-hook")).unwrap();
+        let SettingsOutcome::Added { backup } = &change.outcome else {
+            panic!("expected a backup, got {:?}", change.outcome);
+        };
+        assert_eq!(std::fs::read_to_string(backup).unwrap(), original);
+
+        let merged: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(merged["permissions"]["allow"][0], "Bash(ls)");
+        let pretool = merged["hooks"]["PreToolUse"].as_array().unwrap();
+        assert_eq!(pretool.len(), 2);
+        assert_eq!(
+            pretool[1]["hooks"][0]["command"],
+            "/usr/local/bin/lawctl-hook"
+        );
+
+        // Running setup again changes nothing
+        let again = install_hook_into(&path, Path::new("/usr/local/bin/lawctl-hook")).unwrap();
+        assert_eq!(again.outcome, SettingsOutcome::AlreadyInstalled);
+    }
+
+    #[test]
+    fn test_missing_settings_created() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join(".claude/settings.json");
+        let change = install_hook_into(&path, Path::new("lawctl-hook")).unwrap();
+        assert_eq!(change.outcome, SettingsOutcome::Created);
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("lawctl-hook"));
+    }
+}
@@ -0,0 +1,311 @@
+//! `lawctl status --global` — every project lawctl knows about, at a glance.
+//!
+//! Projects come from the log index (anywhere a session has run) and the
+//! policy approvals (anywhere a policy has been checked). For each one:
+//! whether its policy is still there, how the last session went, how much
+//! was denied, and whether the Claude Code hook would fire there.
+
+use crate::audit::{AuditLogger, AuditReader, ProjectIndex, SessionStatus, SessionSummary};
+use crate::cli::log::format_duration;
+use crate::integrity::{self, Check, IntegrityPaths, Status};
+use crate::policy::parser;
+use crate::utils::term::glyph;
+use anyhow::Result;
+use chrono::Utc;
+use colored::Colorize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// What `status --global` shows for one project.
+#[derive(Debug)]
+pub struct ProjectStatus {
+    pub workspace: PathBuf,
+    /// The policy's `law:` name, or why there isn't one
+    pub policy: std::result::Result<String, String>,
+    pub last_session: Option<(SessionSummary, SessionStatus)>,
+    pub sessions: usize,
+    /// Denied actions across all of the project's sessions
+    pub denied: usize,
+    pub hook: Check,
+}
+
+impl ProjectStatus {
+    /// Look a project up in `log_dir`.
+    pub fn gather(workspace: &Path, log_dir: &Path, paths: &IntegrityPaths) -> Self {
+        let found = parser::policy_file_in(workspace);
+        let policy_path = found
+            .clone()
+            .unwrap_or_else(|| workspace.join(parser::POLICY_FILE));
+        let policy = match found {
+            None => Err("no .lawctl.yaml".to_string()),
+            Some(_) => parser::parse_policy_file(&policy_path)
+                .map(|p| p.law)
+                .map_err(|_| {
+                    format!(
+                        "{} doesn't parse",
+                        policy_path
+                            .file_name()
+                            .unwrap_or_default()
+                            .to_string_lossy()
+                    )
+                }),
+        };
+
+        let reader = AuditReader::with_dir(log_dir).scoped_to(workspace);
+        let summaries: Vec<SessionSummary> = reader
+            .list_sessions()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|id| reader.read_session(id).ok())
+            .filter(|entries| !entries.is_empty())
+            .map(|entries| AuditReader::summarize(&entries))
+            .collect();
+        let denied = summaries.iter().map(|s| s.denied).sum();
+        let sessions = summaries.len();
+        let last_session = summaries
+            .into_iter()
+            .max_by_key(|s| s.end_time.or(s.start_time))
+            .map(|summary| {
+                let status = reader
+                    .session_status(&summary.session_id)
+                    .unwrap_or(SessionStatus::Unknown);
+                (summary, status)
+            });
+
+        let hook = integrity::check_hook(
+            paths,
+            &integrity::Expectations {
+                policy_hash: String::new(),
+                policy_path,
+                workspace: workspace.to_path_buf(),
+                requires_hook: false,
+            },
+        );
+
+        Self {
+            workspace: workspace.to_path_buf(),
+            policy,
+            last_session,
+            sessions,
+            denied,
+            hook,
+        }
+    }
+
+    /// The worst of the project's problems.
+    pub fn status(&self) -> Status {
+        let session_abnormal = self
+            .last_session
+            .as_ref()
+            .is_some_and(|(_, status)| status.is_abnormal());
+        if self.policy.is_err() || self.hook.status == Status::Failed {
+            Status::Failed
+        } else if session_abnormal || self.hook.status == Status::Warning {
+            Status::Warning
+        } else {
+            Status::Ok
+        }
+    }
+
+    fn when(&self) -> Option<chrono::DateTime<Utc>> {
+        self.last_session
+            .as_ref()
+            .and_then(|(s, _)| s.end_time.or(s.start_time))
+    }
+
+    fn print(&self) {
+        let icon = match self.status() {
+            Status::Ok => glyph("✓").green(),
+            Status::Warning => glyph("⚠").yellow(),
+            Status::Failed => glyph("✗").red(),
+        };
+        let missing = if self.workspace.exists() {
+            String::new()
+        } else {
+            " (directory is gone)".to_string()
+        };
+        println!(
+            "  {} {}{}",
+            icon,
+            self.workspace.display().to_string().bold(),
+            missing.dimmed()
+        );
+        match &self.policy {
+            Ok(law) => println!("      Policy:  {}", law.cyan()),
+            Err(why) => println!("      Policy:  {}", why.red()),
+        }
+        match &self.last_session {
+            Some((summary, status)) => {
+                let age = self
+                    .when()
+                    .map(|t| format!("{} ago", format_duration((Utc::now() - t).num_seconds())))
+                    .unwrap_or_else(|| "unknown time".to_string());
+                let ended = status
+                    .describe()
+                    .map(|d| format!(", {}", d))
+                    .unwrap_or_default();
+                let line = format!("{}{} — {}", age, ended, summary.one_line());
+                if status.is_abnormal() {
+                    println!("      Last:    {}", line.yellow());
+                } else {
+                    println!("      Last:    {}", line);
+                }
+                let denied = format!(
+                    "{} denied across {} session{}",
+                    self.denied,
+                    self.sessions,
+                    if self.sessions == 1 { "" } else { "s" }
+                );
+                if self.denied > 0 {
+                    println!("      Denied:  {}", denied.red());
+                } else {
+                    println!("      Denied:  {}", denied.dimmed());
+                }
+            }
+            None => println!("      Last:    {}", "no sessions yet".dimmed()),
+        }
+        let hook = match self.hook.status {
+            Status::Ok => self.hook.detail.normal(),
+            Status::Warning => self.hook.detail.yellow(),
+            Status::Failed => self.hook.detail.red(),
+        };
+        println!("      Hook:    {}", hook);
+    }
+}
+
+/// Every workspace lawctl has seen: the log index plus approved policies.
+pub fn known_projects(log_dir: &Path, paths: &IntegrityPaths) -> Vec<PathBuf> {
+    let mut projects: BTreeSet<PathBuf> =
+        ProjectIndex::load(log_dir).projects.into_values().collect();
+    projects.extend(
+        integrity::approved_policies(&paths.approvals)
+            .into_iter()
+            .filter_map(|policy| policy.parent().map(Path::to_path_buf)),
+    );
+    projects.into_iter().collect()
+}
+
+/// Run `lawctl status --global`.
+pub fn run_global_status() -> Result<()> {
+    let log_dir = AuditLogger::log_directory()?;
+    let paths = IntegrityPaths::default();
+    let mut projects: Vec<ProjectStatus> = known_projects(&log_dir, &paths)
+        .iter()
+        .map(|workspace| ProjectStatus::gather(workspace, &log_dir, &paths))
+        .collect();
+    // Most recently used first
+    projects.sort_by_key(|p| std::cmp::Reverse(p.when()));
+
+    println!();
+    if projects.is_empty() {
+        println!(
+            "  {} lawctl hasn't protected any projects on this machine yet.",
+            glyph("ℹ").blue()
+        );
+        println!("  Set one up with {}", "lawctl setup".bold());
+        println!();
+        return Ok(());
+    }
+
+    let failing = projects
+        .iter()
+        .filter(|p| p.status() == Status::Failed)
+        .count();
+    println!(
+        "  {}  {}",
+        "lawctl".bold(),
+        format!(
+            "— {} project{}",
+            projects.len(),
+            if projects.len() == 1 { "" } else { "s" }
+        )
+        .dimmed()
+    );
+    println!(
+        "  {}",
+        glyph("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━").dimmed()
+    );
+    for project in &projects {
+        println!();
+        project.print();
+    }
+    println!();
+    if failing > 0 {
+        println!(
+            "  {} {} project{} not protected — run {} there",
+            glyph("✗").red(),
+            failing,
+            if failing == 1 { " is" } else { "s are" },
+            "lawctl doctor".bold()
+        );
+        println!();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{projects, Actor, LogEntry};
+    use crate::policy::types::{Action, Decision};
+
+    fn entry(session: &str, decision: Decision) -> String {
+        serde_json::to_string(&LogEntry {
+            timestamp: Utc::now(),
+            session_id: session.to_string(),
+            agent: "claude".to_string(),
+            action: Action::Write,
+            target: "src/main.rs".to_string(),
+            policy_rule: None,
+            decision,
+            diff: None,
+            approved_by: None,
+            eval_duration_us: None,
+            network: None,
+            warning: None,
+            output: None,
+            threats: Vec::new(),
+            actor: Actor::Agent,
+            process_chain: Vec::new(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_gather() {
+        let logs = tempfile::TempDir::new().unwrap();
+        let home = tempfile::TempDir::new().unwrap();
+        let workspace = tempfile::TempDir::new().unwrap();
+        let paths = IntegrityPaths {
+            claude_settings: home.path().join("settings.json"),
+            shim_dir: home.path().join("shims"),
+            approvals: home.path().join("approved-policies.json"),
+        };
+
+        let dir = projects::project_log_dir(logs.path(), workspace.path());
+        std::fs::create_dir_all(&dir).unwrap();
+        let denied = || Decision::Denied {
+            reason: "no".to_string(),
+            matched_rule: None,
+        };
+        let allowed = || Decision::Allowed { matched_rule: None };
+        std::fs::write(
+            dir.join("s1.jsonl"),
+            [entry("s1", denied()), entry("s1", allowed())].join("\n"),
+        )
+        .unwrap();
+        std::fs::write(dir.join("s2.jsonl"), entry("s2", denied())).unwrap();
+        ProjectIndex::register(logs.path(), "key", workspace.path()).unwrap();
+
+        let status = ProjectStatus::gather(workspace.path(), logs.path(), &paths);
+        assert_eq!(status.policy, Err("no .lawctl.yaml".to_string()));
+        assert_eq!((status.sessions, status.denied), (2, 2));
+        assert_eq!(status.last_session.unwrap().0.session_id, "s2");
+        assert_eq!(status.hook.status, Status::Warning);
+
+        assert_eq!(
+            known_projects(logs.path(), &paths),
+            [workspace.path().canonicalize().unwrap()]
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! `lawctl policy upgrade-template` — bring a policy generated from an
+//! older built-in template up to date.
+//!
+//! Only the rules later revisions of the template added go in, at the top
+//! of the rules list the way `lawctl tighten` adds them, and only the ones
+//! the policy doesn't have already. Everything else in the file — edited
+//! rules, removed rules, comments — stays as the user left it (see
+//! [`crate::policy::editor`]).
+
+use crate::policy::defaults::{self, TemplateRef};
+use crate::policy::editor::PolicyEditor;
+use crate::policy::{parser, Policy};
+use crate::utils::term::glyph;
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::path::Path;
+
+/// An upgraded policy file.
+pub struct Upgrade {
+    pub from: TemplateRef,
+    pub to: TemplateRef,
+    /// The rules added, as YAML list items
+    pub added: Vec<&'static str>,
+    pub yaml: String,
+}
+
+/// The one-line nudge towards `lawctl policy upgrade-template`, when the
+/// policy came from an older revision of a built-in template.
+pub fn upgrade_offer(policy: &Policy) -> Option<String> {
+    let template = defaults::outdated_template(policy)?;
+    Some(format!(
+        "Generated from {} v{}; v{} has new protections — run {} to add them.",
+        template.name,
+        template.version,
+        template.latest().version,
+        "lawctl policy upgrade-template".bold()
+    ))
+}
+
+/// `yaml` (which parses to `policy`) brought up to the latest revision of
+/// its template, or None when it's already there.
+pub fn upgrade(yaml: &str, policy: &Policy) -> Result<Option<Upgrade>> {
+    let Some(from) = defaults::template_of(policy) else {
+        bail!("This policy wasn't generated from a built-in template — it has no 'template:' line");
+    };
+    let to = from.latest();
+    if to.version <= from.version {
+        return Ok(None);
+    }
+
+    let existing: Vec<serde_json::Value> = policy
+        .rules
+        .iter()
+        .filter_map(|rule| serde_json::to_value(rule).ok())
+        .collect();
+    let mut added = Vec::new();
+    for rule in from.rules_added_since() {
+        let parsed = parser::parse_policy_str(&format!("law: upgrade\nrules:\n{}", rule))
+            .context("Built-in template rule doesn't parse")?;
+        let value = serde_json::to_value(&parsed.rules[0])?;
+        if !existing.contains(&value) {
+            added.push(rule);
+        }
+    }
+
+    let mut editor = PolicyEditor::new(yaml);
+    if !added.is_empty() {
+        editor.insert_rules(
+            &format!("Added by lawctl policy upgrade-template ({})", to),
+            &added,
+        )?;
+    }
+    editor.set_top_level("template", &to.to_string())?;
+    editor.validate()?;
+    Ok(Some(Upgrade {
+        from,
+        to,
+        added,
+        yaml: editor.text(),
+    }))
+}
+
+/// Run `lawctl policy upgrade-template`.
+pub fn run_upgrade_template(policy_path: &Path) -> Result<()> {
+    if parser::PolicyFormat::of(policy_path) != parser::PolicyFormat::Yaml {
+        bail!(
+            "lawctl policy upgrade-template only edits YAML policies, not {}",
+            policy_path.display()
+        );
+    }
+    let original = std::fs::read_to_string(policy_path)
+        .with_context(|| format!("Failed to read {}", policy_path.display()))?;
+    let policy = parser::parse_policy_file(policy_path)?;
+
+    println!();
+    let Some(upgrade) = upgrade(&original, &policy)? else {
+        println!(
+            "  {} Already on the latest {} template.",
+            glyph("✓").green(),
+            policy.template.as_deref().unwrap_or(&policy.law).cyan()
+        );
+        println!();
+        return Ok(());
+    };
+
+    std::fs::write(policy_path, &upgrade.yaml)
+        .with_context(|| format!("Failed to write {}", policy_path.display()))?;
+    println!(
+        "  {} Upgraded {} from {} v{} to v{}",
+        glyph("✓").green().bold(),
+        policy_path.display().to_string().bold(),
+        upgrade.from.name,
+        upgrade.from.version,
+        upgrade.to.version
+    );
+    if upgrade.added.is_empty() {
+        println!("  The policy already had every new rule.");
+    } else {
+        println!(
+            "  Added {} rule{}:",
+            upgrade.added.len(),
+            if upgrade.added.len() == 1 { "" } else { "s" }
+        );
+        for rule in &upgrade.added {
+            for line in rule.lines() {
+                println!("    {}", line.cyan());
+            }
+        }
+    }
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upgrade_keeps_customizations() {
+        // A pared-down safe-dev v1 copy with a rule of the user's own
+        let yaml = "law: safe-dev-v1\n\nrules:\n  # Mine\n  - allow: write\n    if_path_matches: [\"notes/**\"]\n  - require_approval: git_push\n  - require_approval: remote_exec\n    prompt: \"The AI agent wants to run commands on another machine.\"\n";
+        let policy = parser::parse_policy_str(yaml).unwrap();
+        assert_eq!(
+            defaults::outdated_template(&policy),
+            TemplateRef::parse("safe-dev@1")
+        );
+
+        let upgrade = upgrade(yaml, &policy).unwrap().unwrap();
+        assert_eq!(upgrade.to, TemplateRef::parse("safe-dev@2").unwrap());
+        assert!(upgrade
+            .yaml
+            .starts_with("law: safe-dev-v1\ntemplate: safe-dev@2\n"));
+        assert!(upgrade.yaml.contains("  # Mine\n  - allow: write"));
+        // The remote_exec approval was already there
+        assert!(!upgrade
+            .added
+            .iter()
+            .any(|r| r.starts_with("- require_approval: remote_exec")));
+        assert!(!upgrade.added.is_empty());
+
+        let upgraded = parser::parse_policy_str(&upgrade.yaml).unwrap();
+        assert_eq!(
+            upgraded.rules.len(),
+            policy.rules.len() + upgrade.added.len()
+        );
+        assert!(defaults::outdated_template(&upgraded).is_none());
+        assert!(super::upgrade(&upgrade.yaml, &upgraded).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_upgrade_needs_a_template() {
+        let yaml = "law: mine\nrules:\n  - allow: write\n";
+        let policy = parser::parse_policy_str(yaml).unwrap();
+        assert!(upgrade_offer(&policy).is_none());
+        assert!(upgrade(yaml, &policy).is_err());
+    }
+}
@@ -0,0 +1,406 @@
+//! `lawctl tighten` — turn what permissive sessions got away with into rules.
+//!
+//! The permissive template allows nearly everything, which is fine for
+//! building trust but not somewhere to stay. Once a project has a few
+//! sessions behind it, this replays their audit logs against the current
+//! policy, picks out risky actions the policy still allows (writes to
+//! secrets, dangerous commands, deletes, network hosts, package installs)
+//! and proposes a rule for each kind. Accepted rules go at the top of the
+//! policy's `rules:` so they win over the broad allows below them; the rest
+//! of the file, comments included, is left as it was.
+
+use crate::audit::{AuditReader, LogEntry};
+use crate::cli::simulate;
+use crate::policy::editor::PolicyEditor;
+use crate::policy::{parser, Action, Policy, PolicyEngine};
+use crate::utils::domain::host_and_port;
+use crate::utils::paths::{CompiledCommandMatcher, CompiledMatcher};
+use crate::utils::term::glyph;
+use anyhow::{bail, Result};
+use colored::Colorize;
+use std::collections::BTreeSet;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Sessions a permissive policy runs before `status` and `go` suggest
+/// tightening it.
+pub const OFFER_AFTER_SESSIONS: usize = 5;
+
+/// Files that hold credentials.
+const SECRET_PATHS: &[&str] = &["*.env", "*.env.*", ".ssh/*", "*.pem", "*.key"];
+
+/// Where deletes are harmless.
+const SCRATCH_PATHS: &[&str] = &[
+    "/tmp",
+    "tmp/",
+    "dist/",
+    "build/",
+    "target/",
+    "node_modules/",
+];
+
+/// Commands worth denying outright.
+const RISKY_COMMANDS: &[&str] = &[
+    "rm -rf *",
+    "sudo *",
+    "curl * | *sh",
+    "wget * | *sh",
+    "chmod 777 *",
+    "chmod -R *",
+    "git reset --hard*",
+    "git clean -f*",
+    "dd if=*",
+];
+
+/// Most examples shown per proposal.
+const MAX_EVIDENCE: usize = 3;
+
+/// A rule `tighten` suggests, and the logged actions behind it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proposal {
+    /// What the rule does, in a sentence
+    pub summary: String,
+    /// Allowed actions it would have caught
+    pub evidence: Vec<String>,
+    /// The rule as a YAML list item, unindented
+    pub yaml: String,
+}
+
+/// Whether a policy is the permissive template (or started out as it).
+pub fn is_permissive(policy: &Policy) -> bool {
+    policy.law.starts_with("permissive")
+}
+
+/// A workspace's sessions that have entries, oldest and newest alike.
+pub fn load_sessions(workspace: &Path) -> Vec<Vec<LogEntry>> {
+    let Ok(reader) = AuditReader::for_workspace(workspace) else {
+        return Vec::new();
+    };
+    reader
+        .list_sessions()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|id| reader.read_session(id).ok())
+        .filter(|entries| !entries.is_empty())
+        .collect()
+}
+
+/// The one-line nudge towards `lawctl tighten`, when a permissive policy
+/// has run enough sessions and there's something to tighten.
+pub fn offer(policy: &Policy, workspace: &Path) -> Option<String> {
+    if !is_permissive(policy) {
+        return None;
+    }
+    let sessions = load_sessions(workspace);
+    if sessions.len() < OFFER_AFTER_SESSIONS {
+        return None;
+    }
+    let engine = PolicyEngine::new(policy.clone()).ok()?;
+    let proposals = analyze(&engine, &sessions);
+    if proposals.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "You've run {} sessions on the permissive policy — {} can suggest {} rule{} from what your agents did.",
+        sessions.len(),
+        "lawctl tighten".bold(),
+        proposals.len(),
+        if proposals.len() == 1 { "" } else { "s" }
+    ))
+}
+
+/// Rules that would have caught risky actions these sessions were allowed
+/// and `engine` would still allow.
+pub fn analyze(engine: &PolicyEngine, sessions: &[Vec<LogEntry>]) -> Vec<Proposal> {
+    let still_allowed: Vec<&LogEntry> = sessions
+        .iter()
+        .flatten()
+        .filter(|entry| entry.decision.is_allowed() && !entry.is_human())
+        .filter(|entry| {
+            engine
+                .evaluate(&entry.action, &simulate::replay_context(entry))
+                .is_allowed()
+        })
+        .collect();
+
+    let patterns = |list: &[&str]| list.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+    let secrets = CompiledMatcher::new(&patterns(SECRET_PATHS)).expect("secret globs are valid");
+    let scratch = CompiledMatcher::new(
+        &SCRATCH_PATHS
+            .iter()
+            .map(|p| format!("**/{}/**", p.trim_matches('/')))
+            .collect::<Vec<_>>(),
+    )
+    .expect("scratch globs are valid");
+
+    let mut secret_writes = BTreeSet::new();
+    let mut deletes = BTreeSet::new();
+    let mut commands = BTreeSet::new();
+    let mut risky_patterns = BTreeSet::new();
+    let mut hosts = BTreeSet::new();
+    let mut packages = BTreeSet::new();
+    for entry in still_allowed {
+        match entry.action {
+            Action::Write => {
+                secret_writes.extend(
+                    entry
+                        .target
+                        .split(", ")
+                        .filter(|t| secrets.matches(t))
+                        .map(String::from),
+                );
+            }
+            Action::Delete => {
+                deletes.extend(
+                    entry
+                        .target
+                        .split(", ")
+                        .filter(|t| !scratch.matches(t))
+                        .map(String::from),
+                );
+            }
+            Action::RunCmd => {
+                let Some(command) = entry.diff.as_deref() else {
+                    continue;
+                };
+                for pattern in RISKY_COMMANDS {
+                    let matcher = CompiledCommandMatcher::new(&[pattern.to_string()])
+                        .expect("risky command patterns are valid");
+                    if matcher.matches(command) {
+                        risky_patterns.insert(*pattern);
+                        commands.insert(command.to_string());
+                    }
+                }
+            }
+            Action::Network => {
+                if let Some((host, _)) = host_and_port(&entry.target) {
+                    hosts.insert(host);
+                }
+            }
+            Action::PackageInstall => {
+                packages.extend(entry.target.split(", ").map(String::from));
+            }
+            _ => {}
+        }
+    }
+
+    let quoted = |items: &mut dyn Iterator<Item = &str>| {
+        items
+            .map(|s| serde_json::to_string(s).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let examples = |set: &BTreeSet<String>| set.iter().take(MAX_EVIDENCE).cloned().collect();
+
+    let mut proposals = Vec::new();
+    if !secret_writes.is_empty() {
+        proposals.push(Proposal {
+            summary: "Deny writes to secrets and credentials".to_string(),
+            evidence: examples(&secret_writes),
+            yaml: format!(
+                "- deny: write\n  if_path_matches: [{}]\n  reason: \"Protected file — agents cannot modify secrets or credentials\"",
+                quoted(&mut SECRET_PATHS.iter().copied())
+            ),
+        });
+    }
+    if !risky_patterns.is_empty() {
+        proposals.push(Proposal {
+            summary: "Deny the dangerous commands your agents ran".to_string(),
+            evidence: examples(&commands),
+            yaml: format!(
+                "- deny: run_cmd\n  if_matches: [{}]",
+                quoted(&mut risky_patterns.iter().copied())
+            ),
+        });
+    }
+    if !deletes.is_empty() {
+        proposals.push(Proposal {
+            summary: "Ask before deleting anything outside build and temp directories".to_string(),
+            evidence: examples(&deletes),
+            yaml: format!(
+                "- require_approval: delete\n  unless_path: [{}]",
+                quoted(&mut SCRATCH_PATHS.iter().copied())
+            ),
+        });
+    }
+    if !hosts.is_empty() {
+        proposals.push(Proposal {
+            summary: format!(
+                "Ask before contacting hosts other than the {} your agents used",
+                hosts.len()
+            ),
+            evidence: examples(&hosts),
+            yaml: format!(
+                "- require_approval: network\n  unless_domain: [{}]",
+                quoted(&mut hosts.iter().map(String::as_str))
+            ),
+        });
+    }
+    if !packages.is_empty() {
+        proposals.push(Proposal {
+            summary: format!(
+                "Ask before installing packages other than the {} already installed",
+                packages.len()
+            ),
+            evidence: examples(&packages),
+            yaml: format!(
+                "- require_approval: package_install\n  unless_package: [{}]",
+                quoted(&mut packages.iter().map(String::as_str))
+            ),
+        });
+    }
+    proposals
+}
+
+/// Ask whether to add a rule. Defaults to yes; a closed stdin is a no.
+fn ask_add() -> Result<bool> {
+    print!("  {} Add this rule? [Y/n] ", "?".cyan().bold());
+    io::stdout().flush()?;
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        println!();
+        return Ok(false);
+    }
+    Ok(!matches!(input.trim(), "n" | "N" | "no"))
+}
+
+/// Run `lawctl tighten`.
+pub fn run_tighten(policy_path: &Path, yes: bool) -> Result<()> {
+    let policy = parser::parse_policy_file(policy_path)?;
+    let engine = PolicyEngine::new(policy)?;
+    let workspace = policy_path.parent().unwrap_or(Path::new("."));
+    let sessions = load_sessions(workspace);
+    let proposals = analyze(&engine, &sessions);
+
+    println!();
+    if proposals.is_empty() {
+        println!(
+            "  {} Nothing to tighten — {} session{} in this project's history, and the policy already covers every risky action in them.",
+            glyph("✓").green(),
+            sessions.len(),
+            if sessions.len() == 1 { "" } else { "s" }
+        );
+        println!();
+        return Ok(());
+    }
+    println!(
+        "  Looked at {} session{}: {} rule{} would have caught risky actions your policy allowed.",
+        sessions.len(),
+        if sessions.len() == 1 { "" } else { "s" },
+        proposals.len(),
+        if proposals.len() == 1 { "" } else { "s" }
+    );
+
+    let mut accepted = Vec::new();
+    for proposal in &proposals {
+        println!();
+        println!("  {} {}", glyph("•"), proposal.summary.bold());
+        for example in &proposal.evidence {
+            println!("      {}", example.dimmed());
+        }
+        for line in proposal.yaml.lines() {
+            println!("    {}", line.cyan());
+        }
+        if yes || ask_add()? {
+            accepted.push(proposal.yaml.as_str());
+        }
+    }
+
+    println!();
+    if accepted.is_empty() {
+        println!("  No changes made.");
+        println!();
+        return Ok(());
+    }
+    if parser::PolicyFormat::of(policy_path) != parser::PolicyFormat::Yaml {
+        bail!(
+            "lawctl tighten only edits YAML policies — add the rules above to {} by hand",
+            policy_path.display()
+        );
+    }
+    let mut editor = PolicyEditor::open(policy_path)?;
+    editor.insert_rules("Added by lawctl tighten", &accepted)?;
+    editor.save(policy_path)?;
+    println!(
+        "  {} Added {} rule{} to {}",
+        glyph("✓").green().bold(),
+        accepted.len(),
+        if accepted.len() == 1 { "" } else { "s" },
+        policy_path.display().to_string().bold()
+    );
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::Actor;
+    use crate::policy::Decision;
+    use chrono::Utc;
+
+    fn entry(action: Action, target: &str, diff: Option<&str>) -> LogEntry {
+        LogEntry {
+            timestamp: Utc::now(),
+            session_id: "s1".to_string(),
+            agent: "test".to_string(),
+            action,
+            target: target.to_string(),
+            policy_rule: None,
+            decision: Decision::Allowed { matched_rule: None },
+            diff: diff.map(String::from),
+            approved_by: None,
+            eval_duration_us: None,
+            network: None,
+            warning: None,
+            output: None,
+            threats: Vec::new(),
+            actor: Actor::Agent,
+            process_chain: Vec::new(),
+        }
+    }
+
+    fn permissive() -> Policy {
+        parser::parse_policy_str(crate::policy::defaults::PERMISSIVE_YAML).unwrap()
+    }
+
+    #[test]
+    fn test_analyze_proposes_rules_for_risky_allowances() {
+        let engine = PolicyEngine::new(permissive()).unwrap();
+        let sessions = vec![vec![
+            entry(Action::Write, "src/main.rs", Some("fn main() {}")),
+            entry(Action::Write, ".env", Some("KEY=1")),
+            entry(Action::RunCmd, "shell", Some("sudo apt install jq")),
+            entry(Action::RunCmd, "shell", Some("cargo test")),
+            entry(Action::Delete, "build/out.o, src/old.rs", None),
+            entry(Action::Network, "https://api.github.com/repos", None),
+        ]];
+
+        let proposals = analyze(&engine, &sessions);
+        let yaml: Vec<&str> = proposals.iter().map(|p| p.yaml.as_str()).collect();
+        assert_eq!(proposals.len(), 4, "{:?}", yaml);
+        assert_eq!(proposals[0].evidence, [".env"]);
+        assert!(yaml[1].contains("\"sudo *\""));
+        assert_eq!(proposals[1].evidence, ["sudo apt install jq"]);
+        assert_eq!(proposals[2].evidence, ["src/old.rs"]);
+        assert!(yaml[3].contains("unless_domain: [\"api.github.com\"]"));
+    }
+
+    #[test]
+    fn test_tightened_policy_has_nothing_left_to_propose() {
+        let sessions = vec![vec![
+            entry(Action::Write, ".env", Some("KEY=1")),
+            entry(Action::RunCmd, "shell", Some("rm -rf ./cache")),
+        ]];
+        let proposals = analyze(&PolicyEngine::new(permissive()).unwrap(), &sessions);
+        assert_eq!(proposals.len(), 2);
+
+        let rules: Vec<&str> = proposals.iter().map(|p| p.yaml.as_str()).collect();
+        let mut editor = PolicyEditor::new(crate::policy::defaults::PERMISSIVE_YAML);
+        editor
+            .insert_rules("Added by lawctl tighten", &rules)
+            .unwrap();
+        let policy = editor.validate().unwrap();
+        assert!(analyze(&PolicyEngine::new(policy).unwrap(), &sessions).is_empty());
+    }
+}
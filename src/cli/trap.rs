@@ -0,0 +1,62 @@
+//! `lawctl trap` — planting and clearing honeytokens.
+//!
+//! See [`crate::policy::traps`] for what a trap catches.
+
+use crate::audit::projects::project_root;
+use crate::policy::traps::Traps;
+use crate::utils::term::glyph;
+use anyhow::Result;
+use colored::Colorize;
+use std::path::PathBuf;
+
+/// The project the current directory belongs to.
+fn workspace() -> Result<PathBuf> {
+    Ok(project_root(&std::env::current_dir()?))
+}
+
+/// Plant a canary file of fake AWS keys at `path` in this project.
+pub fn run_trap_install(path: &str) -> Result<()> {
+    let workspace = workspace()?;
+    let trap = Traps::install(&workspace, path)?;
+    println!();
+    println!(
+        "  {} Planted decoy AWS credentials at {}",
+        glyph("✓").green().bold(),
+        trap.path.bold()
+    );
+    println!("    Any agent action that touches the file or its keys is denied and flagged.");
+    println!("    Remove it with {}.", "lawctl trap remove".bold());
+    println!();
+    Ok(())
+}
+
+/// List this project's traps.
+pub fn run_trap_list() -> Result<()> {
+    let traps = Traps::load(&workspace()?);
+    println!();
+    if traps.is_empty() {
+        println!(
+            "  No traps in this project — plant one with {}.",
+            "lawctl trap install".bold()
+        );
+    }
+    for trap in &traps.traps {
+        println!("  {} {}", glyph("•"), trap.path);
+    }
+    println!();
+    Ok(())
+}
+
+/// Delete this project's canary files and forget them.
+pub fn run_trap_remove() -> Result<()> {
+    let removed = Traps::remove(&workspace()?)?;
+    println!();
+    if removed.is_empty() {
+        println!("  No traps in this project.");
+    }
+    for trap in &removed {
+        println!("  {} Removed {}", glyph("✓").green().bold(), trap.path);
+    }
+    println!();
+    Ok(())
+}
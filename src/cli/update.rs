@@ -0,0 +1,401 @@
+//! `lawctl update` — replace lawctl with the latest release.
+//!
+//! lawctl, lawctl-hook and lawctl-shim ship together and must stay at the
+//! same version: an old hook won't understand a new policy. The update
+//! downloads the release tarball for this platform, checks it against the
+//! published SHA-256, stages all three binaries next to the installed ones
+//! and then renames them into place, so a failed download or a full disk
+//! never leaves a mixed install behind.
+//!
+//! Homebrew and cargo installs are left to their package manager — we'd
+//! only confuse it — and `update` says which command to run instead.
+//! Downloads go through `curl` and `tar`, like the install script.
+
+use crate::utils::term::glyph;
+use crate::utils::version::{parse_version, VERSION};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// GitHub repository releases are published to.
+const REPO: &str = "abcxz/lawctl";
+
+/// The binaries in every release tarball, updated together.
+pub const BINARIES: &[&str] = &["lawctl", "lawctl-hook", "lawctl-shim"];
+
+/// Who manages this copy of lawctl.
+#[derive(Debug, PartialEq)]
+enum Installer {
+    Homebrew,
+    Cargo,
+    /// The install script, a .pkg or a manual copy — ours to replace
+    Standalone,
+}
+
+impl Installer {
+    fn detect(exe: &Path) -> Self {
+        let path = exe.to_string_lossy();
+        if path.contains("/Cellar/") || path.contains("/homebrew/") || path.contains("linuxbrew") {
+            Installer::Homebrew
+        } else if path.contains("/.cargo/bin/") {
+            Installer::Cargo
+        } else {
+            Installer::Standalone
+        }
+    }
+
+    /// The command that updates lawctl for this installer.
+    fn update_command(&self) -> Option<&'static str> {
+        match self {
+            Installer::Homebrew => Some("brew upgrade lawctl"),
+            Installer::Cargo => Some("cargo binstall lawctl   (or: cargo install lawctl)"),
+            Installer::Standalone => None,
+        }
+    }
+}
+
+/// Run `lawctl update`. With `check_only`, report and change nothing.
+pub fn run_update(check_only: bool, requested: Option<&str>) -> Result<()> {
+    let exe = std::env::current_exe()
+        .and_then(|p| p.canonicalize())
+        .context("Can't locate the running lawctl binary")?;
+    let install_dir = exe
+        .parent()
+        .context("lawctl binary has no parent directory")?
+        .to_path_buf();
+    let current = VERSION;
+
+    println!();
+    println!("  Installed: lawctl {}", current.cyan());
+    if let Some(warning) = skew_warning(&install_dir, current) {
+        println!("  {} {}", glyph("⚠").yellow(), warning);
+    }
+
+    let tag = match requested {
+        Some(version) => format!("v{}", version.trim_start_matches('v')),
+        None => latest_release_tag()?,
+    };
+    let target = parse_version(&tag).context("Release tag is not a version")?;
+    let installed = parse_version(current).context("Bad built-in version")?;
+
+    let installer = Installer::detect(&exe);
+    let lockstep = versions_in_lockstep(&install_dir, current);
+    if requested.is_none() && target <= installed && lockstep {
+        println!(
+            "  {} lawctl is up to date ({} is the latest)",
+            glyph("✓").green().bold(),
+            tag
+        );
+        println!();
+        return Ok(());
+    }
+    println!("  Available: lawctl {}", tag.trim_start_matches('v').cyan());
+
+    if let Some(command) = installer.update_command() {
+        println!();
+        println!("  lawctl was installed with a package manager. Update it with:");
+        println!("    {}", command.bold());
+        println!();
+        return Ok(());
+    }
+    if check_only {
+        println!();
+        println!("  Run {} to install it.", "lawctl update".bold());
+        println!();
+        return Ok(());
+    }
+
+    let platform = target_triple()?;
+    let work = std::env::temp_dir().join(format!("lawctl-update-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&work)
+        .with_context(|| format!("Failed to create {}", work.display()))?;
+    let result = download_and_install(&tag, platform, &work, &install_dir);
+    let _ = std::fs::remove_dir_all(&work);
+    result?;
+
+    println!(
+        "  {} Updated lawctl, lawctl-hook and lawctl-shim to {} in {}",
+        glyph("✓").green().bold(),
+        tag,
+        install_dir.display()
+    );
+    if let Some(warning) = skew_warning(&install_dir, tag.trim_start_matches('v')) {
+        println!("  {} {}", glyph("⚠").yellow(), warning);
+    }
+    println!();
+    Ok(())
+}
+
+/// Download, verify, stage and swap in a release.
+fn download_and_install(tag: &str, platform: &str, work: &Path, install_dir: &Path) -> Result<()> {
+    let archive_name = format!("lawctl-{}-{}.tar.gz", tag, platform);
+    let base = format!("https://github.com/{}/releases/download/{}", REPO, tag);
+    let archive = work.join(&archive_name);
+    let checksum = work.join(format!("{}.sha256", archive_name));
+
+    println!("  {} Downloading {}...", glyph("→").blue(), archive_name);
+    curl(&format!("{}/{}", base, archive_name), &archive)?;
+    curl(&format!("{}/{}.sha256", base, archive_name), &checksum)?;
+
+    let expected = std::fs::read_to_string(&checksum)
+        .ok()
+        .and_then(|content| parse_checksum(&content, &archive_name))
+        .context("The release's checksum file is missing or malformed")?;
+    let actual = sha256_file(&archive)?;
+    if actual != expected {
+        anyhow::bail!(
+            "Checksum mismatch for {} — expected {}, got {}. Nothing was changed.",
+            archive_name,
+            expected,
+            actual
+        );
+    }
+    println!("  {} Checksum verified", glyph("✓").green());
+
+    let extracted = work.join("extracted");
+    std::fs::create_dir_all(&extracted)?;
+    let status = Command::new("tar")
+        .arg("-xzf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(&extracted)
+        .status()
+        .context("Failed to run tar")?;
+    if !status.success() {
+        anyhow::bail!("Failed to unpack {}", archive_name);
+    }
+
+    replace_binaries(&extracted, install_dir)
+}
+
+/// Swap in all the binaries from `from`, or none of them. Each is copied
+/// next to its destination first (same filesystem), then renamed over it.
+fn replace_binaries(from: &Path, install_dir: &Path) -> Result<()> {
+    for binary in BINARIES {
+        if !from.join(binary).is_file() {
+            anyhow::bail!("The release is missing {} — nothing was changed", binary);
+        }
+    }
+
+    let staged: Vec<(PathBuf, PathBuf)> = BINARIES
+        .iter()
+        .map(|binary| {
+            (
+                install_dir.join(format!(".{}.lawctl-new", binary)),
+                install_dir.join(binary),
+            )
+        })
+        .collect();
+
+    let stage = || -> Result<()> {
+        for ((staging, _), binary) in staged.iter().zip(BINARIES) {
+            std::fs::copy(from.join(binary), staging).with_context(|| {
+                format!(
+                    "Can't write to {} (try again with sudo)",
+                    install_dir.display()
+                )
+            })?;
+            make_executable(staging)?;
+        }
+        Ok(())
+    };
+    if let Err(e) = stage() {
+        for (staging, _) in &staged {
+            let _ = std::fs::remove_file(staging);
+        }
+        return Err(e);
+    }
+
+    for (staging, destination) in &staged {
+        std::fs::rename(staging, destination)
+            .with_context(|| format!("Failed to replace {}", destination.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+        .with_context(|| format!("Failed to make {} executable", path.display()))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// The version each installed binary reports, if it runs.
+pub fn component_versions(install_dir: &Path) -> Vec<(&'static str, Option<String>)> {
+    BINARIES
+        .iter()
+        .map(|binary| {
+            let version = Command::new(install_dir.join(binary))
+                .arg("--version")
+                .output()
+                .ok()
+                .filter(|out| out.status.success())
+                .and_then(|out| {
+                    String::from_utf8_lossy(&out.stdout)
+                        .split_whitespace()
+                        .last()
+                        .map(|v| v.trim_start_matches('v').to_string())
+                });
+            (*binary, version)
+        })
+        .collect()
+}
+
+fn versions_in_lockstep(install_dir: &Path, expected: &str) -> bool {
+    component_versions(install_dir)
+        .iter()
+        .all(|(_, version)| version.as_deref() == Some(expected))
+}
+
+/// A warning if the installed binaries don't all report `expected`.
+pub fn skew_warning(install_dir: &Path, expected: &str) -> Option<String> {
+    let mismatched: Vec<String> = component_versions(install_dir)
+        .into_iter()
+        .filter(|(_, version)| version.as_deref() != Some(expected))
+        .map(|(binary, version)| match version {
+            Some(version) => format!("{} is {}", binary, version),
+            None => format!("{} is missing", binary),
+        })
+        .collect();
+    if mismatched.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "Version skew: expected {} everywhere, but {} (in {})",
+        expected,
+        mismatched.join(", "),
+        install_dir.display()
+    ))
+}
+
+/// Ask GitHub for the latest release tag.
+fn latest_release_tag() -> Result<String> {
+    let output = Command::new("curl")
+        .args(["-fsSL", "-H", "Accept: application/vnd.github+json"])
+        .arg(format!(
+            "https://api.github.com/repos/{}/releases/latest",
+            REPO
+        ))
+        .output()
+        .context("Failed to run curl — is it installed?")?;
+    if !output.status.success() {
+        anyhow::bail!("Couldn't reach GitHub to check for updates");
+    }
+    let release: serde_json::Value =
+        serde_json::from_slice(&output.stdout).context("Unexpected response from GitHub")?;
+    release["tag_name"]
+        .as_str()
+        .map(String::from)
+        .context("GitHub's latest release has no tag")
+}
+
+fn curl(url: &str, to: &Path) -> Result<()> {
+    let status = Command::new("curl")
+        .args(["-fsSL", "-o"])
+        .arg(to)
+        .arg(url)
+        .status()
+        .context("Failed to run curl — is it installed?")?;
+    if !status.success() {
+        anyhow::bail!("Download failed: {}", url);
+    }
+    Ok(())
+}
+
+/// The release target for this machine, as the release workflow names it.
+fn target_triple() -> Result<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
+        ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
+        ("linux", "x86_64") => Ok("x86_64-unknown-linux-gnu"),
+        ("linux", "aarch64") => Ok("aarch64-unknown-linux-gnu"),
+        (os, arch) => anyhow::bail!("No lawctl release for {}/{}", os, arch),
+    }
+}
+
+/// The hash for `archive` in `shasum -a 256` output.
+fn parse_checksum(content: &str, archive: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let hash = fields.next()?;
+        let name = fields.next().map(|n| n.trim_start_matches('*'));
+        let valid = hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit());
+        (valid && name.is_none_or(|n| n == archive)).then(|| hash.to_ascii_lowercase())
+    })
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(format!("{:x}", Sha256::digest(&bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_checksum() {
+        let hash = "a".repeat(64);
+        let content = format!("{}  lawctl-v1.0.0-x86_64-unknown-linux-gnu.tar.gz\n", hash);
+        assert_eq!(
+            parse_checksum(&content, "lawctl-v1.0.0-x86_64-unknown-linux-gnu.tar.gz"),
+            Some(hash)
+        );
+        assert_eq!(parse_checksum(&content, "other.tar.gz"), None);
+        assert_eq!(parse_checksum("not a hash", "x"), None);
+    }
+
+    #[test]
+    fn test_installer_detection() {
+        assert_eq!(
+            Installer::detect(Path::new("/opt/homebrew/Cellar/lawctl/0.1.0/bin/lawctl")),
+            Installer::Homebrew
+        );
+        assert_eq!(
+            Installer::detect(Path::new("/home/me/.cargo/bin/lawctl")),
+            Installer::Cargo
+        );
+        assert_eq!(
+            Installer::detect(Path::new("/usr/local/bin/lawctl")),
+            Installer::Standalone
+        );
+    }
+
+    #[test]
+    fn test_replace_binaries_all_or_nothing() {
+        let release = tempfile::TempDir::new().unwrap();
+        let install = tempfile::TempDir::new().unwrap();
+        for binary in BINARIES {
+            std::fs::write(install.path().join(binary), "old").unwrap();
+        }
+
+        // Incomplete release: nothing changes
+        std::fs::write(release.path().join("lawctl"), "new").unwrap();
+        assert!(replace_binaries(release.path(), install.path()).is_err());
+        assert_eq!(
+            std::fs::read_to_string(install.path().join("lawctl")).unwrap(),
+            "old"
+        );
+
+        for binary in BINARIES {
+            std::fs::write(release.path().join(binary), "new").unwrap();
+        }
+        replace_binaries(release.path(), install.path()).unwrap();
+        for binary in BINARIES {
+            assert_eq!(
+                std::fs::read_to_string(install.path().join(binary)).unwrap(),
+                "new"
+            );
+        }
+        // No staging files left behind
+        assert_eq!(std::fs::read_dir(install.path()).unwrap().count(), 3);
+    }
+}
@@ -7,12 +7,33 @@
 
 use crate::gateway::protocol::{GatewayRequest, GatewayResponse};
 use crate::policy::types::Action;
+use crate::utils::locations;
+use crate::utils::version::{self, VERSION};
 use anyhow::{Context, Result};
 use std::io::{BufRead, BufReader, Write};
 use std::os::unix::net::UnixStream;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
+/// Gateway sockets in `dir` that something is listening on.
+pub fn live_sockets(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut sockets: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let name = e.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("lawctl-") && name.ends_with(".sock")
+        })
+        .map(|e| e.path())
+        .filter(|path| UnixStream::connect(path).is_ok())
+        .collect();
+    sockets.sort();
+    sockets
+}
+
 /// Client for communicating with the lawctl gateway.
 pub struct GatewayClient {
     socket_path: PathBuf,
@@ -26,12 +47,33 @@ impl GatewayClient {
         }
     }
 
-    /// Create a client using the LAWCTL_SOCKET environment variable.
+    /// The gateway socket this client talks to.
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+
+    /// Create a client using the LAWCTL_SOCKET environment variable, or
+    /// the one live gateway in lawctl's socket directories when it's unset.
     pub fn from_env() -> Result<Self> {
-        let socket_path = std::env::var("LAWCTL_SOCKET").context(
-            "LAWCTL_SOCKET environment variable not set. Are you running inside lawctl?",
-        )?;
-        Ok(Self::new(socket_path))
+        if let Some(socket_path) = std::env::var_os("LAWCTL_SOCKET").filter(|v| !v.is_empty()) {
+            return Ok(Self::new(socket_path));
+        }
+        let mut live: Vec<PathBuf> = locations::socket_dirs()
+            .iter()
+            .flat_map(|dir| live_sockets(dir))
+            .collect();
+        match live.len() {
+            1 => Ok(Self::new(live.remove(0))),
+            0 => anyhow::bail!(
+                "LAWCTL_SOCKET environment variable not set and no running gateway found. \
+                 Are you running inside lawctl?"
+            ),
+            n => anyhow::bail!(
+                "LAWCTL_SOCKET environment variable not set and {} gateways are running; \
+                 set it to pick one",
+                n
+            ),
+        }
     }
 
     /// Send a request and receive a response (synchronous).
@@ -58,6 +100,12 @@ impl GatewayClient {
         let response: GatewayResponse = serde_json::from_str(response_line.trim())
             .context("Failed to parse gateway response")?;
 
+        if let Some(warning) =
+            version::component_mismatch("lawctl gateway", response.gateway_version.as_deref())
+        {
+            eprintln!("[lawctl] {}", warning);
+        }
+
         Ok(response)
     }
 
@@ -67,7 +115,30 @@ impl GatewayClient {
             request_id: Uuid::new_v4().to_string(),
             action: Action::Write,
             target: path.to_string(),
+            additional_targets: Vec::new(),
+            method: None,
+            upload_bytes: None,
             payload: Some(content.to_string()),
+            client_version: Some(VERSION.to_string()),
+            shell: None,
+            recursive: false,
+        };
+        self.send(&request)
+    }
+
+    /// Convenience: request a file's contents.
+    pub fn read_file(&self, path: &str) -> Result<GatewayResponse> {
+        let request = GatewayRequest {
+            request_id: Uuid::new_v4().to_string(),
+            action: Action::Read,
+            target: path.to_string(),
+            additional_targets: Vec::new(),
+            method: None,
+            upload_bytes: None,
+            payload: None,
+            client_version: Some(VERSION.to_string()),
+            shell: None,
+            recursive: false,
         };
         self.send(&request)
     }
@@ -78,18 +149,219 @@ impl GatewayClient {
             request_id: Uuid::new_v4().to_string(),
             action: Action::Delete,
             target: path.to_string(),
+            additional_targets: Vec::new(),
+            method: None,
+            upload_bytes: None,
             payload: None,
+            client_version: Some(VERSION.to_string()),
+            shell: None,
+            recursive: false,
+        };
+        self.send(&request)
+    }
+
+    /// Convenience: request to delete several files in one decision.
+    /// The gateway denies the whole batch if any single path is denied.
+    pub fn delete_files(&self, paths: &[String]) -> Result<GatewayResponse> {
+        let (first, rest) = paths
+            .split_first()
+            .context("delete_files requires at least one path")?;
+        let request = GatewayRequest {
+            request_id: Uuid::new_v4().to_string(),
+            action: Action::Delete,
+            target: first.clone(),
+            additional_targets: rest.to_vec(),
+            method: None,
+            upload_bytes: None,
+            payload: None,
+            client_version: Some(VERSION.to_string()),
+            shell: None,
+            recursive: false,
+        };
+        self.send(&request)
+    }
+
+    /// Convenience: request to move files. With several sources,
+    /// `destination` is the directory they move into.
+    pub fn move_files(&self, sources: &[String], destination: &str) -> Result<GatewayResponse> {
+        let (first, rest) = sources
+            .split_first()
+            .context("move_files requires at least one source")?;
+        let request = GatewayRequest {
+            request_id: Uuid::new_v4().to_string(),
+            action: Action::Move,
+            target: first.clone(),
+            additional_targets: rest.to_vec(),
+            method: None,
+            upload_bytes: None,
+            payload: Some(destination.to_string()),
+            client_version: Some(VERSION.to_string()),
+            shell: None,
+            recursive: false,
+        };
+        self.send(&request)
+    }
+
+    /// Convenience: request to change the mode of files.
+    pub fn chmod(&self, paths: &[String], mode: &str, recursive: bool) -> Result<GatewayResponse> {
+        let (first, rest) = paths
+            .split_first()
+            .context("chmod requires at least one path")?;
+        let request = GatewayRequest {
+            request_id: Uuid::new_v4().to_string(),
+            action: Action::Chmod,
+            target: first.clone(),
+            additional_targets: rest.to_vec(),
+            method: None,
+            upload_bytes: None,
+            payload: Some(mode.to_string()),
+            client_version: Some(VERSION.to_string()),
+            shell: None,
+            recursive,
+        };
+        self.send(&request)
+    }
+
+    /// Convenience: ask the human to pre-approve a plan, written in
+    /// markdown, for the rest of the session.
+    pub fn approve_plan(&self, plan: &str) -> Result<GatewayResponse> {
+        let request = GatewayRequest {
+            request_id: Uuid::new_v4().to_string(),
+            action: Action::PlanApproval,
+            target: String::new(),
+            additional_targets: Vec::new(),
+            method: None,
+            upload_bytes: None,
+            payload: Some(plan.to_string()),
+            client_version: Some(VERSION.to_string()),
+            shell: None,
+            recursive: false,
+        };
+        self.send(&request)
+    }
+
+    /// Convenience: ask whether environment variables may be read. Nothing
+    /// runs on the gateway; the caller reads them itself once allowed.
+    pub fn env_access(&self, names: &[String]) -> Result<GatewayResponse> {
+        let (first, rest) = names
+            .split_first()
+            .context("env_access requires at least one variable")?;
+        let request = GatewayRequest {
+            request_id: Uuid::new_v4().to_string(),
+            action: Action::EnvAccess,
+            target: first.clone(),
+            additional_targets: rest.to_vec(),
+            method: None,
+            upload_bytes: None,
+            payload: None,
+            client_version: Some(VERSION.to_string()),
+            shell: None,
+            recursive: false,
+        };
+        self.send(&request)
+    }
+
+    /// Convenience: ask whether `manager` may install these packages.
+    /// Nothing runs on the gateway; the caller runs the installer once allowed.
+    pub fn package_install(&self, manager: &str, packages: &[String]) -> Result<GatewayResponse> {
+        let (first, rest) = packages
+            .split_first()
+            .context("package_install requires at least one package")?;
+        let request = GatewayRequest {
+            request_id: Uuid::new_v4().to_string(),
+            action: Action::PackageInstall,
+            target: first.clone(),
+            additional_targets: rest.to_vec(),
+            method: None,
+            upload_bytes: None,
+            payload: Some(manager.to_string()),
+            client_version: Some(VERSION.to_string()),
+            shell: None,
+            recursive: false,
+        };
+        self.send(&request)
+    }
+
+    /// Convenience: ask whether a command may reach another machine —
+    /// `RemoteExec` with the remote command (if any), or `RemoteCopy`.
+    /// Nothing runs on the gateway; the caller runs ssh, scp or rsync once allowed.
+    pub fn remote(
+        &self,
+        action: Action,
+        host: &str,
+        command: Option<&str>,
+    ) -> Result<GatewayResponse> {
+        let request = GatewayRequest {
+            request_id: Uuid::new_v4().to_string(),
+            action,
+            target: host.to_string(),
+            additional_targets: Vec::new(),
+            method: None,
+            upload_bytes: None,
+            payload: command.map(String::from),
+            client_version: Some(VERSION.to_string()),
+            shell: None,
+            recursive: false,
+        };
+        self.send(&request)
+    }
+
+    /// Convenience: ask whether a kubectl or helm command may run against
+    /// `context` (empty when unknown). Nothing runs on the gateway; the
+    /// caller runs the tool once allowed.
+    pub fn k8s(&self, context: &str, command: &str) -> Result<GatewayResponse> {
+        let request = GatewayRequest {
+            request_id: Uuid::new_v4().to_string(),
+            action: Action::K8s,
+            target: context.to_string(),
+            additional_targets: Vec::new(),
+            method: None,
+            upload_bytes: None,
+            payload: Some(command.to_string()),
+            client_version: Some(VERSION.to_string()),
+            shell: None,
+            recursive: false,
+        };
+        self.send(&request)
+    }
+
+    /// Convenience: ask whether an aws, gcloud or az command may run against
+    /// `account` (empty when unknown). Nothing runs on the gateway; the
+    /// caller runs the CLI once allowed.
+    pub fn cloud(&self, account: &str, command: &str) -> Result<GatewayResponse> {
+        let request = GatewayRequest {
+            request_id: Uuid::new_v4().to_string(),
+            action: Action::Cloud,
+            target: account.to_string(),
+            additional_targets: Vec::new(),
+            method: None,
+            upload_bytes: None,
+            payload: Some(command.to_string()),
+            client_version: Some(VERSION.to_string()),
+            shell: None,
+            recursive: false,
         };
         self.send(&request)
     }
 
     /// Convenience: request to run a shell command.
     pub fn run_cmd(&self, command: &str) -> Result<GatewayResponse> {
+        self.run_cmd_in(command, None)
+    }
+
+    /// Request to run a command written for a particular shell.
+    pub fn run_cmd_in(&self, command: &str, shell: Option<&str>) -> Result<GatewayResponse> {
         let request = GatewayRequest {
             request_id: Uuid::new_v4().to_string(),
             action: Action::RunCmd,
             target: "shell".to_string(),
+            additional_targets: Vec::new(),
+            method: None,
+            upload_bytes: None,
             payload: Some(command.to_string()),
+            client_version: Some(VERSION.to_string()),
+            shell: shell.map(str::to_string),
+            recursive: false,
         };
         self.send(&request)
     }
@@ -100,7 +372,13 @@ impl GatewayClient {
             request_id: Uuid::new_v4().to_string(),
             action: Action::GitPush,
             target: branch.to_string(),
+            additional_targets: Vec::new(),
+            method: None,
+            upload_bytes: None,
             payload: None,
+            client_version: Some(VERSION.to_string()),
+            shell: None,
+            recursive: false,
         };
         self.send(&request)
     }
@@ -111,7 +389,13 @@ impl GatewayClient {
             request_id: Uuid::new_v4().to_string(),
             action: Action::Network,
             target: url.to_string(),
+            additional_targets: Vec::new(),
+            method: None,
+            upload_bytes: None,
             payload: Some(url.to_string()),
+            client_version: Some(VERSION.to_string()),
+            shell: None,
+            recursive: false,
         };
         self.send(&request)
     }
@@ -0,0 +1,278 @@
+//! Handler for permission changes.
+//!
+//! Modes are applied here rather than by running `chmod`, so the paths
+//! changed are exactly the ones validated: inside the workspace and out of
+//! git metadata. A batch is validated up front, and a recursive change
+//! lists the whole tree before touching anything. As with `chmod -R`,
+//! symlinks found while recursing are skipped. A symlink named as a target
+//! is refused: its mode would land on a file the policy never checked.
+
+use super::resolve_entry;
+use crate::utils::paths::{is_vcs_metadata, lexical_join};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// Change the mode of every path, approved as a single decision.
+pub fn execute_chmod(
+    workspace_root: &Path,
+    relative_paths: &[&str],
+    mode: &str,
+    recursive: bool,
+) -> Result<String> {
+    // Reject a bad mode before anything changes
+    apply_mode(mode, 0, false)?;
+    let canonical_root = workspace_root
+        .canonicalize()
+        .with_context(|| format!("Workspace root not found: {}", workspace_root.display()))?;
+
+    let mut planned = Vec::new();
+    for relative_path in relative_paths {
+        let target = resolve_target(workspace_root, &canonical_root, relative_path, recursive)?;
+        if recursive && target.is_dir() {
+            planned.extend(walk(&target));
+        }
+        planned.push(target);
+    }
+
+    for path in planned {
+        let metadata = fs::metadata(&path)
+            .with_context(|| format!("Failed to read permissions: {}", path.display()))?;
+        let current = metadata.permissions().mode() & 0o7777;
+        let new = apply_mode(mode, current, metadata.is_dir())?;
+        if new != current {
+            fs::set_permissions(&path, fs::Permissions::from_mode(new))
+                .with_context(|| format!("Failed to change permissions: {}", path.display()))?;
+        }
+    }
+    Ok(relative_paths
+        .iter()
+        .map(|relative_path| format!("Changed mode: {} ({})", relative_path, mode))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Resolve a chmod target and make sure it exists inside the workspace.
+fn resolve_target(
+    workspace_root: &Path,
+    canonical_root: &Path,
+    relative_path: &str,
+    recursive: bool,
+) -> Result<PathBuf> {
+    // The workspace itself has no parent inside it to resolve
+    let canonical_target =
+        if lexical_join(workspace_root, relative_path) == lexical_join(workspace_root, ".") {
+            canonical_root.to_path_buf()
+        } else {
+            resolve_entry(
+                workspace_root,
+                canonical_root,
+                relative_path,
+                "change permissions",
+            )?
+        };
+    let meta = fs::symlink_metadata(&canonical_target)
+        .map_err(|_| anyhow::anyhow!("File not found: {}", relative_path))?;
+    if meta.file_type().is_symlink() {
+        bail!(
+            "Refusing to change permissions through a symlink: {}",
+            relative_path
+        );
+    }
+
+    let rel = canonical_target
+        .strip_prefix(canonical_root)
+        .unwrap_or(&canonical_target);
+    if is_vcs_metadata(&rel.to_string_lossy())
+        || (recursive && canonical_target.join(".git").is_dir())
+    {
+        bail!(
+            "Refusing to change permissions of git metadata: {}",
+            relative_path
+        );
+    }
+    Ok(canonical_target)
+}
+
+/// Everything under `dir`, children before their directory so a mode that
+/// takes away access doesn't stop the walk. Symlinks aren't followed or
+/// listed.
+fn walk(dir: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return found;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_symlink() {
+            continue;
+        }
+        let path = entry.path();
+        if file_type.is_dir() {
+            found.extend(walk(&path));
+        }
+        found.push(path);
+    }
+    found
+}
+
+/// The mode `mode` gives a file currently at `current`: octal (`755`) or
+/// symbolic clauses (`u+x,go-w`, `a=rX`, `g=u`). Without a `who`, clauses
+/// apply to everyone; the umask isn't consulted.
+pub fn apply_mode(mode: &str, current: u32, is_dir: bool) -> Result<u32> {
+    if !mode.is_empty() && mode.len() <= 4 && mode.chars().all(|c| c.is_digit(8)) {
+        return Ok(u32::from_str_radix(mode, 8)?);
+    }
+
+    let invalid = || anyhow::anyhow!("Invalid mode: '{}'", mode);
+    let mut result = current;
+    for clause in mode.split(',') {
+        let ops_at = clause.find(['+', '-', '=']).ok_or_else(invalid)?;
+        let (who, mut rest) = clause.split_at(ops_at);
+        let mut mask = 0;
+        for c in who.chars() {
+            mask |= match c {
+                'u' => 0o4700,
+                'g' => 0o2070,
+                'o' => 0o1007,
+                'a' => 0o7777,
+                _ => return Err(invalid()),
+            };
+        }
+        if mask == 0 {
+            mask = 0o7777;
+        }
+
+        while let Some(op) = rest.chars().next() {
+            let end = rest[1..]
+                .find(['+', '-', '='])
+                .map_or(rest.len(), |i| i + 1);
+            let perms = &rest[1..end];
+            rest = &rest[end..];
+
+            let bits = match perms {
+                // Copy another class's bits: `g=u`
+                "u" => spread((result >> 6) & 0o7),
+                "g" => spread((result >> 3) & 0o7),
+                "o" => spread(result & 0o7),
+                _ => {
+                    let mut bits = 0;
+                    for c in perms.chars() {
+                        bits |= match c {
+                            'r' => 0o444,
+                            'w' => 0o222,
+                            'x' => 0o111,
+                            'X' if is_dir || result & 0o111 != 0 => 0o111,
+                            'X' => 0,
+                            's' => 0o6000,
+                            't' => 0o1000,
+                            _ => return Err(invalid()),
+                        };
+                    }
+                    bits
+                }
+            } & mask;
+            result = match op {
+                '+' => result | bits,
+                '-' => result & !bits,
+                _ => (result & !(mask & 0o777)) | bits,
+            };
+        }
+    }
+    Ok(result)
+}
+
+/// One class's `rwx` bits applied to all three classes.
+fn spread(bits: u32) -> u32 {
+    bits << 6 | bits << 3 | bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn mode_of(path: &Path) -> u32 {
+        fs::metadata(path).unwrap().permissions().mode() & 0o7777
+    }
+
+    #[test]
+    fn test_apply_mode() {
+        assert_eq!(apply_mode("755", 0o644, false).unwrap(), 0o755);
+        assert_eq!(apply_mode("+x", 0o644, false).unwrap(), 0o755);
+        assert_eq!(apply_mode("u+x,go-w", 0o666, false).unwrap(), 0o744);
+        assert_eq!(apply_mode("a=rX", 0o700, false).unwrap(), 0o555);
+        assert_eq!(apply_mode("a=rX", 0o600, false).unwrap(), 0o444);
+        assert_eq!(apply_mode("a=rX", 0o600, true).unwrap(), 0o555);
+        assert_eq!(apply_mode("g=u", 0o740, false).unwrap(), 0o770);
+        assert_eq!(apply_mode("u+s", 0o755, false).unwrap(), 0o4755);
+        assert_eq!(apply_mode("-w", 0o664, false).unwrap(), 0o444);
+        assert!(apply_mode("999", 0, false).is_err());
+        assert!(apply_mode("z+x", 0, false).is_err());
+        assert!(apply_mode("u+q", 0, false).is_err());
+        assert!(apply_mode("", 0, false).is_err());
+    }
+
+    #[test]
+    fn test_chmod_files_and_trees() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("run.sh"), "#!/bin/sh").unwrap();
+        fs::create_dir_all(tmp.path().join("dir/sub")).unwrap();
+        fs::write(tmp.path().join("dir/sub/a.txt"), "a").unwrap();
+        fs::set_permissions(tmp.path().join("run.sh"), fs::Permissions::from_mode(0o644)).unwrap();
+
+        execute_chmod(tmp.path(), &["run.sh"], "u+x", false).unwrap();
+        assert_eq!(mode_of(&tmp.path().join("run.sh")), 0o744);
+
+        execute_chmod(tmp.path(), &["dir"], "go-rwx", true).unwrap();
+        assert_eq!(mode_of(&tmp.path().join("dir")) & 0o077, 0);
+        assert_eq!(mode_of(&tmp.path().join("dir/sub/a.txt")) & 0o077, 0);
+    }
+
+    #[test]
+    fn test_batch_validated_up_front() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("a.txt"), "a").unwrap();
+        fs::set_permissions(tmp.path().join("a.txt"), fs::Permissions::from_mode(0o644)).unwrap();
+
+        assert!(execute_chmod(tmp.path(), &["a.txt", "missing.txt"], "777", false).is_err());
+        assert!(execute_chmod(tmp.path(), &["a.txt"], "bogus", false).is_err());
+        assert!(execute_chmod(tmp.path(), &["../outside"], "777", false).is_err());
+        assert_eq!(mode_of(&tmp.path().join("a.txt")), 0o644);
+    }
+
+    #[test]
+    fn test_git_metadata_refused() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join(".git/hooks")).unwrap();
+
+        assert!(execute_chmod(tmp.path(), &[".git/hooks"], "777", false).is_err());
+        assert!(execute_chmod(tmp.path(), &["."], "777", true).is_err());
+    }
+
+    #[test]
+    fn test_symlinks_refused() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join(".env"), "SECRET=1").unwrap();
+        fs::create_dir(tmp.path().join("secrets")).unwrap();
+        fs::write(tmp.path().join("secrets/key"), "k").unwrap();
+        fs::set_permissions(tmp.path().join(".env"), fs::Permissions::from_mode(0o600)).unwrap();
+        std::os::unix::fs::symlink(".env", tmp.path().join("x")).unwrap();
+        std::os::unix::fs::symlink("secrets", tmp.path().join("s")).unwrap();
+
+        assert!(execute_chmod(tmp.path(), &["x"], "777", false).is_err());
+        assert!(execute_chmod(tmp.path(), &["s"], "777", true).is_err());
+        assert!(execute_chmod(tmp.path(), &["s/key"], "777", false).is_err());
+        assert_eq!(mode_of(&tmp.path().join(".env")), 0o600);
+
+        // `..` is folded before the target is judged
+        fs::create_dir(tmp.path().join("sub")).unwrap();
+        execute_chmod(tmp.path(), &["sub/../.env"], "640", false).unwrap();
+        assert_eq!(mode_of(&tmp.path().join(".env")), 0o640);
+        execute_chmod(tmp.path(), &["."], "go-w", true).unwrap();
+        assert_eq!(mode_of(&tmp.path().join("sub")) & 0o022, 0);
+    }
+}
@@ -1,42 +1,72 @@
 //! Handler for file delete operations.
 //!
 //! Receives a path from the agent, validates it's within the workspace,
-//! and deletes if the policy allows it.
+//! and deletes if the policy allows it. Batches (`rm a b c`) are validated
+//! up front so a bad path deletes nothing rather than half the batch.
+//! The path deleted is the path the policy checked: a symlink is removed
+//! itself, never what it points at, and no path reaches through a
+//! symlinked directory.
 
+use super::resolve_entry;
+use crate::utils::paths::is_vcs_metadata;
 use anyhow::{Context, Result};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Execute a file deletion.
 pub fn execute_delete(workspace_root: &Path, relative_path: &str) -> Result<String> {
-    let target_path = workspace_root.join(relative_path);
+    execute_delete_batch(workspace_root, &[relative_path])
+}
 
-    // Safety: ensure the resolved path is within the workspace
+/// Execute a batch deletion approved as a single decision.
+/// Every path is resolved and checked before anything is removed.
+pub fn execute_delete_batch(workspace_root: &Path, relative_paths: &[&str]) -> Result<String> {
     let canonical_root = workspace_root
         .canonicalize()
         .with_context(|| format!("Workspace root not found: {}", workspace_root.display()))?;
 
-    if !target_path.exists() {
-        anyhow::bail!("File not found: {}", relative_path);
-    }
+    let resolved = relative_paths
+        .iter()
+        .map(|relative_path| {
+            resolve_target(workspace_root, &canonical_root, relative_path)
+                .map(|path| (*relative_path, path))
+        })
+        .collect::<Result<Vec<_>>>()?;
 
-    let canonical_target = target_path.canonicalize()?;
-    if !canonical_target.starts_with(&canonical_root) {
-        anyhow::bail!(
-            "Path traversal detected: {} escapes workspace root",
-            relative_path
-        );
+    let mut deleted = Vec::with_capacity(resolved.len());
+    for (relative_path, (target, is_dir)) in resolved {
+        if is_dir {
+            fs::remove_dir_all(&target)
+                .with_context(|| format!("Failed to delete directory: {}", relative_path))?;
+        } else {
+            fs::remove_file(&target)
+                .with_context(|| format!("Failed to delete file: {}", relative_path))?;
+        }
+        deleted.push(format!("Deleted: {}", relative_path));
     }
 
-    if canonical_target.is_dir() {
-        fs::remove_dir_all(&canonical_target)
-            .with_context(|| format!("Failed to delete directory: {}", relative_path))?;
-    } else {
-        fs::remove_file(&canonical_target)
-            .with_context(|| format!("Failed to delete file: {}", relative_path))?;
+    Ok(deleted.join("\n"))
+}
+
+/// Resolve a delete target and make sure it exists inside the workspace.
+/// Deleting a symlink deletes the link. Returns the path and whether it's
+/// a directory (not a symlink to one).
+fn resolve_target(
+    workspace_root: &Path,
+    canonical_root: &Path,
+    relative_path: &str,
+) -> Result<(PathBuf, bool)> {
+    let target = resolve_entry(workspace_root, canonical_root, relative_path, "delete")?;
+    let meta = fs::symlink_metadata(&target)
+        .map_err(|_| anyhow::anyhow!("File not found: {}", relative_path))?;
+
+    // Git hooks, config and refs are never deleted, nor a directory holding them
+    let rel = target.strip_prefix(canonical_root).unwrap_or(&target);
+    if is_vcs_metadata(&rel.to_string_lossy()) || (meta.is_dir() && target.join(".git").is_dir()) {
+        anyhow::bail!("Refusing to delete git metadata: {}", relative_path);
     }
 
-    Ok(format!("Deleted: {}", relative_path))
+    Ok((target, meta.is_dir()))
 }
 
 #[cfg(test)]
@@ -61,10 +91,75 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_delete_batch_is_all_or_nothing() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("a.txt"), "a").unwrap();
+        fs::write(tmp.path().join("b.txt"), "b").unwrap();
+
+        let result = execute_delete_batch(tmp.path(), &["a.txt", "missing.txt", "b.txt"]);
+        assert!(result.is_err());
+        assert!(tmp.path().join("a.txt").exists());
+        assert!(tmp.path().join("b.txt").exists());
+
+        let result = execute_delete_batch(tmp.path(), &["a.txt", "b.txt"]).unwrap();
+        assert_eq!(result, "Deleted: a.txt\nDeleted: b.txt");
+        assert!(!tmp.path().join("a.txt").exists());
+        assert!(!tmp.path().join("b.txt").exists());
+    }
+
     #[test]
     fn test_delete_path_traversal() {
         let tmp = TempDir::new().unwrap();
         let result = execute_delete(tmp.path(), "../../../etc/important");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_delete_symlink_removes_the_link() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join(".env"), "SECRET=1").unwrap();
+        fs::create_dir(tmp.path().join("secrets")).unwrap();
+        fs::write(tmp.path().join("secrets/key"), "k").unwrap();
+        std::os::unix::fs::symlink(".env", tmp.path().join("x")).unwrap();
+        std::os::unix::fs::symlink("secrets", tmp.path().join("s")).unwrap();
+
+        execute_delete(tmp.path(), "x").unwrap();
+        assert!(fs::symlink_metadata(tmp.path().join("x")).is_err());
+        assert!(tmp.path().join(".env").exists());
+
+        // A link to a directory goes, the directory stays
+        execute_delete(tmp.path(), "s").unwrap();
+        assert!(tmp.path().join("secrets/key").exists());
+
+        // Reaching through a symlinked directory is refused
+        std::os::unix::fs::symlink("secrets", tmp.path().join("s")).unwrap();
+        assert!(execute_delete(tmp.path(), "s/key").is_err());
+        assert!(tmp.path().join("secrets/key").exists());
+    }
+
+    #[test]
+    fn test_delete_folds_dot_dot() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir(tmp.path().join("sub")).unwrap();
+        fs::write(tmp.path().join("a.txt"), "a").unwrap();
+
+        execute_delete(tmp.path(), "sub/../a.txt").unwrap();
+        assert!(!tmp.path().join("a.txt").exists());
+        assert!(execute_delete(tmp.path(), ".").is_err());
+        assert!(tmp.path().join("sub").exists());
+    }
+
+    #[test]
+    fn test_git_metadata_refused() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("vendor/.git/refs")).unwrap();
+        fs::write(tmp.path().join("notes.txt"), "x").unwrap();
+
+        assert!(execute_delete(tmp.path(), "vendor/.git/refs").is_err());
+        assert!(execute_delete_batch(tmp.path(), &["notes.txt", "vendor"]).is_err());
+        // Nothing in the batch went
+        assert!(tmp.path().join("notes.txt").exists());
+        assert!(tmp.path().join("vendor/.git/refs").exists());
+    }
 }
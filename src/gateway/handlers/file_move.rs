@@ -0,0 +1,140 @@
+//! Handler for file moves and renames.
+//!
+//! Both ends must stay inside the workspace and out of git metadata. Like
+//! deletes, a batch (`mv a b dir/`) is validated up front so a bad path
+//! moves nothing rather than half the batch. Both ends are the paths the
+//! policy checked: a symlink moves as itself, and neither end reaches
+//! through a symlinked directory.
+
+use super::resolve_entry;
+use crate::utils::paths::is_vcs_metadata;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The destination as policy sees it: with a trailing `/` when the sources
+/// move into a directory (several sources, or an existing directory).
+pub fn destination(workspace_root: &Path, destination: &str, single_source: bool) -> String {
+    let into_dir = !single_source || workspace_root.join(destination).is_dir();
+    if into_dir && !destination.ends_with('/') {
+        format!("{}/", destination)
+    } else {
+        destination.to_string()
+    }
+}
+
+/// Move every source to `destination`, approved as a single decision.
+pub fn execute_move(workspace_root: &Path, sources: &[&str], destination: &str) -> Result<String> {
+    let canonical_root = workspace_root
+        .canonicalize()
+        .with_context(|| format!("Workspace root not found: {}", workspace_root.display()))?;
+    let destination = self::destination(workspace_root, destination, sources.len() == 1);
+
+    let mut planned = Vec::with_capacity(sources.len());
+    for source in sources {
+        let from = resolve(workspace_root, &canonical_root, source)?;
+        if fs::symlink_metadata(&from).is_err() {
+            anyhow::bail!("File not found: {}", source);
+        }
+        let to_relative = match destination.strip_suffix('/') {
+            Some(dir) => format!(
+                "{}/{}",
+                dir,
+                Path::new(source)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            ),
+            None => destination.clone(),
+        };
+        let to = resolve(workspace_root, &canonical_root, &to_relative)?;
+        planned.push((*source, from, to_relative, to));
+    }
+
+    let mut moved = Vec::with_capacity(planned.len());
+    for (source, from, to_relative, to) in planned {
+        fs::rename(&from, &to)
+            .with_context(|| format!("Failed to move {} to {}", source, to_relative))?;
+        moved.push(format!("Moved: {} -> {}", source, to_relative));
+    }
+    Ok(moved.join("\n"))
+}
+
+/// Resolve one end of a move: its directory must exist inside the
+/// workspace, and the path mustn't be git metadata.
+fn resolve(workspace_root: &Path, canonical_root: &Path, relative_path: &str) -> Result<PathBuf> {
+    let resolved = resolve_entry(workspace_root, canonical_root, relative_path, "move")?;
+    let rel = resolved.strip_prefix(canonical_root).unwrap_or(&resolved);
+    if is_vcs_metadata(&rel.to_string_lossy()) || resolved.join(".git").is_dir() {
+        anyhow::bail!("Refusing to move git metadata: {}", relative_path);
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rename_and_move_into_dir() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("a.txt"), "a").unwrap();
+        fs::write(tmp.path().join("b.txt"), "b").unwrap();
+        fs::create_dir(tmp.path().join("dir")).unwrap();
+
+        execute_move(tmp.path(), &["a.txt"], "c.txt").unwrap();
+        assert!(tmp.path().join("c.txt").exists() && !tmp.path().join("a.txt").exists());
+
+        execute_move(tmp.path(), &["b.txt", "c.txt"], "dir").unwrap();
+        assert!(tmp.path().join("dir/b.txt").exists());
+        assert!(tmp.path().join("dir/c.txt").exists());
+    }
+
+    #[test]
+    fn test_batch_validated_up_front() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("a.txt"), "a").unwrap();
+        fs::create_dir(tmp.path().join("dir")).unwrap();
+
+        assert!(execute_move(tmp.path(), &["a.txt", "missing.txt"], "dir").is_err());
+        assert!(tmp.path().join("a.txt").exists());
+        assert!(execute_move(tmp.path(), &["a.txt"], "../escaped.txt").is_err());
+        assert!(execute_move(tmp.path(), &["a.txt"], "nowhere/a.txt").is_err());
+    }
+
+    #[test]
+    fn test_git_metadata_refused() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join(".git/hooks")).unwrap();
+        fs::write(tmp.path().join("hook.sh"), "#!/bin/sh").unwrap();
+
+        assert!(execute_move(tmp.path(), &["hook.sh"], ".git/hooks/pre-commit").is_err());
+        assert!(execute_move(tmp.path(), &[".git/hooks"], "hooks").is_err());
+        assert!(tmp.path().join("hook.sh").exists());
+    }
+
+    #[test]
+    fn test_move_folds_dot_dot_and_refuses_symlinked_dirs() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("secrets")).unwrap();
+        fs::create_dir(tmp.path().join("sub")).unwrap();
+        fs::write(tmp.path().join("secrets/secret"), "s").unwrap();
+        fs::write(tmp.path().join("a.txt"), "a").unwrap();
+        std::os::unix::fs::symlink("secrets", tmp.path().join("link")).unwrap();
+
+        execute_move(tmp.path(), &["sub/../a.txt"], "b.txt").unwrap();
+        assert!(tmp.path().join("b.txt").exists());
+
+        assert!(execute_move(tmp.path(), &["link/secret"], "out").is_err());
+        assert!(execute_move(tmp.path(), &["b.txt"], "link/b.txt").is_err());
+        assert!(tmp.path().join("secrets/secret").exists());
+        assert!(!tmp.path().join("out").exists());
+
+        // The link itself moves, not the directory it points at
+        execute_move(tmp.path(), &["link"], "moved").unwrap();
+        assert!(fs::symlink_metadata(tmp.path().join("moved")).is_ok());
+        assert!(tmp.path().join("secrets/secret").exists());
+        assert!(execute_move(tmp.path(), &["."], "elsewhere").is_err());
+    }
+}
@@ -0,0 +1,53 @@
+//! Handler for file reads.
+//!
+//! Returns a file's contents once policy has allowed the read. Like writes,
+//! reads are confined to the workspace after resolving symlinks.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// Read a file inside the workspace.
+pub fn execute_read(workspace_root: &Path, relative_path: &str) -> Result<String> {
+    let canonical_root = workspace_root
+        .canonicalize()
+        .with_context(|| format!("Workspace root not found: {}", workspace_root.display()))?;
+    let target_path = workspace_root.join(relative_path);
+    let canonical_target = target_path
+        .canonicalize()
+        .with_context(|| format!("File not found: {}", relative_path))?;
+
+    if !canonical_target.starts_with(&canonical_root) {
+        anyhow::bail!(
+            "Path traversal detected: {} escapes workspace root",
+            relative_path
+        );
+    }
+
+    fs::read_to_string(&canonical_target)
+        .with_context(|| format!("Failed to read file: {}", target_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_file() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("notes.txt"), "hello").unwrap();
+        assert_eq!(execute_read(tmp.path(), "notes.txt").unwrap(), "hello");
+        assert!(execute_read(tmp.path(), "missing.txt").is_err());
+    }
+
+    #[test]
+    fn test_read_outside_workspace() {
+        let tmp = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        fs::write(outside.path().join("secret"), "x").unwrap();
+        std::os::unix::fs::symlink(outside.path().join("secret"), tmp.path().join("link")).unwrap();
+        assert!(execute_read(tmp.path(), "link").is_err());
+        assert!(execute_read(tmp.path(), "../etc/passwd").is_err());
+    }
+}
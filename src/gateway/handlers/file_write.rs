@@ -3,6 +3,7 @@
 //! Receives a path + diff from the agent, validates against policy,
 //! and applies the diff if allowed. The diff is preserved in the audit log.
 
+use crate::utils::paths::is_vcs_metadata;
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
@@ -41,6 +42,12 @@ pub fn execute_write(workspace_root: &Path, relative_path: &str, content: &str)
             relative_path
         );
     }
+    // Checked again on the resolved path, so a symlink can't reach .git
+    if let Ok(rel) = canonical_target.strip_prefix(&canonical_root) {
+        if is_vcs_metadata(&rel.to_string_lossy()) {
+            anyhow::bail!("Refusing to write git metadata: {}", relative_path);
+        }
+    }
 
     // Write the file
     fs::write(&target_path, content)
@@ -78,4 +85,21 @@ mod tests {
         let result = execute_write(tmp.path(), "../../../etc/passwd", "hacked");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_git_metadata_refused() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join(".git/hooks")).unwrap();
+        assert!(execute_write(tmp.path(), ".git/hooks/pre-commit", "exit 0").is_err());
+        assert!(!tmp.path().join(".git/hooks/pre-commit").exists());
+
+        // Through a symlink too
+        std::os::unix::fs::symlink(".git/config", tmp.path().join("innocent")).unwrap();
+        fs::write(tmp.path().join(".git/config"), "[core]").unwrap();
+        assert!(execute_write(tmp.path(), "innocent", "[user]").is_err());
+        assert_eq!(
+            fs::read_to_string(tmp.path().join(".git/config")).unwrap(),
+            "[core]"
+        );
+    }
 }
@@ -5,12 +5,49 @@
 //! The gateway receives "push to branch X" and executes it from the
 //! host's real git context.
 
+use crate::policy::types::AnnotatePushes;
 use anyhow::{Context, Result};
 use std::path::Path;
 use std::process::Command;
 
+/// Notes ref used for `annotate_pushes: notes`.
+pub const NOTES_REF: &str = "refs/notes/lawctl";
+
+/// What gets recorded on pushed commits when the policy sets `annotate_pushes`.
+pub struct PushTrace<'a> {
+    pub mode: AnnotatePushes,
+    pub session_id: &'a str,
+    pub policy_hash: &'a str,
+}
+
+impl PushTrace<'_> {
+    fn trailers(&self) -> [String; 2] {
+        [
+            format!("Lawctl-Session: {}", self.session_id),
+            format!("Lawctl-Policy: {}", self.policy_hash),
+        ]
+    }
+}
+
 /// Execute a git push operation from the host side.
-pub fn execute_git_push(workspace_root: &Path, branch: &str) -> Result<String> {
+/// With a trace, the commits about to be pushed are annotated first.
+pub fn execute_git_push(
+    workspace_root: &Path,
+    branch: &str,
+    trace: Option<&PushTrace>,
+) -> Result<String> {
+    let annotated = match trace {
+        Some(trace) => {
+            let commits = unpushed_commits(workspace_root, branch)?;
+            match trace.mode {
+                AnnotatePushes::Notes => add_notes(workspace_root, &commits, trace)?,
+                AnnotatePushes::Trailer => add_trailers(workspace_root, branch, &commits, trace)?,
+            }
+            commits.len()
+        }
+        None => 0,
+    };
+
     let output = Command::new("git")
         .args(["push", "origin", branch])
         .current_dir(workspace_root)
@@ -20,20 +57,119 @@ pub fn execute_git_push(workspace_root: &Path, branch: &str) -> Result<String> {
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
 
-    if output.status.success() {
-        Ok(format!(
-            "Pushed to {}.\n{}{}",
-            branch,
-            stdout,
-            if stderr.is_empty() {
-                String::new()
-            } else {
-                format!("\n{}", stderr)
-            }
-        ))
-    } else {
+    if !output.status.success() {
         anyhow::bail!("git push failed: {}", stderr)
     }
+
+    let mut result = format!(
+        "Pushed to {}.\n{}{}",
+        branch,
+        stdout,
+        if stderr.is_empty() {
+            String::new()
+        } else {
+            format!("\n{}", stderr)
+        }
+    );
+    if let Some(trace) = trace {
+        if annotated > 0 {
+            result.push_str(&format!(
+                "\nAnnotated {} commit(s) with lawctl session {}.",
+                annotated, trace.session_id
+            ));
+        }
+        if trace.mode == AnnotatePushes::Notes && annotated > 0 {
+            // Best-effort: the branch is already pushed, so a rejected notes
+            // ref (someone else's notes upstream) is a warning, not a failure
+            let notes = git(workspace_root, &["push", "origin", NOTES_REF]);
+            if let Err(e) = notes {
+                result.push_str(&format!("\nWarning: couldn't push {}: {}", NOTES_REF, e));
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Commits on `branch` that no `origin` ref has yet, oldest first.
+fn unpushed_commits(workspace_root: &Path, branch: &str) -> Result<Vec<String>> {
+    let output = git(
+        workspace_root,
+        &["rev-list", "--reverse", branch, "--not", "--remotes=origin"],
+    )
+    .with_context(|| format!("Failed to list unpushed commits on {}", branch))?;
+    Ok(output.lines().map(String::from).collect())
+}
+
+/// Attach the session note to each commit (replacing any earlier one).
+fn add_notes(workspace_root: &Path, commits: &[String], trace: &PushTrace) -> Result<()> {
+    let message = trace.trailers().join("\n");
+    for commit in commits {
+        git(
+            workspace_root,
+            &[
+                "notes", "--ref", NOTES_REF, "add", "-f", "-m", &message, commit,
+            ],
+        )
+        .with_context(|| format!("Failed to add lawctl note to {}", commit))?;
+    }
+    Ok(())
+}
+
+/// Rewrite the unpushed commits with session trailers. Nothing upstream
+/// has them yet, so this never rewrites published history.
+fn add_trailers(
+    workspace_root: &Path,
+    branch: &str,
+    commits: &[String],
+    trace: &PushTrace,
+) -> Result<()> {
+    let Some(oldest) = commits.first() else {
+        return Ok(());
+    };
+
+    let mut amend =
+        String::from("git -c trailer.ifexists=replace commit --amend --no-edit --no-verify");
+    for trailer in trace.trailers() {
+        amend.push_str(&format!(" --trailer {}", shell_quote(&trailer)));
+    }
+
+    let parent = git(
+        workspace_root,
+        &["rev-parse", "--verify", "--quiet", &format!("{}^", oldest)],
+    );
+    let mut args = vec!["rebase", "--exec", amend.as_str()];
+    let parent = parent.ok().map(|p| p.trim().to_string());
+    match parent.as_deref() {
+        Some(parent) => args.push(parent),
+        None => args.push("--root"),
+    }
+    args.push(branch);
+
+    if let Err(e) = git(workspace_root, &args) {
+        let _ = git(workspace_root, &["rebase", "--abort"]);
+        return Err(e.context(format!("Failed to add lawctl trailers to {}", branch)));
+    }
+    Ok(())
+}
+
+/// Run git in the workspace, returning stdout or failing with stderr.
+fn git(workspace_root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(workspace_root)
+        // --exec rebases are non-interactive; never open an editor
+        .env("GIT_SEQUENCE_EDITOR", ":")
+        .env("GIT_EDITOR", ":")
+        .output()
+        .with_context(|| format!("Failed to run git {}", args.first().unwrap_or(&"")))?;
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
 }
 
 /// Get current git status for display in approval prompts.
@@ -57,3 +193,81 @@ pub fn get_git_diff_summary(workspace_root: &Path) -> Result<String> {
 
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A repo with one pushed commit and two unpushed ones, plus its bare origin.
+    fn setup_repo() -> (TempDir, TempDir) {
+        let origin = TempDir::new().unwrap();
+        let work = TempDir::new().unwrap();
+        git(origin.path(), &["init", "--bare", "-q"]).unwrap();
+        let dir = work.path();
+        git(dir, &["init", "-q", "-b", "main"]).unwrap();
+        git(dir, &["config", "user.name", "Test"]).unwrap();
+        git(dir, &["config", "user.email", "test@example.com"]).unwrap();
+        git(
+            dir,
+            &["remote", "add", "origin", origin.path().to_str().unwrap()],
+        )
+        .unwrap();
+        for (i, name) in ["a.txt", "b.txt", "c.txt"].iter().enumerate() {
+            std::fs::write(dir.join(name), name).unwrap();
+            git(dir, &["add", name]).unwrap();
+            git(dir, &["commit", "-q", "-m", &format!("Add {}", name)]).unwrap();
+            if i == 0 {
+                git(dir, &["push", "-q", "origin", "main"]).unwrap();
+            }
+        }
+        (work, origin)
+    }
+
+    fn trace(mode: AnnotatePushes) -> PushTrace<'static> {
+        PushTrace {
+            mode,
+            session_id: "session-1",
+            policy_hash: "0123abcd",
+        }
+    }
+
+    #[test]
+    fn test_push_with_notes() {
+        let (work, origin) = setup_repo();
+        let result =
+            execute_git_push(work.path(), "main", Some(&trace(AnnotatePushes::Notes))).unwrap();
+        assert!(result.contains("Annotated 2 commit(s)"));
+
+        let note = git(
+            origin.path(),
+            &["notes", "--ref", NOTES_REF, "show", "main"],
+        )
+        .unwrap();
+        assert_eq!(
+            note.trim(),
+            "Lawctl-Session: session-1\nLawctl-Policy: 0123abcd"
+        );
+        // The already-published commit is left alone
+        assert!(git(
+            origin.path(),
+            &["notes", "--ref", NOTES_REF, "show", "main~2"]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_push_with_trailers_rewrites_only_unpushed() {
+        let (work, origin) = setup_repo();
+        let published = git(work.path(), &["rev-parse", "main~2"]).unwrap();
+        execute_git_push(work.path(), "main", Some(&trace(AnnotatePushes::Trailer))).unwrap();
+
+        let log = git(origin.path(), &["log", "--format=%s%n%b---", "main"]).unwrap();
+        assert_eq!(log.matches("Lawctl-Session: session-1").count(), 2);
+        assert_eq!(log.matches("Lawctl-Policy: 0123abcd").count(), 2);
+        assert_eq!(
+            git(origin.path(), &["rev-parse", "main~2"]).unwrap(),
+            published
+        );
+    }
+}
@@ -1,5 +1,54 @@
+pub mod file_chmod;
 pub mod file_delete;
+pub mod file_move;
+pub mod file_read;
 pub mod file_write;
 pub mod git;
 pub mod network;
 pub mod shell;
+
+use crate::utils::paths::lexical_join;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Resolve a path the policy judged, as written, to where it is on disk.
+/// Only its directory is resolved; the last component is left alone, so a
+/// symlink is acted on as itself. The directory must be inside the
+/// workspace and mustn't be reached through a symlinked directory, which
+/// would make `verb` act on a path the policy never saw.
+pub(crate) fn resolve_entry(
+    workspace_root: &Path,
+    canonical_root: &Path,
+    relative_path: &str,
+    verb: &str,
+) -> Result<PathBuf> {
+    let path = lexical_join(workspace_root, relative_path);
+    let (Some(name), Some(parent)) = (path.file_name(), path.parent()) else {
+        anyhow::bail!("Not a file path: {}", relative_path);
+    };
+    let escapes = || {
+        anyhow::anyhow!(
+            "Path traversal detected: {} escapes workspace root",
+            relative_path
+        )
+    };
+    let relative_parent = parent
+        .strip_prefix(workspace_root)
+        .or_else(|_| parent.strip_prefix(canonical_root))
+        .map_err(|_| escapes())?;
+
+    let canonical_parent = parent
+        .canonicalize()
+        .with_context(|| format!("Directory not found for {}", relative_path))?;
+    if !canonical_parent.starts_with(canonical_root) {
+        return Err(escapes());
+    }
+    if canonical_parent != canonical_root.join(relative_parent) {
+        anyhow::bail!(
+            "Refusing to {} through a symlinked directory: {}",
+            verb,
+            relative_path
+        );
+    }
+    Ok(canonical_parent.join(name))
+}
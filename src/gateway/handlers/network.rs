@@ -1,7 +1,8 @@
 //! Handler for network requests (future implementation).
 //!
-//! In v1, network control is primarily handled at the Docker network level.
-//! This handler exists for policy evaluation and logging.
+//! In Docker mode, network control is handled at the container network level.
+//! In direct mode the optional local proxy (`gateway::proxy`) routes requests
+//! through the gateway. This handler exists for policy evaluation and logging.
 
 use anyhow::Result;
 
@@ -16,13 +17,10 @@ pub fn validate_network_request(url: &str) -> Result<String> {
 
 /// Extract the domain from a URL.
 pub fn extract_domain(url: &str) -> Option<String> {
-    // Simple extraction — handles http(s)://domain/path
-    let url = url
-        .strip_prefix("https://")
-        .or_else(|| url.strip_prefix("http://"))?;
-    let domain = url.split('/').next()?;
-    let domain = domain.split(':').next()?; // Remove port
-    Some(domain.to_string())
+    if !url.contains("://") {
+        return None;
+    }
+    crate::utils::domain::host_and_port(url).map(|(host, _)| host)
 }
 
 #[cfg(test)]
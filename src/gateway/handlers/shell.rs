@@ -3,10 +3,48 @@
 //! Runs commands in a controlled environment. In sandbox mode, commands
 //! execute inside the container. In direct mode (for development),
 //! they run on the host with the workspace as the working directory.
+//!
+//! Commands that `cd` out of the workspace are stopped by the policy engine
+//! before they get here. The container only mounts the workspace, so there
+//! the filesystem itself is the boundary; on the host the best we can do is
+//! not hand `cd` any way to jump elsewhere (`CDPATH`, `cd -`).
+//!
+//! Which interpreter runs the command is picked by [`select_shell`]: the
+//! shell the client says the command was written for, then the policy's
+//! `shell:`, then `sh -c` (`cmd /C` on Windows).
+//!
+//! Output is capped per stream (`max_output_kb`, 1 MB by default) so a
+//! `cat huge.bin` can't push hundreds of megabytes back over the socket.
+//! Past the cap the rest is drained and dropped — or, with `spill_output`,
+//! written to a file next to the session log that the audit entry points to.
 
+use crate::audit::CommandOutput;
 use anyhow::{Context, Result};
-use std::path::Path;
-use std::process::Command;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Output kept per stream when the policy doesn't set `max_output_kb`.
+pub const DEFAULT_MAX_OUTPUT_KB: u64 = 1024;
+
+/// How much of a command's output comes back to the agent.
+#[derive(Debug, Clone)]
+pub struct OutputLimits {
+    /// Bytes kept of stdout and of stderr, each
+    pub max_bytes: usize,
+    /// Where a cut-off stream's full output goes, as
+    /// `<spill_to>.stdout` / `<spill_to>.stderr`
+    pub spill_to: Option<PathBuf>,
+}
+
+impl Default for OutputLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: (DEFAULT_MAX_OUTPUT_KB * 1024) as usize,
+            spill_to: None,
+        }
+    }
+}
 
 /// Result of a shell command execution.
 #[derive(Debug)]
@@ -14,6 +52,11 @@ pub struct ShellResult {
     pub stdout: String,
     pub stderr: String,
     pub exit_code: i32,
+    /// Full size of each stream; more than was kept when it was truncated
+    pub stdout_bytes: u64,
+    pub stderr_bytes: u64,
+    /// Files holding the complete output of truncated streams
+    pub spill_files: Vec<PathBuf>,
 }
 
 impl ShellResult {
@@ -22,6 +65,7 @@ impl ShellResult {
         let mut output = String::new();
         if !self.stdout.is_empty() {
             output.push_str(&self.stdout);
+            output.push_str(&self.truncation_notice("stdout", self.stdout_bytes));
         }
         if !self.stderr.is_empty() {
             if !output.is_empty() {
@@ -29,29 +73,238 @@ impl ShellResult {
             }
             output.push_str("[stderr] ");
             output.push_str(&self.stderr);
+            output.push_str(&self.truncation_notice("stderr", self.stderr_bytes));
         }
         if output.is_empty() {
             output = format!("(exit code: {})", self.exit_code);
         }
         output
     }
+
+    /// Standard output as the command wrote it, and standard error with
+    /// lawctl's truncation notices appended — stdout stays parseable.
+    pub fn streams(&self) -> (String, String) {
+        let mut stderr = self.stderr.clone();
+        for notice in [
+            self.truncation_notice("stdout", self.stdout_bytes),
+            self.truncation_notice("stderr", self.stderr_bytes),
+        ] {
+            if notice.is_empty() {
+                continue;
+            }
+            if stderr.ends_with('\n') || stderr.is_empty() {
+                stderr.push_str(notice.trim_start_matches('\n'));
+            } else {
+                stderr.push_str(&notice);
+            }
+        }
+        (self.stdout.clone(), stderr)
+    }
+
+    /// Whether either stream was cut off.
+    pub fn truncated(&self) -> bool {
+        self.stdout_bytes > self.stdout.len() as u64 || self.stderr_bytes > self.stderr.len() as u64
+    }
+
+    /// What goes in the audit log for this command.
+    pub fn summary(&self) -> CommandOutput {
+        CommandOutput {
+            exit_code: self.exit_code,
+            stdout_bytes: self.stdout_bytes,
+            stderr_bytes: self.stderr_bytes,
+            truncated: self.truncated(),
+            spill_files: self.spill_files.clone(),
+        }
+    }
+
+    /// A line saying a stream was cut off, or nothing if it wasn't.
+    fn truncation_notice(&self, stream: &str, total: u64) -> String {
+        let kept = if stream == "stdout" {
+            self.stdout.len()
+        } else {
+            self.stderr.len()
+        };
+        if total <= kept as u64 {
+            return String::new();
+        }
+        let spill = self
+            .spill_files
+            .iter()
+            .find(|f| f.extension().is_some_and(|e| e == stream))
+            .map(|f| format!("; full output in {}", f.display()))
+            .unwrap_or_default();
+        format!(
+            "\n[lawctl] {} truncated: showing {} of {} bytes{}\n",
+            stream, kept, total, spill
+        )
+    }
+}
+
+/// One stream, read to the end with everything past the cap dropped or
+/// spilled.
+struct Captured {
+    kept: Vec<u8>,
+    total: u64,
+    spill_file: Option<PathBuf>,
+}
+
+fn capture(mut reader: impl Read, max: usize, spill_to: Option<PathBuf>) -> Captured {
+    let mut kept = Vec::new();
+    let mut total = 0u64;
+    let mut spill: Option<std::fs::File> = None;
+    let mut spill_failed = false;
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        };
+        total += n as u64;
+        let take = n.min(max - kept.len());
+        kept.extend_from_slice(&buf[..take]);
+        if total <= max as u64 || spill_failed {
+            continue;
+        }
+        let Some(ref path) = spill_to else {
+            continue;
+        };
+        let written = match spill.as_mut() {
+            Some(file) => file.write_all(&buf[..n]),
+            None => open_spill(path).and_then(|mut file| {
+                // The first overflowing read: everything so far goes in too
+                file.write_all(&kept)?;
+                file.write_all(&buf[take..n])?;
+                spill = Some(file);
+                Ok(())
+            }),
+        };
+        if let Err(e) = written {
+            tracing::warn!(
+                "Failed to spill command output to {}: {}",
+                path.display(),
+                e
+            );
+            spill_failed = true;
+        }
+    }
+    Captured {
+        kept,
+        total,
+        spill_file: spill.and(spill_to).filter(|_| !spill_failed),
+    }
+}
+
+fn open_spill(path: &Path) -> std::io::Result<std::fs::File> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::File::create(path)
 }
 
-/// Execute a shell command in the workspace directory.
-/// This is the host-side execution — in sandbox mode, this runs
-/// inside the container via Docker exec.
-pub fn execute_command(workspace_root: &Path, command: &str) -> Result<ShellResult> {
-    let output = Command::new("sh")
-        .arg("-c")
+/// The platform's shell when nothing else is asked for.
+#[cfg(windows)]
+const DEFAULT_SHELL: &[&str] = &["cmd", "/C"];
+#[cfg(not(windows))]
+const DEFAULT_SHELL: &[&str] = &["sh", "-c"];
+
+/// How to run a command in a shell a client may name. Anything not listed
+/// here is ignored rather than run — a request can't pick an arbitrary
+/// program as its "shell".
+fn known_shell(name: &str) -> Option<Vec<String>> {
+    let program = Path::new(name).file_stem()?.to_str()?;
+    let args: &[&str] = match program {
+        "sh" | "bash" | "zsh" | "dash" | "ksh" | "fish" => &["-c"],
+        "pwsh" | "powershell" => &["-NoProfile", "-Command"],
+        "cmd" => &["/C"],
+        _ => return None,
+    };
+    Some(
+        std::iter::once(program)
+            .chain(args.iter().copied())
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// The argv a command runs under, to which the command itself is appended:
+/// the `requested` shell if it's one lawctl knows, else the policy's
+/// `configured` shell, else the platform default.
+pub fn select_shell(configured: &[String], requested: Option<&str>) -> Vec<String> {
+    if let Some(shell) = requested.and_then(known_shell) {
+        return shell;
+    }
+    if !configured.is_empty() {
+        return configured.to_vec();
+    }
+    DEFAULT_SHELL.iter().map(|s| s.to_string()).collect()
+}
+
+/// Execute a command in the workspace directory under `shell` (as chosen by
+/// [`select_shell`]), keeping output within `limits`. This is the host-side
+/// execution — in sandbox mode, this runs inside the container via Docker
+/// exec.
+pub fn execute_command(
+    workspace_root: &Path,
+    shell: &[String],
+    command: &str,
+    limits: &OutputLimits,
+) -> Result<ShellResult> {
+    execute_command_tracked(workspace_root, shell, command, limits, |_| {})
+}
+
+/// [`execute_command`], calling `on_spawn` with the process id as soon as
+/// the command has started, so requests from what it starts can be traced
+/// back to it.
+pub fn execute_command_tracked(
+    workspace_root: &Path,
+    shell: &[String],
+    command: &str,
+    limits: &OutputLimits,
+    on_spawn: impl FnOnce(u32),
+) -> Result<ShellResult> {
+    let (program, args) = shell
+        .split_first()
+        .context("No shell to run the command with")?;
+    let mut output = Command::new(program)
+        .args(args)
         .arg(command)
         .current_dir(workspace_root)
-        .output()
+        .env("PWD", workspace_root)
+        .env_remove("OLDPWD")
+        .env_remove("CDPATH")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
         .with_context(|| format!("Failed to execute command: {}", command))?;
+    on_spawn(output.id());
+
+    let spill = |stream: &str| {
+        limits
+            .spill_to
+            .as_ref()
+            .map(|base| base.with_extension(stream))
+    };
+    let stdout = output.stdout.take().context("stdout not captured")?;
+    let stderr = output.stderr.take().context("stderr not captured")?;
+    let (out, err) = std::thread::scope(|scope| {
+        let err = scope.spawn(|| capture(stderr, limits.max_bytes, spill("stderr")));
+        let out = capture(stdout, limits.max_bytes, spill("stdout"));
+        (out, err.join().expect("stderr reader panicked"))
+    });
+    let status = output
+        .wait()
+        .with_context(|| format!("Failed to wait for command: {}", command))?;
 
     Ok(ShellResult {
-        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-        exit_code: output.status.code().unwrap_or(-1),
+        stdout: String::from_utf8_lossy(&out.kept).to_string(),
+        stderr: String::from_utf8_lossy(&err.kept).to_string(),
+        exit_code: status.code().unwrap_or(-1),
+        stdout_bytes: out.total,
+        stderr_bytes: err.total,
+        spill_files: out.spill_file.into_iter().chain(err.spill_file).collect(),
     })
 }
 
@@ -60,18 +313,111 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    fn sh() -> Vec<String> {
+        select_shell(&[], None)
+    }
+
     #[test]
     fn test_execute_simple_command() {
         let tmp = TempDir::new().unwrap();
-        let result = execute_command(tmp.path(), "echo hello").unwrap();
+        let result =
+            execute_command(tmp.path(), &sh(), "echo hello", &OutputLimits::default()).unwrap();
         assert_eq!(result.stdout.trim(), "hello");
         assert_eq!(result.exit_code, 0);
     }
 
+    #[test]
+    fn test_cd_has_nowhere_to_jump() {
+        let tmp = TempDir::new().unwrap();
+        let result = execute_command(
+            tmp.path(),
+            &sh(),
+            "cd - >/dev/null 2>&1; pwd -P",
+            &OutputLimits::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            Path::new(result.stdout.trim()),
+            tmp.path().canonicalize().unwrap()
+        );
+    }
+
     #[test]
     fn test_execute_failing_command() {
         let tmp = TempDir::new().unwrap();
-        let result = execute_command(tmp.path(), "false").unwrap();
+        let result = execute_command(tmp.path(), &sh(), "false", &OutputLimits::default()).unwrap();
         assert_ne!(result.exit_code, 0);
     }
+
+    #[test]
+    fn test_select_shell() {
+        let bash = vec!["bash".to_string(), "-lc".to_string()];
+        assert_eq!(select_shell(&bash, None), bash);
+        assert_eq!(select_shell(&bash, Some("/usr/bin/fish")), ["fish", "-c"]);
+        assert_eq!(
+            select_shell(&[], Some("powershell.exe")),
+            ["powershell", "-NoProfile", "-Command"]
+        );
+        // Unknown programs aren't shells
+        assert_eq!(select_shell(&bash, Some("python")), bash);
+        assert_eq!(select_shell(&[], Some("rm")), DEFAULT_SHELL);
+    }
+
+    #[test]
+    fn test_execute_with_configured_shell() {
+        let tmp = TempDir::new().unwrap();
+        let shell = select_shell(&["sh".to_string(), "-c".to_string()], Some("bash"));
+        let result =
+            execute_command(tmp.path(), &shell, "echo $0", &OutputLimits::default()).unwrap();
+        assert_eq!(result.stdout.trim(), "bash");
+    }
+
+    #[test]
+    fn test_output_is_capped() {
+        let tmp = TempDir::new().unwrap();
+        let command = "head -c 5000 /dev/zero | tr '\\0' a; echo oops >&2";
+        let limits = OutputLimits {
+            max_bytes: 1000,
+            spill_to: None,
+        };
+        let result = execute_command(tmp.path(), &sh(), command, &limits).unwrap();
+        assert_eq!(result.stdout.len(), 1000);
+        assert_eq!((result.stdout_bytes, result.stderr_bytes), (5000, 5));
+        assert!(result.truncated());
+        let output = result.to_output();
+        assert!(output.contains("[lawctl] stdout truncated: showing 1000 of 5000 bytes\n"));
+        assert!(output.ends_with("[stderr] oops\n"));
+        assert!(result.spill_files.is_empty());
+
+        let (stdout, stderr) = result.streams();
+        assert_eq!(stdout, "a".repeat(1000));
+        assert_eq!(
+            stderr,
+            "oops\n[lawctl] stdout truncated: showing 1000 of 5000 bytes\n"
+        );
+    }
+
+    #[test]
+    fn test_truncated_output_spills() {
+        let tmp = TempDir::new().unwrap();
+        let spill_to = tmp.path().join("session.output").join("req-1");
+        let limits = OutputLimits {
+            max_bytes: 1000,
+            spill_to: Some(spill_to.clone()),
+        };
+        let result =
+            execute_command(tmp.path(), &sh(), "seq 1 2000; echo short >&2", &limits).unwrap();
+        let spilled = spill_to.with_extension("stdout");
+        assert_eq!(result.spill_files, std::slice::from_ref(&spilled));
+        let full = std::fs::read_to_string(&spilled).unwrap();
+        assert_eq!(full.len() as u64, result.stdout_bytes);
+        assert!(full.ends_with("1999\n2000\n"));
+        assert!(result
+            .to_output()
+            .contains(&format!("; full output in {}", spilled.display())));
+
+        let summary = result.summary();
+        assert!(summary.truncated);
+        assert_eq!(summary.spill_files, [spilled]);
+    }
 }
@@ -1,7 +1,10 @@
 pub mod client;
 pub mod handlers;
 pub mod protocol;
+pub mod proxy;
 pub mod server;
+pub mod stale;
 
 pub use client::GatewayClient;
-pub use server::GatewayServer;
+pub use proxy::NetworkProxy;
+pub use server::{GatewayServer, LogCallback};
@@ -7,6 +7,7 @@
 //! and returns GatewayResponses.
 
 use crate::policy::types::Action;
+use crate::utils::version::VERSION;
 use serde::{Deserialize, Serialize};
 
 /// A request from the agent to perform an action.
@@ -26,13 +27,83 @@ pub struct GatewayRequest {
     /// - For network: the URL
     pub target: String,
 
+    /// Further targets for multi-target operations (e.g. `rm a b c`).
+    /// The gateway evaluates every target and denies if any is denied.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_targets: Vec<String>,
+
+    /// For network: the HTTP method, when the caller knows it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+
+    /// For network: bytes the request sends (headers and body), when the
+    /// caller knows it. Counted towards `max_upload_mb_per_domain`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_bytes: Option<u64>,
+
     /// Additional payload:
     /// - For file_write: the diff content
     /// - For run_cmd: the full command string
     /// - For file_delete: None
+    /// - For move: the destination
+    /// - For chmod: the mode
+    /// - For plan_approval: the plan, as markdown
     /// - For git_push: optional commit message
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payload: Option<String>,
+
+    /// Version of the client sending this (the shim), so the gateway can
+    /// flag skew. Absent from older clients.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_version: Option<String>,
+
+    /// For run_cmd: the shell the command was written for ("bash", "fish",
+    /// "powershell"...), when the client knows it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+
+    /// For chmod: apply to directories' contents too (`chmod -R`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub recursive: bool,
+}
+
+/// Why a request line was turned away before it could be evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    /// The request's ID, when the line got far enough to have one
+    pub request_id: Option<String>,
+    /// What was wrong with it, for the agent and the audit log
+    pub reason: String,
+}
+
+impl GatewayRequest {
+    /// Parse one request line, telling malformed JSON, unknown actions and
+    /// requests with missing or mistyped fields apart when it isn't one.
+    pub fn parse(line: &str) -> Result<Self, Rejection> {
+        let value: serde_json::Value = serde_json::from_str(line).map_err(|e| Rejection {
+            request_id: None,
+            reason: format!("Malformed JSON: {}", e),
+        })?;
+        let request_id = value
+            .get("request_id")
+            .and_then(|id| id.as_str())
+            .map(String::from);
+        if let Some(action) = value.get("action").and_then(|a| a.as_str()) {
+            let known = serde_json::from_value::<Action>(action.into())
+                .is_ok_and(|a| !matches!(a, Action::Rejected | Action::UnknownTool | Action::Any));
+            if !known {
+                let shown: String = action.chars().take(64).collect();
+                return Err(Rejection {
+                    request_id,
+                    reason: format!("Unknown action '{}'", shown.escape_debug()),
+                });
+            }
+        }
+        serde_json::from_value(value).map_err(|e| Rejection {
+            request_id,
+            reason: format!("Invalid request: {}", e),
+        })
+    }
 }
 
 /// A response from Lawctl back to the agent.
@@ -49,11 +120,29 @@ pub struct GatewayResponse {
     pub error: Option<String>,
 
     /// If allowed: the result of the action
-    /// - For run_cmd: stdout + stderr
+    /// - For run_cmd: stdout + stderr (kept for older shims; newer ones
+    ///   use the separate fields below)
     /// - For file operations: "ok"
     /// - For git_push: push output
     #[serde(skip_serializing_if = "Option::is_none")]
     pub result: Option<String>,
+
+    /// For run_cmd: the command's standard output, unchanged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdout: Option<String>,
+
+    /// For run_cmd: the command's standard error, plus any notes from
+    /// lawctl (e.g. that output was truncated)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
+
+    /// For run_cmd: the command's exit code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+
+    /// Version of the gateway that answered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway_version: Option<String>,
 }
 
 impl GatewayResponse {
@@ -64,9 +153,21 @@ impl GatewayResponse {
             allowed: true,
             error: None,
             result: Some(result.into()),
+            stdout: None,
+            stderr: None,
+            exit_code: None,
+            gateway_version: Some(VERSION.to_string()),
         }
     }
 
+    /// Attach a command's separate output streams and exit code.
+    pub fn with_command_output(mut self, stdout: String, stderr: String, exit_code: i32) -> Self {
+        self.stdout = Some(stdout);
+        self.stderr = Some(stderr);
+        self.exit_code = Some(exit_code);
+        self
+    }
+
     /// Create a "denied" response with a reason.
     pub fn denied(request_id: String, reason: impl Into<String>) -> Self {
         Self {
@@ -74,6 +175,10 @@ impl GatewayResponse {
             allowed: false,
             error: Some(reason.into()),
             result: None,
+            stdout: None,
+            stderr: None,
+            exit_code: None,
+            gateway_version: Some(VERSION.to_string()),
         }
     }
 
@@ -84,6 +189,10 @@ impl GatewayResponse {
             allowed: false,
             error: Some(format!("Internal error: {}", error.into())),
             result: None,
+            stdout: None,
+            stderr: None,
+            exit_code: None,
+            gateway_version: Some(VERSION.to_string()),
         }
     }
 }
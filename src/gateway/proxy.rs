@@ -0,0 +1,540 @@
+//! Local HTTP(S) proxy — network enforcement for direct mode.
+//!
+//! In Docker mode egress is controlled at the container network level.
+//! Direct mode has nothing underneath the agent, so when the proxy is
+//! enabled the gateway listens on 127.0.0.1 and the agent is launched with
+//! HTTP_PROXY / HTTPS_PROXY pointing at it. Every request becomes an
+//! `Action::Network` gateway request and goes through the usual
+//! evaluate → approve → log path before any bytes leave the host:
+//!
+//! - `CONNECT host:port` (HTTPS) is tunnelled opaquely once allowed
+//! - Absolute-form HTTP requests are forwarded with `Connection: close`,
+//!   so a kept-alive connection can't smuggle a second, unchecked request
+//! - `max_response_mb` on the deciding rule caps the bytes sent back to the
+//!   agent; the connection is cut at the cap and the truncation is logged
+//!
+//! This only sees tools that honor the proxy environment variables — it's
+//! a policy and audit layer, not an isolation boundary.
+
+use crate::audit::{Actor, LogEntry, NetworkDetails, SessionRecord};
+use crate::gateway::protocol::{GatewayRequest, GatewayResponse};
+use crate::gateway::server::{process_request, SessionState};
+use crate::policy::{Action, ActionContext, Decision};
+use crate::utils::domain::host_and_port;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// Upper bound on a request head (request line + headers).
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+
+/// Hop-by-hop headers that must not be forwarded upstream.
+const HOP_BY_HOP: &[&str] = &[
+    "connection",
+    "proxy-connection",
+    "proxy-authorization",
+    "keep-alive",
+];
+
+/// Evaluate (and log) a network request for `url`.
+/// `method` is None for CONNECT tunnels, where it isn't visible;
+/// `upload_bytes` is what's known to be sent before the pipe opens.
+async fn check(
+    state: &SessionState,
+    url: &str,
+    method: Option<&str>,
+    upload_bytes: u64,
+) -> GatewayResponse {
+    let request = GatewayRequest {
+        request_id: uuid::Uuid::new_v4().to_string(),
+        action: Action::Network,
+        target: url.to_string(),
+        additional_targets: Vec::new(),
+        method: method.map(String::from),
+        upload_bytes: Some(upload_bytes),
+        payload: Some(url.to_string()),
+        client_version: None,
+        shell: None,
+        recursive: false,
+    };
+    process_request(&request, state, &[]).await
+}
+
+fn network_context(url: &str, method: Option<&str>) -> ActionContext {
+    let context = ActionContext::new(url).with_url(url);
+    match method {
+        Some(method) => context.with_method(method),
+        None => context,
+    }
+}
+
+/// The response cap for a request from the deciding rule, as (MB, bytes).
+fn response_limit(state: &SessionState, url: &str, method: Option<&str>) -> Option<(u64, u64)> {
+    state
+        .engine
+        .network_response_limit_mb(&network_context(url, method))
+        .map(|mb| (mb, mb.saturating_mul(1024 * 1024)))
+}
+
+/// Write an audit entry for something that happened after the decision.
+async fn log_after_transfer(
+    state: &SessionState,
+    url: &str,
+    decision: Decision,
+    network: NetworkDetails,
+) {
+    let entry = LogEntry {
+        timestamp: Utc::now(),
+        session_id: state.session_id.clone(),
+        agent: state.agent_name.clone(),
+        action: Action::Network,
+        target: url.to_string(),
+        policy_rule: decision.matched_rule().map(String::from),
+        decision,
+        diff: None,
+        approved_by: None,
+        eval_duration_us: None,
+        network: Some(network),
+        warning: None,
+        output: None,
+        threats: Vec::new(),
+        actor: Actor::Agent,
+        process_chain: Vec::new(),
+    };
+    state.log(&entry).await;
+}
+
+/// Record that a response was cut off at its cap.
+async fn log_truncation(state: &SessionState, url: &str, method: Option<&str>, limit_mb: u64) {
+    let context = network_context(url, method);
+    let mut network =
+        NetworkDetails::from_context(&context, Some(limit_mb), None).unwrap_or_default();
+    network.response_truncated = true;
+    let decision = Decision::Denied {
+        reason: format!("Response exceeded max_response_mb ({} MB)", limit_mb),
+        matched_rule: Some("max_response_mb".to_string()),
+    };
+    log_after_transfer(state, url, decision, network).await;
+}
+
+/// Account for upload bytes that weren't known when the request was
+/// decided — a tunnel's traffic, or a body without Content-Length. They go
+/// in an upload record, not an entry: no decision was made about them.
+async fn record_extra_upload(state: &SessionState, url: &str, extra: u64) {
+    let Some((host, _)) = host_and_port(url) else {
+        return;
+    };
+    state.uploads.add(&host, extra);
+    state
+        .log_record(&SessionRecord::Upload {
+            timestamp: Utc::now(),
+            session_id: state.session_id.clone(),
+            host,
+            bytes: extra,
+        })
+        .await;
+}
+
+/// A running proxy. Stops when dropped.
+pub struct NetworkProxy {
+    addr: SocketAddr,
+    handle: JoinHandle<()>,
+}
+
+impl NetworkProxy {
+    /// Bind to an ephemeral port on 127.0.0.1 and start serving.
+    pub(crate) async fn start(state: Arc<SessionState>) -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .context("Failed to bind network proxy")?;
+        let addr = listener.local_addr()?;
+
+        tracing::info!("Network proxy listening on {}", addr);
+
+        let handle = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        let state = state.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = handle_client(stream, &state).await {
+                                tracing::debug!("Proxy connection error: {}", e);
+                            }
+                        });
+                    }
+                    Err(e) => tracing::error!("Proxy failed to accept connection: {}", e),
+                }
+            }
+        });
+
+        Ok(Self { addr, handle })
+    }
+
+    /// The address the proxy is listening on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The proxy URL to hand to the agent.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Environment variables that route an agent's traffic through the proxy.
+    /// Both casings are set since tools disagree on which they read, and
+    /// NO_PROXY is cleared so nothing is exempted behind our back.
+    pub fn env_vars(&self) -> Vec<(&'static str, String)> {
+        let url = self.url();
+        vec![
+            ("HTTP_PROXY", url.clone()),
+            ("HTTPS_PROXY", url.clone()),
+            ("ALL_PROXY", url.clone()),
+            ("http_proxy", url.clone()),
+            ("https_proxy", url.clone()),
+            ("all_proxy", url),
+            ("NO_PROXY", String::new()),
+            ("no_proxy", String::new()),
+        ]
+    }
+}
+
+impl Drop for NetworkProxy {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// Handle one client connection: one checked request, then a raw byte pipe.
+async fn handle_client(stream: TcpStream, state: &SessionState) -> Result<()> {
+    let mut reader = BufReader::new(stream);
+    let head = read_head(&mut reader).await?;
+
+    let Some(request) = parse_request_line(&head[0]) else {
+        return reply(reader.get_mut(), 400, "Malformed request line").await;
+    };
+
+    let is_connect = request.method.eq_ignore_ascii_case("CONNECT");
+    let method = (!is_connect).then_some(request.method.as_str());
+    let (url, upstream_addr, forwarded_head) = if is_connect {
+        (
+            format!("https://{}", request.target),
+            with_default_port(&request.target, 443),
+            None,
+        )
+    } else {
+        let Some((authority, path)) = split_http_url(&request.target) else {
+            return reply(
+                reader.get_mut(),
+                400,
+                "Only absolute http:// request URIs are supported",
+            )
+            .await;
+        };
+        let head = rewrite_head(&request, path, &head[1..]);
+        (
+            request.target.clone(),
+            with_default_port(authority, 80),
+            Some(head),
+        )
+    };
+
+    // What's known to go out before the pipe opens: the rewritten head plus
+    // any declared body. Tunnels reveal nothing up front.
+    let declared_bytes = forwarded_head.as_ref().map_or(0, |forwarded| {
+        forwarded.len() as u64 + content_length(&head[1..]).unwrap_or(0)
+    });
+    let response = check(state, &url, method, declared_bytes).await;
+    if !response.allowed {
+        let reason = response
+            .error
+            .unwrap_or_else(|| "denied by policy".to_string());
+        return reply(
+            reader.get_mut(),
+            403,
+            &format!("[lawctl] BLOCKED: {} — {}", url, reason),
+        )
+        .await;
+    }
+
+    let mut upstream = match TcpStream::connect(&upstream_addr).await {
+        Ok(s) => s,
+        Err(e) => {
+            return reply(
+                reader.get_mut(),
+                502,
+                &format!("Failed to connect to {}: {}", upstream_addr, e),
+            )
+            .await
+        }
+    };
+
+    let head_bytes = match forwarded_head {
+        None => {
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+                .await?;
+            0
+        }
+        Some(head) => {
+            upstream.write_all(head.as_bytes()).await?;
+            head.len() as u64
+        }
+    };
+
+    // Anything the client sent after the head (body, TLS hello) is still buffered
+    let buffered = reader.buffer().to_vec();
+    let mut client = reader.into_inner();
+    upstream.write_all(&buffered).await?;
+
+    let limit = response_limit(state, &url, method);
+    let piped = pipe(&mut client, &mut upstream, limit.map(|(_, bytes)| bytes)).await;
+
+    if let (Some((limit_mb, _)), true) = (limit, piped.truncated) {
+        log_truncation(state, &url, method, limit_mb).await;
+    }
+    let uploaded = head_bytes + buffered.len() as u64 + piped.uploaded;
+    let extra = uploaded.saturating_sub(declared_bytes);
+    if extra > 0 {
+        record_extra_upload(state, &url, extra).await;
+    }
+    Ok(())
+}
+
+/// What went through a piped connection.
+struct PipeStats {
+    /// Bytes sent client → upstream
+    uploaded: u64,
+    /// Whether the response was cut off at the limit
+    truncated: bool,
+}
+
+/// Pipe bytes both ways until the response side ends, cutting the
+/// connection once more than `limit` bytes have come back from upstream.
+/// I/O errors just end the pipe — the stats still count what got through.
+async fn pipe(client: &mut TcpStream, upstream: &mut TcpStream, limit: Option<u64>) -> PipeStats {
+    let (mut client_read, mut client_write) = client.split();
+    let (mut upstream_read, mut upstream_write) = upstream.split();
+    let limit = limit.unwrap_or(u64::MAX);
+    let uploaded = AtomicU64::new(0);
+
+    let upload = async {
+        let mut buf = vec![0u8; 16 * 1024];
+        loop {
+            let n = client_read.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            upstream_write.write_all(&buf[..n]).await?;
+            uploaded.fetch_add(n as u64, Ordering::Relaxed);
+        }
+        upstream_write.shutdown().await
+    };
+    let download = async {
+        let copied =
+            tokio::io::copy(&mut (&mut upstream_read).take(limit), &mut client_write).await?;
+        let mut probe = [0u8; 1];
+        let exceeded = copied >= limit && upstream_read.read(&mut probe).await? > 0;
+        client_write.shutdown().await?;
+        Ok::<bool, std::io::Error>(exceeded)
+    };
+    tokio::pin!(upload, download);
+
+    // Once the response side is done the connection is over; the upload
+    // side finishing first (request sent) just means waiting for the reply.
+    let result = tokio::select! {
+        result = &mut download => result,
+        _ = &mut upload => download.await,
+    };
+    let truncated = result.unwrap_or_else(|e| {
+        tracing::debug!("Proxy pipe ended: {}", e);
+        false
+    });
+    PipeStats {
+        uploaded: uploaded.load(Ordering::Relaxed),
+        truncated,
+    }
+}
+
+/// The declared body size, if the request has a Content-Length header.
+fn content_length(headers: &[String]) -> Option<u64> {
+    headers.iter().find_map(|header| {
+        let (name, value) = header.split_once(':')?;
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            value.trim().parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+/// Read the request line and headers, without the terminating blank line.
+async fn read_head(reader: &mut BufReader<TcpStream>) -> Result<Vec<String>> {
+    let mut lines = Vec::new();
+    let mut total = 0;
+    loop {
+        let mut line = String::new();
+        let n = reader.read_line(&mut line).await?;
+        if n == 0 {
+            anyhow::bail!("Connection closed before end of request head");
+        }
+        total += n;
+        if total > MAX_HEAD_BYTES {
+            anyhow::bail!("Request head exceeds {} bytes", MAX_HEAD_BYTES);
+        }
+        let line = line.trim_end_matches(['\r', '\n']).to_string();
+        if line.is_empty() {
+            if lines.is_empty() {
+                continue; // tolerate stray CRLF before the request line
+            }
+            return Ok(lines);
+        }
+        lines.push(line);
+    }
+}
+
+/// The parts of an HTTP request line.
+#[derive(Debug, PartialEq, Eq)]
+struct RequestLine {
+    method: String,
+    target: String,
+    version: String,
+}
+
+fn parse_request_line(line: &str) -> Option<RequestLine> {
+    let mut parts = line.split_whitespace();
+    let request = RequestLine {
+        method: parts.next()?.to_string(),
+        target: parts.next()?.to_string(),
+        version: parts.next()?.to_string(),
+    };
+    parts.next().is_none().then_some(request)
+}
+
+/// Split `http://host[:port]/path` into (`host[:port]`, `/path`).
+fn split_http_url(url: &str) -> Option<(&str, &str)> {
+    let rest = url.strip_prefix("http://")?;
+    let (authority, path) = match rest.find(['/', '?']) {
+        Some(idx) => rest.split_at(idx),
+        None => (rest, "/"),
+    };
+    (!authority.is_empty()).then_some((authority, path))
+}
+
+/// Append the default port to an authority that doesn't carry one.
+fn with_default_port(authority: &str, port: u16) -> String {
+    let has_port = match authority.rfind(']') {
+        // IPv6 literal: [::1]:8080
+        Some(idx) => authority[idx..].contains(':'),
+        None => authority.contains(':'),
+    };
+    if has_port {
+        authority.to_string()
+    } else {
+        format!("{}:{}", authority, port)
+    }
+}
+
+/// Rebuild the request head in origin form for the upstream server.
+fn rewrite_head(request: &RequestLine, path: &str, headers: &[String]) -> String {
+    let path = if path.starts_with('?') {
+        format!("/{}", path)
+    } else {
+        path.to_string()
+    };
+    let mut head = format!("{} {} {}\r\n", request.method, path, request.version);
+    for header in headers {
+        let name = header.split(':').next().unwrap_or("").trim();
+        if HOP_BY_HOP.iter().any(|h| name.eq_ignore_ascii_case(h)) {
+            continue;
+        }
+        head.push_str(header);
+        head.push_str("\r\n");
+    }
+    head.push_str("Connection: close\r\n\r\n");
+    head
+}
+
+/// Send a short plain-text response and close.
+async fn reply(stream: &mut TcpStream, status: u16, message: &str) -> Result<()> {
+    let reason = match status {
+        400 => "Bad Request",
+        403 => "Forbidden",
+        _ => "Bad Gateway",
+    };
+    let body = format!("{}\n", message);
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_line() {
+        let line = parse_request_line("CONNECT github.com:443 HTTP/1.1").unwrap();
+        assert_eq!(line.method, "CONNECT");
+        assert_eq!(line.target, "github.com:443");
+        assert!(parse_request_line("GET /").is_none());
+        assert!(parse_request_line("GET / HTTP/1.1 extra").is_none());
+    }
+
+    #[test]
+    fn test_split_http_url() {
+        assert_eq!(
+            split_http_url("http://example.com/a/b?c=1"),
+            Some(("example.com", "/a/b?c=1"))
+        );
+        assert_eq!(
+            split_http_url("http://example.com:8080"),
+            Some(("example.com:8080", "/"))
+        );
+        assert_eq!(
+            split_http_url("http://example.com?q"),
+            Some(("example.com", "?q"))
+        );
+        assert_eq!(split_http_url("https://example.com/"), None);
+        assert_eq!(split_http_url("/relative"), None);
+    }
+
+    #[test]
+    fn test_with_default_port() {
+        assert_eq!(with_default_port("example.com", 443), "example.com:443");
+        assert_eq!(
+            with_default_port("example.com:8443", 443),
+            "example.com:8443"
+        );
+        assert_eq!(with_default_port("[::1]", 80), "[::1]:80");
+        assert_eq!(with_default_port("[::1]:8080", 80), "[::1]:8080");
+    }
+
+    #[test]
+    fn test_rewrite_head_strips_hop_by_hop() {
+        let request = parse_request_line("GET http://example.com/x HTTP/1.1").unwrap();
+        let headers = vec![
+            "Host: example.com".to_string(),
+            "Proxy-Connection: keep-alive".to_string(),
+            "Connection: keep-alive".to_string(),
+            "Accept: */*".to_string(),
+        ];
+        let head = rewrite_head(&request, "/x", &headers);
+        assert_eq!(
+            head,
+            "GET /x HTTP/1.1\r\nHost: example.com\r\nAccept: */*\r\nConnection: close\r\n\r\n"
+        );
+    }
+}
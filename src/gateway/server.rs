@@ -5,38 +5,189 @@
 //! 1. Evaluates the request against the policy
 //! 2. If allowed: executes the action on the host side
 //! 3. If denied: returns an error to the agent
-//! 4. If requires_approval: pauses and asks the human
+//! 4. If requires_approval: pauses and asks the human, unless a plan the
+//!    human approved this session names the file or command
 //! 5. Logs everything regardless of outcome
 
+use crate::approval::plan::{self as approved_plan, Plan};
 use crate::approval::ApprovalHandler;
-use crate::audit::{AuditLogger, LogEntry};
+use crate::audit::{command_threats, Actor, AuditLogger, LogEntry, NetworkDetails, SessionRecord};
 use crate::gateway::handlers;
-use crate::gateway::protocol::{GatewayRequest, GatewayResponse};
+use crate::gateway::handlers::shell::ShellResult;
+use crate::gateway::protocol::{GatewayRequest, GatewayResponse, Rejection};
+use crate::gateway::proxy::NetworkProxy;
+use crate::gateway::stale::Snapshots;
+use crate::i18n::tf;
+use crate::policy::rate::{ActionCounter, DiffBudget, ReadFiles};
 use crate::policy::{ActionContext, Decision, PolicyEngine};
+use crate::utils::cloud;
+use crate::utils::kube;
+use crate::utils::lockfile;
+use crate::utils::paths::{self, collect_contained_paths, ContainedPaths};
+use crate::utils::process::{self, ProcessInfo};
+use crate::utils::redact;
+use crate::utils::version;
 use anyhow::{Context, Result};
 use chrono::Utc;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::UnixListener;
 use tokio::sync::Mutex;
 
+/// Called with every entry the gateway writes to the audit log, so the
+/// caller can show actions as they happen.
+pub type LogCallback = Arc<dyn Fn(&LogEntry) + Send + Sync>;
+
 /// The gateway server that mediates all agent actions.
 pub struct GatewayServer {
     /// Path to the Unix socket
     socket_path: PathBuf,
+    /// Everything requests need, shared with connections and the proxy
+    state: Arc<SessionState>,
+}
+
+/// Per-session state shared by every request, whether it arrives over the
+/// socket or through the network proxy.
+pub(crate) struct SessionState {
     /// The policy engine for evaluating actions
-    engine: Arc<PolicyEngine>,
+    pub engine: PolicyEngine,
     /// Workspace root on the host filesystem
-    workspace_root: PathBuf,
+    pub workspace_root: PathBuf,
     /// Session ID for audit logging
-    session_id: String,
+    pub session_id: String,
     /// Agent name for logging
-    agent_name: String,
+    pub agent_name: String,
     /// Audit logger
-    logger: Arc<Mutex<AuditLogger>>,
+    pub logger: Mutex<AuditLogger>,
     /// Approval handler for require_approval actions
-    approval_handler: Arc<dyn ApprovalHandler + Send + Sync>,
+    pub approval_handler: Arc<dyn ApprovalHandler + Send + Sync>,
+    /// Bytes sent per host so far, for `max_upload_mb_per_domain`
+    pub uploads: UploadTotals,
+    /// Actions made so far, for `max_per_minute` and `max_per_session`
+    pub counter: std::sync::Mutex<ActionCounter>,
+    /// Diff lines written so far, for `max_total_diff_lines`
+    pub diffs: std::sync::Mutex<DiffBudget>,
+    /// Files read so far, for `require_prior_read`
+    pub reads: std::sync::Mutex<ReadFiles>,
+    /// Files as the agent last saw them, for `detect_stale_writes`
+    pub snapshots: Snapshots,
+    /// Commands running under sub-policies, for `sub_policy`
+    pub delegations: Delegations,
+    /// The plan the human approved this session, if any
+    pub plan: std::sync::Mutex<Option<Plan>>,
+    /// Warnings already written to the log, so each is logged once
+    pub reported_warnings: std::sync::Mutex<HashSet<String>>,
+    /// Told about every logged entry
+    pub on_log: Option<LogCallback>,
+    /// Where full command output goes when it's cut off (`spill_output`)
+    pub spill_dir: PathBuf,
+}
+
+impl SessionState {
+    /// Write an entry to the audit log and pass it to the log callback.
+    pub async fn log(&self, entry: &LogEntry) {
+        if let Err(e) = self.logger.lock().await.log(entry) {
+            tracing::error!("Failed to write audit log: {}", e);
+        }
+        if let Some(on_log) = &self.on_log {
+            on_log(entry);
+        }
+    }
+
+    /// Write a record (not an action) to the session's log.
+    pub async fn log_record(&self, record: &SessionRecord) {
+        if let Err(e) = self.logger.lock().await.log_record(record) {
+            tracing::error!("Failed to write audit log: {}", e);
+        }
+    }
+
+    /// Whether the plan approved this session names what `request` does:
+    /// the command exactly as written, or every file it reads or writes.
+    fn plan_covers(&self, request: &GatewayRequest, context: &ActionContext) -> bool {
+        let plan = self.plan.lock().unwrap_or_else(|e| e.into_inner());
+        plan.as_ref().is_some_and(|plan| match request.action {
+            crate::policy::Action::RunCmd => context
+                .command
+                .as_deref()
+                .is_some_and(|command| plan.covers_command(command)),
+            crate::policy::Action::Write | crate::policy::Action::Read => {
+                plan.covers_files(context.all_targets(), &self.workspace_root)
+            }
+            _ => false,
+        })
+    }
+
+    /// Version skew behind this request — an out-of-date shim, or a policy
+    /// written for a newer lawctl — the first time it shows up.
+    fn version_warning(&self, request: &GatewayRequest) -> Option<String> {
+        let warnings: Vec<String> = [
+            version::policy_requirement(self.engine.policy().requires_lawctl.as_deref()),
+            version::component_mismatch("lawctl-shim", request.client_version.as_deref()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let mut reported = self
+            .reported_warnings
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let new: Vec<String> = warnings
+            .into_iter()
+            .filter(|w| reported.insert(w.clone()))
+            .collect();
+        (!new.is_empty()).then(|| new.join("; "))
+    }
+}
+
+/// Running per-host upload totals for a session.
+#[derive(Debug, Default)]
+pub(crate) struct UploadTotals(std::sync::Mutex<HashMap<String, u64>>);
+
+impl UploadTotals {
+    /// Bytes sent to `host` so far.
+    pub fn get(&self, host: &str) -> u64 {
+        let totals = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        totals.get(host).copied().unwrap_or(0)
+    }
+
+    /// Add `bytes` to `host`'s total.
+    pub fn add(&self, host: &str, bytes: u64) {
+        let mut totals = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let total = totals.entry(host.to_string()).or_default();
+        *total = total.saturating_add(bytes);
+    }
+}
+
+/// Gateway-run commands whose processes are held to sub-policies: the
+/// command's pid and the sub-policies, outermost first.
+#[derive(Debug, Default)]
+pub(crate) struct Delegations(std::sync::Mutex<HashMap<u32, Vec<String>>>);
+
+impl Delegations {
+    /// The sub-policies a request from the innermost process of
+    /// `process_chain` is held to: those of its nearest delegated ancestor.
+    pub fn of(&self, process_chain: &[ProcessInfo]) -> Vec<String> {
+        let running = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        process_chain
+            .iter()
+            .find_map(|process| running.get(&process.pid))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Hold everything process `pid` starts to `sub_policies`.
+    pub fn start(&self, pid: u32, sub_policies: Vec<String>) {
+        let mut running = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        running.insert(pid, sub_policies);
+    }
+
+    /// Forget `pid` once its command is done, before the pid is reused.
+    pub fn end(&self, pid: u32) {
+        let mut running = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        running.remove(&pid);
+    }
 }
 
 impl GatewayServer {
@@ -49,17 +200,46 @@ impl GatewayServer {
         logger: AuditLogger,
         approval_handler: Arc<dyn ApprovalHandler + Send + Sync>,
     ) -> Self {
+        let spill_dir = logger.log_path().with_extension("output");
         Self {
             socket_path: socket_path.as_ref().to_path_buf(),
-            engine: Arc::new(engine),
-            workspace_root: workspace_root.as_ref().to_path_buf(),
-            session_id,
-            agent_name,
-            logger: Arc::new(Mutex::new(logger)),
-            approval_handler,
+            state: Arc::new(SessionState {
+                engine,
+                workspace_root: workspace_root.as_ref().to_path_buf(),
+                session_id,
+                agent_name,
+                logger: Mutex::new(logger),
+                approval_handler,
+                uploads: UploadTotals::default(),
+                counter: Default::default(),
+                diffs: Default::default(),
+                reads: Default::default(),
+                snapshots: Snapshots::default(),
+                delegations: Delegations::default(),
+                plan: Default::default(),
+                reported_warnings: Default::default(),
+                on_log: None,
+                spill_dir,
+            }),
         }
     }
 
+    /// Call `on_log` with every entry this gateway (and its network proxy)
+    /// logs. Must be set before the gateway starts.
+    pub fn with_log_callback(mut self, on_log: LogCallback) -> Self {
+        Arc::get_mut(&mut self.state)
+            .expect("log callback set after the gateway started")
+            .on_log = Some(on_log);
+        self
+    }
+
+    /// Start the local network proxy, sharing this gateway's policy, logger
+    /// and approval handler. Used in direct mode, where there's no container
+    /// network to enforce `network` rules.
+    pub async fn start_network_proxy(&self) -> Result<NetworkProxy> {
+        NetworkProxy::start(self.state.clone()).await
+    }
+
     /// Start the gateway server. Listens for connections and handles requests.
     pub async fn run(&self) -> Result<()> {
         // Remove existing socket if present
@@ -75,19 +255,10 @@ impl GatewayServer {
         loop {
             match listener.accept().await {
                 Ok((stream, _addr)) => {
-                    let engine = self.engine.clone();
-                    let workspace = self.workspace_root.clone();
-                    let session_id = self.session_id.clone();
-                    let agent_name = self.agent_name.clone();
-                    let logger = self.logger.clone();
-                    let approval = self.approval_handler.clone();
+                    let state = self.state.clone();
 
                     tokio::spawn(async move {
-                        if let Err(e) = handle_connection(
-                            stream, engine, workspace, session_id, agent_name, logger, approval,
-                        )
-                        .await
-                        {
+                        if let Err(e) = handle_connection(stream, &state).await {
                             tracing::error!("Connection handler error: {}", e);
                         }
                     });
@@ -101,15 +272,14 @@ impl GatewayServer {
 }
 
 /// Handle a single connection from an agent.
-async fn handle_connection(
-    stream: tokio::net::UnixStream,
-    engine: Arc<PolicyEngine>,
-    workspace_root: PathBuf,
-    session_id: String,
-    agent_name: String,
-    logger: Arc<Mutex<AuditLogger>>,
-    approval_handler: Arc<dyn ApprovalHandler + Send + Sync>,
-) -> Result<()> {
+async fn handle_connection(stream: tokio::net::UnixStream, state: &SessionState) -> Result<()> {
+    // Who is on the other end, for attributing its requests
+    let process_chain = stream
+        .peer_cred()
+        .ok()
+        .and_then(|cred| cred.pid())
+        .map(|pid| process::chain(pid as u32, std::process::id()))
+        .unwrap_or_default();
     let (reader, mut writer) = stream.into_split();
     let mut reader = BufReader::new(reader);
     let mut line = String::new();
@@ -121,12 +291,23 @@ async fn handle_connection(
             break; // Connection closed
         }
 
-        let request: GatewayRequest = match serde_json::from_str(line.trim()) {
+        let request = match GatewayRequest::parse(line.trim()) {
             Ok(req) => req,
-            Err(e) => {
+            Err(rejection) => {
+                tracing::debug!(reason = %rejection.reason, bytes = bytes_read, "rejected gateway request");
+                state
+                    .log(&rejected_entry(
+                        state,
+                        line.trim(),
+                        &rejection,
+                        &process_chain,
+                    ))
+                    .await;
                 let error_response = GatewayResponse::internal_error(
-                    "unknown".to_string(),
-                    format!("Invalid request JSON: {}", e),
+                    rejection
+                        .request_id
+                        .unwrap_or_else(|| "unknown".to_string()),
+                    rejection.reason,
                 );
                 let json = serde_json::to_string(&error_response)?;
                 writer.write_all(json.as_bytes()).await?;
@@ -135,16 +316,7 @@ async fn handle_connection(
             }
         };
 
-        let response = process_request(
-            &request,
-            &engine,
-            &workspace_root,
-            &session_id,
-            &agent_name,
-            &logger,
-            &approval_handler,
-        )
-        .await;
+        let response = process_request(&request, state, &process_chain).await;
 
         let json = serde_json::to_string(&response)?;
         writer.write_all(json.as_bytes()).await?;
@@ -155,50 +327,337 @@ async fn handle_connection(
     Ok(())
 }
 
-/// Process a single gateway request.
-async fn process_request(
+/// How much of a rejected request line the audit log keeps.
+const REJECTED_PREVIEW_CHARS: usize = 200;
+
+/// The audit entry for a request line that never reached the policy, so
+/// probing of the gateway shows up in the log. The line itself is kept as
+/// the target, shortened and with secrets redacted.
+fn rejected_entry(
+    state: &SessionState,
+    line: &str,
+    rejection: &Rejection,
+    process_chain: &[ProcessInfo],
+) -> LogEntry {
+    let mut preview: String = line.chars().take(REJECTED_PREVIEW_CHARS).collect();
+    if preview.len() < line.len() {
+        preview = format!("{}... ({} bytes total)", preview, line.len());
+    }
+    LogEntry {
+        timestamp: Utc::now(),
+        session_id: state.session_id.clone(),
+        agent: state.agent_name.clone(),
+        action: crate::policy::Action::Rejected,
+        target: redact::redact(&preview),
+        policy_rule: None,
+        decision: Decision::Denied {
+            reason: rejection.reason.clone(),
+            matched_rule: None,
+        },
+        diff: None,
+        approved_by: None,
+        eval_duration_us: None,
+        network: None,
+        warning: None,
+        output: None,
+        threats: Vec::new(),
+        actor: Actor::Agent,
+        process_chain: process_chain.to_vec(),
+    }
+}
+
+/// A delete, move or chmod request with its paths as the handler will
+/// resolve them: `.` and `..` folded, and relative to the workspace when
+/// inside it. The policy then judges exactly the paths acted on, a move's
+/// destination included.
+fn with_workspace_paths(request: &GatewayRequest, workspace_root: &Path) -> GatewayRequest {
+    let resolve =
+        |target: &String| paths::workspace_path(Some(workspace_root), workspace_root, target);
+    let payload = match request.action {
+        crate::policy::Action::Move => request.payload.as_ref().map(resolve),
+        _ => request.payload.clone(),
+    };
+    GatewayRequest {
+        target: resolve(&request.target),
+        additional_targets: request.additional_targets.iter().map(resolve).collect(),
+        payload,
+        ..request.clone()
+    }
+}
+
+/// Why a write to a file changed under the agent needs approval.
+fn stale_write_reason(path: &str) -> String {
+    format!(
+        "'{}' changed on disk since the agent last saw it — writing would discard those changes",
+        path
+    )
+}
+
+/// Process a single gateway request, sent by the innermost process of
+/// `process_chain` (empty when unknown).
+pub(crate) async fn process_request(
     request: &GatewayRequest,
-    engine: &PolicyEngine,
-    workspace_root: &Path,
-    session_id: &str,
-    agent_name: &str,
-    logger: &Mutex<AuditLogger>,
-    approval_handler: &Arc<dyn ApprovalHandler + Send + Sync>,
+    state: &SessionState,
+    process_chain: &[ProcessInfo],
 ) -> GatewayResponse {
+    let engine = &state.engine;
+    let workspace_root = state.workspace_root.as_path();
+    let received = std::time::Instant::now();
+    let normalized;
+    let request = if matches!(
+        request.action,
+        crate::policy::Action::Delete | crate::policy::Action::Move | crate::policy::Action::Chmod
+    ) {
+        normalized = with_workspace_paths(request, workspace_root);
+        &normalized
+    } else {
+        request
+    };
+    // Payloads are file contents and diffs — trace their size, never them
+    tracing::debug!(
+        request_id = %request.request_id,
+        action = %request.action,
+        target = %request.target,
+        additional_targets = request.additional_targets.len(),
+        payload_bytes = request.payload.as_deref().map_or(0, str::len),
+        client_version = ?request.client_version,
+        "gateway request"
+    );
+    if request.action == crate::policy::Action::PlanApproval {
+        return approve_plan(request, state, process_chain).await;
+    }
+
     // Build action context for policy evaluation
     let mut context = ActionContext::new(&request.target);
+    // Who's asking, for `if_context`
+    context = context.with_extra("agent", state.agent_name.as_str());
+    if let Ok(user) = std::env::var("USER") {
+        context = context.with_extra("user", user);
+    }
+    if let Some(process) = process_chain.first() {
+        context = context.with_extra("process", process.name.as_str());
+    }
+    if let Some(ref method) = request.method {
+        context = context.with_method(method.as_str());
+    }
+    context
+        .additional_targets
+        .clone_from(&request.additional_targets);
+    if request.action == crate::policy::Action::GitPush {
+        // The git handler always pushes to origin
+        context = context
+            .with_branch(&request.target)
+            .with_extra("remote", "origin");
+    }
+    if matches!(
+        request.action,
+        crate::policy::Action::RemoteExec | crate::policy::Action::RemoteCopy
+    ) {
+        context = context.with_domain(&request.target);
+    }
+    if matches!(
+        request.action,
+        crate::policy::Action::Delete | crate::policy::Action::Move
+    ) || (request.action == crate::policy::Action::Chmod && request.recursive)
+    {
+        let contained: ContainedPaths = context
+            .all_targets()
+            .map(|t| collect_contained_paths(workspace_root, t, paths::MAX_CONTAINED_PATHS))
+            .collect();
+        context = context
+            .with_contained_paths(contained.paths)
+            .with_contents_truncated(contained.truncated);
+    }
     if let Some(ref payload) = request.payload {
         match request.action {
             crate::policy::Action::Write => {
+                if lockfile::is_lockfile(&request.target) {
+                    let old = std::fs::read_to_string(workspace_root.join(&request.target))
+                        .unwrap_or_default();
+                    context = context.with_new_dependencies(lockfile::added_dependencies(
+                        &request.target,
+                        &old,
+                        payload,
+                    ));
+                }
                 context = context.with_diff(payload.clone());
             }
             crate::policy::Action::RunCmd => {
                 context = context.with_command(payload.clone());
             }
             crate::policy::Action::Network => {
-                if let Some(domain) = handlers::network::extract_domain(payload) {
-                    context = context.with_domain(domain);
+                context = context.with_url(payload);
+            }
+            crate::policy::Action::Move => {
+                context = context.with_destination(handlers::file_move::destination(
+                    workspace_root,
+                    payload,
+                    request.additional_targets.is_empty(),
+                ));
+            }
+            crate::policy::Action::Chmod => {
+                context = context.with_mode(payload.as_str());
+            }
+            crate::policy::Action::PackageInstall => {
+                context = context.with_package_manager(payload.as_str());
+                for package in std::iter::once(&request.target).chain(&request.additional_targets) {
+                    context = context.with_extra("packages", package.as_str());
+                }
+            }
+            crate::policy::Action::RemoteExec => {
+                context = context.with_command(payload.clone());
+            }
+            crate::policy::Action::Cloud => {
+                if let Some(invocation) = cloud::invocations(payload).first() {
+                    context = context.with_cloud(invocation);
+                }
+                // The target is the account the caller resolved
+                if !request.target.is_empty() {
+                    context.cloud_account = Some(request.target.clone());
                 }
+                context = context.with_command(payload.clone());
+            }
+            crate::policy::Action::K8s => {
+                if let Some(invocation) = kube::invocations(payload).first() {
+                    context = context.with_kube(invocation);
+                }
+                // The target is the context the caller resolved
+                if !request.target.is_empty() {
+                    context.k8s_context = Some(request.target.clone());
+                }
+                context = context.with_command(payload.clone());
             }
             _ => {}
         }
     }
 
-    // Evaluate against policy
+    // Count this action towards the session's rate limits
+    let counts = state
+        .counter
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .record(&request.action, Utc::now());
+    context = context.with_counts(counts);
+
+    // Add this write to the session's diff totals
+    let diff_lines = match (&request.action, context.diff_lines) {
+        (crate::policy::Action::Write, Some(lines)) => {
+            let totals = state
+                .diffs
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .with(&context.target, lines);
+            context = context.with_diff_totals(totals);
+            Some(lines)
+        }
+        _ => None,
+    };
+
+    // Say whether the write creates the file, and whether an overwritten
+    // file was read first
+    let exists = workspace_root.join(&request.target).exists();
+    if request.action == crate::policy::Action::Write {
+        context = context.with_file_exists(exists);
+    }
+    if request.action == crate::policy::Action::Write && engine.tracks_reads() && exists {
+        let read = state
+            .reads
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(&request.target, workspace_root);
+        context = context.with_previously_read(read);
+    }
+
+    // Count what this network request sends towards its host's running total.
+    // Callers that know the body size say so; otherwise it's the URL itself.
+    let upload_bytes = match (&request.action, context.domain.clone()) {
+        (crate::policy::Action::Network, Some(host)) => {
+            let sent = request
+                .upload_bytes
+                .unwrap_or_else(|| request.payload.as_deref().unwrap_or("").len() as u64);
+            context = context.with_uploaded_bytes(state.uploads.get(&host).saturating_add(sent));
+            Some((host, sent))
+        }
+        _ => None,
+    };
+
+    // Evaluate against policy, and the sub-policies of the command that
+    // started the requesting process
+    let inherited = state.delegations.of(process_chain);
     let start = std::time::Instant::now();
-    let decision = engine.evaluate(&request.action, &context);
+    let decision = engine.evaluate_delegated(&request.action, &context, &inherited);
     let eval_duration = start.elapsed().as_micros() as u64;
 
+    // A write to a file that changed on disk since the agent saw it could
+    // lose someone's edits: a human has to look first
+    let stale = match request.action {
+        crate::policy::Action::Write
+            if engine.policy().detect_stale_writes && !decision.is_denied() =>
+        {
+            state.snapshots.check(
+                workspace_root,
+                &request.target,
+                request.payload.as_deref().unwrap_or(""),
+            )
+        }
+        _ => None,
+    };
+    let decision = match (&stale, decision) {
+        (
+            Some(_),
+            Decision::RequiresApproval {
+                reason,
+                matched_rule,
+            },
+        ) => Decision::RequiresApproval {
+            reason: format!("{}. Also, {}", reason, stale_write_reason(&request.target)),
+            matched_rule,
+        },
+        (Some(_), _) => Decision::RequiresApproval {
+            reason: stale_write_reason(&request.target),
+            matched_rule: Some("builtin:stale-write".to_string()),
+        },
+        (None, decision) => decision,
+    };
+    // What a command starts stays under the sub-policies the command runs
+    // under, plus any the rule allowing it delegates to
+    let mut delegated = Vec::new();
+    if request.action == crate::policy::Action::RunCmd {
+        delegated.clone_from(&inherited);
+        for name in engine.sub_policies_for(&context) {
+            if !delegated.contains(&name) {
+                delegated.push(name);
+            }
+        }
+    }
+    tracing::debug!(
+        request_id = %request.request_id,
+        decision = %decision,
+        rule = ?decision_rule(&decision),
+        eval_us = eval_duration,
+        "policy decision"
+    );
+
+    // An approved plan that names this file or command stands in for the
+    // prompt. A stale write wasn't part of what the human saw, so it's asked.
+    let preauthorized = matches!(decision, Decision::RequiresApproval { .. })
+        && stale.is_none()
+        && state.plan_covers(request, &context);
+
     // Handle the decision
+    let mut command_output = None;
     let (response, final_decision, approved_by) = match &decision {
-        Decision::Allowed { .. } => {
-            let result = execute_action(request, workspace_root).await;
+        Decision::Allowed { .. } | Decision::Logged { .. } => {
+            let result = execute_action(request, state, &delegated).await;
             match result {
-                Ok(output) => (
-                    GatewayResponse::allowed(request.request_id.clone(), output),
-                    decision.clone(),
-                    None,
-                ),
+                Ok(executed) => {
+                    command_output = executed.shell.as_ref().map(ShellResult::summary);
+                    (
+                        executed.into_response(request.request_id.clone()),
+                        decision.clone(),
+                        None,
+                    )
+                }
                 Err(e) => (
                     GatewayResponse::internal_error(request.request_id.clone(), e.to_string()),
                     decision.clone(),
@@ -211,31 +670,73 @@ async fn process_request(
             decision.clone(),
             None,
         ),
+        Decision::RequiresApproval { .. } if preauthorized => {
+            let result = execute_action(request, state, &delegated).await;
+            match result {
+                Ok(executed) => {
+                    command_output = executed.shell.as_ref().map(ShellResult::summary);
+                    (
+                        executed.into_response(request.request_id.clone()),
+                        Decision::Allowed {
+                            matched_rule: Some("approved by plan".to_string()),
+                        },
+                        Some(approved_plan::APPROVED_BY.to_string()),
+                    )
+                }
+                Err(e) => (
+                    GatewayResponse::internal_error(request.request_id.clone(), e.to_string()),
+                    decision.clone(),
+                    None,
+                ),
+            }
+        }
         Decision::RequiresApproval { reason, .. } => {
+            let review = match state.engine.policy().reviewer.clone() {
+                Some(reviewer) => Some(second_opinion(reviewer, request, &context, reason).await),
+                None => None,
+            };
             // Ask the human
             let approval_request = crate::approval::types::ApprovalRequest {
                 action: request.action.clone(),
-                target: request.target.clone(),
-                payload_preview: request.payload.as_ref().map(|p| truncate_preview(p, 500)),
+                target: approval_target(&context),
+                payload_preview: stale
+                    .clone()
+                    .or_else(|| request.payload.as_ref().map(|p| truncate_preview(p, 500))),
                 reason: reason.clone(),
+                review,
             };
 
-            match approval_handler.request_approval(&approval_request).await {
+            let asked = std::time::Instant::now();
+            let answer = state
+                .approval_handler
+                .request_approval(&approval_request)
+                .await;
+            tracing::debug!(
+                request_id = %request.request_id,
+                approved = answer.as_ref().is_ok_and(|a| a.approved),
+                error = ?answer.as_ref().err(),
+                wait_ms = asked.elapsed().as_millis() as u64,
+                "approval answered"
+            );
+            match answer {
                 Ok(approval_response) => {
                     if approval_response.approved {
-                        let result = execute_action(request, workspace_root).await;
+                        let result = execute_action(request, state, &delegated).await;
                         match result {
-                            Ok(output) => (
-                                GatewayResponse::allowed(request.request_id.clone(), output),
-                                Decision::Allowed {
-                                    matched_rule: Some("approved by human".to_string()),
-                                },
-                                Some(
-                                    approval_response
-                                        .approved_by
-                                        .unwrap_or_else(|| "terminal".to_string()),
-                                ),
-                            ),
+                            Ok(executed) => {
+                                command_output = executed.shell.as_ref().map(ShellResult::summary);
+                                (
+                                    executed.into_response(request.request_id.clone()),
+                                    Decision::Allowed {
+                                        matched_rule: Some("approved by human".to_string()),
+                                    },
+                                    Some(
+                                        approval_response
+                                            .approved_by
+                                            .unwrap_or_else(|| "terminal".to_string()),
+                                    ),
+                                )
+                            }
                             Err(e) => (
                                 GatewayResponse::internal_error(
                                     request.request_id.clone(),
@@ -274,53 +775,346 @@ async fn process_request(
         }
     };
 
+    if let (true, Some((host, sent))) = (final_decision.is_allowed(), &upload_bytes) {
+        state.uploads.add(host, *sent);
+    }
+    if final_decision.is_allowed() && request.action == crate::policy::Action::Read {
+        let mut reads = state.reads.lock().unwrap_or_else(|e| e.into_inner());
+        for path in context.all_targets() {
+            reads.add(path, workspace_root);
+        }
+    }
+    if let (true, Some(lines)) = (final_decision.is_allowed(), diff_lines) {
+        state
+            .diffs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .add(&context.target, lines);
+    }
+
     // Log the action (always, regardless of outcome)
     let entry = LogEntry {
         timestamp: Utc::now(),
-        session_id: session_id.to_string(),
-        agent: agent_name.to_string(),
+        session_id: state.session_id.clone(),
+        agent: state.agent_name.clone(),
         action: request.action.clone(),
-        target: request.target.clone(),
-        policy_rule: match &final_decision {
-            Decision::Allowed { matched_rule, .. } => matched_rule.clone(),
-            Decision::Denied { matched_rule, .. } => matched_rule.clone(),
-            Decision::RequiresApproval { matched_rule, .. } => matched_rule.clone(),
-        },
+        target: context.display_targets(),
+        policy_rule: decision_rule(&final_decision),
         decision: final_decision,
         diff: request.payload.clone(),
         approved_by,
         eval_duration_us: Some(eval_duration),
+        network: if request.action == crate::policy::Action::Network {
+            NetworkDetails::from_context(
+                &context,
+                engine.network_response_limit_mb(&context),
+                upload_bytes
+                    .as_ref()
+                    .map(|(_, sent)| *sent)
+                    .filter(|&n| n > 0),
+            )
+        } else {
+            None
+        },
+        warning: state.version_warning(request),
+        output: command_output,
+        threats: command_threats(&request.action, &context),
+        actor: Actor::Agent,
+        process_chain: process_chain.to_vec(),
     };
 
-    if let Err(e) = logger.lock().await.log(&entry) {
-        tracing::error!("Failed to write audit log: {}", e);
-    }
+    state.log(&entry).await;
 
+    tracing::debug!(
+        request_id = %request.request_id,
+        allowed = response.allowed,
+        error = ?response.error,
+        result_bytes = response.result.as_deref().map_or(0, str::len),
+        total_us = received.elapsed().as_micros() as u64,
+        "gateway response"
+    );
     response
 }
 
-/// Execute an allowed action on the host side.
-async fn execute_action(request: &GatewayRequest, workspace_root: &Path) -> Result<String> {
-    match request.action {
+/// Ask the human to approve a plan's files and commands in one go. An
+/// approved plan is kept for the rest of the session, replacing any
+/// earlier one, and logged as a `plan_approval` entry either way.
+async fn approve_plan(
+    request: &GatewayRequest,
+    state: &SessionState,
+    process_chain: &[ProcessInfo],
+) -> GatewayResponse {
+    let plan = Plan::parse(request.payload.as_deref().unwrap_or(""));
+    if plan.is_empty() {
+        return GatewayResponse::denied(
+            request.request_id.clone(),
+            "The plan names no files or commands",
+        );
+    }
+    let target = format!(
+        "{} files, {} commands",
+        plan.files.len(),
+        plan.commands.len()
+    );
+    let reason = "Approve the plan's files and commands for this session".to_string();
+    let approval_request = crate::approval::types::ApprovalRequest {
+        action: request.action.clone(),
+        target: target.clone(),
+        payload_preview: Some(plan.describe()),
+        reason: reason.clone(),
+        review: None,
+    };
+    let approved = state
+        .approval_handler
+        .request_approval(&approval_request)
+        .await
+        .is_ok_and(|answer| answer.approved);
+
+    let entry = LogEntry {
+        timestamp: Utc::now(),
+        session_id: state.session_id.clone(),
+        agent: state.agent_name.clone(),
+        action: request.action.clone(),
+        target: target.clone(),
+        policy_rule: None,
+        decision: if approved {
+            Decision::RequiresApproval {
+                reason,
+                matched_rule: None,
+            }
+        } else {
+            Decision::Denied {
+                reason: "Denied by human reviewer".to_string(),
+                matched_rule: Some("human review".to_string()),
+            }
+        },
+        diff: serde_json::to_string(&plan).ok(),
+        approved_by: approved.then(|| approved_plan::APPROVED_BY.to_string()),
+        eval_duration_us: None,
+        network: None,
+        warning: state.version_warning(request),
+        output: None,
+        threats: Vec::new(),
+        actor: Actor::Agent,
+        process_chain: process_chain.to_vec(),
+    };
+    state.log(&entry).await;
+
+    if !approved {
+        return GatewayResponse::denied(request.request_id.clone(), "Plan not pre-approved");
+    }
+    *state.plan.lock().unwrap_or_else(|e| e.into_inner()) = Some(plan);
+    GatewayResponse::allowed(
+        request.request_id.clone(),
+        format!("Pre-approved: {}", target),
+    )
+}
+
+/// The rule behind a decision, if any.
+fn decision_rule(decision: &Decision) -> Option<String> {
+    decision.matched_rule().map(String::from)
+}
+
+/// What executing an allowed action produced.
+struct Executed {
+    /// Sent back to the agent
+    result: String,
+    /// For commands: the streams and exit code, kept apart
+    shell: Option<ShellResult>,
+}
+
+impl Executed {
+    fn into_response(self, request_id: String) -> GatewayResponse {
+        let response = GatewayResponse::allowed(request_id, self.result);
+        match self.shell {
+            Some(shell) => {
+                let (stdout, stderr) = shell.streams();
+                response.with_command_output(stdout, stderr, shell.exit_code)
+            }
+            None => response,
+        }
+    }
+}
+
+impl From<String> for Executed {
+    fn from(result: String) -> Self {
+        Self {
+            result,
+            shell: None,
+        }
+    }
+}
+
+/// Execute an allowed action on the host side. A command's processes are
+/// held to the `delegated` sub-policies while it runs.
+async fn execute_action(
+    request: &GatewayRequest,
+    state: &SessionState,
+    delegated: &[String],
+) -> Result<Executed> {
+    let workspace_root = state.workspace_root.as_path();
+    let result = match request.action {
         crate::policy::Action::Write => {
             let content = request.payload.as_deref().unwrap_or("");
-            handlers::file_write::execute_write(workspace_root, &request.target, content)
+            let result =
+                handlers::file_write::execute_write(workspace_root, &request.target, content);
+            if result.is_ok() && state.engine.policy().detect_stale_writes {
+                state
+                    .snapshots
+                    .record(workspace_root, &request.target, content);
+            }
+            result
+        }
+        crate::policy::Action::Read => {
+            let result = handlers::file_read::execute_read(workspace_root, &request.target);
+            if let (Ok(content), true) = (&result, state.engine.policy().detect_stale_writes) {
+                state
+                    .snapshots
+                    .record(workspace_root, &request.target, content);
+            }
+            result
         }
         crate::policy::Action::Delete => {
-            handlers::file_delete::execute_delete(workspace_root, &request.target)
+            let targets: Vec<&str> = std::iter::once(&request.target)
+                .chain(&request.additional_targets)
+                .map(String::as_str)
+                .collect();
+            handlers::file_delete::execute_delete_batch(workspace_root, &targets)
+        }
+        crate::policy::Action::Move => {
+            let sources: Vec<&str> = std::iter::once(&request.target)
+                .chain(&request.additional_targets)
+                .map(String::as_str)
+                .collect();
+            let destination = request.payload.as_deref().unwrap_or_default();
+            handlers::file_move::execute_move(workspace_root, &sources, destination)
+        }
+        crate::policy::Action::Chmod => {
+            let paths: Vec<&str> = std::iter::once(&request.target)
+                .chain(&request.additional_targets)
+                .map(String::as_str)
+                .collect();
+            let mode = request.payload.as_deref().unwrap_or_default();
+            handlers::file_chmod::execute_chmod(workspace_root, &paths, mode, request.recursive)
         }
         crate::policy::Action::RunCmd => {
             let command = request.payload.as_deref().unwrap_or(&request.target);
-            let result = handlers::shell::execute_command(workspace_root, command)?;
-            Ok(result.to_output())
+            let shell = handlers::shell::select_shell(
+                &state.engine.policy().shell,
+                request.shell.as_deref(),
+            );
+            let policy = state.engine.policy();
+            let limits = handlers::shell::OutputLimits {
+                max_bytes: (policy
+                    .max_output_kb
+                    .unwrap_or(handlers::shell::DEFAULT_MAX_OUTPUT_KB)
+                    * 1024) as usize,
+                spill_to: policy
+                    .spill_output
+                    .then(|| state.spill_dir.join(&request.request_id)),
+            };
+            let mut spawned = None;
+            let mut run = || {
+                handlers::shell::execute_command_tracked(
+                    workspace_root,
+                    &shell,
+                    command,
+                    &limits,
+                    |pid| {
+                        if !delegated.is_empty() {
+                            state.delegations.start(pid, delegated.to_vec());
+                            spawned = Some(pid);
+                        }
+                    },
+                )
+            };
+            // The command may itself ask the gateway; let other requests
+            // run while it does
+            let result = match tokio::runtime::Handle::current().runtime_flavor() {
+                tokio::runtime::RuntimeFlavor::CurrentThread => run(),
+                _ => tokio::task::block_in_place(run),
+            };
+            if let Some(pid) = spawned {
+                state.delegations.end(pid);
+            }
+            let result = result?;
+            return Ok(Executed {
+                result: result.to_output(),
+                shell: Some(result),
+            });
         }
         crate::policy::Action::GitPush => {
-            handlers::git::execute_git_push(workspace_root, &request.target)
+            let policy_hash = state.engine.policy_hash();
+            let trace =
+                state
+                    .engine
+                    .policy()
+                    .annotate_pushes
+                    .map(|mode| handlers::git::PushTrace {
+                        mode,
+                        session_id: &state.session_id,
+                        policy_hash: &policy_hash,
+                    });
+            handlers::git::execute_git_push(workspace_root, &request.target, trace.as_ref())
         }
         crate::policy::Action::Network => {
             let url = request.payload.as_deref().unwrap_or(&request.target);
             handlers::network::validate_network_request(url)
         }
+        // Only checks: the caller reads the variables, or runs the installer,
+        // ssh, scp, rsync, kubectl, helm or the cloud CLI
+        crate::policy::Action::EnvAccess
+        | crate::policy::Action::PackageInstall
+        | crate::policy::Action::RemoteExec
+        | crate::policy::Action::RemoteCopy
+        | crate::policy::Action::K8s
+        | crate::policy::Action::Cloud => Ok(format!(
+            "Allowed {}: {}",
+            request.action,
+            std::iter::once(&request.target)
+                .chain(&request.additional_targets)
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+        // Turned away by GatewayRequest::parse, or (plans) answered
+        // before evaluation
+        crate::policy::Action::Rejected
+        | crate::policy::Action::UnknownTool
+        | crate::policy::Action::Any
+        | crate::policy::Action::PlanApproval => Err(anyhow::anyhow!(
+            "{} requests are never executed",
+            request.action
+        )),
+    };
+    result.map(Executed::from)
+}
+
+/// The reviewer's assessment of an action waiting for approval, or a note
+/// saying why there isn't one.
+async fn second_opinion(
+    reviewer: crate::approval::reviewer::Reviewer,
+    request: &GatewayRequest,
+    context: &ActionContext,
+    reason: &str,
+) -> String {
+    let (action, target, payload, reason) = (
+        request.action.clone(),
+        context.display_targets(),
+        request.payload.clone(),
+        reason.to_string(),
+    );
+    let review = tokio::task::spawn_blocking(move || {
+        reviewer.review(&action, &target, payload.as_deref(), &reason)
+    })
+    .await;
+    match review {
+        Ok(Ok(review)) => review,
+        Ok(Err(e)) => {
+            tracing::warn!("{:#}", e);
+            tf("approval.review_failed", &[("error", &format!("{:#}", e))])
+        }
+        Err(e) => tf("approval.review_failed", &[("error", &e)]),
     }
 }
 
@@ -332,3 +1126,18 @@ fn truncate_preview(s: &str, max_len: usize) -> String {
         format!("{}... ({} chars total)", &s[..max_len], s.len())
     }
 }
+
+/// What the approval prompt shows as the target. Cloud commands name the
+/// provider and account they act on and what they do there, so a human can
+/// tell a staging read from a production change at a glance.
+fn approval_target(context: &ActionContext) -> String {
+    match (&context.cloud_provider, &context.cloud_effect) {
+        (Some(provider), Some(effect)) => format!(
+            "{} account '{}' ({})",
+            provider,
+            context.cloud_account.as_deref().unwrap_or("unknown"),
+            effect
+        ),
+        _ => context.display_targets(),
+    }
+}
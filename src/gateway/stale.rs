@@ -0,0 +1,121 @@
+//! Stale-write detection (`detect_stale_writes: true`).
+//!
+//! While the agent works, the human may be editing the same files. The
+//! gateway remembers each file as the agent last saw it — what it read or
+//! wrote, and the modification time then. A write to a file that has changed
+//! on disk since is held for approval, with the changes on both sides shown
+//! against what the agent saw, so nobody's edits get lost silently.
+
+use similar::TextDiff;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Longest file kept; bigger ones aren't checked.
+const MAX_SNAPSHOT_BYTES: usize = 1024 * 1024;
+
+/// A file as the agent last saw it.
+#[derive(Debug)]
+struct Snapshot {
+    modified: SystemTime,
+    content: String,
+}
+
+/// The files the agent has seen this session, by workspace-relative path.
+#[derive(Debug, Default)]
+pub struct Snapshots(Mutex<HashMap<String, Snapshot>>);
+
+impl Snapshots {
+    /// Remember `path` as holding `content`, as the agent just read or wrote it.
+    pub fn record(&self, workspace_root: &Path, path: &str, content: &str) {
+        let mut snapshots = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let modified = modified(workspace_root, path);
+        match modified {
+            Some(modified) if content.len() <= MAX_SNAPSHOT_BYTES => {
+                snapshots.insert(
+                    path.to_string(),
+                    Snapshot {
+                        modified,
+                        content: content.to_string(),
+                    },
+                );
+            }
+            _ => {
+                snapshots.remove(path);
+            }
+        }
+    }
+
+    /// If `path` has changed on disk since the agent saw it, a preview of
+    /// both sides' changes for the approval prompt.
+    pub fn check(&self, workspace_root: &Path, path: &str, proposed: &str) -> Option<String> {
+        let snapshots = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let seen = snapshots.get(path)?;
+        if modified(workspace_root, path) == Some(seen.modified) {
+            return None;
+        }
+        // Deleted since: nothing on disk to clobber
+        let on_disk = std::fs::read_to_string(workspace_root.join(path)).ok()?;
+        Some(three_way_preview(&seen.content, &on_disk, proposed))
+    }
+}
+
+fn modified(workspace_root: &Path, path: &str) -> Option<SystemTime> {
+    std::fs::metadata(workspace_root.join(path))
+        .and_then(|m| m.modified())
+        .ok()
+}
+
+/// What changed on disk and what the agent would write, each as a unified
+/// diff against the version the agent saw.
+pub fn three_way_preview(seen: &str, on_disk: &str, proposed: &str) -> String {
+    let changes = |after: &str, label: &str| {
+        TextDiff::from_lines(seen, after)
+            .unified_diff()
+            .context_radius(2)
+            .header("seen by agent", label)
+            .to_string()
+    };
+    format!(
+        "Changed on disk since the agent saw it:\n{}\nThe agent's write:\n{}",
+        changes(on_disk, "on disk now"),
+        changes(proposed, "agent's write")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detects_changes_since_the_agent_saw_a_file() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("notes.txt");
+        std::fs::write(&file, "one\ntwo\n").unwrap();
+        let snapshots = Snapshots::default();
+
+        // Never seen: nothing to compare against
+        assert!(snapshots.check(tmp.path(), "notes.txt", "x\n").is_none());
+
+        snapshots.record(tmp.path(), "notes.txt", "one\ntwo\n");
+        assert!(snapshots.check(tmp.path(), "notes.txt", "x\n").is_none());
+
+        // The human edits it
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        std::fs::write(&file, "one\ntwo\nthree\n").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        let preview = snapshots
+            .check(tmp.path(), "notes.txt", "one\n2\n")
+            .unwrap();
+        assert!(preview.contains("+three"));
+        assert!(preview.contains("+2"));
+        assert!(preview.contains("-two"));
+    }
+}
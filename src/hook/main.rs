@@ -19,10 +19,26 @@
 //!   "tool_input": { "command": "rm -rf /" }
 //! }
 
+use chrono::Utc;
+use lawctl::approval::plan::{self, Plan};
 use lawctl::audit::AuditLogger;
-use lawctl::audit::LogEntry;
-use lawctl::policy::types::{Action, ActionContext, Decision};
-use lawctl::policy::{parser, PolicyEngine};
+use lawctl::audit::{command_threats, Actor, AuditReader, LogEntry, NetworkDetails};
+use lawctl::gateway::client::GatewayClient;
+use lawctl::gateway::handlers::file_move;
+use lawctl::i18n::{t, tf};
+use lawctl::policy::rate::{ActionCounter, DiffBudget, ReadFiles};
+use lawctl::policy::types::{Action, ActionContext, Decision, UnknownTools};
+use lawctl::policy::{history, parser, traps, PolicyEngine};
+use lawctl::utils::cloud;
+use lawctl::utils::command::{
+    env_access_targets, env_reads, files_read, network_targets, package_installs_by_manager,
+    parse_chmod_args, parse_mv_args, parse_rm_args, remote_accesses, RemoteAccess,
+};
+use lawctl::utils::kube;
+use lawctl::utils::lockfile;
+use lawctl::utils::paths::{collect_contained_paths, ContainedPaths, MAX_CONTAINED_PATHS};
+use lawctl::utils::shell::simple_commands;
+use lawctl::utils::version;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::process;
@@ -36,9 +52,18 @@ struct HookInput {
     hook_event_name: Option<String>,
     tool_name: String,
     tool_input: serde_json::Value,
+    /// Sent by Claude Code versions that report them
+    model: Option<String>,
+    permission_mode: Option<String>,
 }
 
 fn main() {
+    // `lawctl update` checks the hook is at the same version as the CLI
+    if std::env::args().nth(1).as_deref() == Some("--version") {
+        println!("lawctl-hook {}", env!("CARGO_PKG_VERSION"));
+        return;
+    }
+
     // Read stdin
     let mut input = String::new();
     if let Err(e) = std::io::stdin().read_to_string(&mut input) {
@@ -73,7 +98,7 @@ fn main() {
     };
 
     // Parse policy + create engine
-    let policy = match parser::parse_policy_file(&policy_path) {
+    let mut policy = match parser::parse_policy_tree(&policy_path) {
         Ok(p) => p,
         Err(e) => {
             eprintln!("[lawctl] Failed to parse policy: {}", e);
@@ -82,73 +107,236 @@ fn main() {
         }
     };
 
+    for skipped in &policy.skipped_layers {
+        eprintln!("[lawctl] Nested policy {}", skipped);
+    }
+
+    // Rules `lawctl run --disable-rule` switched off for this session
+    if let Ok(selectors) = std::env::var(lawctl::cli::run::DISABLED_RULES_ENV) {
+        let selectors: Vec<String> = selectors.split(',').map(String::from).collect();
+        if let Err(e) = policy.disable_rules(&selectors) {
+            eprintln!("[lawctl] Ignoring --disable-rule: {}", e);
+        }
+    }
+
+    // The policy lives at the project root, next to CODEOWNERS
+    let workspace = policy_path.parent().unwrap_or(&cwd);
+    history::record_load(
+        workspace,
+        &policy_path,
+        "lawctl-hook",
+        policy.commit_policy_history,
+    );
+    // Extract session_id before we borrow hook_input again
+    let session_id = hook_input
+        .session_id
+        .clone()
+        .unwrap_or_else(|| "claude-hook".to_string());
+
     let engine = match PolicyEngine::new(policy) {
-        Ok(e) => e,
+        Ok(e) => e
+            .with_workspace(workspace)
+            .with_policy_file(&policy_path)
+            .with_session(&session_id),
         Err(e) => {
             eprintln!("[lawctl] Failed to create policy engine: {}", e);
             process::exit(0);
         }
     };
 
+    // An older hook than the policy expects may miss newer rules — flag it
+    // on the first entry this call logs
+    let mut version_warning =
+        version::policy_requirement(engine.policy().requires_lawctl.as_deref());
+
+    // Leaving plan mode: offer to approve the whole plan at once
+    if hook_input.tool_name == "ExitPlanMode" {
+        approve_plan(&hook_input, &session_id, workspace);
+        process::exit(0);
+    }
+
     // Map Claude Code tool to lawctl action(s) + context
     let actions = match map_tool_to_actions(&hook_input) {
-        Some(a) => a,
+        Some(a) => a
+            .into_iter()
+            .map(|(action, ctx)| (action, with_call_metadata(ctx, &hook_input)))
+            .collect::<Vec<_>>(),
         None => {
             // Tool we don't care about (Read, Glob, Grep, etc.) — allow
             process::exit(0);
         }
     };
 
-    // Extract session_id before we borrow hook_input again
-    let session_id = hook_input
-        .session_id
-        .clone()
-        .unwrap_or_else(|| "claude-hook".to_string());
-
-    // Evaluate ALL actions — if any is denied, block.
+    // Evaluate ALL actions before acting on any — if any is denied, block.
     // This handles dual-action commands like `rm -rf /` which is both
-    // RunCmd (matches command-pattern rules) and Delete (matches path rules).
-    //
-    // When an action is approved by the user (e.g., GitPush), we skip
-    // remaining checks — the user explicitly OK'd this command.
-    let mut user_approved = false;
+    // RunCmd (matches command-pattern rules) and Delete (matches path rules),
+    // and chains like `git push && rm -rf secrets/`. Only once nothing is
+    // denied is the human asked, once, about whatever needs approval.
+    let mut logger = AuditLogger::new(&session_id, workspace).ok();
+    let mut counter = engine
+        .counts_actions()
+        .then(|| session_counter(&session_id));
+    let diffs = engine.tracks_diffs().then(|| session_diffs(&session_id));
+    let reads = engine
+        .tracks_reads()
+        .then(|| session_reads(&session_id, workspace));
+    // The plan approved this session, read when something needs approval
+    let mut approved_plan: Option<Option<Plan>> = None;
+    let mut denials: Vec<(String, String, bool)> = Vec::new();
+    let mut approvals: Vec<(String, String)> = Vec::new();
 
     for (action, context) in &actions {
-        // If user already approved this command via a dialog, skip further checks.
-        // e.g., user approved GitPush → don't also block the RunCmd for "git push".
-        if user_approved {
-            break;
-        }
+        let counted = counter
+            .as_mut()
+            .map(|c| context.clone().with_counts(c.record(action, Utc::now())));
+        let context = counted.as_ref().unwrap_or(context);
+        let budgeted = match (action, context.diff_lines, diffs.as_ref()) {
+            (Action::Write, Some(lines), Some(diffs)) => Some(
+                context
+                    .clone()
+                    .with_diff_totals(diffs.with(&context.target, lines)),
+            ),
+            _ => None,
+        };
+        let context = budgeted.as_ref().unwrap_or(context);
+        let checked = match action {
+            Action::Write => {
+                let exists = cwd.join(&context.target).exists();
+                let checked = context.clone().with_file_exists(exists);
+                Some(match reads.as_ref() {
+                    Some(reads) if exists => {
+                        checked.with_previously_read(reads.contains(&context.target, workspace))
+                    }
+                    _ => checked,
+                })
+            }
+            _ => None,
+        };
+        let context = checked.as_ref().unwrap_or(context);
 
         let start = std::time::Instant::now();
         let decision = engine.evaluate(action, context);
         let eval_us = start.elapsed().as_micros() as u64;
 
+        // Flag unchecked tools the policy wants to keep an eye on
+        if *action == Action::UnknownTool
+            && engine.policy().unknown_tools == Some(UnknownTools::LogOnly)
+        {
+            let note = "lawctl can't check this tool; allowed by unknown_tools: log_only";
+            version_warning = Some(match version_warning.take() {
+                Some(warning) => format!("{}; {}", warning, note),
+                None => note.to_string(),
+            });
+        }
+
         // Log every decision (best-effort)
-        log_decision(&session_id, action, context, &decision, eval_us);
+        let network = if *action == Action::Network {
+            // The URL is all a web tool sends
+            let sent = Some(context.target.len() as u64).filter(|&n| n > 0);
+            NetworkDetails::from_context(context, engine.network_response_limit_mb(context), sent)
+        } else {
+            None
+        };
+        // An approved plan that names this file or command stands in for
+        // the prompt
+        let preauthorized = matches!(decision, Decision::RequiresApproval { .. })
+            && approved_plan
+                .get_or_insert_with(|| session_plan(&session_id))
+                .as_ref()
+                .is_some_and(|plan| match bash_command(&hook_input) {
+                    Some(command) => plan.covers_command(command),
+                    None => {
+                        matches!(action, Action::Write | Action::Read)
+                            && plan.covers_files(context.all_targets(), workspace)
+                    }
+                });
+
+        if let Some(logger) = logger.as_mut() {
+            let mut entry = decision_entry(
+                &session_id,
+                action,
+                context,
+                &decision,
+                eval_us,
+                network,
+                version_warning.take(),
+            );
+            if preauthorized {
+                entry.approved_by = Some(plan::APPROVED_BY.to_string());
+            }
+            let _ = logger.log(&entry); // Don't fail on log errors
+        }
 
         match &decision {
-            Decision::Denied { reason, .. } => {
+            Decision::Denied {
+                reason,
+                matched_rule,
+            } => denials.push((
+                describe_action(action, &hook_input),
+                reason.clone(),
+                matched_rule.as_deref() == Some(traps::RULE),
+            )),
+            Decision::RequiresApproval { .. } if preauthorized => {
                 eprintln!(
-                    "[lawctl] BLOCKED: {} — {}",
-                    describe_action(action, &hook_input),
-                    reason
+                    "[lawctl] APPROVED: {} — covered by the approved plan",
+                    describe_action(action, &hook_input)
                 );
-                process::exit(2);
             }
             Decision::RequiresApproval { reason, .. } => {
-                let action_desc = describe_action(action, &hook_input);
-                if prompt_native_approval(&action_desc, reason) {
-                    eprintln!("[lawctl] APPROVED: {}", action_desc);
-                    user_approved = true;
-                } else {
-                    eprintln!("[lawctl] DENIED: {} — user declined", action_desc);
-                    process::exit(2);
-                }
+                // Only worth asking where there's a dialog to show it in
+                let reason = match &engine.policy().reviewer {
+                    Some(reviewer) if cfg!(target_os = "macos") => {
+                        let payload = context.command.as_deref().or(context.diff.as_deref());
+                        let review = reviewer
+                            .review(action, &context.display_targets(), payload, reason)
+                            .unwrap_or_else(|e| {
+                                tf("approval.review_failed", &[("error", &format!("{:#}", e))])
+                            });
+                        format!("{}\n\n{} {}", reason, t("approval.review"), review)
+                    }
+                    _ => reason.clone(),
+                };
+                approvals.push((describe_action(action, &hook_input), reason));
             }
             Decision::Allowed { .. } => {
                 // This action is fine — continue checking the others
             }
+            Decision::Logged { .. } => {
+                // Shadow enforcement: the log entry records what strict
+                // mode would have done; the action goes ahead
+            }
+        }
+    }
+
+    // Anything denied blocks the call, whatever else might be approved
+    if !denials.is_empty() {
+        for (action_desc, reason, tripwire) in &denials {
+            eprintln!("[lawctl] BLOCKED: {} — {}", action_desc, reason);
+            if *tripwire {
+                alert_tripwire(reason);
+            }
+        }
+        process::exit(2);
+    }
+
+    // One prompt for everything left that needs approval
+    if !approvals.is_empty() {
+        approvals.dedup();
+        let action_desc = approvals
+            .iter()
+            .map(|(desc, _)| desc.as_str())
+            .collect::<Vec<_>>()
+            .join("; ");
+        let reason = approvals
+            .iter()
+            .map(|(_, reason)| reason.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        if prompt_native_approval(&action_desc, &reason) {
+            eprintln!("[lawctl] APPROVED: {}", action_desc);
+        } else {
+            eprintln!("[lawctl] DENIED: {} — user declined", action_desc);
+            process::exit(2);
         }
     }
 
@@ -156,6 +344,26 @@ fn main() {
     process::exit(0);
 }
 
+/// Tell the human a honeytoken was tripped. The block message goes to the
+/// agent, so on macOS this also raises a notification.
+fn alert_tripwire(reason: &str) {
+    let alert = tf("trap.tripped", &[("reason", &reason)]);
+    eprintln!("[lawctl] {}", alert);
+    if cfg!(target_os = "macos") {
+        let safe_alert = alert
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', " ");
+        let _ = std::process::Command::new("osascript")
+            .arg("-e")
+            .arg(format!(
+                r#"display notification "{}" with title "lawctl" sound name "Basso""#,
+                safe_alert
+            ))
+            .output();
+    }
+}
+
 /// Prompt the user for approval via native OS dialog.
 ///
 /// On macOS: uses osascript to show a native dialog with Approve/Deny buttons.
@@ -185,9 +393,14 @@ fn prompt_macos_dialog(action_desc: &str, reason: &str) -> bool {
         .replace('"', "\\\"")
         .replace('\n', " ");
 
+    let (title, approve, deny) = (
+        t("approval.dialog_title"),
+        t("approval.dialog_approve"),
+        t("approval.dialog_deny"),
+    );
     let script = format!(
-        r#"display dialog "lawctl — Approval Required\n\n{}\n\n{}" buttons {{"Deny", "Approve"}} default button "Deny" with title "lawctl" with icon caution"#,
-        safe_action, safe_reason
+        r#"display dialog "{}\n\n{}\n\n{}" buttons {{"{}", "{}"}} default button "{}" with title "lawctl" with icon caution"#,
+        title, safe_action, safe_reason, deny, approve, deny
     );
 
     match std::process::Command::new("osascript")
@@ -198,7 +411,7 @@ fn prompt_macos_dialog(action_desc: &str, reason: &str) -> bool {
         Ok(output) => {
             if output.status.success() {
                 let button = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                button == "Approve"
+                button == approve
             } else {
                 // User cancelled or error — treat as deny
                 false
@@ -212,7 +425,7 @@ fn prompt_macos_dialog(action_desc: &str, reason: &str) -> bool {
 }
 
 /// Map a Claude Code tool call to lawctl Action(s) + ActionContext.
-/// Returns None for tools we don't need to check (read-only tools).
+/// Returns None for tools we don't need to check (searches, todo lists).
 /// Some tools map to multiple actions (e.g., `rm` is both Delete and RunCmd).
 fn map_tool_to_actions(input: &HookInput) -> Option<Vec<(Action, ActionContext)>> {
     match input.tool_name.as_str() {
@@ -229,6 +442,11 @@ fn map_tool_to_actions(input: &HookInput) -> Option<Vec<(Action, ActionContext)>
                 .unwrap_or("");
 
             let mut ctx = ActionContext::new(file_path);
+            if lockfile::is_lockfile(file_path) {
+                let old = std::fs::read_to_string(file_path).unwrap_or_default();
+                ctx = ctx
+                    .with_new_dependencies(lockfile::added_dependencies(file_path, &old, content));
+            }
             ctx = ctx.with_diff(content.to_string());
             Some(vec![(Action::Write, ctx)])
         }
@@ -246,10 +464,40 @@ fn map_tool_to_actions(input: &HookInput) -> Option<Vec<(Action, ActionContext)>
                 .unwrap_or("");
 
             let mut ctx = ActionContext::new(file_path);
+            if lockfile::is_lockfile(file_path) {
+                // Replay the edit to see the lockfile as it would end up
+                let old = std::fs::read_to_string(file_path).unwrap_or_default();
+                let old_string = input
+                    .tool_input
+                    .get("old_string")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("");
+                let replace_all = input
+                    .tool_input
+                    .get("replace_all")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                let new = if replace_all {
+                    old.replace(old_string, new_string)
+                } else {
+                    old.replacen(old_string, new_string, 1)
+                };
+                ctx =
+                    ctx.with_new_dependencies(lockfile::added_dependencies(file_path, &old, &new));
+            }
             ctx = ctx.with_diff(new_string.to_string());
             Some(vec![(Action::Write, ctx)])
         }
 
+        "Read" => {
+            let file_path = input
+                .tool_input
+                .get("file_path")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown");
+            Some(vec![(Action::Read, ActionContext::new(file_path))])
+        }
+
         "Bash" => {
             let command = input
                 .tool_input
@@ -257,58 +505,197 @@ fn map_tool_to_actions(input: &HookInput) -> Option<Vec<(Action, ActionContext)>
                 .and_then(|v| v.as_str())
                 .unwrap_or("");
 
-            let trimmed = command.trim();
-
-            // Git push → check as GitPush + RunCmd
-            // Check contains() not just starts_with() because Claude often chains:
-            //   git add . && git commit -m "..." && git push origin main
-            if trimmed.starts_with("git push")
-                || trimmed.contains("&& git push")
-                || trimmed.contains("; git push")
-            {
-                // Extract branch from the git push portion
-                let push_part = if let Some(idx) = trimmed.find("git push") {
-                    &trimmed[idx..]
-                } else {
-                    trimmed
-                };
-                let branch = push_part
-                    .strip_prefix("git push")
-                    .unwrap_or("")
-                    .split_whitespace()
-                    .last()
-                    .unwrap_or("main");
-                let cmd_ctx = ActionContext::new("shell").with_command(command.to_string());
-                return Some(vec![
-                    (Action::GitPush, ActionContext::new(branch)),
-                    (Action::RunCmd, cmd_ctx),
-                ]);
-            }
-
-            // rm commands → check as BOTH Delete AND RunCmd
-            // This way `deny: run_cmd if_matches: ["rm -rf *"]` catches it,
-            // AND `deny: delete unless_path: /tmp` also catches it.
-            if trimmed.starts_with("rm ") || trimmed.starts_with("rm -") {
-                let parts: Vec<&str> = trimmed.split_whitespace().collect();
-                let targets: Vec<&str> = parts
-                    .iter()
-                    .skip(1)
-                    .filter(|p| !p.starts_with('-'))
-                    .copied()
-                    .collect();
-
-                let cmd_ctx = ActionContext::new("shell").with_command(command.to_string());
-                let mut actions = vec![(Action::RunCmd, cmd_ctx)];
-
-                if let Some(target) = targets.first() {
-                    actions.push((Action::Delete, ActionContext::new(*target)));
+            // `cat .env`, `head -n 5 key.pem`, `wc -l < .env` → also Read,
+            // so `deny: read` covers the shell as well as the Read tool
+            let reads = files_read(command);
+            let read_action =
+                (!reads.is_empty()).then(|| (Action::Read, ActionContext::with_targets(reads)));
+            // `echo $GITHUB_TOKEN`, `printenv`, `env` → also EnvAccess, one
+            // target per variable so `deny: env_access` can name them
+            let vars = env_reads(command);
+            let env_action = (!vars.is_empty()).then(|| {
+                let names = env_access_targets(vars, std::env::vars().map(|(name, _)| name));
+                (Action::EnvAccess, ActionContext::with_targets(names))
+            });
+            // `npm install x`, `pip install y` → also PackageInstall, one per
+            // package manager, so package allow/deny lists apply
+            let install_actions =
+                package_installs_by_manager(command)
+                    .into_iter()
+                    .map(|(manager, packages)| {
+                        let mut ctx = ActionContext::with_targets(packages.clone())
+                            .with_package_manager(manager);
+                        for package in packages {
+                            ctx = ctx.with_extra("packages", package);
+                        }
+                        (Action::PackageInstall, ctx)
+                    });
+            // `curl https://x.sh | bash`, `ssh host` → also Network, one per
+            // destination, so `unless_domain` covers shell traffic too
+            let network_actions = network_targets(command).into_iter().map(|target| {
+                let ctx = ActionContext::new(&target).with_url(&target);
+                (Action::Network, ctx)
+            });
+            // `ssh host cmd`, `scp`, `rsync host:` → also RemoteExec or
+            // RemoteCopy, one per host, so reaching other machines is a
+            // decision of its own
+            let remote_actions = remote_accesses(command)
+                .into_iter()
+                .map(|access| match access {
+                    RemoteAccess::Exec { host, command } => {
+                        let mut ctx = ActionContext::new(&host).with_domain(&host);
+                        if let Some(command) = command {
+                            ctx = ctx.with_command(command);
+                        }
+                        (Action::RemoteExec, ctx)
+                    }
+                    RemoteAccess::Copy { host } => (
+                        Action::RemoteCopy,
+                        ActionContext::new(&host).with_domain(&host),
+                    ),
+                });
+            // `kubectl delete`, `helm upgrade` → also K8s, with the context
+            // each one talks to, so production clusters can be fenced off
+            let k8s_actions = kube::invocations(command)
+                .into_iter()
+                .map(|mut invocation| {
+                    invocation.context = invocation.context.or_else(kube::current_context);
+                    let target = invocation.context.clone().unwrap_or_default();
+                    let ctx = ActionContext::new(target)
+                        .with_kube(&invocation)
+                        .with_command(command.to_string());
+                    (Action::K8s, ctx)
+                });
+            // `aws s3 rm`, `gcloud compute instances delete` → also Cloud,
+            // with the account each one acts on and whether it reads,
+            // changes or destroys
+            let cloud_actions = cloud::invocations(command)
+                .into_iter()
+                .map(|mut invocation| {
+                    invocation.account = invocation
+                        .account
+                        .or_else(|| cloud::default_account(&invocation.provider));
+                    let target = invocation
+                        .account
+                        .clone()
+                        .unwrap_or_else(|| invocation.provider.clone());
+                    let ctx = ActionContext::new(target)
+                        .with_cloud(&invocation)
+                        .with_command(command.to_string());
+                    (Action::Cloud, ctx)
+                });
+            let side_checks: Vec<_> = read_action
+                .into_iter()
+                .chain(env_action)
+                .chain(install_actions)
+                .chain(network_actions)
+                .chain(remote_actions)
+                .chain(k8s_actions)
+                .chain(cloud_actions)
+                .collect();
+
+            // The command as a whole is a RunCmd. Each of its simple
+            // commands (Claude often chains: `git add . && git commit -m
+            // "..." && git push origin main`) can add more:
+            //   git push → GitPush, for `require_approval: git_push`
+            //   rm       → Delete, so `deny: delete unless_path: /tmp` applies
+            //              as well as `deny: run_cmd if_matches: ["rm -rf *"]`
+            //   mv       → Move, so `deny: move` and the protected-file checks
+            //              see where things go
+            //   chmod    → Chmod, so `deny: chmod` sees the paths and the mode
+            let base = input.cwd.as_deref().map(PathBuf::from).unwrap_or_default();
+            let mut pushes = Vec::new();
+            let mut file_actions = Vec::new();
+            for part in simple_commands(command) {
+                let args = &part.argv[1..];
+                match part.program() {
+                    Some("git") if args.first().is_some_and(|a| a == "push") => {
+                        let named: Vec<&String> =
+                            args[1..].iter().filter(|a| !a.starts_with('-')).collect();
+                        let branch = named.last().map_or("main", |a| a.as_str());
+                        let mut ctx = ActionContext::new(branch).with_branch(branch);
+                        // `git push origin main`: the remote comes first
+                        if named.len() > 1 {
+                            ctx = ctx.with_extra("remote", named[0].as_str());
+                        }
+                        pushes.push((Action::GitPush, ctx));
+                    }
+                    Some("rm") => {
+                        // Same flag parsing as the shim, so `rm -- -file` targets
+                        // `-file`. Unparseable flags fall back to "every non-dash
+                        // argument".
+                        let targets: Vec<String> = match parse_rm_args(args) {
+                            Ok(inv) => inv.operands,
+                            Err(_) => args
+                                .iter()
+                                .filter(|a| !a.starts_with('-'))
+                                .cloned()
+                                .collect(),
+                        };
+                        // Every target is checked — one protected file denies the
+                        // whole rm. Directories are expanded so `rm -rf build/`
+                        // can't take a protected file down with it.
+                        if !targets.is_empty() {
+                            let contained: ContainedPaths = targets
+                                .iter()
+                                .map(|t| collect_contained_paths(&base, t, MAX_CONTAINED_PATHS))
+                                .collect();
+                            let ctx = ActionContext::with_targets(targets)
+                                .with_contained_paths(contained.paths)
+                                .with_contents_truncated(contained.truncated);
+                            file_actions.push((Action::Delete, ctx));
+                        }
+                    }
+                    // Unparseable mv and chmod fail on their own; the RunCmd
+                    // check still applies
+                    Some("mv") => {
+                        if let Ok(inv) = parse_mv_args(args) {
+                            let destination = file_move::destination(
+                                &base,
+                                &inv.destination,
+                                !inv.into_directory,
+                            );
+                            let contained: ContainedPaths = inv
+                                .sources
+                                .iter()
+                                .map(|s| collect_contained_paths(&base, s, MAX_CONTAINED_PATHS))
+                                .collect();
+                            let ctx = ActionContext::with_targets(inv.sources)
+                                .with_destination(destination)
+                                .with_contained_paths(contained.paths)
+                                .with_contents_truncated(contained.truncated);
+                            file_actions.push((Action::Move, ctx));
+                        }
+                    }
+                    Some("chmod") => {
+                        if let Ok(inv) = parse_chmod_args(args) {
+                            let contained: ContainedPaths = if inv.recursive {
+                                inv.paths
+                                    .iter()
+                                    .map(|p| collect_contained_paths(&base, p, MAX_CONTAINED_PATHS))
+                                    .collect()
+                            } else {
+                                ContainedPaths::default()
+                            };
+                            let mut ctx = ActionContext::with_targets(inv.paths)
+                                .with_contained_paths(contained.paths)
+                                .with_contents_truncated(contained.truncated);
+                            if !inv.mode.is_empty() {
+                                ctx = ctx.with_mode(inv.mode);
+                            }
+                            file_actions.push((Action::Chmod, ctx));
+                        }
+                    }
+                    _ => {}
                 }
-                return Some(actions);
             }
 
-            // Normal command → just RunCmd
             let ctx = ActionContext::new("shell").with_command(command.to_string());
-            Some(vec![(Action::RunCmd, ctx)])
+            let mut actions = pushes;
+            actions.push((Action::RunCmd, ctx));
+            actions.extend(file_actions);
+            actions.extend(side_checks);
+            Some(actions)
         }
 
         "WebFetch" | "WebSearch" => {
@@ -317,7 +704,13 @@ fn map_tool_to_actions(input: &HookInput) -> Option<Vec<(Action, ActionContext)>
                 .get("url")
                 .and_then(|v| v.as_str())
                 .unwrap_or("");
-            let ctx = ActionContext::new(url).with_domain(extract_domain(url).unwrap_or_default());
+            // Claude Code's web tools only ever fetch
+            let mut ctx = ActionContext::new(url).with_url(url).with_method("GET");
+            if let Some(ref host) = ctx.domain {
+                let session_id = input.session_id.as_deref().unwrap_or("claude-hook");
+                let total = session_upload_total(session_id, host) + url.len() as u64;
+                ctx = ctx.with_uploaded_bytes(total);
+            }
             Some(vec![(Action::Network, ctx)])
         }
 
@@ -336,32 +729,172 @@ fn map_tool_to_actions(input: &HookInput) -> Option<Vec<(Action, ActionContext)>
             Some(vec![(Action::Write, ctx)])
         }
 
-        // Read-only tools — always allow, no policy check needed
-        "Read" | "Glob" | "Grep" | "Task" | "TodoWrite" | "ExitPlanMode" => None,
+        // Searches and bookkeeping — always allow, no policy check needed
+        "Glob" | "Grep" | "Task" | "TodoWrite" => None,
+
+        // Tools lawctl can't check (MCP tools, new Claude Code tools): the
+        // policy's unknown_tools decides, and the input goes in the log
+        tool => {
+            let ctx = ActionContext::new(tool).with_diff(input.tool_input.to_string());
+            Some(vec![(Action::UnknownTool, ctx)])
+        }
+    }
+}
+
+/// Add what's known about the tool call itself, for `if_context`: the
+/// tool, the agent, the user and, when Claude Code reports them, the model
+/// and permission mode.
+fn with_call_metadata(ctx: ActionContext, input: &HookInput) -> ActionContext {
+    let mut ctx = ctx
+        .with_extra("tool", input.tool_name.as_str())
+        .with_extra("agent", "claude-code");
+    if let Ok(user) = std::env::var("USER") {
+        ctx = ctx.with_extra("user", user);
+    }
+    if let Some(model) = &input.model {
+        ctx = ctx.with_extra("model", model.as_str());
+    }
+    if let Some(mode) = &input.permission_mode {
+        ctx = ctx.with_extra("permission_mode", mode.as_str());
+    }
+    ctx
+}
+
+/// Ask the human to approve a plan's files and commands in one go. An
+/// approved plan goes in the session's log, where later calls find it.
+/// Under a lawctl gateway the gateway asks instead, and keeps the plan for
+/// the actions that go through it. Declining just means the usual prompts;
+/// Claude Code itself still asks whether to start on the plan.
+fn approve_plan(input: &HookInput, session_id: &str, workspace: &Path) {
+    let text = input
+        .tool_input
+        .get("plan")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let plan = Plan::parse(text);
+    if plan.is_empty() {
+        return;
+    }
+    let action_desc = format!(
+        "Pre-approve this plan: {} files, {} commands",
+        plan.files.len(),
+        plan.commands.len()
+    );
+    let approved = match std::env::var_os("LAWCTL_SOCKET").filter(|v| !v.is_empty()) {
+        Some(socket) => GatewayClient::new(socket)
+            .approve_plan(text)
+            .is_ok_and(|response| response.allowed),
+        None => prompt_native_approval(&action_desc, &plan.describe()),
+    };
+    if !approved {
+        eprintln!("[lawctl] Plan not pre-approved — each action will be asked about");
+        return;
+    }
+    eprintln!("[lawctl] APPROVED: {}", action_desc);
+    let entry = LogEntry {
+        timestamp: Utc::now(),
+        session_id: session_id.to_string(),
+        agent: "claude-code".to_string(),
+        action: Action::PlanApproval,
+        target: format!(
+            "{} files, {} commands",
+            plan.files.len(),
+            plan.commands.len()
+        ),
+        policy_rule: None,
+        decision: Decision::RequiresApproval {
+            reason: "Approve the plan's files and commands for this session".to_string(),
+            matched_rule: None,
+        },
+        diff: serde_json::to_string(&plan).ok(),
+        approved_by: Some(plan::APPROVED_BY.to_string()),
+        eval_duration_us: None,
+        network: None,
+        warning: None,
+        output: None,
+        threats: Vec::new(),
+        actor: Actor::Agent,
+        process_chain: Vec::new(),
+    };
+    if let Ok(mut logger) = AuditLogger::new(session_id, workspace) {
+        let _ = logger.log(&entry);
+    }
+}
+
+/// The plan most recently approved this session, if any.
+fn session_plan(session_id: &str) -> Option<Plan> {
+    AuditReader::new()
+        .and_then(|reader| reader.read_session(session_id))
+        .ok()
+        .and_then(|entries| Plan::approved_in(&entries))
+}
 
-        // Unknown tools — allow by default
+/// The shell command behind a Bash call.
+fn bash_command(input: &HookInput) -> Option<&str> {
+    match input.tool_name.as_str() {
+        "Bash" => input.tool_input.get("command").and_then(|v| v.as_str()),
         _ => None,
     }
 }
 
-/// Find .lawctl.yaml walking up from the given directory.
+/// Bytes already sent to `host` this session, from the session's audit log.
+fn session_upload_total(session_id: &str, host: &str) -> u64 {
+    let Ok(reader) = AuditReader::new() else {
+        return 0;
+    };
+    let Ok(entries) = reader.read_session(session_id) else {
+        return 0;
+    };
+    let mut summary = AuditReader::summarize(&entries);
+    summary.add_uploads(&reader.read_session_records(session_id).unwrap_or_default());
+    summary.uploads.get(host).copied().unwrap_or(0)
+}
+
+/// Actions this session has already made, from its audit log.
+fn session_counter(session_id: &str) -> ActionCounter {
+    AuditReader::new()
+        .and_then(|reader| reader.read_session(session_id))
+        .map(|entries| ActionCounter::from_entries(&entries))
+        .unwrap_or_default()
+}
+
+/// Diff lines this session has already written, from its audit log.
+fn session_diffs(session_id: &str) -> DiffBudget {
+    AuditReader::new()
+        .and_then(|reader| reader.read_session(session_id))
+        .map(|entries| DiffBudget::from_entries(&entries))
+        .unwrap_or_default()
+}
+
+/// Files this session has already read, from its audit log.
+fn session_reads(session_id: &str, workspace: &Path) -> ReadFiles {
+    AuditReader::new()
+        .and_then(|reader| reader.read_session(session_id))
+        .map(|entries| ReadFiles::from_entries(&entries, workspace))
+        .unwrap_or_default()
+}
+
+/// Find the project's policy file walking up from the given directory:
+/// the outermost one inside the git repository, since the ones below it
+/// are nested policies it loads itself. Outside a repository, the nearest.
 fn find_policy(start: &Path) -> Option<PathBuf> {
-    let mut dir = start.to_path_buf();
-    loop {
-        let candidate = dir.join(".lawctl.yaml");
-        if candidate.exists() {
-            return Some(candidate);
+    let mut found = Vec::new();
+    for dir in start.ancestors() {
+        if let Some(candidate) = parser::policy_file_in(dir) {
+            found.push(candidate);
         }
-        if !dir.pop() {
-            return None;
+        if dir.join(".git").exists() {
+            return found.pop();
         }
     }
+    // Outside a repository, don't reach past the nearest (into ~, say)
+    found.into_iter().next()
 }
 
 /// Describe what action we're checking (for error messages).
 fn describe_action(action: &Action, input: &HookInput) -> String {
     let target = match input.tool_name.as_str() {
-        "Write" | "Edit" => input
+        "Write" | "Edit" | "Read" => input
             .tool_input
             .get("file_path")
             .and_then(|v| v.as_str())
@@ -380,43 +913,33 @@ fn describe_action(action: &Action, input: &HookInput) -> String {
     format!("{} '{}'", action, target)
 }
 
-/// Extract domain from a URL.
-fn extract_domain(url: &str) -> Option<String> {
-    url.split("://")
-        .nth(1)
-        .and_then(|s| s.split('/').next())
-        .map(|s| s.to_string())
-}
-
-/// Log a decision to the audit log (best-effort).
-fn log_decision(
+/// The audit log entry for a decision.
+fn decision_entry(
     session_id: &str,
     action: &Action,
     context: &ActionContext,
     decision: &Decision,
     eval_us: u64,
-) {
-    let mut logger = match AuditLogger::new(session_id) {
-        Ok(l) => l,
-        Err(_) => return, // Don't fail on log errors
-    };
-
-    let entry = LogEntry {
+    network: Option<NetworkDetails>,
+    warning: Option<String>,
+) -> LogEntry {
+    LogEntry {
         timestamp: chrono::Utc::now(),
         session_id: session_id.to_string(),
         agent: "claude-code".to_string(),
         action: action.clone(),
-        target: context.target.clone(),
-        policy_rule: match decision {
-            Decision::Allowed { matched_rule, .. } => matched_rule.clone(),
-            Decision::Denied { matched_rule, .. } => matched_rule.clone(),
-            Decision::RequiresApproval { matched_rule, .. } => matched_rule.clone(),
-        },
+        target: context.display_targets(),
+        policy_rule: decision.matched_rule().map(String::from),
         decision: decision.clone(),
-        diff: context.diff.clone(),
+        // Like the gateway, which logs the payload: file content or the command
+        diff: context.diff.clone().or_else(|| context.command.clone()),
         approved_by: None,
         eval_duration_us: Some(eval_us),
-    };
-
-    let _ = logger.log(&entry);
+        network,
+        warning,
+        output: None,
+        threats: command_threats(action, context),
+        actor: Actor::Agent,
+        process_chain: Vec::new(),
+    }
 }
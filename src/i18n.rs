@@ -0,0 +1,549 @@
+//! Message catalog for the words lawctl says to humans.
+//!
+//! Setup, the test drive, approval prompts and session summaries go
+//! through `t()`/`tf()`. The language comes from `LAWCTL_LANG`, falling
+//! back to the usual locale variables (`LC_ALL`, `LC_MESSAGES`, `LANG`);
+//! anything without a translation is shown in English.
+//!
+//! Not translated: messages addressed to the agent (`[lawctl] BLOCKED: ...`
+//! on the hook and shim's stderr, which agents read and react to),
+//! policy-written reasons, and log/report formats that tools parse.
+//! Approval keys stay A/D/V in every language.
+
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+/// A language with a catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    Es,
+}
+
+impl Locale {
+    /// Pick a locale from a `LANG`-style value (`es_MX.UTF-8`, `es`, `C`).
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "en" | "c" | "posix" => Some(Locale::En),
+            "es" => Some(Locale::Es),
+            _ => None,
+        }
+    }
+
+    /// The locale from the environment, English if unset or unsupported.
+    fn from_env() -> Self {
+        ["LAWCTL_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::from_tag(&value))
+            .unwrap_or(Locale::En)
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::Es => ES,
+        }
+    }
+}
+
+/// The locale for this process, read once.
+pub fn locale() -> Locale {
+    static LOCALE: OnceLock<Locale> = OnceLock::new();
+    *LOCALE.get_or_init(Locale::from_env)
+}
+
+/// The message for `key` in the current locale.
+pub fn t(key: &'static str) -> &'static str {
+    lookup(locale(), key)
+}
+
+/// The message for `key` with `{name}` placeholders filled in.
+pub fn tf(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    fill(t(key), args)
+}
+
+fn lookup(locale: Locale, key: &'static str) -> &'static str {
+    let find = |catalog: &'static [(&'static str, &'static str)]| {
+        catalog.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+    };
+    find(locale.catalog()).or_else(|| find(EN)).unwrap_or(key)
+}
+
+fn fill(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = template.to_string();
+    for (name, value) in args {
+        out = out.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    out
+}
+
+const EN: &[(&str, &str)] = &[
+    // Setup
+    ("setup.tagline", "— keep your AI agent from breaking things"),
+    (
+        "setup.welcome.intro",
+        "Lawctl watches what your AI agent does and stops it from:",
+    ),
+    ("setup.welcome.deleting", "Deleting important files"),
+    (
+        "setup.welcome.secrets",
+        "Leaking your secrets (.env, API keys, SSH keys)",
+    ),
+    ("setup.welcome.commands", "Running dangerous commands"),
+    ("setup.welcome.pushing", "Pushing code without your OK"),
+    (
+        "setup.welcome.start",
+        "Let's set it up. This takes about 30 seconds.",
+    ),
+    ("setup.already.title", "You're already set up!"),
+    ("setup.already.policy", "Policy file: {path}"),
+    (
+        "setup.already.watching",
+        "Just use your agent normally — lawctl is watching.",
+    ),
+    (
+        "setup.already.reconfigure",
+        "To reconfigure, delete .lawctl.yaml and run {command} again.",
+    ),
+    ("setup.agents.found", "Found on your machine:"),
+    ("setup.agents.question", "Which AI agents are you using?"),
+    ("setup.agents.hint", "(pick one or more, e.g. 1 3)"),
+    ("setup.agents.other", "Something else / not sure"),
+    (
+        "setup.agents.none",
+        "No problem — lawctl works with any agent.",
+    ),
+    ("setup.level.question", "How careful should lawctl be?"),
+    ("setup.level.standard", "Standard"),
+    ("setup.level.recommended", "(recommended)"),
+    (
+        "setup.level.standard.about",
+        "Blocks dangerous stuff, asks before git push",
+    ),
+    ("setup.level.strict", "Strict"),
+    (
+        "setup.level.strict.about",
+        "Locks things down more — for important projects",
+    ),
+    ("setup.level.relaxed", "Relaxed"),
+    (
+        "setup.level.relaxed.about",
+        "Logs everything but doesn't block much — for trying it out",
+    ),
+    ("setup.pick.one", "Pick a number 1-{max}"),
+    (
+        "setup.pick.many",
+        "Pick one or more numbers 1-{max} (e.g. 1 3)",
+    ),
+    (
+        "setup.test_drive.question",
+        "Want a quick test drive? See what lawctl does with a few fake agent actions. [Y/n]",
+    ),
+    ("setup.done.title", "You're protected!"),
+    ("setup.done.will", "Lawctl will now:"),
+    (
+        "setup.done.block_secrets",
+        "Block agents from touching your secrets",
+    ),
+    (
+        "setup.done.block_commands",
+        "Block dangerous shell commands",
+    ),
+    (
+        "setup.done.prevent_deletes",
+        "Stop accidental file deletions",
+    ),
+    ("setup.done.ask_push", "Ask you before any git push"),
+    ("setup.done.log", "Log everything the agent does"),
+    ("setup.done.block_push", "Block all git pushes"),
+    (
+        "setup.done.build_only",
+        "Only allow writes to build output dirs",
+    ),
+    (
+        "setup.done.registries",
+        "Restrict network to package registries",
+    ),
+    (
+        "setup.done.relaxed_note",
+        "  Most actions are allowed — good for getting started.",
+    ),
+    (
+        "setup.done.claude_ready",
+        "That's it. Just use Claude Code normally.",
+    ),
+    (
+        "setup.done.background",
+        "Lawctl runs in the background on every action.",
+    ),
+    (
+        "setup.done.blocked_example",
+        "If lawctl blocks something, you'll see:",
+    ),
+    (
+        "setup.done.run_claude",
+        "To run Claude Code with protection:",
+    ),
+    ("setup.done.cursor", "To use with Cursor:"),
+    (
+        "setup.done.cursor_soon",
+        "Cursor extension coming soon. For now:",
+    ),
+    ("setup.done.run_named", "To run {agent} with protection:"),
+    ("setup.done.run_any", "To run your agent with protection:"),
+    ("setup.done.see_log", "To see what your agent did:"),
+    ("setup.done.saved", "Policy saved to: {path}"),
+    // Test drive
+    ("drive.title", "Test drive — {note}"),
+    ("drive.note", "nothing below actually runs"),
+    ("drive.allowed", "goes ahead, and is logged"),
+    ("drive.blocked", "blocked:"),
+    ("drive.asks", "asks you first:"),
+    (
+        "drive.logged",
+        "allowed, but logged — strict mode would block:",
+    ),
+    (
+        "drive.footer",
+        "That's what you'll see in `lawctl log` once your agent runs.",
+    ),
+    ("drive.edit_code", "Agent edits your code"),
+    (
+        "drive.overwrite_secrets",
+        "Agent overwrites your secrets file",
+    ),
+    ("drive.rm_rf", "Agent runs `rm -rf /`"),
+    (
+        "drive.curl_sh",
+        "Agent pipes a script from the internet into sh",
+    ),
+    ("drive.push_main", "Agent pushes to main"),
+    // Approval prompt
+    ("approval.title", "APPROVAL REQUIRED"),
+    ("approval.action", "Action:"),
+    ("approval.target", "Target:"),
+    ("approval.preview", "Preview:"),
+    ("approval.reason", "Reason:"),
+    ("approval.review", "Reviewer:"),
+    ("approval.review_failed", "(no second opinion: {error})"),
+    ("approval.approve", "[A] Approve"),
+    ("approval.deny", "[D] Deny"),
+    ("approval.view", "[V] View full payload"),
+    ("approval.payload_start", "--- Full payload ---"),
+    ("approval.payload_end", "--- End payload ---"),
+    ("approval.no_payload", "(no payload)"),
+    ("approval.approved", "Approved"),
+    ("approval.denied", "Denied"),
+    ("approval.dialog_title", "lawctl — Approval Required"),
+    ("approval.dialog_approve", "Approve"),
+    ("approval.dialog_deny", "Deny"),
+    (
+        "approval.plain_question",
+        "Approve this action? Type y or n (v shows the full payload), then Enter [y/N]:",
+    ),
+    ("approval.plain_retry", "Please type y or n."),
+    // Session summary
+    ("summary.none", "No actions were logged this session."),
+    ("summary.complete", "Session complete"),
+    (
+        "summary.counts",
+        "{total} actions | {allowed} allowed | {denied} denied | {approved} approved",
+    ),
+    (
+        "summary.rejected",
+        "{count} malformed or unknown gateway requests were rejected",
+    ),
+    (
+        "summary.would_deny",
+        "{count} allowed actions would have been denied by log_only rules",
+    ),
+    ("summary.view_log", "View full log: {command}"),
+    (
+        "summary.anomalies",
+        "Unusual for this project (score {score}, against {sessions} past sessions):",
+    ),
+    // Anomalies against the project's baseline
+    ("anomaly.live", "Unusual for this project: {message}"),
+    ("anomaly.new_area", "{action} in {area} for the first time"),
+    ("anomaly.new_program", "ran {program} for the first time"),
+    ("anomaly.new_host", "contacted {host} for the first time"),
+    (
+        "anomaly.rate",
+        "{count} {action} actions in a minute (at most {peak} before)",
+    ),
+    // Honeytokens
+    (
+        "trap.tripped",
+        "TRIPWIRE — the agent went for a decoy secret: {reason}",
+    ),
+];
+
+const ES: &[(&str, &str)] = &[
+    // Setup
+    ("setup.tagline", "— que tu agente de IA no rompa nada"),
+    (
+        "setup.welcome.intro",
+        "Lawctl vigila lo que hace tu agente de IA y le impide:",
+    ),
+    ("setup.welcome.deleting", "Borrar archivos importantes"),
+    (
+        "setup.welcome.secrets",
+        "Filtrar tus secretos (.env, claves de API, claves SSH)",
+    ),
+    ("setup.welcome.commands", "Ejecutar comandos peligrosos"),
+    ("setup.welcome.pushing", "Subir código sin tu permiso"),
+    (
+        "setup.welcome.start",
+        "Vamos a configurarlo. Tarda unos 30 segundos.",
+    ),
+    ("setup.already.title", "¡Ya está configurado!"),
+    ("setup.already.policy", "Archivo de política: {path}"),
+    (
+        "setup.already.watching",
+        "Usa tu agente como siempre: lawctl está vigilando.",
+    ),
+    (
+        "setup.already.reconfigure",
+        "Para reconfigurarlo, borra .lawctl.yaml y vuelve a ejecutar {command}.",
+    ),
+    ("setup.agents.found", "Encontrados en tu equipo:"),
+    ("setup.agents.question", "¿Qué agentes de IA usas?"),
+    ("setup.agents.hint", "(elige uno o varios, p. ej. 1 3)"),
+    ("setup.agents.other", "Otro / no estoy seguro"),
+    (
+        "setup.agents.none",
+        "Sin problema: lawctl funciona con cualquier agente.",
+    ),
+    ("setup.level.question", "¿Qué tan estricto debe ser lawctl?"),
+    ("setup.level.standard", "Estándar"),
+    ("setup.level.recommended", "(recomendado)"),
+    (
+        "setup.level.standard.about",
+        "Bloquea lo peligroso y pregunta antes de git push",
+    ),
+    ("setup.level.strict", "Estricto"),
+    (
+        "setup.level.strict.about",
+        "Lo cierra todo más: para proyectos importantes",
+    ),
+    ("setup.level.relaxed", "Relajado"),
+    (
+        "setup.level.relaxed.about",
+        "Registra todo pero casi no bloquea: para probarlo",
+    ),
+    ("setup.pick.one", "Elige un número del 1 al {max}"),
+    (
+        "setup.pick.many",
+        "Elige uno o varios números del 1 al {max} (p. ej. 1 3)",
+    ),
+    (
+        "setup.test_drive.question",
+        "¿Una prueba rápida? Mira qué hace lawctl con unas acciones de agente simuladas. [S/n]",
+    ),
+    ("setup.done.title", "¡Estás protegido!"),
+    ("setup.done.will", "Ahora lawctl va a:"),
+    (
+        "setup.done.block_secrets",
+        "Impedir que los agentes toquen tus secretos",
+    ),
+    (
+        "setup.done.block_commands",
+        "Bloquear comandos de shell peligrosos",
+    ),
+    (
+        "setup.done.prevent_deletes",
+        "Frenar borrados accidentales de archivos",
+    ),
+    (
+        "setup.done.ask_push",
+        "Preguntarte antes de cualquier git push",
+    ),
+    ("setup.done.log", "Registrar todo lo que hace el agente"),
+    ("setup.done.block_push", "Bloquear todos los git push"),
+    (
+        "setup.done.build_only",
+        "Permitir escrituras solo en carpetas de compilación",
+    ),
+    (
+        "setup.done.registries",
+        "Limitar la red a registros de paquetes",
+    ),
+    (
+        "setup.done.relaxed_note",
+        "  Casi todo está permitido: ideal para empezar.",
+    ),
+    (
+        "setup.done.claude_ready",
+        "Listo. Usa Claude Code como siempre.",
+    ),
+    (
+        "setup.done.background",
+        "Lawctl actúa en segundo plano en cada acción.",
+    ),
+    (
+        "setup.done.blocked_example",
+        "Si lawctl bloquea algo, verás:",
+    ),
+    (
+        "setup.done.run_claude",
+        "Para ejecutar Claude Code con protección:",
+    ),
+    ("setup.done.cursor", "Para usarlo con Cursor:"),
+    (
+        "setup.done.cursor_soon",
+        "La extensión para Cursor llegará pronto. Por ahora:",
+    ),
+    (
+        "setup.done.run_named",
+        "Para ejecutar {agent} con protección:",
+    ),
+    (
+        "setup.done.run_any",
+        "Para ejecutar tu agente con protección:",
+    ),
+    ("setup.done.see_log", "Para ver qué hizo tu agente:"),
+    ("setup.done.saved", "Política guardada en: {path}"),
+    // Test drive
+    ("drive.title", "Prueba — {note}"),
+    ("drive.note", "nada de esto se ejecuta de verdad"),
+    ("drive.allowed", "sigue adelante y queda registrado"),
+    ("drive.blocked", "bloqueado:"),
+    ("drive.asks", "te pregunta antes:"),
+    (
+        "drive.logged",
+        "permitido, pero registrado — el modo estricto lo bloquearía:",
+    ),
+    (
+        "drive.footer",
+        "Eso es lo que verás en `lawctl log` cuando tu agente trabaje.",
+    ),
+    ("drive.edit_code", "El agente edita tu código"),
+    (
+        "drive.overwrite_secrets",
+        "El agente sobrescribe tu archivo de secretos",
+    ),
+    ("drive.rm_rf", "El agente ejecuta `rm -rf /`"),
+    ("drive.curl_sh", "El agente pasa un script de internet a sh"),
+    ("drive.push_main", "El agente hace push a main"),
+    // Approval prompt
+    ("approval.title", "SE NECESITA TU APROBACIÓN"),
+    ("approval.action", "Acción:"),
+    ("approval.target", "Destino:"),
+    ("approval.preview", "Vista previa:"),
+    ("approval.reason", "Motivo:"),
+    ("approval.review", "Revisor:"),
+    ("approval.review_failed", "(sin segunda opinión: {error})"),
+    ("approval.approve", "[A] Aprobar"),
+    ("approval.deny", "[D] Denegar"),
+    ("approval.view", "[V] Ver contenido completo"),
+    ("approval.payload_start", "--- Contenido completo ---"),
+    ("approval.payload_end", "--- Fin del contenido ---"),
+    ("approval.no_payload", "(sin contenido)"),
+    ("approval.approved", "Aprobado"),
+    ("approval.denied", "Denegado"),
+    (
+        "approval.dialog_title",
+        "lawctl — Se necesita tu aprobación",
+    ),
+    ("approval.dialog_approve", "Aprobar"),
+    ("approval.dialog_deny", "Denegar"),
+    (
+        "approval.plain_question",
+        "¿Aprobar esta acción? Escribe s o n (v muestra el contenido completo) y pulsa Enter [s/N]:",
+    ),
+    ("approval.plain_retry", "Escribe s o n."),
+    // Session summary
+    (
+        "summary.none",
+        "No se registró ninguna acción en esta sesión.",
+    ),
+    ("summary.complete", "Sesión terminada"),
+    (
+        "summary.counts",
+        "{total} acciones | {allowed} permitidas | {denied} denegadas | {approved} aprobadas",
+    ),
+    (
+        "summary.rejected",
+        "Se rechazaron {count} solicitudes al gateway mal formadas o desconocidas",
+    ),
+    (
+        "summary.would_deny",
+        "{count} acciones permitidas habrían sido denegadas por reglas log_only",
+    ),
+    ("summary.view_log", "Ver el registro completo: {command}"),
+    (
+        "summary.anomalies",
+        "Inusual en este proyecto (puntuación {score}, frente a {sessions} sesiones anteriores):",
+    ),
+    // Anomalías frente a lo habitual del proyecto
+    ("anomaly.live", "Inusual en este proyecto: {message}"),
+    ("anomaly.new_area", "{action} en {area} por primera vez"),
+    ("anomaly.new_program", "ejecutó {program} por primera vez"),
+    ("anomaly.new_host", "contactó con {host} por primera vez"),
+    (
+        "anomaly.rate",
+        "{count} acciones {action} en un minuto (antes como mucho {peak})",
+    ),
+    // Honeytokens
+    (
+        "trap.tripped",
+        "ALERTA — el agente fue a por un secreto señuelo: {reason}",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(message: &str) -> Vec<&str> {
+        let mut found: Vec<&str> = message
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn test_catalogs_match() {
+        for (key, message) in ES {
+            let english = EN.iter().find(|(k, _)| k == key);
+            assert!(english.is_some(), "'{}' has no English message", key);
+            assert_eq!(
+                placeholders(message),
+                placeholders(english.unwrap().1),
+                "placeholders differ for '{}'",
+                key
+            );
+        }
+        for (key, _) in EN {
+            assert!(
+                ES.iter().any(|(k, _)| k == key),
+                "'{}' is missing from the Spanish catalog",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_locale_and_lookup() {
+        assert_eq!(Locale::from_tag("es_MX.UTF-8"), Some(Locale::Es));
+        assert_eq!(Locale::from_tag("C"), Some(Locale::En));
+        assert_eq!(Locale::from_tag("fr_FR"), None);
+
+        assert_eq!(lookup(Locale::Es, "approval.deny"), "[D] Denegar");
+        assert_eq!(lookup(Locale::Es, "no.such.key"), "no.such.key");
+        assert_eq!(
+            fill(lookup(Locale::En, "setup.pick.one"), &[("max", &3)]),
+            "Pick a number 1-3"
+        );
+    }
+}
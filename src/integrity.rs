@@ -0,0 +1,467 @@
+//! Integrity self-check — is lawctl still wired up the way it was?
+//!
+//! Protection only works while the pieces are in place. Before a session
+//! starts (and periodically while it runs) this verifies that:
+//!
+//! - the Claude Code hook is still registered, still covers the tools it
+//!   needs to, points at a binary that exists, and hooks aren't disabled
+//! - shim symlinks in `~/.lawctl/shims/` (if any) still point at `lawctl-shim`
+//! - the policy is the one last approved — its hash is recorded per policy
+//!   file in `~/.lawctl/approved-policies.json` (trusted on first use)
+
+use crate::utils::locations;
+use crate::utils::term::glyph;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Tools the hook must see for the policy to be enforced.
+const HOOKED_TOOLS: &[&str] = &["Bash", "Write", "Edit"];
+
+/// How a check came out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// Worth knowing, but protection isn't compromised
+    Warning,
+    /// Protection is off or the policy isn't the approved one
+    Failed,
+}
+
+/// One integrity check's outcome.
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: Status,
+    pub detail: String,
+}
+
+impl Check {
+    pub fn new(name: &'static str, status: Status, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+        }
+    }
+
+    /// One line for the terminal.
+    pub fn display(&self) -> String {
+        let icon = match self.status {
+            Status::Ok => glyph("✓").green().to_string(),
+            Status::Warning => glyph("⚠").yellow().to_string(),
+            Status::Failed => glyph("✗").red().to_string(),
+        };
+        format!("  {} {}: {}", icon, self.name, self.detail)
+    }
+}
+
+/// The outcome of every check.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    pub fn has_failures(&self) -> bool {
+        self.checks.iter().any(|c| c.status == Status::Failed)
+    }
+
+    /// Checks that didn't pass.
+    pub fn problems(&self) -> impl Iterator<Item = &Check> {
+        self.checks.iter().filter(|c| c.status != Status::Ok)
+    }
+
+    /// Whether the policy differs from the approved one.
+    pub fn policy_changed(&self) -> bool {
+        self.checks
+            .iter()
+            .any(|c| c.name == "Policy" && c.status == Status::Failed)
+    }
+}
+
+/// Where the checked pieces live. `Default` is the real locations.
+#[derive(Debug, Clone)]
+pub struct IntegrityPaths {
+    /// Claude Code user settings (where `lawctl setup` installs the hook)
+    pub claude_settings: PathBuf,
+    /// Directory of shim symlinks (`rm` → `lawctl-shim`, ...)
+    pub shim_dir: PathBuf,
+    /// Approved policy hashes
+    pub approvals: PathBuf,
+}
+
+impl Default for IntegrityPaths {
+    fn default() -> Self {
+        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+        let data = locations::data_dir().unwrap_or_else(|_| home.join(".lawctl"));
+        Self {
+            claude_settings: home.join(".claude").join("settings.json"),
+            shim_dir: data.join("shims"),
+            approvals: data.join("approved-policies.json"),
+        }
+    }
+}
+
+/// What to verify for a session.
+#[derive(Debug, Clone)]
+pub struct Expectations {
+    /// The policy file in use
+    pub policy_path: PathBuf,
+    /// `PolicyEngine::policy_hash` of the loaded policy
+    pub policy_hash: String,
+    /// Project root, whose `.claude/` settings can also disable hooks
+    pub workspace: PathBuf,
+    /// Whether the agent relies on the Claude Code hook (a missing hook
+    /// fails the check rather than warning)
+    pub requires_hook: bool,
+}
+
+/// Run every check.
+pub fn verify(paths: &IntegrityPaths, expect: &Expectations) -> Report {
+    Report {
+        checks: vec![
+            check_hook(paths, expect),
+            check_shims(&paths.shim_dir),
+            check_policy(&paths.approvals, &expect.policy_path, &expect.policy_hash),
+        ],
+    }
+}
+
+/// Re-run the checks every `every` while a session runs, printing each
+/// problem once when it appears. The policy is re-read from disk, so an
+/// edit mid-session is reported (the session keeps enforcing the policy
+/// it started with).
+pub async fn watch(paths: IntegrityPaths, mut expect: Expectations, every: std::time::Duration) {
+    let mut reported: Vec<String> = Vec::new();
+    let mut interval = tokio::time::interval(every);
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        match current_policy_hash(&expect.policy_path) {
+            Some(hash) => expect.policy_hash = hash,
+            None => {
+                let problem = format!("{} no longer parses", expect.policy_path.display());
+                if !reported.contains(&problem) {
+                    eprintln!("\n  {} Integrity: {}", glyph("⚠").yellow(), problem);
+                    reported.push(problem);
+                }
+                continue;
+            }
+        }
+        for check in verify(&paths, &expect).problems() {
+            let problem = check.display();
+            if !reported.contains(&problem) {
+                eprintln!("\n{}", problem);
+                reported.push(problem);
+            }
+        }
+    }
+}
+
+/// The hash of the policy file as it is on disk now.
+pub fn current_policy_hash(policy_path: &Path) -> Option<String> {
+    let policy = crate::policy::parser::parse_policy_file(policy_path).ok()?;
+    crate::policy::PolicyEngine::new(policy)
+        .ok()
+        .map(|engine| engine.policy_hash())
+}
+
+/// Record `hash` as the approved version of the policy at `policy_path`.
+pub fn approve_policy(approvals: &Path, policy_path: &Path, hash: &str) -> Result<()> {
+    let mut store = read_approvals(approvals);
+    store.insert(
+        policy_key(policy_path),
+        Approval {
+            hash: hash.to_string(),
+            approved_at: Utc::now(),
+        },
+    );
+    if let Some(parent) = approvals.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let content = serde_json::to_string_pretty(&store)?;
+    std::fs::write(approvals, content)
+        .with_context(|| format!("Failed to write {}", approvals.display()))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Approval {
+    hash: String,
+    approved_at: DateTime<Utc>,
+}
+
+fn read_approvals(path: &Path) -> BTreeMap<String, Approval> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Every policy file that has an approved hash.
+pub fn approved_policies(approvals: &Path) -> Vec<PathBuf> {
+    read_approvals(approvals)
+        .into_keys()
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn policy_key(policy_path: &Path) -> String {
+    policy_path
+        .canonicalize()
+        .unwrap_or_else(|_| policy_path.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn check_policy(approvals: &Path, policy_path: &Path, hash: &str) -> Check {
+    const NAME: &str = "Policy";
+    match read_approvals(approvals).get(&policy_key(policy_path)) {
+        None => match approve_policy(approvals, policy_path, hash) {
+            Ok(()) => Check::new(NAME, Status::Ok, format!("{} (first use, recorded)", hash)),
+            Err(e) => Check::new(
+                NAME,
+                Status::Warning,
+                format!("couldn't record hash: {}", e),
+            ),
+        },
+        Some(approval) if approval.hash == hash => Check::new(NAME, Status::Ok, hash),
+        Some(approval) => Check::new(
+            NAME,
+            Status::Failed,
+            format!(
+                "changed since it was approved on {} ({} → {})",
+                approval.approved_at.format("%Y-%m-%d %H:%M UTC"),
+                approval.hash,
+                hash
+            ),
+        ),
+    }
+}
+
+/// Whether the Claude Code hook is installed and would fire in the workspace.
+pub fn check_hook(paths: &IntegrityPaths, expect: &Expectations) -> Check {
+    const NAME: &str = "Claude Code hook";
+    let missing = if expect.requires_hook {
+        Status::Failed
+    } else {
+        Status::Warning
+    };
+
+    // Project settings can switch every hook off too
+    let project = expect.workspace.join(".claude");
+    for settings in [
+        paths.claude_settings.clone(),
+        project.join("settings.json"),
+        project.join("settings.local.json"),
+    ] {
+        if read_json(&settings).and_then(|s| s.get("disableAllHooks").and_then(|v| v.as_bool()))
+            == Some(true)
+        {
+            return Check::new(
+                NAME,
+                Status::Failed,
+                format!("hooks are disabled in {}", settings.display()),
+            );
+        }
+    }
+
+    let Some(settings) = read_json(&paths.claude_settings) else {
+        return Check::new(NAME, missing, "not installed (run `lawctl setup`)");
+    };
+    let entries = settings
+        .pointer("/hooks/PreToolUse")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let Some((matcher, command)) = entries.iter().find_map(|rule| {
+        let command = rule
+            .get("hooks")?
+            .as_array()?
+            .iter()
+            .filter_map(|h| h.get("command")?.as_str())
+            .find(|c| c.contains("lawctl-hook"))?;
+        let matcher = rule.get("matcher").and_then(|m| m.as_str()).unwrap_or("");
+        Some((matcher.to_string(), command.to_string()))
+    }) else {
+        return Check::new(
+            NAME,
+            missing,
+            "no longer registered in Claude Code settings (run `lawctl setup`)",
+        );
+    };
+
+    let uncovered: Vec<&str> = HOOKED_TOOLS
+        .iter()
+        .copied()
+        .filter(|tool| !matcher_covers(&matcher, tool))
+        .collect();
+    if !uncovered.is_empty() {
+        return Check::new(
+            NAME,
+            Status::Failed,
+            format!(
+                "matcher '{}' no longer covers {}",
+                matcher,
+                uncovered.join(", ")
+            ),
+        );
+    }
+
+    let binary = command.split_whitespace().next().unwrap_or("");
+    if Path::new(binary).is_absolute() && !Path::new(binary).is_file() {
+        return Check::new(
+            NAME,
+            Status::Failed,
+            format!("{} is missing (reinstall lawctl)", binary),
+        );
+    }
+    Check::new(NAME, Status::Ok, "registered")
+}
+
+/// Whether a Claude Code hook matcher (a regex; empty or `*` means all)
+/// matches a tool name.
+fn matcher_covers(matcher: &str, tool: &str) -> bool {
+    if matcher.is_empty() || matcher == "*" {
+        return true;
+    }
+    regex::Regex::new(&format!("^(?:{})$", matcher))
+        .map(|re| re.is_match(tool))
+        .unwrap_or(false)
+}
+
+fn check_shims(shim_dir: &Path) -> Check {
+    const NAME: &str = "Shims";
+    let Ok(entries) = std::fs::read_dir(shim_dir) else {
+        return Check::new(NAME, Status::Ok, "none installed");
+    };
+    let mut count = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        count += 1;
+        let intact = std::fs::read_link(&path).is_ok()
+            && path
+                .canonicalize()
+                .ok()
+                .and_then(|target| target.file_name().map(|n| n == "lawctl-shim"))
+                .unwrap_or(false);
+        if !intact {
+            return Check::new(
+                NAME,
+                Status::Failed,
+                format!("{} no longer points at lawctl-shim", path.display()),
+            );
+        }
+    }
+    Check::new(NAME, Status::Ok, format!("{} intact", count))
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup(tmp: &TempDir, settings: &str) -> (IntegrityPaths, PathBuf) {
+        let paths = IntegrityPaths {
+            claude_settings: tmp.path().join("settings.json"),
+            shim_dir: tmp.path().join("shims"),
+            approvals: tmp.path().join("approved.json"),
+        };
+        std::fs::write(&paths.claude_settings, settings).unwrap();
+        let policy = tmp.path().join(".lawctl.yaml");
+        std::fs::write(&policy, "law: x").unwrap();
+        (paths, policy)
+    }
+
+    fn expect(policy: &Path, hash: &str, workspace: &Path) -> Expectations {
+        Expectations {
+            policy_path: policy.to_path_buf(),
+            policy_hash: hash.to_string(),
+            workspace: workspace.to_path_buf(),
+            requires_hook: true,
+        }
+    }
+
+    const HOOKED: &str = r#"{"hooks": {"PreToolUse": [
+        {"matcher": "Bash|Write|Edit|NotebookEdit", "hooks": [{"type": "command", "command": "lawctl-hook"}]}
+    ]}}"#;
+
+    #[test]
+    fn test_clean_install_passes() {
+        let tmp = TempDir::new().unwrap();
+        let (paths, policy) = setup(&tmp, HOOKED);
+        let report = verify(&paths, &expect(&policy, "abc", tmp.path()));
+        assert!(!report.has_failures(), "{:?}", report);
+        // The first run recorded the hash
+        assert!(verify(&paths, &expect(&policy, "abc", tmp.path()))
+            .problems()
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn test_policy_change_detected_until_approved() {
+        let tmp = TempDir::new().unwrap();
+        let (paths, policy) = setup(&tmp, HOOKED);
+        verify(&paths, &expect(&policy, "abc", tmp.path()));
+
+        let report = verify(&paths, &expect(&policy, "def", tmp.path()));
+        assert!(report.policy_changed());
+        approve_policy(&paths.approvals, &policy, "def").unwrap();
+        assert!(!verify(&paths, &expect(&policy, "def", tmp.path())).has_failures());
+    }
+
+    #[test]
+    fn test_hook_tampering_detected() {
+        let tmp = TempDir::new().unwrap();
+        let (paths, policy) = setup(&tmp, r#"{"hooks": {}}"#);
+        assert!(verify(&paths, &expect(&policy, "abc", tmp.path())).has_failures());
+
+        std::fs::write(&paths.claude_settings, HOOKED.replace("Bash|", "")).unwrap();
+        let report = verify(&paths, &expect(&policy, "abc", tmp.path()));
+        assert!(report
+            .problems()
+            .any(|c| c.detail.contains("no longer covers Bash")));
+
+        std::fs::write(&paths.claude_settings, HOOKED).unwrap();
+        std::fs::create_dir_all(tmp.path().join(".claude")).unwrap();
+        std::fs::write(
+            tmp.path().join(".claude/settings.local.json"),
+            r#"{"disableAllHooks": true}"#,
+        )
+        .unwrap();
+        assert!(verify(&paths, &expect(&policy, "abc", tmp.path())).has_failures());
+    }
+
+    #[test]
+    fn test_redirected_shim_detected() {
+        let tmp = TempDir::new().unwrap();
+        let (paths, policy) = setup(&tmp, HOOKED);
+        std::fs::create_dir_all(&paths.shim_dir).unwrap();
+        std::fs::write(tmp.path().join("lawctl-shim"), "").unwrap();
+        std::os::unix::fs::symlink(tmp.path().join("lawctl-shim"), paths.shim_dir.join("rm"))
+            .unwrap();
+        assert!(!verify(&paths, &expect(&policy, "abc", tmp.path())).has_failures());
+
+        std::fs::write(tmp.path().join("real-rm"), "").unwrap();
+        std::fs::remove_file(paths.shim_dir.join("rm")).unwrap();
+        std::os::unix::fs::symlink(tmp.path().join("real-rm"), paths.shim_dir.join("rm")).unwrap();
+        assert!(verify(&paths, &expect(&policy, "abc", tmp.path())).has_failures());
+    }
+
+    #[test]
+    fn test_matcher_covers() {
+        assert!(matcher_covers("", "Bash"));
+        assert!(matcher_covers("Bash|Write|Edit", "Edit"));
+        assert!(!matcher_covers("Bash|Write|Edit", "NotebookEdit"));
+        assert!(matcher_covers(".*", "Write"));
+    }
+}
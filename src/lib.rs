@@ -11,6 +11,8 @@ pub mod approval;
 pub mod audit;
 pub mod cli;
 pub mod gateway;
+pub mod i18n;
+pub mod integrity;
 pub mod policy;
 pub mod sandbox;
 pub mod utils;
@@ -16,13 +16,18 @@ mod approval;
 mod audit;
 mod cli;
 mod gateway;
+mod i18n;
+mod integrity;
 mod policy;
 mod sandbox;
 mod utils;
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::engine::ArgValueCompleter;
+use clap_complete::env::CompleteEnv;
 use colored::Colorize;
 use std::path::PathBuf;
+use utils::term::glyph;
 
 /// Lawctl — keeps your AI agent from breaking things.
 ///
@@ -41,6 +46,11 @@ use std::path::PathBuf;
                   lawctl log          # see what your agent did"
 )]
 struct Cli {
+    /// Plain output: no colour, ASCII symbols, typed y/n approvals
+    /// (also NO_COLOR or TERM=dumb)
+    #[arg(long, global = true, env = "LAWCTL_PLAIN")]
+    plain: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -48,10 +58,51 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Set up lawctl for your project (interactive wizard)
-    Setup,
+    Setup {
+        /// Don't ask anything: detected agents, standard level
+        #[arg(short, long, env = "LAWCTL_SETUP_YES")]
+        yes: bool,
+
+        /// Protection level
+        #[arg(
+            long,
+            env = "LAWCTL_SETUP_LEVEL",
+            help = "Protection level: standard, strict, relaxed"
+        )]
+        level: Option<String>,
+
+        /// Agents to protect (repeat or comma-separate)
+        #[arg(
+            long = "agent",
+            env = "LAWCTL_SETUP_AGENTS",
+            value_delimiter = ',',
+            help = "Agents: claude-code, cursor, codex, aider, auto, none"
+        )]
+        agents: Vec<String>,
+    },
 
     /// Run your agent with protection (the main command)
     Go {
+        /// Only show denials and the final summary
+        #[arg(short, long, conflicts_with = "verbose")]
+        quiet: bool,
+        /// Show every evaluated action as it happens
+        #[arg(short, long)]
+        verbose: bool,
+        /// Capture a redacted internal trace to debug/<session>.log in the data directory
+        #[arg(long)]
+        debug: bool,
+        /// Start the agent as an unprivileged user (default: lawctl-agent; needs root)
+        #[arg(
+            long,
+            value_name = "USER",
+            num_args = 0..=1,
+            default_missing_value = sandbox::user::DEFAULT_AGENT_USER
+        )]
+        as_user: Option<String>,
+        /// Stop a session already running in this workspace and take over
+        #[arg(long)]
+        force: bool,
         /// The agent command to run (auto-detects if not given)
         #[arg(last = true, help = "The agent command (optional — auto-detects)")]
         command: Vec<String>,
@@ -60,7 +111,12 @@ enum Commands {
     /// See what your agent did
     Log {
         /// Show a specific session
-        #[arg(short, long, help = "Session ID to view")]
+        #[arg(
+            short,
+            long,
+            help = "Session ID to view",
+            add = ArgValueCompleter::new(cli::completions::session_candidates)
+        )]
         session: Option<String>,
 
         /// Filter by action type
@@ -83,9 +139,102 @@ enum Commands {
         #[arg(long, help = "Show only the session summary")]
         summary: bool,
 
-        /// List all available sessions
-        #[arg(long, help = "List all recorded sessions")]
+        /// List recorded sessions
+        #[arg(long, help = "List recorded sessions")]
         list: bool,
+
+        /// Include sessions from every project, not just this one
+        #[arg(long, help = "Include sessions from every project")]
+        all: bool,
+
+        /// Group entries by the process that made each request
+        #[arg(long, help = "Group entries by the requesting process")]
+        by_process: bool,
+    },
+
+    /// Create a policy from another tool's settings
+    Import {
+        #[command(subcommand)]
+        source: ImportSource,
+    },
+
+    /// Turn the policy into another tool's settings
+    Export {
+        #[command(subcommand)]
+        target: ExportTarget,
+    },
+
+    /// Turn a session into a report you can share
+    Report {
+        #[command(subcommand)]
+        format: ReportFormat,
+    },
+
+    /// Work with the policy file
+    Policy {
+        #[command(subcommand)]
+        action: PolicyAction,
+    },
+
+    /// Plant decoy secrets that flag any agent going for them
+    Trap {
+        #[command(subcommand)]
+        action: TrapAction,
+    },
+
+    /// Compare a session from before a policy change with one from after
+    DiffPolicyEffect {
+        /// Session run under the old policy
+        #[arg(add = ArgValueCompleter::new(cli::completions::session_candidates))]
+        before: String,
+
+        /// Session run under the new policy
+        #[arg(add = ArgValueCompleter::new(cli::completions::session_candidates))]
+        after: String,
+
+        /// The new policy, to replay the old session through
+        #[arg(long, default_value = ".lawctl.yaml")]
+        policy: PathBuf,
+    },
+
+    /// Suggest rules from what agents did under a permissive policy
+    Tighten {
+        /// Path to policy file
+        #[arg(default_value = ".lawctl.yaml")]
+        policy: PathBuf,
+
+        /// Add every suggested rule without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Show what's protected and how the last session went
+    Status {
+        /// Every project lawctl knows about on this machine
+        #[arg(long)]
+        global: bool,
+    },
+
+    /// Look for anything that could stop lawctl protecting you
+    Doctor {
+        /// Path to policy file
+        #[arg(default_value = ".lawctl.yaml")]
+        policy: PathBuf,
+    },
+
+    /// Write a bug report with environment info and the last debug trace
+    Bugreport {
+        /// Session to report on (default: the latest `--debug` session)
+        #[arg(
+            short,
+            long,
+            add = ArgValueCompleter::new(cli::completions::session_candidates)
+        )]
+        session: Option<String>,
+
+        /// Where to write the report (default: ./lawctl-bugreport-<time>.md)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
 
     /// Validate your policy file
@@ -93,16 +242,106 @@ enum Commands {
         /// Path to policy file
         #[arg(default_value = ".lawctl.yaml")]
         policy: PathBuf,
+
+        /// Add the rules the suggestions call for to the policy file,
+        /// keeping a backup of the original
+        #[arg(long)]
+        fix: bool,
+
+        /// With --fix, add them without asking
+        #[arg(short, long, requires = "fix")]
+        yes: bool,
+    },
+
+    /// Show how the policy decides an action, rule by rule
+    Explain {
+        /// The action: write, delete, run_cmd, git_push, network...
+        action: String,
+
+        /// What it's on: a path, a branch, a host — or the command line
+        /// for run_cmd, k8s and cloud, the URL for network
+        target: Option<String>,
+
+        /// Command line, for remote_exec (the target is then the host)
+        #[arg(long)]
+        command: Option<String>,
+
+        /// Content being written
+        #[arg(long)]
+        diff: Option<String>,
+
+        /// Where a move goes
+        #[arg(long)]
+        destination: Option<String>,
+
+        /// Path to policy file
+        #[arg(long, default_value = ".lawctl.yaml")]
+        policy: PathBuf,
+
+        /// Print the trace as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Run a policy's unit tests from .lawctl.tests.yaml
+    Test {
+        /// Path to the test file
+        #[arg(default_value = ".lawctl.tests.yaml")]
+        tests: PathBuf,
+
+        /// Policy to test, instead of the one the test file names
+        #[arg(long)]
+        policy: Option<PathBuf>,
+    },
+
+    /// Print a JSON Schema of the policy file format, for editors and CI
+    Schema {
+        /// Output format
+        #[arg(long, default_value = "json-schema")]
+        format: String,
+    },
+
+    /// Update lawctl, lawctl-hook and lawctl-shim to the latest release
+    Update {
+        /// Only check for a newer release
+        #[arg(long)]
+        check: bool,
+
+        /// Install this version instead of the latest (e.g. 0.3.1)
+        #[arg(long, value_name = "VERSION")]
+        to: Option<String>,
+    },
+
+    /// Print a shell completion script (add it to your shell's startup file)
+    Completions {
+        /// bash, zsh, fish, elvish or powershell
+        #[arg(value_parser = ["bash", "zsh", "fish", "elvish", "powershell"])]
+        shell: String,
+    },
+
+    /// Print the manpage
+    Man {
+        /// Write lawctl.1 and a page per command into this directory instead
+        #[arg(long, value_name = "DIR")]
+        out_dir: Option<PathBuf>,
     },
 
     // ── Power user commands (hidden from main help) ──
     /// Create a policy file from a template [advanced]
     #[command(hide = true)]
     Init {
-        #[arg(short, long, default_value = "safe-dev")]
+        #[arg(
+            short,
+            long,
+            default_value = "safe-dev",
+            add = ArgValueCompleter::new(cli::completions::template_candidates)
+        )]
         template: String,
         #[arg(short, long)]
         output: Option<String>,
+        /// Also add lawctl to .devcontainer/devcontainer.json (Codespaces)
+        #[arg(long)]
+        devcontainer: bool,
     },
 
     /// Run an agent with full control over options [advanced]
@@ -116,33 +355,190 @@ enum Commands {
         approval: String,
         #[arg(long, default_value = "agent")]
         agent: String,
+        /// Enforce network rules via a local HTTP(S) proxy (direct mode)
+        #[arg(long)]
+        network_proxy: bool,
+        /// Only show denials and the final summary
+        #[arg(short, long, conflicts_with = "verbose")]
+        quiet: bool,
+        /// Show every evaluated action as it happens
+        #[arg(short, long)]
+        verbose: bool,
+        /// Capture a redacted internal trace to debug/<session>.log in the data directory
+        #[arg(long)]
+        debug: bool,
+        /// Start the agent as an unprivileged user (default: lawctl-agent; needs root)
+        #[arg(
+            long,
+            value_name = "USER",
+            num_args = 0..=1,
+            default_missing_value = sandbox::user::DEFAULT_AGENT_USER,
+            conflicts_with = "docker"
+        )]
+        as_user: Option<String>,
+        /// Stop a session already running in this workspace and take over
+        #[arg(long)]
+        force: bool,
+        /// Turn off a rule for this session, by id or `tag:NAME` (repeatable)
+        #[arg(long = "disable-rule", value_name = "ID")]
+        disable_rule: Vec<String>,
+        /// Log changes made to the workspace outside the agent, as `actor: human`
+        #[arg(long)]
+        watch_workspace: bool,
         #[arg(last = true, required = true)]
         command: Vec<String>,
     },
 }
 
+#[derive(Subcommand)]
+enum ExportTarget {
+    /// Claude Code permission allow/deny entries matching the policy
+    ClaudePermissions {
+        /// Path to policy file
+        #[arg(default_value = ".lawctl.yaml")]
+        policy: PathBuf,
+
+        /// Merge into this project's .claude/settings.json instead of printing
+        #[arg(long)]
+        write: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ImportSource {
+    /// Claude Code's permission allow/ask/deny lists
+    ClaudeSettings {
+        /// Settings file to read (repeatable; default: ~/.claude/settings.json
+        /// and this project's .claude/settings.json and settings.local.json)
+        #[arg(long)]
+        from: Vec<PathBuf>,
+
+        /// Where to write the policy (default: .lawctl.yaml; `-` for stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Replace an existing policy file
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PolicyAction {
+    /// Add the rules newer revisions of its built-in template added
+    UpgradeTemplate {
+        /// Path to policy file
+        #[arg(default_value = ".lawctl.yaml")]
+        policy: PathBuf,
+    },
+
+    /// Show every recorded version of the policy, with what changed
+    History {
+        /// Path to policy file
+        #[arg(default_value = ".lawctl.yaml")]
+        policy: PathBuf,
+
+        /// Show only the most recent versions
+        #[arg(long, short = 'n')]
+        limit: Option<usize>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TrapAction {
+    /// Plant a canary file of fake AWS keys in this project
+    Install {
+        /// Where to plant it, relative to the project
+        #[arg(long, default_value = policy::traps::DEFAULT_PATH)]
+        path: String,
+    },
+    /// List this project's traps
+    List,
+    /// Delete this project's canary files
+    Remove,
+}
+
+#[derive(Subcommand)]
+enum ReportFormat {
+    /// Markdown summary for a PR description
+    #[command(visible_alias = "markdown")]
+    Md {
+        /// Report on a specific session (default: latest)
+        #[arg(
+            short,
+            long,
+            help = "Session ID to report on",
+            add = ArgValueCompleter::new(cli::completions::session_candidates)
+        )]
+        session: Option<String>,
+
+        /// Write the report to a file instead of stdout
+        #[arg(short, long, help = "Output file")]
+        output: Option<PathBuf>,
+    },
+}
+
 #[tokio::main]
 async fn main() {
-    // Set up tracing (only show at RUST_LOG=debug level to keep output clean)
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive("lawctl=warn".parse().unwrap()),
-        )
-        .with_target(false)
-        .without_time()
-        .init();
+    // TAB completion calls back into lawctl; answer and exit
+    CompleteEnv::with_factory(Cli::command)
+        .var(cli::completions::COMPLETE_VAR)
+        .complete();
 
     let cli = Cli::parse();
+    if cli.plain || utils::term::is_plain() {
+        utils::term::set_plain();
+    }
+
+    // Set up tracing (only show at RUST_LOG=debug level to keep output clean).
+    // With --debug the session ID is picked now, so the trace can be named
+    // after it from the first event.
+    let debug_session = match &cli.command {
+        Some(Commands::Go { debug: true, .. }) | Some(Commands::Run { debug: true, .. }) => {
+            Some(uuid::Uuid::new_v4().to_string())
+        }
+        _ => None,
+    };
+    let debug_log = match debug_session.as_deref().map(cli::debug::debug_log_path) {
+        Some(Ok(path)) => Some(path),
+        Some(Err(e)) => {
+            eprintln!("  {} {:#}", glyph("✗").red().bold(), e);
+            std::process::exit(1);
+        }
+        None => None,
+    };
+    if let Err(e) = cli::debug::init_tracing(debug_log.as_deref()) {
+        eprintln!("  {} {:#}", glyph("✗").red().bold(), e);
+        std::process::exit(1);
+    }
 
     let result = match cli.command {
         // ── No subcommand: smart default ──
         None => run_smart_default().await,
 
         // ── User-facing commands ──
-        Some(Commands::Setup) => cli::setup::run_setup(),
+        Some(Commands::Setup { yes, level, agents }) => {
+            cli::setup::run_setup(&cli::setup::SetupOptions { yes, level, agents })
+        }
 
-        Some(Commands::Go { command }) => cli::go::run_go(command).await,
+        Some(Commands::Go {
+            quiet,
+            verbose,
+            debug: _,
+            as_user,
+            force,
+            command,
+        }) => {
+            let options = cli::run::RunOptions {
+                output: cli::run::OutputLevel::from_flags(quiet, verbose),
+                session_id: debug_session,
+                debug_log,
+                as_user,
+                force,
+                ..Default::default()
+            };
+            cli::go::run_go(command, options).await
+        }
 
         Some(Commands::Log {
             session,
@@ -151,9 +547,11 @@ async fn main() {
             limit,
             summary,
             list,
+            all,
+            by_process,
         }) => {
             if list {
-                cli::log::run_log_list()
+                cli::log::run_log_list(all)
             } else {
                 cli::log::run_log(
                     session.as_deref(),
@@ -161,15 +559,139 @@ async fn main() {
                     decision.as_deref(),
                     limit,
                     summary,
+                    all,
+                    by_process,
                 )
             }
         }
 
-        Some(Commands::Check { policy }) => run_check(&policy),
+        Some(Commands::Import { source }) => match source {
+            ImportSource::ClaudeSettings {
+                from,
+                output,
+                force,
+            } => cli::import::run_import_claude_settings(&from, output.as_deref(), force),
+        },
+
+        Some(Commands::Export { target }) => match target {
+            ExportTarget::ClaudePermissions { policy, write } => {
+                cli::export::run_export_claude_permissions(
+                    &policy::parser::locate_policy(&policy),
+                    write,
+                )
+            }
+        },
+
+        Some(Commands::Report { format }) => match format {
+            ReportFormat::Md { session, output } => {
+                cli::report::run_report_markdown(session.as_deref(), output.as_deref())
+            }
+        },
+
+        Some(Commands::Policy { action }) => match action {
+            PolicyAction::UpgradeTemplate { policy } => {
+                cli::template::run_upgrade_template(&policy::parser::locate_policy(&policy))
+            }
+            PolicyAction::History { policy, limit } => {
+                cli::history::run_policy_history(&policy::parser::locate_policy(&policy), limit)
+            }
+        },
+
+        Some(Commands::Trap { action }) => match action {
+            TrapAction::Install { path } => cli::trap::run_trap_install(&path),
+            TrapAction::List => cli::trap::run_trap_list(),
+            TrapAction::Remove => cli::trap::run_trap_remove(),
+        },
+
+        Some(Commands::Explain {
+            action,
+            target,
+            command,
+            diff,
+            destination,
+            policy,
+            json,
+        }) => cli::explain::run_explain(
+            policy::testkit::ActionSpec {
+                action,
+                target,
+                command,
+                diff,
+                destination,
+                ..Default::default()
+            },
+            &policy::parser::locate_policy(&policy),
+            json,
+        ),
+
+        Some(Commands::Test { tests, policy }) => {
+            cli::policy_test::run_policy_tests(&tests, policy.as_deref())
+        }
+
+        Some(Commands::Schema { format }) => cli::schema::run_schema(&format),
+
+        Some(Commands::Check { policy, fix, yes }) => {
+            run_check(&policy::parser::locate_policy(&policy), fix, yes)
+        }
+
+        Some(Commands::DiffPolicyEffect {
+            before,
+            after,
+            policy,
+        }) => cli::policy_effect::run_diff_policy_effect(
+            &before,
+            &after,
+            &policy::parser::locate_policy(&policy),
+        ),
+
+        Some(Commands::Tighten { policy, yes }) => {
+            cli::tighten::run_tighten(&policy::parser::locate_policy(&policy), yes)
+        }
 
         // ── Power user commands ──
-        Some(Commands::Init { template, output }) => {
-            cli::init::run_init(Some(&template), output.as_deref())
+        Some(Commands::Status { global: true }) => cli::status::run_global_status(),
+        Some(Commands::Status { global: false }) => match std::env::current_dir()
+            .ok()
+            .and_then(|cwd| find_policy_walking_up(&cwd))
+        {
+            Some(path) => show_status(&path),
+            None => {
+                println!();
+                println!(
+                    "  lawctl isn't set up here yet — run {} to protect this project.",
+                    "lawctl setup".bold()
+                );
+                println!();
+                Ok(())
+            }
+        },
+
+        Some(Commands::Doctor { policy }) => {
+            cli::doctor::run_doctor(&policy::parser::locate_policy(&policy)).await
+        }
+
+        Some(Commands::Bugreport { session, output }) => {
+            cli::bugreport::run_bugreport(session.as_deref(), output.as_deref())
+        }
+
+        Some(Commands::Completions { shell }) => cli::completions::print_completions(&shell),
+
+        Some(Commands::Man { out_dir }) => {
+            cli::completions::print_man(Cli::command(), out_dir.as_deref())
+        }
+
+        Some(Commands::Update { check, to }) => cli::update::run_update(check, to.as_deref()),
+
+        Some(Commands::Init {
+            template,
+            output,
+            devcontainer,
+        }) => {
+            if devcontainer {
+                cli::devcontainer::run_devcontainer_init(Some(&template), output.as_deref())
+            } else {
+                cli::init::run_init(Some(&template), output.as_deref())
+            }
         }
 
         Some(Commands::Run {
@@ -177,20 +699,36 @@ async fn main() {
             docker,
             approval,
             agent,
+            network_proxy,
+            quiet,
+            verbose,
+            debug: _,
+            as_user,
+            force,
+            disable_rule,
+            watch_workspace,
             command,
         }) => {
             if command.is_empty() {
-                eprintln!("  {} No agent command specified.", "✗".red());
+                eprintln!("  {} No agent command specified.", glyph("✗").red());
                 eprintln!("  Usage: lawctl run -- <agent command>");
                 std::process::exit(1);
             }
 
             let options = cli::run::RunOptions {
-                policy_path: policy,
+                policy_path: policy::parser::locate_policy(&policy),
                 agent_command: command,
                 use_docker: docker,
                 approval_mode: approval,
                 agent_name: agent,
+                network_proxy,
+                output: cli::run::OutputLevel::from_flags(quiet, verbose),
+                session_id: debug_session,
+                debug_log,
+                as_user,
+                force,
+                disabled_rules: disable_rule,
+                watch_workspace,
                 ..Default::default()
             };
 
@@ -200,7 +738,7 @@ async fn main() {
 
     if let Err(e) = result {
         eprintln!();
-        eprintln!("  {} {}", "✗".red().bold(), e);
+        eprintln!("  {} {}", glyph("✗").red().bold(), e);
         for cause in e.chain().skip(1) {
             eprintln!("  {} {}", "caused by:".dimmed(), cause);
         }
@@ -219,7 +757,7 @@ async fn run_smart_default() -> anyhow::Result<()> {
     match policy_path {
         None => {
             // First time — run the wizard
-            cli::setup::run_setup()
+            cli::setup::run_setup(&Default::default())
         }
         Some(path) => {
             // Already set up — show status
@@ -228,12 +766,11 @@ async fn run_smart_default() -> anyhow::Result<()> {
     }
 }
 
-/// Find .lawctl.yaml walking up the directory tree.
+/// Find the policy file walking up the directory tree.
 fn find_policy_walking_up(start: &std::path::Path) -> Option<PathBuf> {
     let mut dir = start.to_path_buf();
     loop {
-        let candidate = dir.join(".lawctl.yaml");
-        if candidate.exists() {
+        if let Some(candidate) = policy::parser::policy_file_in(&dir) {
             return Some(candidate);
         }
         if !dir.pop() {
@@ -252,7 +789,10 @@ fn show_status(policy_path: &std::path::Path) -> anyhow::Result<()> {
         "lawctl".bold(),
         "— your project is protected".green()
     );
-    println!("  {}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".dimmed());
+    println!(
+        "  {}",
+        glyph("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━").dimmed()
+    );
     println!();
     println!(
         "  Policy: {} ({} rules)",
@@ -261,8 +801,28 @@ fn show_status(policy_path: &std::path::Path) -> anyhow::Result<()> {
     );
     println!("  File:   {}", policy_path.display().to_string().dimmed());
 
-    // Show recent activity if any
-    if let Ok(reader) = audit::AuditReader::new() {
+    if let Ok(engine) = policy::PolicyEngine::new(policy.clone()) {
+        let workspace = policy_path.parent().unwrap_or(std::path::Path::new("."));
+        let expectations = integrity::Expectations {
+            policy_path: policy_path.to_path_buf(),
+            policy_hash: engine.policy_hash(),
+            workspace: workspace.to_path_buf(),
+            requires_hook: false,
+        };
+        println!();
+        println!("  {}", "Integrity:".dimmed());
+        let report = integrity::verify(&integrity::IntegrityPaths::default(), &expectations);
+        for check in &report.checks {
+            println!("{}", check.display());
+        }
+        for check in cli::doctor::version_checks(Some(&policy)) {
+            println!("{}", check.display());
+        }
+    }
+
+    // Show this project's recent activity if any
+    let workspace = policy_path.parent().unwrap_or(std::path::Path::new("."));
+    if let Ok(reader) = audit::AuditReader::for_workspace(workspace) {
         if let Ok(entries) = reader.read_latest_session() {
             if !entries.is_empty() {
                 let summary = audit::AuditReader::summarize(&entries);
@@ -273,9 +833,20 @@ fn show_status(policy_path: &std::path::Path) -> anyhow::Result<()> {
                     summary.allowed.to_string().green(),
                     summary.denied.to_string().red(),
                 );
+                for warning in cli::doctor::logged_warnings(&entries) {
+                    println!("  {} {}", glyph("⚠").yellow(), warning);
+                }
             }
         }
     }
+    if let Some(offer) = cli::tighten::offer(&policy, workspace) {
+        println!();
+        println!("  {} {}", glyph("ℹ").blue(), offer);
+    }
+    if let Some(offer) = cli::template::upgrade_offer(&policy) {
+        println!();
+        println!("  {} {}", glyph("ℹ").blue(), offer);
+    }
 
     println!();
     println!("  {}", "Commands:".dimmed());
@@ -288,6 +859,7 @@ fn show_status(policy_path: &std::path::Path) -> anyhow::Result<()> {
         "lawctl log".bold()
     );
     println!("    {}       validate your policy", "lawctl check".bold());
+    println!("    {}      look for problems", "lawctl doctor".bold());
     println!(
         "    {}       reconfigure from scratch",
         "lawctl setup".bold()
@@ -298,18 +870,108 @@ fn show_status(policy_path: &std::path::Path) -> anyhow::Result<()> {
 }
 
 /// Run the `lawctl check` command with linting.
-fn run_check(policy_path: &std::path::Path) -> anyhow::Result<()> {
-    match policy::parser::parse_policy_file(policy_path) {
+/// `lawctl check --fix`: add the rules the linter's suggestions call for
+/// at the top of the policy's `rules:`, after a confirmation and with the
+/// original kept next to it as `<file>.lawctl-backup`.
+fn fix_policy(
+    policy_path: &std::path::Path,
+    warnings: &[policy::linter::LintWarning],
+    yes: bool,
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use std::io::Write;
+
+    let rules = policy::linter::fixes(warnings);
+    if rules.is_empty() {
+        println!("  Nothing for --fix to add.");
+        println!();
+        return Ok(());
+    }
+    if policy::parser::PolicyFormat::of(policy_path) != policy::parser::PolicyFormat::Yaml {
+        anyhow::bail!(
+            "lawctl check --fix only edits YAML policies — add the rules above to {} by hand",
+            policy_path.display()
+        );
+    }
+    let mut editor = policy::editor::PolicyEditor::open(policy_path)?;
+    editor.insert_rules("Added by lawctl check --fix", &rules)?;
+    editor.validate()?;
+
+    println!(
+        "  {} rule{} to add:",
+        rules.len(),
+        if rules.len() == 1 { "" } else { "s" }
+    );
+    for rule in &rules {
+        println!();
+        for line in rule.lines() {
+            println!("    {}", line.cyan());
+        }
+    }
+    println!();
+    if !yes {
+        print!(
+            "  {} Add them to {}? [Y/n] ",
+            "?".cyan().bold(),
+            policy_path.display()
+        );
+        std::io::stdout().flush()?;
+        let mut input = String::new();
+        if std::io::stdin().read_line(&mut input)? == 0 || matches!(input.trim(), "n" | "N" | "no")
+        {
+            println!("  No changes made.");
+            println!();
+            return Ok(());
+        }
+    }
+
+    let mut backup = policy_path.as_os_str().to_owned();
+    backup.push(".lawctl-backup");
+    let backup = PathBuf::from(backup);
+    std::fs::copy(policy_path, &backup)
+        .with_context(|| format!("Failed to back up {}", policy_path.display()))?;
+    editor.save(policy_path)?;
+    println!(
+        "  {} Added {} rule{} to {} (original saved as {})",
+        glyph("✓").green().bold(),
+        rules.len(),
+        if rules.len() == 1 { "" } else { "s" },
+        policy_path.display().to_string().bold(),
+        backup.display()
+    );
+    println!();
+    Ok(())
+}
+
+fn run_check(policy_path: &std::path::Path, fix: bool, yes: bool) -> anyhow::Result<()> {
+    match policy::parser::parse_policy_tree(policy_path) {
         Ok(p) => {
             match policy::PolicyEngine::new(p.clone()) {
-                Ok(_engine) => {
+                Ok(engine) => {
                     println!();
-                    println!("  {} Policy is valid!", "✓".green().bold());
+                    println!("  {} Policy is valid!", glyph("✓").green().bold());
                     println!("  Law:   {}", p.law.cyan());
                     println!("  Rules: {}", p.rules.len());
+                    if !p.included.is_empty() {
+                        println!("  Layers: {}", p.included.join(", ").dimmed());
+                    }
+                    for skipped in &p.skipped_layers {
+                        println!("  {} Nested policy {}", glyph("⚠").yellow(), skipped);
+                    }
+                    println!("  Hash:  {}", engine.policy_hash().dimmed());
                     println!();
+                    let today = chrono::Local::now().date_naive();
                     for (i, rule) in p.rules.iter().enumerate() {
-                        println!("  {}. {}", i + 1, rule.describe());
+                        if rule.is_expired(today) {
+                            println!(
+                                "  {}. {} {}",
+                                i + 1,
+                                rule.describe().dimmed(),
+                                "EXPIRED — not applied".red().bold()
+                            );
+                        } else {
+                            println!("  {}. {}", i + 1, rule.describe());
+                        }
                     }
 
                     // Run the linter
@@ -318,7 +980,7 @@ fn run_check(policy_path: &std::path::Path) -> anyhow::Result<()> {
                         println!();
                         println!(
                             "  {} {} {}:",
-                            "─".repeat(20).dimmed(),
+                            glyph("─").repeat(20).dimmed(),
                             warnings.len(),
                             if warnings.len() == 1 {
                                 "suggestion"
@@ -332,10 +994,18 @@ fn run_check(policy_path: &std::path::Path) -> anyhow::Result<()> {
                         }
                     } else {
                         println!();
-                        println!("  {} No issues found — policy looks solid.", "✓".green());
+                        println!(
+                            "  {} No issues found — policy looks solid.",
+                            glyph("✓").green()
+                        );
                     }
 
                     println!();
+                    println!("{}", policy::score::score_policy(&p).display());
+                    println!();
+                    if fix {
+                        fix_policy(policy_path, &warnings, yes)?;
+                    }
                     Ok(())
                 }
                 Err(e) => Err(e.context("Policy parsed but has invalid glob patterns")),
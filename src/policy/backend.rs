@@ -0,0 +1,54 @@
+//! Rule languages besides lawctl's own `rules:`.
+//!
+//! A backend is consulted first for every target; when none of its rules
+//! apply it returns `None` and the policy's `rules:`, then lawctl's
+//! defaults, decide as usual. Everything around the decision — built-in
+//! protections, approvals, the audit log — is the same whichever rules made
+//! it. The one backend so far is Cedar (`cedar:` / `cedar_file:` in the
+//! policy), compiled in with the `cedar` feature.
+
+use crate::policy::types::{Action, ActionContext, Decision, Policy};
+use anyhow::Result;
+
+/// A source of rule decisions.
+pub trait RuleBackend: Send + Sync {
+    /// The decision for one (normalized) target, or None when none of the
+    /// backend's rules apply to it.
+    fn decide(&self, action: &Action, target: &str, context: &ActionContext) -> Option<Decision>;
+}
+
+/// The backend a policy asks for, if any.
+pub fn from_policy(policy: &Policy) -> Result<Option<Box<dyn RuleBackend>>> {
+    let Some(text) = &policy.cedar else {
+        return Ok(None);
+    };
+    #[cfg(feature = "cedar")]
+    {
+        Ok(Some(Box::new(crate::policy::cedar::CedarBackend::new(
+            text,
+        )?)))
+    }
+    #[cfg(not(feature = "cedar"))]
+    {
+        let _ = text;
+        anyhow::bail!(NO_CEDAR)
+    }
+}
+
+/// Check Cedar policy text parses, so mistakes surface when the policy is
+/// loaded rather than on the first action.
+pub fn validate_cedar(text: &str) -> Result<()> {
+    #[cfg(feature = "cedar")]
+    {
+        crate::policy::cedar::CedarBackend::new(text).map(|_| ())
+    }
+    #[cfg(not(feature = "cedar"))]
+    {
+        let _ = text;
+        anyhow::bail!(NO_CEDAR)
+    }
+}
+
+#[cfg(not(feature = "cedar"))]
+const NO_CEDAR: &str =
+    "This policy uses Cedar, but this lawctl was built without it (install with `--features cedar`)";
@@ -0,0 +1,359 @@
+//! Cedar policies as a rule backend.
+//!
+//! Each action becomes a Cedar request:
+//!
+//! - principal `Agent::"agent"`
+//! - action `Action::"write"`, `"read"`, `"delete"`, `"move"`,
+//!   `"chmod"`, `"run_cmd"`, `"git_push"`, `"network"`, `"env_access"`,
+//!   `"package_install"`, `"remote_exec"`, `"remote_copy"`, `"k8s"`, `"cloud"`
+//! - resource `File::"src/main.rs"` (in `Dir::"src"`, and so on up),
+//!   `Command::"cargo test"` (with a `program` attribute),
+//!   `Host::"github.com"`, `Branch::"main"`, `EnvVar::"AWS_SECRET_ACCESS_KEY"`,
+//!   `Package::"left-pad"` (with a `manager` attribute),
+//!   `Cluster::"prod-eu"` (with `verb` and `namespace` attributes) or
+//!   `CloudAccount::"prod-admin"` (with `provider` and `effect` attributes)
+//! - context `{ target, command?, domain?, scheme?, method?, port?,
+//!   diff_lines?, destination?, mode?, package_manager?, branch? }`
+//!
+//! ```cedar
+//! @reason("Secrets are off limits")
+//! forbid(principal, action == Action::"write", resource)
+//!   when { resource.path like "*.env" };
+//!
+//! @approval("Publishing needs a human")
+//! permit(principal, action == Action::"run_cmd", resource)
+//!   when { resource.program == "cargo" && context.command like "cargo publish*" };
+//!
+//! permit(principal, action == Action::"write", resource in Dir::"src");
+//! ```
+//!
+//! A matching `forbid` denies, a matching `permit` allows (or asks, with an
+//! `@approval` annotation). When neither matches, the request falls through
+//! to the policy's `rules:`. Policies are named by their `@id` annotation.
+
+use crate::policy::backend::RuleBackend;
+use crate::policy::types::{Action, ActionContext, Decision};
+use anyhow::{anyhow, Result};
+use cedar_policy::{
+    Authorizer, Context, Entities, Entity, EntityId, EntityTypeName, EntityUid, PolicyId,
+    PolicySet, Request, RestrictedExpression,
+};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+/// Compiled Cedar policies.
+pub struct CedarBackend {
+    policies: PolicySet,
+    authorizer: Authorizer,
+}
+
+fn uid(type_name: &str, id: &str) -> Result<EntityUid> {
+    let type_name = EntityTypeName::from_str(type_name)
+        .map_err(|e| anyhow!("Invalid Cedar type {}: {}", type_name, e))?;
+    let id = EntityId::from_str(id).map_err(|e| anyhow!("{}", e))?;
+    Ok(EntityUid::from_type_name_and_id(type_name, id))
+}
+
+fn string(value: &str) -> RestrictedExpression {
+    RestrictedExpression::new_string(value.to_string())
+}
+
+/// `File::"a/b/c.rs"` with `Dir::"a/b"` and `Dir::"a"` as ancestors, and
+/// the `Dir` entities themselves.
+fn file_entities(path: &str) -> Result<(EntityUid, Vec<Entity>)> {
+    let mut dirs: Vec<String> = Vec::new();
+    let mut prefix = String::new();
+    let parts: Vec<&str> = path.split('/').collect();
+    for (i, part) in parts[..parts.len().saturating_sub(1)].iter().enumerate() {
+        if i == 0 && part.is_empty() {
+            prefix.push('/');
+            dirs.push("/".to_string());
+            continue;
+        }
+        if !prefix.is_empty() && !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+        prefix.push_str(part);
+        dirs.push(prefix.clone());
+    }
+
+    let mut entities = Vec::new();
+    let mut parent: Option<EntityUid> = None;
+    for dir in &dirs {
+        let dir_uid = uid("Dir", dir)?;
+        entities.push(Entity::new(
+            dir_uid.clone(),
+            HashMap::from([("path".to_string(), string(dir))]),
+            parent.iter().cloned().collect(),
+        ));
+        parent = Some(dir_uid);
+    }
+    let file = uid("File", path)?;
+    entities.push(Entity::new(
+        file.clone(),
+        HashMap::from([("path".to_string(), string(path))]),
+        parent.into_iter().collect::<HashSet<_>>(),
+    ));
+    Ok((file, entities))
+}
+
+impl CedarBackend {
+    pub fn new(text: &str) -> Result<Self> {
+        let policies =
+            PolicySet::from_str(text).map_err(|e| anyhow!("Invalid Cedar policies: {}", e))?;
+        Ok(Self {
+            policies,
+            authorizer: Authorizer::new(),
+        })
+    }
+
+    /// The resource and the entities the request needs.
+    fn resource(
+        action: &Action,
+        target: &str,
+        context: &ActionContext,
+    ) -> Result<(EntityUid, Vec<Entity>)> {
+        let single = |type_name: &str, id: &str, attrs: HashMap<String, RestrictedExpression>| {
+            let resource = uid(type_name, id)?;
+            let entity = Entity::new(resource.clone(), attrs, HashSet::new());
+            Ok((resource, vec![entity]))
+        };
+        match action {
+            Action::Write | Action::Read | Action::Delete | Action::Move | Action::Chmod => {
+                file_entities(target)
+            }
+            Action::RunCmd => {
+                let command = context.command.as_deref().unwrap_or(target);
+                let program = command.split_whitespace().next().unwrap_or("");
+                single(
+                    "Command",
+                    command,
+                    HashMap::from([("program".to_string(), string(program))]),
+                )
+            }
+            Action::Network => {
+                let host = context.domain.as_deref().unwrap_or(target);
+                single("Host", host, HashMap::new())
+            }
+            Action::RemoteExec | Action::RemoteCopy => single("Host", target, HashMap::new()),
+            Action::Cloud => single(
+                "CloudAccount",
+                target,
+                HashMap::from([
+                    (
+                        "provider".to_string(),
+                        string(context.cloud_provider.as_deref().unwrap_or("")),
+                    ),
+                    (
+                        "effect".to_string(),
+                        string(context.cloud_effect.as_deref().unwrap_or("")),
+                    ),
+                ]),
+            ),
+            Action::K8s => single(
+                "Cluster",
+                target,
+                HashMap::from([
+                    (
+                        "verb".to_string(),
+                        string(context.k8s_verb.as_deref().unwrap_or("")),
+                    ),
+                    (
+                        "namespace".to_string(),
+                        string(context.k8s_namespace.as_deref().unwrap_or("")),
+                    ),
+                ]),
+            ),
+            Action::GitPush => single("Branch", target, HashMap::new()),
+            Action::EnvAccess => single("EnvVar", target, HashMap::new()),
+            Action::PackageInstall => single(
+                "Package",
+                target,
+                HashMap::from([(
+                    "manager".to_string(),
+                    string(context.package_manager.as_deref().unwrap_or("")),
+                )]),
+            ),
+            Action::Rejected => single("Request", target, HashMap::new()),
+            Action::UnknownTool => single("Tool", target, HashMap::new()),
+            Action::PlanApproval => single("Plan", target, HashMap::new()),
+            // Rules name it; nothing is ever asked about it
+            Action::Any => single("Request", target, HashMap::new()),
+        }
+    }
+
+    fn context(target: &str, context: &ActionContext) -> Context {
+        let mut pairs = vec![("target".to_string(), string(target))];
+        let strings = [
+            ("command", &context.command),
+            ("domain", &context.domain),
+            ("scheme", &context.scheme),
+            ("method", &context.method),
+            ("destination", &context.destination),
+            ("mode", &context.mode),
+            ("package_manager", &context.package_manager),
+            ("branch", &context.branch),
+        ];
+        for (name, value) in strings {
+            if let Some(value) = value {
+                pairs.push((name.to_string(), string(value)));
+            }
+        }
+        if let Some(port) = context.port {
+            pairs.push((
+                "port".to_string(),
+                RestrictedExpression::new_long(port.into()),
+            ));
+        }
+        if let Some(lines) = context.diff_lines {
+            pairs.push((
+                "diff_lines".to_string(),
+                RestrictedExpression::new_long(lines as i64),
+            ));
+        }
+        Context::from_pairs(pairs)
+    }
+
+    /// `cedar:<@id or policy id>`
+    fn rule_name(&self, id: &PolicyId) -> String {
+        match self.policies.annotation(id, "id") {
+            Some(name) => format!("cedar:{}", name),
+            None => format!("cedar:{}", id),
+        }
+    }
+
+    fn evaluate(
+        &self,
+        action: &Action,
+        target: &str,
+        context: &ActionContext,
+    ) -> Result<Option<Decision>> {
+        let (resource, entities) = Self::resource(action, target, context)?;
+        let request = Request::new(
+            Some(uid("Agent", "agent")?),
+            Some(uid("Action", &action.to_string())?),
+            Some(resource),
+            Self::context(target, context),
+        );
+        let entities = Entities::from_entities(entities).map_err(|e| anyhow!("{}", e))?;
+        let response = self
+            .authorizer
+            .is_authorized(&request, &self.policies, &entities);
+        for error in response.diagnostics().errors() {
+            tracing::warn!("Cedar evaluation error: {}", error);
+        }
+
+        let mut reasons: Vec<&PolicyId> = response.diagnostics().reason().collect();
+        if reasons.is_empty() {
+            return Ok(None);
+        }
+        reasons.sort_by_key(|id| id.to_string());
+        let matched_rule = Some(
+            reasons
+                .iter()
+                .map(|id| self.rule_name(id))
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+        let annotation = |key: &str| {
+            reasons
+                .iter()
+                .find_map(|id| self.policies.annotation(id, key))
+        };
+        Ok(Some(match response.decision() {
+            cedar_policy::Decision::Deny => Decision::Denied {
+                reason: annotation("reason").map(str::to_string).unwrap_or_else(|| {
+                    format!("Forbidden by Cedar policy {}", self.rule_name(reasons[0]))
+                }),
+                matched_rule,
+            },
+            cedar_policy::Decision::Allow => match annotation("approval") {
+                Some(prompt) => Decision::RequiresApproval {
+                    reason: prompt.to_string(),
+                    matched_rule,
+                },
+                None => Decision::Allowed { matched_rule },
+            },
+        }))
+    }
+}
+
+impl RuleBackend for CedarBackend {
+    fn decide(&self, action: &Action, target: &str, context: &ActionContext) -> Option<Decision> {
+        self.evaluate(action, target, context).unwrap_or_else(|e| {
+            tracing::warn!("Couldn't evaluate Cedar policies: {:#}", e);
+            None
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICIES: &str = r#"
+        @id("no-secrets")
+        @reason("Secrets are off limits")
+        forbid(principal, action == Action::"write", resource)
+          when { resource.path like "*.env" };
+
+        permit(principal, action == Action::"write", resource in Dir::"src");
+
+        @approval("Publishing needs a human")
+        permit(principal, action == Action::"run_cmd", resource)
+          when { resource.program == "cargo" && context.command like "cargo publish*" };
+
+        permit(principal, action == Action::"network", resource == Host::"docs.rs");
+    "#;
+
+    fn decide(action: Action, target: &str, context: ActionContext) -> Option<Decision> {
+        CedarBackend::new(POLICIES)
+            .unwrap()
+            .decide(&action, target, &context)
+    }
+
+    #[test]
+    fn test_decisions() {
+        assert!(matches!(
+            decide(
+                Action::Write,
+                "src/deep/main.rs",
+                ActionContext::new("src/deep/main.rs")
+            ),
+            Some(Decision::Allowed { .. })
+        ));
+        assert_eq!(
+            decide(Action::Write, "src/.env", ActionContext::new("src/.env")),
+            Some(Decision::Denied {
+                reason: "Secrets are off limits".to_string(),
+                matched_rule: Some("cedar:no-secrets".to_string()),
+            })
+        );
+        assert!(matches!(
+            decide(
+                Action::RunCmd,
+                "shell",
+                ActionContext::new("shell").with_command("cargo publish --dry-run")
+            ),
+            Some(Decision::RequiresApproval { .. })
+        ));
+        assert!(matches!(
+            decide(
+                Action::Network,
+                "https://docs.rs/x",
+                ActionContext::new("https://docs.rs/x").with_domain("docs.rs")
+            ),
+            Some(Decision::Allowed { .. })
+        ));
+        // Nothing applies: the YAML rules decide
+        assert_eq!(
+            decide(Action::Write, "README.md", ActionContext::new("README.md")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_invalid_policies() {
+        assert!(CedarBackend::new("permit(principal, action, resource").is_err());
+    }
+}
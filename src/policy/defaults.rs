@@ -5,6 +5,8 @@
 //! - `safe-ci`: Stricter — for CI/CD pipelines where no human is watching
 //! - `permissive`: Allow everything but log it all — for trust-building and testing
 
+use crate::policy::types::Policy;
+
 /// Default development policy.
 /// Blocks destructive actions, protects secrets, requires approval for git push.
 /// This is what most vibe coders should start with.
@@ -13,6 +15,7 @@ pub const SAFE_DEV_YAML: &str = r#"# Lawctl Policy: safe-dev
 # Blocks dangerous actions, protects your secrets, and asks before pushing code.
 
 law: safe-dev-v1
+template: safe-dev@2
 
 description: >
   Default safety policy for development. Protects secrets, prevents
@@ -47,10 +50,40 @@ rules:
       - ":(){:|:&};:"
     reason: "Blocked — this command pattern is on the denylist"
 
+  # -- Block miners, reverse shells and fork bombs (built-in detection) --
+  - deny: run_cmd
+    if_threat: [crypto_mining, reverse_shell, resource_abuse]
+    reason: "Blocked — this looks like a crypto miner, reverse shell or resource abuse"
+  - deny: remote_exec
+    if_threat: [crypto_mining, reverse_shell, resource_abuse]
+    reason: "Blocked — this looks like a crypto miner, reverse shell or resource abuse"
+
   # -- Require approval for git operations --
   - require_approval: git_push
     prompt: "The AI agent wants to push code. Review the changes before approving."
 
+  # -- Ask before reaching into other machines (ssh, scp, rsync) --
+  - require_approval: remote_exec
+    prompt: "The AI agent wants to run commands on another machine."
+  - require_approval: remote_copy
+    prompt: "The AI agent wants to copy files to or from another machine."
+
+  # -- Keep agents away from production clusters --
+  - deny: k8s
+    if_context_matches: ["*prod*"]
+    reason: "Production Kubernetes contexts are off limits to agents"
+  - require_approval: k8s
+    if_verb: [apply, delete, patch, replace, scale, drain, install, upgrade, uninstall, rollback]
+    prompt: "The AI agent wants to change a Kubernetes cluster."
+
+  # -- Cloud accounts: never destroy, ask before changing anything --
+  - deny: cloud
+    if_effect: destroy
+    reason: "Deleting cloud resources is off limits to agents"
+  - require_approval: cloud
+    if_effect: mutate
+    prompt: "The AI agent wants to change resources in your cloud account."
+
   # -- Allow writes to common source directories --
   - allow: write
     if_path_matches: ["src/**", "lib/**", "app/**", "pages/**", "components/**", "tests/**", "test/**", "spec/**", "__tests__/**", "docs/**"]
@@ -135,6 +168,7 @@ pub const SAFE_CI_YAML: &str = r#"# Lawctl Policy: safe-ci
 # No human is watching — deny anything risky, allow only build operations.
 
 law: safe-ci-v1
+template: safe-ci@2
 
 description: >
   Strict policy for CI/CD pipelines. Denies all git push operations,
@@ -161,6 +195,8 @@ rules:
       - "curl * | bash"
       - "wget * | sh"
       - "chmod 777 *"
+  - deny: run_cmd
+    if_threat: [crypto_mining, reverse_shell, resource_abuse]
 
   # -- Allow only build/test commands --
   - allow: run_cmd
@@ -193,6 +229,7 @@ pub const PERMISSIVE_YAML: &str = r#"# Lawctl Policy: permissive
 # Switch to safe-dev once you're comfortable.
 
 law: permissive-v1
+template: permissive@1
 
 description: >
   Allow all actions with full logging. Use this to audit what an agent
@@ -216,6 +253,106 @@ rules:
   - allow: network
 "#;
 
+/// Rules each revision of a built-in template added, as YAML list items.
+/// A generated copy records the revision it came from (`template:
+/// safe-dev@2`); `lawctl policy upgrade-template` adds the rules of every
+/// later revision, so the rules a user deleted from their copy stay deleted.
+const TEMPLATE_CHANGES: &[(&str, u32, &[&str])] = &[
+    (
+        "safe-dev",
+        2,
+        &[
+            "- deny: run_cmd\n  if_threat: [crypto_mining, reverse_shell, resource_abuse]\n  reason: \"Blocked — this looks like a crypto miner, reverse shell or resource abuse\"\n",
+            "- deny: remote_exec\n  if_threat: [crypto_mining, reverse_shell, resource_abuse]\n  reason: \"Blocked — this looks like a crypto miner, reverse shell or resource abuse\"\n",
+            "- require_approval: remote_exec\n  prompt: \"The AI agent wants to run commands on another machine.\"\n",
+            "- require_approval: remote_copy\n  prompt: \"The AI agent wants to copy files to or from another machine.\"\n",
+            "- deny: k8s\n  if_context_matches: [\"*prod*\"]\n  reason: \"Production Kubernetes contexts are off limits to agents\"\n",
+            "- require_approval: k8s\n  if_verb: [apply, delete, patch, replace, scale, drain, install, upgrade, uninstall, rollback]\n  prompt: \"The AI agent wants to change a Kubernetes cluster.\"\n",
+            "- deny: cloud\n  if_effect: destroy\n  reason: \"Deleting cloud resources is off limits to agents\"\n",
+            "- require_approval: cloud\n  if_effect: mutate\n  prompt: \"The AI agent wants to change resources in your cloud account.\"\n",
+        ],
+    ),
+    (
+        "safe-ci",
+        2,
+        &["- deny: run_cmd\n  if_threat: [crypto_mining, reverse_shell, resource_abuse]\n"],
+    ),
+];
+
+/// A built-in template revision, written `safe-dev@2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateRef {
+    pub name: &'static str,
+    pub version: u32,
+}
+
+impl TemplateRef {
+    /// Parse `name@version`, naming the template by any of its aliases.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (name, version) = text.trim().split_once('@')?;
+        Some(Self {
+            name: canonical_name(name)?,
+            version: version.parse().ok().filter(|&v| v >= 1)?,
+        })
+    }
+
+    /// The newest revision of this template.
+    pub fn latest(&self) -> Self {
+        let version = TEMPLATE_CHANGES
+            .iter()
+            .filter(|(name, _, _)| *name == self.name)
+            .map(|(_, version, _)| *version)
+            .max()
+            .unwrap_or(1);
+        Self {
+            name: self.name,
+            version,
+        }
+    }
+
+    /// The rules revisions after this one added, oldest first.
+    pub fn rules_added_since(&self) -> Vec<&'static str> {
+        TEMPLATE_CHANGES
+            .iter()
+            .filter(|(name, version, _)| *name == self.name && *version > self.version)
+            .flat_map(|(_, _, rules)| rules.iter().copied())
+            .collect()
+    }
+}
+
+impl std::fmt::Display for TemplateRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.name, self.version)
+    }
+}
+
+/// Which template revision a policy was generated from: its `template:`
+/// line, or for copies made before templates recorded it, revision 1 of
+/// the template its `law:` name (`safe-dev-v1`) comes from.
+pub fn template_of(policy: &Policy) -> Option<TemplateRef> {
+    if let Some(template) = &policy.template {
+        return TemplateRef::parse(template);
+    }
+    available_templates()
+        .into_iter()
+        .find(|(name, _)| policy.law == format!("{}-v1", name))
+        .map(|(name, _)| TemplateRef { name, version: 1 })
+}
+
+/// The template revision a policy was generated from, when a newer one
+/// exists.
+pub fn outdated_template(policy: &Policy) -> Option<TemplateRef> {
+    template_of(policy).filter(|template| template.latest().version > template.version)
+}
+
+fn canonical_name(name: &str) -> Option<&'static str> {
+    let text = get_default_policy(name)?;
+    available_templates()
+        .into_iter()
+        .map(|(name, _)| name)
+        .find(|name| get_default_policy(name) == Some(text))
+}
+
 /// Get the YAML content for a named default policy template.
 pub fn get_default_policy(name: &str) -> Option<&'static str> {
     match name.to_lowercase().as_str() {
@@ -243,3 +380,48 @@ pub fn available_templates() -> Vec<(&'static str, &'static str)> {
         ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::parser::parse_policy_str;
+
+    #[test]
+    fn test_templates_record_their_latest_revision() {
+        for (name, _) in available_templates() {
+            let policy = parse_policy_str(get_default_policy(name).unwrap()).unwrap();
+            let template = template_of(&policy).unwrap();
+            assert_eq!(template, template.latest(), "{}", name);
+
+            // Every rule a revision added is in the template as written
+            let rules: Vec<_> = policy
+                .rules
+                .iter()
+                .map(|r| serde_json::to_value(r).unwrap())
+                .collect();
+            let first = TemplateRef {
+                name: template.name,
+                version: 1,
+            };
+            for rule in first.rules_added_since() {
+                let added = parse_policy_str(&format!("law: x\nrules:\n{}", rule)).unwrap();
+                let added = serde_json::to_value(&added.rules[0]).unwrap();
+                assert!(rules.contains(&added), "{} lacks {}", name, rule);
+            }
+        }
+    }
+
+    #[test]
+    fn test_template_ref() {
+        assert_eq!(
+            TemplateRef::parse("dev@1"),
+            Some(TemplateRef {
+                name: "safe-dev",
+                version: 1
+            })
+        );
+        assert_eq!(TemplateRef::parse("safe-dev@0"), None);
+        assert_eq!(TemplateRef::parse("mine@1"), None);
+        assert_eq!(TemplateRef::parse("safe-dev"), None);
+    }
+}
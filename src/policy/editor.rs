@@ -0,0 +1,252 @@
+//! Comment-preserving edits to YAML policy files.
+//!
+//! Commands that change a user's `.lawctl.yaml` — `lawctl tighten`,
+//! `lawctl policy upgrade-template` — must not throw away their comments,
+//! blank lines or rule order, which a parse-and-reserialize round trip
+//! would. So edits here are targeted text edits: find the lines a change
+//! touches, rewrite only those, and leave every other byte alone. Each
+//! edit is checked by parsing the result before it's saved.
+//!
+//! Only the block style lawctl itself writes is editable (`rules:` on its
+//! own line followed by `- ` items); a flow-style `rules: [...]` is an
+//! error rather than a guess.
+
+use crate::policy::parser::{self, PolicyFormat};
+use crate::policy::Policy;
+use anyhow::{bail, Context, Result};
+use std::ops::Range;
+use std::path::Path;
+
+/// A policy file's text, being edited.
+#[derive(Debug, Clone)]
+pub struct PolicyEditor {
+    lines: Vec<String>,
+    trailing_newline: bool,
+}
+
+impl PolicyEditor {
+    /// Start editing the YAML text of a policy.
+    pub fn new(yaml: &str) -> Self {
+        Self {
+            lines: yaml.lines().map(String::from).collect(),
+            trailing_newline: yaml.is_empty() || yaml.ends_with('\n'),
+        }
+    }
+
+    /// Read a policy file for editing. Only YAML policies can be edited.
+    pub fn open(path: &Path) -> Result<Self> {
+        if PolicyFormat::of(path) != PolicyFormat::Yaml {
+            bail!(
+                "Only YAML policies can be edited automatically, not {}",
+                path.display()
+            );
+        }
+        let yaml = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(Self::new(&yaml))
+    }
+
+    /// The edited text.
+    pub fn text(&self) -> String {
+        let mut text = self.lines.join("\n");
+        if self.trailing_newline && !self.lines.is_empty() {
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Parse the edited text, to check an edit left a valid policy.
+    pub fn validate(&self) -> Result<Policy> {
+        parser::parse_policy_str(&self.text()).context("The edited policy doesn't validate")
+    }
+
+    /// Validate the edited text and write it to `path`.
+    pub fn save(&self, path: &Path) -> Result<Policy> {
+        let policy = self.validate()?;
+        std::fs::write(path, self.text())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(policy)
+    }
+
+    /// Set a top-level `key: value` line, replacing the existing one or
+    /// adding it after the `law:` line.
+    pub fn set_top_level(&mut self, key: &str, value: &str) -> Result<()> {
+        let line = format!("{}: {}", key, value);
+        if let Some(existing) = self.top_level_line(key) {
+            self.lines[existing] = line;
+        } else if let Some(law) = self.top_level_line("law") {
+            self.lines.insert(law + 1, line);
+        } else {
+            bail!("Couldn't find the top-level 'law:' line");
+        }
+        Ok(())
+    }
+
+    /// Add `rules` (YAML list items, `- allow: ...`) at the top of the
+    /// `rules:` list under a `# {note}` comment, indented to match the
+    /// rules already there. At the top, they take precedence over the
+    /// rest since the first matching rule wins.
+    pub fn insert_rules(&mut self, note: &str, rules: &[&str]) -> Result<()> {
+        let start = self.rules_line()?;
+        let pad = " ".repeat(self.rule_indent(start));
+        let mut added = vec![format!("{}# {}", pad, note)];
+        for rule in rules {
+            added.extend(rule.lines().map(|l| format!("{}{}", pad, l)));
+        }
+        added.push(String::new());
+        self.lines.splice(start + 1..start + 1, added);
+        Ok(())
+    }
+
+    /// Replace the rule at `index` (0-based, in file order) with `rule`,
+    /// keeping the comments around it.
+    pub fn replace_rule(&mut self, index: usize, rule: &str) -> Result<()> {
+        let span = self.rule_span(index)?;
+        let pad = " ".repeat(indent_of(&self.lines[span.start]));
+        let replacement: Vec<String> = rule.lines().map(|l| format!("{}{}", pad, l)).collect();
+        self.lines.splice(span, replacement);
+        Ok(())
+    }
+
+    /// Remove the rule at `index` (0-based, in file order), along with the
+    /// comment lines directly above it.
+    pub fn remove_rule(&mut self, index: usize) -> Result<()> {
+        let span = self.rule_span(index)?;
+        let mut start = span.start;
+        while start > 0 && self.lines[start - 1].trim_start().starts_with('#') {
+            start -= 1;
+        }
+        self.lines.drain(start..span.end);
+        Ok(())
+    }
+
+    /// Number of rules in the file's own `rules:` list.
+    pub fn rule_count(&self) -> Result<usize> {
+        Ok(self.rule_spans()?.len())
+    }
+
+    fn top_level_line(&self, key: &str) -> Option<usize> {
+        self.lines.iter().position(|line| {
+            line.strip_prefix(key)
+                .is_some_and(|rest| rest.starts_with(':'))
+        })
+    }
+
+    /// The line of a block-style `rules:` key.
+    fn rules_line(&self) -> Result<usize> {
+        let Some(start) = self.lines.iter().position(|line| {
+            line.strip_prefix("rules:")
+                .is_some_and(|rest| rest.trim().is_empty() || rest.trim().starts_with('#'))
+        }) else {
+            bail!("Couldn't find a block-style 'rules:' list to edit");
+        };
+        Ok(start)
+    }
+
+    /// The indent of the list items under `rules:`, 2 when it's empty.
+    fn rule_indent(&self, start: usize) -> usize {
+        self.lines[start + 1..]
+            .iter()
+            .take_while(|line| is_blank_or_comment(line) || indent_of(line) > 0)
+            .find(|line| line.trim_start().starts_with("- "))
+            .map(|line| indent_of(line))
+            .unwrap_or(2)
+    }
+
+    /// The lines of each rule, from its `- ` to its last non-comment line.
+    fn rule_spans(&self) -> Result<Vec<Range<usize>>> {
+        let start = self.rules_line()?;
+        let indent = self.rule_indent(start);
+        let mut spans: Vec<Range<usize>> = Vec::new();
+        for (i, line) in self.lines.iter().enumerate().skip(start + 1) {
+            if is_blank_or_comment(line) {
+                continue;
+            }
+            let line_indent = indent_of(line);
+            if line_indent < indent {
+                break;
+            }
+            if line_indent == indent && line.trim_start().starts_with('-') {
+                spans.push(i..i + 1);
+            } else if let Some(span) = spans.last_mut() {
+                span.end = i + 1;
+            }
+        }
+        Ok(spans)
+    }
+
+    fn rule_span(&self, index: usize) -> Result<Range<usize>> {
+        let spans = self.rule_spans()?;
+        let count = spans.len();
+        spans
+            .into_iter()
+            .nth(index)
+            .with_context(|| format!("No rule #{} — the file has {} rules", index + 1, count))
+    }
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn is_blank_or_comment(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YAML: &str = "# my policy\nlaw: permissive-v1\n\nrules:   # first match wins\n    # Secrets\n    - deny: write\n      if_path_matches: [\"*.env\"]\n\n    - allow: write\n\nredact_secrets: true\n";
+
+    #[test]
+    fn test_insert_rules_keeps_the_rest_of_the_file() {
+        let mut editor = PolicyEditor::new(YAML);
+        editor
+            .insert_rules("Added by lawctl tighten", &["- deny: delete"])
+            .unwrap();
+        assert_eq!(
+            editor.text(),
+            "# my policy\nlaw: permissive-v1\n\nrules:   # first match wins\n    # Added by lawctl tighten\n    - deny: delete\n\n    # Secrets\n    - deny: write\n      if_path_matches: [\"*.env\"]\n\n    - allow: write\n\nredact_secrets: true\n"
+        );
+        assert_eq!(editor.rule_count().unwrap(), 3);
+        assert!(editor.validate().is_ok());
+
+        let mut flow = PolicyEditor::new("law: x\nrules: []\n");
+        assert!(flow.insert_rules("Added", &["- deny: write"]).is_err());
+    }
+
+    #[test]
+    fn test_replace_and_remove_rules() {
+        let mut editor = PolicyEditor::new(YAML);
+        editor
+            .replace_rule(1, "- allow: write\n  if_path_matches: [\"src/**\"]")
+            .unwrap();
+        assert!(editor.text().contains(
+            "\n    - allow: write\n      if_path_matches: [\"src/**\"]\n\nredact_secrets"
+        ));
+
+        editor.remove_rule(0).unwrap();
+        assert_eq!(
+            editor.text(),
+            "# my policy\nlaw: permissive-v1\n\nrules:   # first match wins\n\n    - allow: write\n      if_path_matches: [\"src/**\"]\n\nredact_secrets: true\n"
+        );
+        assert!(editor.remove_rule(1).is_err());
+        assert_eq!(editor.validate().unwrap().rules.len(), 1);
+    }
+
+    #[test]
+    fn test_set_top_level() {
+        let mut editor = PolicyEditor::new(YAML);
+        editor.set_top_level("template", "permissive@1").unwrap();
+        assert!(editor
+            .text()
+            .starts_with("# my policy\nlaw: permissive-v1\ntemplate: permissive@1\n\n"));
+        editor.set_top_level("redact_secrets", "false").unwrap();
+        assert!(editor.text().ends_with("redact_secrets: false\n"));
+        assert!(PolicyEditor::new("rules:\n")
+            .set_top_level("template", "x@1")
+            .is_err());
+    }
+}
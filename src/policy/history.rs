@@ -0,0 +1,256 @@
+//! Policy provenance — a record of every version of a project's policy.
+//!
+//! Each time lawctl loads a policy whose text differs from the last one it
+//! saw, a copy goes into `policy-history/<project>/` in lawctl's data
+//! directory along with who and what changed it: the git commit and author
+//! when the new version is committed, nothing when it's a local edit (the
+//! case worth a second look — that's what an agent quietly loosening its
+//! own rules looks like), the OS user, and which lawctl command noticed.
+//! `lawctl policy history` lists the versions with the diff between each.
+//!
+//! With `commit_policy_history: true` the history directory is also a git
+//! repository with one commit per version, for `git log -p` and friends.
+
+use crate::utils::locations;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The list of versions, one JSON object per line, oldest first.
+const INDEX_FILE: &str = "index.jsonl";
+
+/// One version of the policy file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Revision {
+    /// When lawctl first saw this version
+    pub recorded_at: DateTime<Utc>,
+    /// First 16 hex digits of the SHA-256 of the file's text
+    pub hash: String,
+    pub file: PathBuf,
+    /// The lawctl command that loaded it (`lawctl run`, `lawctl-hook`)
+    pub via: String,
+    /// The OS user lawctl ran as
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// The commit this version is in, when the file matched `HEAD`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// That commit's author, as "Name <email>"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+}
+
+impl Revision {
+    /// Who the change is attributed to.
+    pub fn changed_by(&self) -> String {
+        match (&self.author, &self.commit) {
+            (Some(author), Some(commit)) => format!("{} in {}", author, commit),
+            _ => format!(
+                "uncommitted edit{}",
+                self.user
+                    .as_deref()
+                    .map(|u| format!(" (seen as {})", u))
+                    .unwrap_or_default()
+            ),
+        }
+    }
+
+    fn snapshot_name(&self) -> String {
+        format!("{}.{}", self.hash, extension(&self.file))
+    }
+}
+
+/// Where a project's policy history is kept.
+pub fn history_dir(workspace: &Path) -> Result<PathBuf> {
+    Ok(locations::data_dir()?
+        .join("policy-history")
+        .join(locations::workspace_key(workspace)))
+}
+
+/// Every recorded version, oldest first. Unreadable lines are skipped.
+pub fn revisions(dir: &Path) -> Vec<Revision> {
+    std::fs::read_to_string(dir.join(INDEX_FILE))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// The text of a recorded version.
+pub fn snapshot(dir: &Path, revision: &Revision) -> Result<String> {
+    let path = dir.join("snapshots").join(revision.snapshot_name());
+    std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read policy snapshot {}", path.display()))
+}
+
+/// Record the policy at `policy_path` if it differs from the last version
+/// in `dir`. Returns the new revision, or None when nothing changed.
+pub fn record(dir: &Path, policy_path: &Path, via: &str, commit: bool) -> Result<Option<Revision>> {
+    let text = std::fs::read_to_string(policy_path)
+        .with_context(|| format!("Failed to read {}", policy_path.display()))?;
+    let hash = text_hash(&text);
+    if revisions(dir).last().is_some_and(|last| last.hash == hash) {
+        return Ok(None);
+    }
+
+    let file = policy_path
+        .canonicalize()
+        .unwrap_or_else(|_| policy_path.to_path_buf());
+    let (commit_id, author) = git_provenance(&file).unzip();
+    let revision = Revision {
+        recorded_at: Utc::now(),
+        hash,
+        file,
+        via: via.to_string(),
+        user: std::env::var("USER").ok().filter(|u| !u.is_empty()),
+        commit: commit_id,
+        author,
+    };
+
+    let snapshots = dir.join("snapshots");
+    std::fs::create_dir_all(&snapshots)
+        .with_context(|| format!("Failed to create {}", snapshots.display()))?;
+    let snapshot = snapshots.join(revision.snapshot_name());
+    if !snapshot.exists() {
+        std::fs::write(&snapshot, &text)
+            .with_context(|| format!("Failed to write {}", snapshot.display()))?;
+    }
+    let mut index = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(INDEX_FILE))
+        .with_context(|| format!("Failed to open the policy history in {}", dir.display()))?;
+    writeln!(index, "{}", serde_json::to_string(&revision)?)?;
+
+    if commit {
+        commit_revision(dir, &revision, &text)?;
+    }
+    Ok(Some(revision))
+}
+
+/// Record a policy load, warning rather than failing: a session shouldn't
+/// stop because its history couldn't be written.
+pub fn record_load(workspace: &Path, policy_path: &Path, via: &str, commit: bool) {
+    let result = history_dir(workspace).and_then(|dir| record(&dir, policy_path, via, commit));
+    if let Err(e) = result {
+        tracing::warn!("Couldn't record the policy history: {:#}", e);
+    }
+}
+
+fn text_hash(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))[..16].to_string()
+}
+
+fn extension(path: &Path) -> &str {
+    path.extension().and_then(|e| e.to_str()).unwrap_or("yaml")
+}
+
+/// The commit (short hash) and author of the last change to a file, when
+/// the file is tracked and unchanged from `HEAD`.
+fn git_provenance(path: &Path) -> Option<(String, String)> {
+    let dir = path.parent().filter(|d| !d.as_os_str().is_empty())?;
+    let name = path.file_name()?;
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .arg("--")
+            .arg(name)
+            .current_dir(dir)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    };
+    // Modified or untracked files show up; committed, unchanged ones don't
+    if !git(&["status", "--porcelain"])?.is_empty() {
+        return None;
+    }
+    let log = git(&["log", "-1", "--format=%h%x09%an <%ae>"])?;
+    let (commit, author) = log.split_once('\t')?;
+    Some((commit.to_string(), author.to_string()))
+}
+
+/// Commit a version to the history directory's own git repository.
+fn commit_revision(dir: &Path, revision: &Revision, text: &str) -> Result<()> {
+    let git = |args: &[&str]| -> Result<()> {
+        let output = Command::new("git")
+            .args([
+                "-c",
+                "user.name=lawctl",
+                "-c",
+                "user.email=lawctl@localhost",
+            ])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .context("Failed to run git")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    };
+    if !dir.join(".git").exists() {
+        git(&["init", "-q"])?;
+    }
+    let name = format!("policy.{}", extension(&revision.file));
+    std::fs::write(dir.join(&name), text)
+        .with_context(|| format!("Failed to write {}", dir.join(&name).display()))?;
+    git(&["add", &name])?;
+    git(&[
+        "commit",
+        "-q",
+        "--allow-empty",
+        "-m",
+        &format!(
+            "Policy {} ({}, via {})",
+            revision.hash,
+            revision.changed_by(),
+            revision.via
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_records_only_changes() {
+        let project = TempDir::new().unwrap();
+        let history = TempDir::new().unwrap();
+        let policy = project.path().join(".lawctl.yaml");
+
+        std::fs::write(&policy, "law: a\nrules:\n  - allow: write\n").unwrap();
+        let first = record(history.path(), &policy, "lawctl run", false)
+            .unwrap()
+            .unwrap();
+        assert!(record(history.path(), &policy, "lawctl-hook", false)
+            .unwrap()
+            .is_none());
+
+        std::fs::write(&policy, "law: a\nrules:\n  - deny: write\n").unwrap();
+        let second = record(history.path(), &policy, "lawctl-hook", false)
+            .unwrap()
+            .unwrap();
+        assert_ne!(first.hash, second.hash);
+        // Not in a git repository, so it's nobody's commit
+        assert!(second.commit.is_none());
+        assert!(second.changed_by().starts_with("uncommitted edit"));
+
+        let revisions = revisions(history.path());
+        assert_eq!(revisions, [first, second]);
+        assert_eq!(
+            snapshot(history.path(), &revisions[1]).unwrap(),
+            "law: a\nrules:\n  - deny: write\n"
+        );
+    }
+}
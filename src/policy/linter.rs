@@ -5,9 +5,11 @@
 //! - Rules that may conflict with each other
 //! - Common patterns that vibe coders forget
 //!
-//! This is the "are you sure your policy is good?" check.
+//! This is the "are you sure your policy is good?" check. Gaps a missing
+//! rule explains come with that rule, which `lawctl check --fix` adds.
 
 use crate::policy::types::*;
+use crate::utils::paths::EXCLUDE_PREFIX;
 use colored::Colorize;
 
 /// A lint warning — something the user should know about their policy.
@@ -16,6 +18,9 @@ pub struct LintWarning {
     pub severity: Severity,
     pub message: String,
     pub suggestion: Option<String>,
+    /// The rule that closes the gap, as a YAML list item (`- deny: ...`),
+    /// for `lawctl check --fix` to add
+    pub rule: Option<String>,
 }
 
 #[derive(Debug)]
@@ -32,6 +37,7 @@ impl LintWarning {
             severity: Severity::Warning,
             message: msg.into(),
             suggestion: None,
+            rule: None,
         }
     }
 
@@ -40,6 +46,7 @@ impl LintWarning {
             severity: Severity::Warning,
             message: msg.into(),
             suggestion: Some(fix.into()),
+            rule: None,
         }
     }
 
@@ -48,9 +55,25 @@ impl LintWarning {
             severity: Severity::Info,
             message: msg.into(),
             suggestion: None,
+            rule: None,
         }
     }
 
+    fn info_with_fix(msg: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Info,
+            message: msg.into(),
+            suggestion: Some(fix.into()),
+            rule: None,
+        }
+    }
+
+    /// Mark the warning as fixed by adding `rule`.
+    fn fixed_by(mut self, rule: &str) -> Self {
+        self.rule = Some(rule.to_string());
+        self
+    }
+
     /// Format for terminal output.
     pub fn display(&self) -> String {
         let icon = match self.severity {
@@ -70,16 +93,56 @@ pub fn lint_policy(policy: &Policy) -> Vec<LintWarning> {
     let mut warnings = Vec::new();
 
     check_secrets_protection(policy, &mut warnings);
+    check_secret_content(policy, &mut warnings);
     check_delete_protection(policy, &mut warnings);
     check_dangerous_commands(policy, &mut warnings);
     check_git_protection(policy, &mut warnings);
     check_network_rules(policy, &mut warnings);
     check_rule_ordering(policy, &mut warnings);
     check_catch_all(policy, &mut warnings);
+    check_template_version(policy, &mut warnings);
+    check_expired_rules(policy, chrono::Local::now().date_naive(), &mut warnings);
 
     warnings
 }
 
+/// The rules that fix `warnings`, in order.
+pub fn fixes(warnings: &[LintWarning]) -> Vec<&str> {
+    warnings.iter().filter_map(|w| w.rule.as_deref()).collect()
+}
+
+/// Check: are temporary rules past their `expires` date still in the file?
+fn check_expired_rules(policy: &Policy, today: chrono::NaiveDate, warnings: &mut Vec<LintWarning>) {
+    for (i, rule) in policy.rules.iter().enumerate() {
+        if rule.is_expired(today) {
+            warnings.push(LintWarning::warn_with_fix(
+                format!(
+                    "Rule {} ({}) has expired and is no longer applied",
+                    i + 1,
+                    rule.describe()
+                ),
+                "Remove the rule, or move its 'expires' date if it's still needed",
+            ));
+        }
+    }
+}
+
+/// Check: was the policy generated from an older revision of a built-in
+/// template?
+fn check_template_version(policy: &Policy, warnings: &mut Vec<LintWarning>) {
+    if let Some(template) = crate::policy::defaults::outdated_template(policy) {
+        warnings.push(LintWarning::info_with_fix(
+            format!(
+                "Generated from {} v{} — v{} adds protections your copy doesn't have.",
+                template.name,
+                template.version,
+                template.latest().version
+            ),
+            "Run `lawctl policy upgrade-template` to add them, keeping your changes",
+        ));
+    }
+}
+
 /// Check: does the policy protect secrets files?
 fn check_secrets_protection(policy: &Policy, warnings: &mut Vec<LintWarning>) {
     let has_secrets_deny = policy.rules.iter().any(|rule| {
@@ -89,7 +152,7 @@ fn check_secrets_protection(policy: &Policy, warnings: &mut Vec<LintWarning>) {
         {
             *action == Action::Write
                 && conditions.if_path_matches.iter().any(|p| {
-                    p.contains(".env")
+                    !p.starts_with(EXCLUDE_PREFIX) && p.contains(".env")
                         || p.contains(".ssh")
                         || p.contains(".pem")
                         || p.contains(".key")
@@ -100,10 +163,38 @@ fn check_secrets_protection(policy: &Policy, warnings: &mut Vec<LintWarning>) {
     });
 
     if !has_secrets_deny {
-        warnings.push(LintWarning::warn_with_fix(
-            "No rule protects secrets files (.env, .ssh, .pem, .key)",
-            "Add: deny: write, if_path_matches: [\"*.env\", \".ssh/*\", \"*.pem\", \"*.key\"]",
-        ));
+        warnings.push(
+            LintWarning::warn_with_fix(
+                "No rule protects secrets files (.env, .ssh, .pem, .key)",
+                "Add: deny: write, if_path_matches: [\"*.env\", \".ssh/*\", \"*.pem\", \"*.key\"]",
+            )
+            .fixed_by(
+                "- deny: write\n  if_path_matches: [\"*.env\", \".ssh/*\", \"*.pem\", \"*.key\"]\n  reason: \"Secrets files are off-limits\"",
+            ),
+        );
+    }
+}
+
+/// Check: does the policy stop secrets being written into ordinary files?
+fn check_secret_content(policy: &Policy, warnings: &mut Vec<LintWarning>) {
+    let scans_content = policy.rules.iter().any(|rule| {
+        matches!(
+            rule,
+            Rule::Deny {
+                action: Action::Write,
+                ..
+            }
+        ) && !rule.conditions().if_content_matches.is_empty()
+    });
+
+    if !scans_content {
+        warnings.push(
+            LintWarning::info_with_fix(
+                "No rule scans written content for secrets — an agent could paste a key into any source file",
+                "Add: deny: write, if_content_matches: [\"@secrets\"]",
+            )
+            .fixed_by("- deny: write\n  if_content_matches: [\"@secrets\"]"),
+        );
     }
 }
 
@@ -112,13 +203,16 @@ fn check_delete_protection(policy: &Policy, warnings: &mut Vec<LintWarning>) {
     let has_delete_rule = policy
         .rules
         .iter()
-        .any(|rule| *rule.action() == Action::Delete);
+        .any(|rule| rule.applies_to(&Action::Delete));
 
     if !has_delete_rule {
-        warnings.push(LintWarning::warn_with_fix(
-            "No rules for file deletion — destructive deletes will be denied by default, but an explicit rule is clearer",
-            "Add: deny: delete, unless_path: /tmp",
-        ));
+        warnings.push(
+            LintWarning::warn_with_fix(
+                "No rules for file deletion — destructive deletes will be denied by default, but an explicit rule is clearer",
+                "Add: deny: delete, unless_path: /tmp",
+            )
+            .fixed_by("- deny: delete\n  unless_path: /tmp"),
+        );
     }
 }
 
@@ -136,10 +230,13 @@ fn check_dangerous_commands(policy: &Policy, warnings: &mut Vec<LintWarning>) {
     });
 
     if !has_cmd_deny {
-        warnings.push(LintWarning::warn_with_fix(
-            "No command denylist — agents could run dangerous shell commands",
-            "Add: deny: run_cmd, if_matches: [\"rm -rf *\", \"curl * | bash\"]",
-        ));
+        warnings.push(
+            LintWarning::warn_with_fix(
+                "No command denylist — agents could run dangerous shell commands",
+                "Add: deny: run_cmd, if_matches: [\"rm -rf *\", \"curl * | bash\"]",
+            )
+            .fixed_by("- deny: run_cmd\n  if_matches: [\"rm -rf *\", \"curl * | bash\"]"),
+        );
     }
 }
 
@@ -148,13 +245,16 @@ fn check_git_protection(policy: &Policy, warnings: &mut Vec<LintWarning>) {
     let has_git_rule = policy
         .rules
         .iter()
-        .any(|rule| *rule.action() == Action::GitPush);
+        .any(|rule| rule.applies_to(&Action::GitPush));
 
     if !has_git_rule {
-        warnings.push(LintWarning::warn_with_fix(
-            "No rule for git push — pushes will be denied by default (it's destructive)",
-            "Add: require_approval: git_push (recommended) or deny: git_push",
-        ));
+        warnings.push(
+            LintWarning::warn_with_fix(
+                "No rule for git push — pushes will be denied by default (it's destructive)",
+                "Add: require_approval: git_push (recommended) or deny: git_push",
+            )
+            .fixed_by("- require_approval: git_push"),
+        );
     }
 }
 
@@ -163,13 +263,26 @@ fn check_network_rules(policy: &Policy, warnings: &mut Vec<LintWarning>) {
     let has_network_rule = policy
         .rules
         .iter()
-        .any(|rule| *rule.action() == Action::Network);
+        .any(|rule| rule.applies_to(&Action::Network));
 
     if !has_network_rule {
         warnings.push(LintWarning::info(
             "No network rules — network access is allowed by default. Consider adding domain restrictions for sensitive environments.",
         ));
     }
+
+    for (i, rule) in policy.rules.iter().enumerate() {
+        for entry in &rule.conditions().unless_domain {
+            if let Some(reason) = crate::utils::domain::suspicious_reason(entry) {
+                warnings.push(LintWarning::warn(format!(
+                    "Rule {}: unless_domain entry '{}' {}",
+                    i + 1,
+                    entry,
+                    reason
+                )));
+            }
+        }
+    }
 }
 
 /// Check for rule ordering issues.
@@ -251,6 +364,20 @@ rules:
         );
     }
 
+    #[test]
+    fn test_lint_flags_expired_rules() {
+        let yaml = "law: t\nrules:\n  - allow: git_push\n    expires: 2025-07-01\n  - deny: delete\n    expires: 2025-08-01\n";
+        let policy = parser::parse_policy_str(yaml).unwrap();
+        let mut warnings = Vec::new();
+        check_expired_rules(
+            &policy,
+            chrono::NaiveDate::from_ymd_opt(2025, 7, 15).unwrap(),
+            &mut warnings,
+        );
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.starts_with("Rule 1 (allow:git_push"));
+    }
+
     #[test]
     fn test_lint_safe_dev_minimal_warnings() {
         let yaml = crate::policy::defaults::SAFE_DEV_YAML;
@@ -287,4 +414,53 @@ rules:
             .any(|w| w.message.contains("first match wins"));
         assert!(has_order_warning, "Should warn about rule ordering");
     }
+
+    #[test]
+    fn test_lint_secret_content() {
+        let recommends = |yaml: &str| {
+            lint_policy(&parser::parse_policy_str(yaml).unwrap())
+                .iter()
+                .any(|w| w.message.contains("scans written content"))
+        };
+        assert!(recommends("law: t\nrules:\n  - allow: write\n"));
+        assert!(!recommends(
+            "law: t\nrules:\n  - deny: write\n    if_content_matches: [\"@secrets\"]\n"
+        ));
+    }
+
+    #[test]
+    fn test_fixes_close_the_gaps() {
+        let yaml = "law: t\nrules:\n  - allow: write\n  - allow: run_cmd\n";
+        let warnings = lint_policy(&parser::parse_policy_str(yaml).unwrap());
+        let rules = fixes(&warnings);
+        assert_eq!(rules.len(), 5);
+
+        let mut editor = crate::policy::editor::PolicyEditor::new(yaml);
+        editor
+            .insert_rules("Added by lawctl check --fix", &rules)
+            .unwrap();
+        let fixed = editor.validate().unwrap();
+        assert_eq!(fixed.rules.len(), 7);
+        assert!(fixes(&lint_policy(&fixed)).is_empty());
+    }
+
+    #[test]
+    fn test_lint_suspicious_domains() {
+        let yaml = r#"
+law: domains
+rules:
+  - deny: network
+    unless_domain: ["github.com", "https://npmjs.org", "com"]
+"#;
+        let policy = parser::parse_policy_str(yaml).unwrap();
+        let warnings = lint_policy(&policy);
+
+        let domain_warnings: Vec<_> = warnings
+            .iter()
+            .filter(|w| w.message.contains("unless_domain entry"))
+            .collect();
+        assert_eq!(domain_warnings.len(), 2);
+        assert!(domain_warnings[0].message.contains("https://npmjs.org"));
+        assert!(domain_warnings[1].message.contains("'com'"));
+    }
 }
@@ -1,7 +1,18 @@
+pub mod backend;
+#[cfg(feature = "cedar")]
+pub mod cedar;
 pub mod defaults;
+pub mod editor;
 pub mod engine;
+pub mod history;
 pub mod linter;
 pub mod parser;
+pub mod plugins;
+pub mod protected;
+pub mod rate;
+pub mod score;
+pub mod testkit;
+pub mod traps;
 pub mod types;
 
 pub use engine::PolicyEngine;
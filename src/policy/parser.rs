@@ -2,6 +2,9 @@
 //!
 //! Parses human-friendly YAML policy files into the internal Policy struct.
 //! The YAML format is intentionally simple — designed for vibe coders, not DevOps engineers.
+//! Teams that standardize on other config formats can write the same
+//! policy as `.lawctl.toml` or `.lawctl.json`; the format follows the
+//! file's extension, including for `include`d files.
 //!
 //! # Example policy file:
 //! ```yaml
@@ -13,60 +16,305 @@
 //!     if_path_matches: ["*.env", ".ssh/*"]
 //!   - require_approval: git_push
 //!   - allow: write
-//!     if_path_matches: ["src/**", "tests/**"]
+//!     if_path_matches: ["src/**", "tests/**", "!src/generated/**"]
 //!     max_diff_lines: 500
 //! ```
+//!
+//! # Layering policies
+//! A policy can build on others with `extends: safe-dev` (a built-in
+//! template or a file) and `include: [./policies/base.yaml, ~/.lawctl/org.yaml]`.
+//! Relative paths resolve against the including file's directory. Since the
+//! first matching rule wins, precedence is the order rules end up in:
+//!
+//! 1. the file's own rules,
+//! 2. then each `include`, in the order listed,
+//! 3. then the `extends` base.
+//!
+//! Settings follow the same order — the first one set wins — while lists
+//! like `trusted_packages` and `env_passthrough` are combined. A file that
+//! includes itself, directly or through others, is an error.
+//!
+//! # Nested policies
+//! In a monorepo, `packages/payments/.lawctl.yaml` can add stricter rules
+//! for just that subtree. [`parse_policy_tree`] loads the root policy along
+//! with every `.lawctl.yaml` below it; rules from a nested file only apply
+//! to files inside its directory, and only tighten: the engine takes the
+//! strictest of what they and the root rules decide. Only their rules are
+//! used, and only deny, require_approval and log_only rules for files
+//! (write, read, delete, move, chmod). A nested file that breaks these
+//! limits, or doesn't parse, is skipped whole and reported in
+//! `skipped_layers` rather than failing the load.
 
+use crate::policy::backend;
+use crate::policy::defaults;
 use crate::policy::types::*;
+use crate::utils::cloud::CloudEffect;
+use crate::utils::paths::{command_pattern_to_regex, EXCLUDE_PREFIX, REGEX_PREFIX};
+use crate::utils::schedule::{self, TimeWindow};
+use crate::utils::threats::Threat;
 use anyhow::{bail, Context, Result};
+use schemars::JsonSchema;
 use serde::Deserialize;
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 /// Raw YAML representation before conversion to internal types.
 /// This intermediate form handles the flexible YAML syntax.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
+#[schemars(title = "lawctl policy")]
 struct RawPolicy {
+    /// Policy name, e.g. "safe-dev-v1"
     law: String,
+    /// A built-in template (safe-dev, safe-ci, permissive) or policy file to build on
+    #[serde(default)]
+    extends: Option<String>,
+    /// Policy files whose rules and settings are merged in, in order
+    #[serde(default)]
+    include: Option<StringOrVec>,
+    /// Human-readable description
     #[serde(default)]
     description: Option<String>,
+    /// The built-in template revision this file was generated from, e.g. "safe-dev@2"
+    #[serde(default)]
+    template: Option<String>,
+    /// What each action gets when no rule matches: `allow`, `deny` or
+    /// `require_approval`, e.g. `{write: deny, network: allow}`
+    #[serde(default)]
+    defaults: BTreeMap<String, DefaultDecision>,
+    /// What the Claude Code hook does with tools it can't check: `allow`
+    /// (the default), `deny`, `require_approval` or `log_only`
+    #[serde(default)]
+    unknown_tools: Option<UnknownTools>,
+    /// Trace pushed commits back to their session: `notes` or `trailer`
+    #[serde(default)]
+    annotate_pushes: Option<AnnotatePushes>,
+    /// Deny lockfile changes adding a dependency under these SPDX licenses
+    #[serde(default)]
+    deny_new_deps_with_license: Vec<String>,
+    /// Ask before a lockfile change adds any dependency
+    #[serde(default)]
+    require_approval_on_new_dependency: bool,
+    /// Package names exempt from typosquat checks
+    #[serde(default)]
+    trusted_packages: Vec<String>,
+    /// Oldest lawctl this policy was written for, e.g. "0.3"
+    #[serde(default)]
+    requires_lawctl: Option<String>,
+    /// Environment variables (or `PREFIX_*` patterns) passed through to the agent
+    #[serde(default)]
+    env_passthrough: Vec<String>,
+    /// Absolute directories commands may run in
+    #[serde(default)]
+    allowed_cwd: Vec<String>,
+    /// Program and arguments commands run with, e.g. ["bash", "-lc"]
+    #[serde(default)]
+    shell: Vec<String>,
+    /// Cap on the command output returned to the agent, in KB
+    #[serde(default)]
+    max_output_kb: Option<u64>,
+    /// Keep the full output of truncated commands in a file
+    #[serde(default)]
+    spill_output: bool,
+    /// Hold writes to files changed on disk since the agent read them
+    #[serde(default)]
+    detect_stale_writes: bool,
+    /// Keep the policy history as a git repository too
+    #[serde(default)]
+    commit_policy_history: bool,
+    /// Cedar policies consulted before the rules
+    #[serde(default)]
+    cedar: Option<String>,
+    /// File holding Cedar policies, relative to this file
+    #[serde(default)]
+    cedar_file: Option<String>,
+    /// Programs deciding `if_plugin` conditions, by name
+    #[serde(default)]
+    plugins: BTreeMap<String, RawPlugin>,
+    /// Tighter rules for what commands allowed with `sub_policy` start, by name
+    #[serde(default)]
+    sub_policies: BTreeMap<String, RawSubPolicy>,
+    /// Program tagging commands for `if_tag` conditions
+    #[serde(default)]
+    classifier: Option<StringOrVec>,
+    /// LLM endpoint giving approval prompts a second opinion
+    #[serde(default)]
+    reviewer: Option<Reviewer>,
+    /// Ordered rules; the first match wins
+    #[serde(default)]
     rules: Vec<RawRule>,
 }
 
 /// A rule as it appears in the YAML file.
-/// Supports three forms: deny, allow, require_approval.
-#[derive(Debug, Deserialize)]
+/// Supports four forms: deny, allow, require_approval, log_only.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[schemars(rename = "Rule")]
 struct RawRule {
+    /// Block this action (write, read, delete, run_cmd, git_push, network, ...)
     #[serde(default)]
     deny: Option<String>,
+    /// Permit this action
     #[serde(default)]
     allow: Option<String>,
+    /// Ask a human before this action
     #[serde(default)]
     require_approval: Option<String>,
+    /// Allow this action, but log that a deny rule would have blocked it
+    #[serde(default)]
+    log_only: Option<String>,
 
     // Conditions — all optional
+    /// Only paths matching these globs; `!` excludes, e.g. `!src/generated/**`
     #[serde(default)]
     if_path_matches: Option<StringOrVec>,
+    /// Except paths under these
     #[serde(default)]
     unless_path: Option<StringOrVec>,
+    /// Only commands matching these patterns (`regex:` for regexes)
     #[serde(default)]
     if_matches: Option<StringOrVec>,
+    /// Only writes changing at most this many lines
     #[serde(default)]
     max_diff_lines: Option<usize>,
+    /// Except these domains
     #[serde(default)]
     unless_domain: Option<StringOrVec>,
+    /// Only these URL schemes
+    #[serde(default)]
+    if_scheme: Option<StringOrVec>,
+    /// Only these HTTP methods
+    #[serde(default)]
+    if_method: Option<StringOrVec>,
+    /// Only once the session's writes add up to more diff lines than this
+    #[serde(default)]
+    max_total_diff_lines: Option<usize>,
+    /// Only once the session's writes to the file add up to more than this
+    #[serde(default)]
+    max_total_diff_lines_per_file: Option<usize>,
+    /// Only writes of more than this many KB
+    #[serde(default)]
+    max_file_size_kb: Option<u64>,
+    /// Allow overwriting an existing file only once the session has read it
+    #[serde(default)]
+    require_prior_read: bool,
+    /// Only writes that create a file
+    #[serde(default)]
+    if_new_file: bool,
+    /// Only writes that replace an existing file
+    #[serde(default)]
+    if_overwrites_existing: bool,
+    /// Cap on response size, in MB
+    #[serde(default)]
+    max_response_mb: Option<u64>,
+    /// Cap on data sent to each domain per session, in MB
+    #[serde(default)]
+    max_upload_mb_per_domain: Option<u64>,
+    /// Only files not owned by these CODEOWNERS
+    #[serde(default)]
+    if_codeowners_not: Option<StringOrVec>,
+    /// Only diffs touching lines matching these patterns
+    #[serde(default)]
+    if_diff_touches: Option<StringOrVec>,
+    /// Only writes whose content matches these patterns
+    #[serde(default)]
+    if_content_matches: Option<StringOrVec>,
+    /// Only when these plugins say the rule applies
+    #[serde(default)]
+    if_plugin: Option<StringOrVec>,
+    /// Only commands the classifier tags with one of these
+    #[serde(default)]
+    if_tag: Option<StringOrVec>,
+    /// Only commands flagged as crypto_mining, reverse_shell or resource_abuse
+    #[serde(default)]
+    if_threat: Option<StringOrVec>,
+    /// Only packages matching these globs
+    #[serde(default)]
+    if_package_matches: Option<StringOrVec>,
+    /// Except packages matching these globs
+    #[serde(default)]
+    unless_package: Option<StringOrVec>,
+    /// Only pushes to branches matching these globs
+    #[serde(default)]
+    if_branch: Option<StringOrVec>,
+    /// Only Kubernetes contexts matching these globs
+    #[serde(default)]
+    if_context_matches: Option<StringOrVec>,
+    /// Only these kubectl or helm verbs
+    #[serde(default)]
+    if_verb: Option<StringOrVec>,
+    /// Only cloud commands with these effects: read, mutate, destroy
+    #[serde(default)]
+    if_effect: Option<StringOrVec>,
+    /// Only cloud accounts matching these globs
+    #[serde(default)]
+    if_account_matches: Option<StringOrVec>,
+    /// Only actions whose metadata matches, e.g. {tool: WebFetch, user: "ci-*"}
+    #[serde(default)]
+    if_context: BTreeMap<String, StringOrVec>,
+    /// Only during this local-time window, e.g. "09:00-18:00"
+    #[serde(default)]
+    only_between: Option<String>,
+    /// Only on these days, e.g. weekdays
+    #[serde(default)]
+    only_on_days: Option<StringOrVec>,
+    /// Only once the session has made more than this many in a minute
+    #[serde(default)]
+    max_per_minute: Option<usize>,
+    /// Only once the session has made more than this many
+    #[serde(default)]
+    max_per_session: Option<usize>,
+    /// Enforce this deny or require_approval rule in only this share of
+    /// sessions (`25%`), picked by session ID; the rest just log it
+    #[serde(default)]
+    enforce: Option<Percent>,
+    /// A stable name for the rule, shown in decisions and the audit log
+    #[serde(default)]
+    id: Option<String>,
+    /// Labels for grouping rules, e.g. [ci, secrets]
+    #[serde(default)]
+    tags: Option<StringOrVec>,
+    /// The date the rule stops applying (2025-07-01), for temporary rules
+    #[serde(default)]
+    expires: Option<String>,
+    /// For allow: run_cmd — hold what the command starts to this sub-policy too
+    #[serde(default)]
+    sub_policy: Option<String>,
+    /// Set false to keep the rule in the file but switch it off
+    #[serde(default)]
+    enabled: Option<bool>,
+    /// Why the action was denied, shown to the agent (for log_only rules,
+    /// why it would have been)
     #[serde(default)]
     reason: Option<String>,
+    /// What the approval prompt says
     #[serde(default)]
     prompt: Option<String>,
 }
 
+/// A plugin as it appears in the YAML file.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[schemars(rename = "Plugin")]
+struct RawPlugin {
+    command: StringOrVec,
+    #[serde(default)]
+    timeout_ms: Option<u64>,
+}
+
+/// A sub-policy as it appears in the YAML file.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[schemars(rename = "SubPolicy")]
+struct RawSubPolicy {
+    #[serde(default)]
+    description: Option<String>,
+    rules: Vec<RawRule>,
+}
+
 /// Allows YAML fields to be either a single string or a list of strings.
 /// This makes policies more ergonomic:
 /// ```yaml
 /// unless_path: /tmp          # single string — works
 /// unless_path: [/tmp, /var]  # list — also works
 /// ```
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 #[serde(untagged)]
 enum StringOrVec {
     Single(String),
@@ -82,51 +330,663 @@ impl StringOrVec {
     }
 }
 
-/// Parse a YAML policy file from a file path.
+/// A percentage written as `25%` or `25`.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum Percent {
+    Number(u64),
+    Text(String),
+}
+
+impl Percent {
+    fn value(&self) -> Option<u8> {
+        let n = match self {
+            Percent::Number(n) => *n,
+            Percent::Text(s) => s
+                .trim()
+                .strip_suffix('%')
+                .unwrap_or(s)
+                .trim()
+                .parse()
+                .ok()?,
+        };
+        u8::try_from(n).ok().filter(|&n| n <= 100)
+    }
+}
+
+/// The syntax a policy is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyFormat {
+    Yaml,
+    Toml,
+    Json,
+}
+
+impl PolicyFormat {
+    /// The format of a policy file, by its extension: `.toml` and `.json`,
+    /// and YAML for anything else.
+    pub fn of(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => PolicyFormat::Toml,
+            Some(ext) if ext.eq_ignore_ascii_case("json") => PolicyFormat::Json,
+            _ => PolicyFormat::Yaml,
+        }
+    }
+
+    fn parse(self, text: &str) -> Result<RawPolicy> {
+        match self {
+            PolicyFormat::Yaml => {
+                serde_yaml::from_str(text).context("Invalid YAML syntax in policy file")
+            }
+            PolicyFormat::Toml => {
+                toml::from_str(text).context("Invalid TOML syntax in policy file")
+            }
+            PolicyFormat::Json => {
+                serde_json::from_str(text).context("Invalid JSON syntax in policy file")
+            }
+        }
+    }
+}
+
+/// Parse a policy file from a file path, in the format its extension says.
 pub fn parse_policy_file(path: impl AsRef<Path>) -> Result<Policy> {
     let path = path.as_ref();
     let content = std::fs::read_to_string(path)
         .with_context(|| format!("Failed to read policy file: {}", path.display()))?;
-    parse_policy_str(&content)
+    let mut chain = vec![source_name(path)];
+    parse_policy_in(&content, PolicyFormat::of(path), path.parent(), &mut chain)
         .with_context(|| format!("Failed to parse policy file: {}", path.display()))
 }
 
+/// The policy file name, at the workspace root and in subdirectories.
+pub const POLICY_FILE: &str = ".lawctl.yaml";
+
+/// Every policy file name, in the order they're looked for.
+pub const POLICY_FILES: &[&str] = &[POLICY_FILE, ".lawctl.toml", ".lawctl.json"];
+
+/// The policy file in `dir`, if it has one.
+pub fn policy_file_in(dir: &Path) -> Option<PathBuf> {
+    POLICY_FILES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// The policy a command should use when given `path`: `path` itself,
+/// unless it's the default `.lawctl.yaml`, which doesn't exist, and the
+/// same directory has a `.lawctl.toml` or `.lawctl.json`.
+pub fn locate_policy(path: &Path) -> PathBuf {
+    if path.exists() || path.file_name().is_none_or(|name| name != POLICY_FILE) {
+        return path.to_path_buf();
+    }
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    policy_file_in(dir).unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Directories never searched for nested policies.
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "vendor", "dist", "build"];
+
+/// Parse the policy at `path` together with the nested policy files in
+/// the directories below it (see "Nested policies" above).
+pub fn parse_policy_tree(path: impl AsRef<Path>) -> Result<Policy> {
+    let path = path.as_ref();
+    let mut policy = parse_policy_file(path)?;
+    let Some(root) = path.parent() else {
+        return Ok(policy);
+    };
+    let mut nested = Vec::new();
+    collect_nested_policies(root, root, &mut nested);
+    // Deepest first, so the most specific rules get the first say
+    nested.sort_by_key(|dir: &PathBuf| (std::cmp::Reverse(dir.components().count()), dir.clone()));
+
+    let mut rules = Vec::new();
+    for dir in nested {
+        let Some(file) = policy_file_in(&root.join(&dir)) else {
+            continue;
+        };
+        match nested_rules(&file, &dir.to_string_lossy()) {
+            Ok(mut layer) => {
+                rules.append(&mut layer);
+                policy.included.push(source_name(&file));
+            }
+            Err(e) => policy
+                .skipped_layers
+                .push(format!("{} (skipped): {:#}", file.display(), e)),
+        }
+    }
+    rules.append(&mut policy.rules);
+    policy.rules = rules;
+    Ok(policy)
+}
+
+/// The rules of a nested policy file, confined to `scope`. Fails if the
+/// file doesn't parse or has a rule a nested policy can't hold.
+fn nested_rules(file: &Path, scope: &str) -> Result<Vec<Rule>> {
+    let layer = parse_policy_file(file)?;
+    layer
+        .rules
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut rule)| {
+            if !rule.action().touches_paths() && *rule.action() != Action::Any {
+                bail!(
+                    "rule {} is a {} rule — only file rules (write, read, delete, move, chmod, any) \
+                     can be confined to a directory; put it in the root policy.",
+                    i,
+                    rule.action()
+                );
+            }
+            if matches!(rule, Rule::Allow { .. }) {
+                bail!(
+                    "rule {} is an allow rule — a nested policy can only add deny, \
+                     require_approval and log_only rules.",
+                    i
+                );
+            }
+            rule.conditions_mut().within = Some(scope.to_string());
+            Ok(rule)
+        })
+        .collect()
+}
+
+/// Find the directories below `dir` holding a policy file, relative to
+/// `root`. Hidden and dependency/build directories are skipped.
+fn collect_nested_policies(root: &Path, dir: &Path, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_ref()) {
+            continue;
+        }
+        // Not following symlinks keeps the walk inside the tree
+        if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+            continue;
+        }
+        let path = entry.path();
+        if policy_file_in(&path).is_some() {
+            if let Ok(relative) = path.strip_prefix(root) {
+                found.push(relative.to_path_buf());
+            }
+        }
+        collect_nested_policies(root, &path, found);
+    }
+}
+
+/// A JSON Schema for policy files, for editors and CI to validate
+/// `.lawctl.yaml` (and the TOML and JSON forms) against.
+pub fn json_schema() -> serde_json::Value {
+    let mut schema = schemars::schema_for!(RawPolicy).to_value();
+    // Each rule is exactly one of deny, allow, require_approval or log_only
+    if let Some(rule) = schema.pointer_mut("/$defs/Rule") {
+        rule["oneOf"] = serde_json::json!([
+            { "required": ["deny"] },
+            { "required": ["allow"] },
+            { "required": ["require_approval"] },
+            { "required": ["log_only"] },
+        ]);
+    }
+    schema
+}
+
 /// Parse a YAML policy string into a Policy struct.
 pub fn parse_policy_str(yaml: &str) -> Result<Policy> {
-    let raw: RawPolicy =
-        serde_yaml::from_str(yaml).context("Invalid YAML syntax in policy file")?;
+    parse_policy_in(yaml, PolicyFormat::Yaml, None, &mut Vec::new())
+}
+
+/// Parse a TOML policy string into a Policy struct.
+pub fn parse_policy_toml(toml: &str) -> Result<Policy> {
+    parse_policy_in(toml, PolicyFormat::Toml, None, &mut Vec::new())
+}
+
+/// Parse a JSON policy string into a Policy struct.
+pub fn parse_policy_json(json: &str) -> Result<Policy> {
+    parse_policy_in(json, PolicyFormat::Json, None, &mut Vec::new())
+}
+
+/// Parse a policy along with everything it extends and includes, then check
+/// the rules against the combined plugins and classifier.
+fn parse_policy_in(
+    text: &str,
+    format: PolicyFormat,
+    base: Option<&Path>,
+    chain: &mut Vec<String>,
+) -> Result<Policy> {
+    let policy = load_policy(text, format, base, chain)?;
+
+    for (i, rule) in policy.rules.iter().enumerate() {
+        if let Some(name) = rule
+            .conditions()
+            .if_plugin
+            .iter()
+            .find(|name| !policy.plugins.contains_key(*name))
+        {
+            bail!(
+                "Rule {}: unknown plugin '{}' — register it under 'plugins:'",
+                i,
+                name
+            );
+        }
+        if let Some(name) = rule
+            .conditions()
+            .sub_policy
+            .as_ref()
+            .filter(|name| !policy.sub_policies.contains_key(*name))
+        {
+            bail!(
+                "Rule {}: unknown sub-policy '{}' — define it under 'sub_policies:'",
+                i,
+                name
+            );
+        }
+        if !rule.conditions().if_tag.is_empty() && policy.classifier.is_none() {
+            bail!("Rule {}: 'if_tag' needs a 'classifier' to tag commands", i);
+        }
+        if let Some(id) = &rule.conditions().id {
+            if policy.rules[..i]
+                .iter()
+                .any(|earlier| earlier.conditions().id.as_ref() == Some(id))
+            {
+                bail!("Rule {}: id '{}' is used by more than one rule", i, id);
+            }
+        }
+    }
+
+    if policy.rules.is_empty() && policy.cedar.is_none() {
+        bail!("Policy must have at least one rule");
+    }
+    Ok(policy)
+}
+
+/// Load one policy file's contents and merge in what it layers on top of.
+/// `cedar_file`, plugin programs and includes are relative to `base` (the
+/// policy file's directory), or the current directory without one. `chain`
+/// holds the files being loaded, outermost first, to catch cycles.
+fn load_policy(
+    text: &str,
+    format: PolicyFormat,
+    base: Option<&Path>,
+    chain: &mut Vec<String>,
+) -> Result<Policy> {
+    let raw = format.parse(text)?;
 
     // Validate the law name
     if raw.law.trim().is_empty() {
         bail!("Policy must have a non-empty 'law' name");
     }
+    if raw
+        .deny_new_deps_with_license
+        .iter()
+        .any(|license| license.trim().is_empty())
+    {
+        bail!("'deny_new_deps_with_license' entries must be non-empty license ids");
+    }
+    if let Some(required) = &raw.requires_lawctl {
+        if crate::utils::version::parse_version(required).is_none() {
+            bail!(
+                "'requires_lawctl' must be a version like \"0.3\" or \"0.3.1\", got '{}'",
+                required
+            );
+        }
+    }
+
+    if let Some(entry) = raw
+        .env_passthrough
+        .iter()
+        .find(|entry| !crate::sandbox::env::is_valid_pattern(entry))
+    {
+        bail!(
+            "'env_passthrough' entries must be variable names or prefixes ending in '*', got '{}'",
+            entry
+        );
+    }
+
+    if let Some(dir) = raw
+        .allowed_cwd
+        .iter()
+        .find(|dir| !Path::new(dir).is_absolute())
+    {
+        bail!(
+            "'allowed_cwd' entries must be absolute paths, got '{}'",
+            dir
+        );
+    }
+
+    if raw
+        .shell
+        .first()
+        .is_some_and(|program| program.trim().is_empty())
+    {
+        bail!("'shell' must start with the program to run, e.g. [\"bash\", \"-lc\"]");
+    }
+
+    if let Some(template) = &raw.template {
+        if defaults::TemplateRef::parse(template).is_none() {
+            bail!(
+                "'template' must name a built-in template and revision like \"safe-dev@2\", got '{}'",
+                template
+            );
+        }
+    }
+
+    if raw.max_output_kb == Some(0) {
+        bail!("'max_output_kb' must be at least 1");
+    }
+
+    let cedar = match (raw.cedar, raw.cedar_file) {
+        (Some(_), Some(_)) => bail!("Use either 'cedar' or 'cedar_file', not both"),
+        (Some(text), None) => Some(text),
+        (None, Some(file)) => {
+            let path = match base {
+                Some(base) => base.join(&file),
+                None => PathBuf::from(&file),
+            };
+            Some(
+                std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read cedar_file: {}", path.display()))?,
+            )
+        }
+        (None, None) => None,
+    };
+    if let Some(text) = &cedar {
+        backend::validate_cedar(text)?;
+    }
+
+    let mut plugins = BTreeMap::new();
+    for (name, plugin) in raw.plugins {
+        let mut command = plugin.command.into_vec();
+        if !resolve_program(&mut command, base) {
+            bail!("Plugin '{}' needs a command to run", name);
+        }
+        if plugin.timeout_ms == Some(0) {
+            bail!("Plugin '{}': 'timeout_ms' must be at least 1", name);
+        }
+        plugins.insert(
+            name,
+            Plugin {
+                command,
+                timeout_ms: plugin.timeout_ms,
+            },
+        );
+    }
+
+    let mut sub_policies = BTreeMap::new();
+    for (name, sub_policy) in raw.sub_policies {
+        if !is_valid_rule_name(&name) {
+            bail!(
+                "Sub-policy names must be letters, digits, '-', '_' or '.', got '{}'",
+                name
+            );
+        }
+        let mut rules = Vec::with_capacity(sub_policy.rules.len());
+        for (i, raw_rule) in sub_policy.rules.into_iter().enumerate() {
+            if raw_rule.enabled == Some(false) {
+                continue;
+            }
+            let rule = convert_rule(raw_rule, i).with_context(|| {
+                format!("Invalid rule at position {} of sub-policy '{}'", i, name)
+            })?;
+            if rule.conditions().sub_policy.is_some() {
+                bail!(
+                    "Sub-policy '{}', rule {}: sub-policies can't delegate to another sub-policy",
+                    name,
+                    i
+                );
+            }
+            rules.push(rule);
+        }
+        if rules.is_empty() {
+            bail!("Sub-policy '{}' must have at least one rule", name);
+        }
+        sub_policies.insert(
+            name,
+            SubPolicy {
+                description: sub_policy.description,
+                rules,
+            },
+        );
+    }
+
+    let classifier = match raw.classifier {
+        Some(command) => {
+            let mut command = command.into_vec();
+            if !resolve_program(&mut command, base) {
+                bail!("'classifier' needs a command to run");
+            }
+            Some(Plugin {
+                command,
+                timeout_ms: None,
+            })
+        }
+        None => None,
+    };
+
+    if let Some(reviewer) = &raw.reviewer {
+        if !(reviewer.url.starts_with("https://") || reviewer.url.starts_with("http://")) {
+            bail!(
+                "'reviewer.url' must be an http(s) URL, got '{}'",
+                reviewer.url
+            );
+        }
+        if reviewer.timeout_ms == Some(0) {
+            bail!("'reviewer.timeout_ms' must be at least 1");
+        }
+        if reviewer
+            .api_key_env
+            .as_deref()
+            .is_some_and(|var| var.trim().is_empty())
+        {
+            bail!("'reviewer.api_key_env' must name an environment variable");
+        }
+    }
+
+    let mut defaults = BTreeMap::new();
+    for (name, decision) in raw.defaults {
+        let Some(action) = Action::from_str_loose(&name) else {
+            bail!("Unknown action '{}' in 'defaults'", name);
+        };
+        if defaults.insert(action.clone(), decision).is_some() {
+            bail!("'defaults' sets {} more than once", action);
+        }
+    }
 
     // Convert raw rules to typed rules
     let mut rules = Vec::with_capacity(raw.rules.len());
     for (i, raw_rule) in raw.rules.into_iter().enumerate() {
+        if raw_rule.enabled == Some(false) {
+            continue;
+        }
         let rule = convert_rule(raw_rule, i)
             .with_context(|| format!("Invalid rule at position {} (0-indexed)", i))?;
         rules.push(rule);
     }
 
-    if rules.is_empty() {
-        bail!("Policy must have at least one rule");
-    }
-
-    Ok(Policy {
+    let mut policy = Policy {
         law: raw.law,
         description: raw.description,
+        template: raw.template,
         rules,
-    })
+        defaults,
+        unknown_tools: raw.unknown_tools,
+        annotate_pushes: raw.annotate_pushes,
+        deny_new_deps_with_license: raw.deny_new_deps_with_license,
+        require_approval_on_new_dependency: raw.require_approval_on_new_dependency,
+        trusted_packages: raw.trusted_packages,
+        requires_lawctl: raw.requires_lawctl,
+        env_passthrough: raw.env_passthrough,
+        allowed_cwd: raw.allowed_cwd,
+        shell: raw.shell,
+        max_output_kb: raw.max_output_kb,
+        spill_output: raw.spill_output,
+        detect_stale_writes: raw.detect_stale_writes,
+        commit_policy_history: raw.commit_policy_history,
+        cedar,
+        plugins,
+        sub_policies,
+        classifier,
+        reviewer: raw.reviewer,
+        included: Vec::new(),
+        skipped_layers: Vec::new(),
+    };
+
+    // Includes in the order listed, then the base they all sit on
+    let layers = raw
+        .include
+        .map(|s| s.into_vec())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|path| (path, false))
+        .chain(raw.extends.map(|name| (name, true)));
+    for (reference, is_extends) in layers {
+        let (name, text, layer_format, layer_base) = match defaults::get_default_policy(&reference)
+        {
+            Some(template) if is_extends => (
+                format!("template:{}", reference),
+                template.to_string(),
+                PolicyFormat::Yaml,
+                None,
+            ),
+            _ => {
+                let path = resolve_include(&reference, base);
+                let name = source_name(&path);
+                let text = std::fs::read_to_string(&path).with_context(|| {
+                    format!("Failed to read included policy: {}", path.display())
+                })?;
+                (
+                    name,
+                    text,
+                    PolicyFormat::of(&path),
+                    path.parent().map(Path::to_path_buf),
+                )
+            }
+        };
+        if chain.contains(&name) {
+            bail!("Include cycle: {} → {}", chain.join(" → "), name);
+        }
+        chain.push(name.clone());
+        let layer = load_policy(&text, layer_format, layer_base.as_deref(), chain)
+            .with_context(|| format!("In included policy {}", name))?;
+        chain.pop();
+        inherit(&mut policy, layer, name);
+    }
+
+    Ok(policy)
+}
+
+/// Where an `include` or `extends` path points: `~/` is the home directory,
+/// other relative paths are relative to `base`.
+fn resolve_include(reference: &str, base: Option<&Path>) -> PathBuf {
+    if let Some(rest) = reference.strip_prefix("~/") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest);
+        }
+    }
+    match base {
+        Some(base) => base.join(reference),
+        None => PathBuf::from(reference),
+    }
+}
+
+/// A policy file's name in `included` and cycle errors.
+fn source_name(path: &Path) -> String {
+    std::fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .display()
+        .to_string()
+}
+
+/// Merge a lower-precedence layer into `policy`: its rules go after
+/// `policy`'s, its settings fill in what `policy` leaves unset, and its
+/// lists are added to `policy`'s.
+fn inherit(policy: &mut Policy, layer: Policy, name: String) {
+    fn extend(list: &mut Vec<String>, more: Vec<String>) {
+        for item in more {
+            if !list.contains(&item) {
+                list.push(item);
+            }
+        }
+    }
+
+    policy.rules.extend(layer.rules);
+    policy.description = policy.description.take().or(layer.description);
+    for (action, decision) in layer.defaults {
+        policy.defaults.entry(action).or_insert(decision);
+    }
+    policy.unknown_tools = policy.unknown_tools.or(layer.unknown_tools);
+    policy.annotate_pushes = policy.annotate_pushes.or(layer.annotate_pushes);
+    extend(
+        &mut policy.deny_new_deps_with_license,
+        layer.deny_new_deps_with_license,
+    );
+    policy.require_approval_on_new_dependency |= layer.require_approval_on_new_dependency;
+    extend(&mut policy.trusted_packages, layer.trusted_packages);
+    // The newest requirement of any layer is the one that matters
+    let version = |v: &Option<String>| v.as_deref().and_then(crate::utils::version::parse_version);
+    if version(&layer.requires_lawctl) > version(&policy.requires_lawctl) {
+        policy.requires_lawctl = layer.requires_lawctl;
+    }
+    extend(&mut policy.env_passthrough, layer.env_passthrough);
+    extend(&mut policy.allowed_cwd, layer.allowed_cwd);
+    if policy.shell.is_empty() {
+        policy.shell = layer.shell;
+    }
+    policy.max_output_kb = policy.max_output_kb.or(layer.max_output_kb);
+    policy.spill_output |= layer.spill_output;
+    policy.detect_stale_writes |= layer.detect_stale_writes;
+    policy.commit_policy_history |= layer.commit_policy_history;
+    policy.cedar = match (policy.cedar.take(), layer.cedar) {
+        (Some(own), Some(inherited)) => Some(format!("{}\n{}", own, inherited)),
+        (own, inherited) => own.or(inherited),
+    };
+    for (plugin, command) in layer.plugins {
+        policy.plugins.entry(plugin).or_insert(command);
+    }
+    for (name, sub_policy) in layer.sub_policies {
+        policy.sub_policies.entry(name).or_insert(sub_policy);
+    }
+    policy.classifier = policy.classifier.take().or(layer.classifier);
+    policy.reviewer = policy.reviewer.take().or(layer.reviewer);
+    policy.included.push(name);
+    policy.included.extend(layer.included);
+}
+
+/// Rule ids and tags: non-empty, and only characters that read cleanly on a
+/// command line and in `matched_rule`.
+fn is_valid_rule_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Resolve a plugin program that names a relative path against `base`.
+/// False when there's no program.
+fn resolve_program(command: &mut [String], base: Option<&Path>) -> bool {
+    let Some(program) = command.first_mut().filter(|p| !p.trim().is_empty()) else {
+        return false;
+    };
+    if program.contains('/') && Path::new(program.as_str()).is_relative() {
+        if let Some(base) = base {
+            let relative = program.strip_prefix("./").unwrap_or(program);
+            *program = base.join(relative).to_string_lossy().into_owned();
+        }
+    }
+    true
 }
 
 /// Convert a raw YAML rule into a typed Rule enum.
 fn convert_rule(raw: RawRule, index: usize) -> Result<Rule> {
-    // Exactly one of deny/allow/require_approval must be set
+    // Exactly one of deny/allow/require_approval/log_only must be set
     let set_count = [
         raw.deny.is_some(),
         raw.allow.is_some(),
         raw.require_approval.is_some(),
+        raw.log_only.is_some(),
     ]
     .iter()
     .filter(|&&b| b)
@@ -134,13 +994,13 @@ fn convert_rule(raw: RawRule, index: usize) -> Result<Rule> {
 
     if set_count == 0 {
         bail!(
-            "Rule {} must specify one of: deny, allow, or require_approval",
+            "Rule {} must specify one of: deny, allow, require_approval, or log_only",
             index
         );
     }
     if set_count > 1 {
         bail!(
-            "Rule {} specifies multiple rule types (deny/allow/require_approval) — pick one",
+            "Rule {} specifies multiple rule types (deny/allow/require_approval/log_only) — pick one",
             index
         );
     }
@@ -153,15 +1013,138 @@ fn convert_rule(raw: RawRule, index: usize) -> Result<Rule> {
         unless_path: raw.unless_path.map(|s| s.into_vec()).unwrap_or_default(),
         if_matches: raw.if_matches.map(|s| s.into_vec()).unwrap_or_default(),
         max_diff_lines: raw.max_diff_lines,
+        max_total_diff_lines: raw.max_total_diff_lines,
+        max_total_diff_lines_per_file: raw.max_total_diff_lines_per_file,
+        max_file_size_kb: raw.max_file_size_kb,
+        require_prior_read: raw.require_prior_read,
+        if_new_file: raw.if_new_file,
+        if_overwrites_existing: raw.if_overwrites_existing,
         unless_domain: raw.unless_domain.map(|s| s.into_vec()).unwrap_or_default(),
+        if_scheme: raw.if_scheme.map(|s| s.into_vec()).unwrap_or_default(),
+        if_method: raw.if_method.map(|s| s.into_vec()).unwrap_or_default(),
+        max_response_mb: raw.max_response_mb,
+        max_upload_mb_per_domain: raw.max_upload_mb_per_domain,
+        if_codeowners_not: raw
+            .if_codeowners_not
+            .map(|s| s.into_vec())
+            .unwrap_or_default(),
+        if_diff_touches: raw
+            .if_diff_touches
+            .map(|s| s.into_vec())
+            .unwrap_or_default(),
+        if_content_matches: raw
+            .if_content_matches
+            .map(|s| s.into_vec())
+            .unwrap_or_default(),
+        if_plugin: raw.if_plugin.map(|s| s.into_vec()).unwrap_or_default(),
+        if_tag: raw.if_tag.map(|s| s.into_vec()).unwrap_or_default(),
+        if_threat: raw.if_threat.map(|s| s.into_vec()).unwrap_or_default(),
+        if_package_matches: raw
+            .if_package_matches
+            .map(|s| s.into_vec())
+            .unwrap_or_default(),
+        unless_package: raw.unless_package.map(|s| s.into_vec()).unwrap_or_default(),
+        if_branch: raw.if_branch.map(|s| s.into_vec()).unwrap_or_default(),
+        if_context_matches: raw
+            .if_context_matches
+            .map(|s| s.into_vec())
+            .unwrap_or_default(),
+        if_verb: raw.if_verb.map(|s| s.into_vec()).unwrap_or_default(),
+        if_effect: raw.if_effect.map(|s| s.into_vec()).unwrap_or_default(),
+        if_account_matches: raw
+            .if_account_matches
+            .map(|s| s.into_vec())
+            .unwrap_or_default(),
+        if_context: raw
+            .if_context
+            .into_iter()
+            .map(|(key, patterns)| (key, patterns.into_vec()))
+            .collect(),
+        only_between: raw.only_between,
+        only_on_days: raw.only_on_days.map(|s| s.into_vec()).unwrap_or_default(),
+        max_per_minute: raw.max_per_minute,
+        max_per_session: raw.max_per_session,
+        within: None,
+        enforce: match raw.enforce {
+            Some(percent) => match percent.value() {
+                Some(value) => Some(value),
+                None => bail!(
+                    "Rule {}: 'enforce' must be a percentage from 0% to 100%, e.g. \"25%\".",
+                    index
+                ),
+            },
+            None => None,
+        },
+        id: raw.id,
+        tags: raw.tags.map(|s| s.into_vec()).unwrap_or_default(),
+        expires: match raw.expires {
+            Some(date) => match chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d") {
+                Ok(date) => Some(date),
+                Err(_) => bail!(
+                    "Rule {}: 'expires' must be a date like \"2025-07-01\", got '{}'",
+                    index,
+                    date
+                ),
+            },
+            None => None,
+        },
+        sub_policy: raw.sub_policy,
     };
-
-    if let Some(action_str) = raw.deny {
-        let action = Action::from_str_loose(&action_str)
-            .ok_or_else(|| anyhow::anyhow!("Unknown action '{}' in deny rule", action_str))?;
-        validate_conditions_for_action(&action, &conditions, index)?;
-        Ok(Rule::Deny {
-            action,
+    if let Some(id) = &conditions.id {
+        if !is_valid_rule_name(id) {
+            bail!(
+                "Rule {}: 'id' must be letters, digits, '-', '_' or '.', got '{}'",
+                index,
+                id
+            );
+        }
+    }
+    if let Some(tag) = conditions.tags.iter().find(|tag| !is_valid_rule_name(tag)) {
+        bail!(
+            "Rule {}: 'tags' must be letters, digits, '-', '_' or '.', got '{}'",
+            index,
+            tag
+        );
+    }
+    if conditions.enforce.is_some() && raw.deny.is_none() && raw.require_approval.is_none() {
+        bail!(
+            "Rule {}: 'enforce' only applies to deny and require_approval rules.",
+            index
+        );
+    }
+
+    if conditions.require_prior_read
+        && raw
+            .allow
+            .as_deref()
+            .and_then(Action::from_str_loose)
+            .is_none_or(|action| action != Action::Write)
+    {
+        bail!(
+            "Rule {}: 'require_prior_read' only applies to allow write rules.",
+            index
+        );
+    }
+
+    if conditions.sub_policy.is_some()
+        && raw
+            .allow
+            .as_deref()
+            .and_then(Action::from_str_loose)
+            .is_none_or(|action| action != Action::RunCmd)
+    {
+        bail!(
+            "Rule {}: 'sub_policy' only applies to allow run_cmd rules.",
+            index
+        );
+    }
+
+    if let Some(action_str) = raw.deny {
+        let action = Action::from_str_loose(&action_str)
+            .ok_or_else(|| anyhow::anyhow!("Unknown action '{}' in deny rule", action_str))?;
+        validate_conditions_for_action(&action, &conditions, index)?;
+        Ok(Rule::Deny {
+            action,
             conditions,
             reason: raw.reason,
         })
@@ -180,6 +1163,15 @@ fn convert_rule(raw: RawRule, index: usize) -> Result<Rule> {
             conditions,
             prompt: raw.prompt,
         })
+    } else if let Some(action_str) = raw.log_only {
+        let action = Action::from_str_loose(&action_str)
+            .ok_or_else(|| anyhow::anyhow!("Unknown action '{}' in log_only rule", action_str))?;
+        validate_conditions_for_action(&action, &conditions, index)?;
+        Ok(Rule::LogOnly {
+            action,
+            conditions,
+            reason: raw.reason,
+        })
     } else {
         unreachable!()
     }
@@ -192,6 +1184,151 @@ fn validate_conditions_for_action(
     conditions: &Conditions,
     index: usize,
 ) -> Result<()> {
+    let has_network_conditions = !conditions.if_scheme.is_empty()
+        || !conditions.if_method.is_empty()
+        || conditions.max_response_mb.is_some()
+        || conditions.max_upload_mb_per_domain.is_some();
+    if has_network_conditions && *action != Action::Network {
+        bail!(
+            "Rule {}: 'if_scheme', 'if_method', 'max_response_mb' and 'max_upload_mb_per_domain' only apply to network actions.",
+            index
+        );
+    }
+
+    if (conditions.max_total_diff_lines.is_some()
+        || conditions.max_total_diff_lines_per_file.is_some())
+        && *action != Action::Write
+    {
+        bail!(
+            "Rule {}: 'max_total_diff_lines' and 'max_total_diff_lines_per_file' only apply to write actions.",
+            index
+        );
+    }
+    if conditions.max_file_size_kb.is_some() && *action != Action::Write {
+        bail!(
+            "Rule {}: 'max_file_size_kb' only applies to write actions.",
+            index
+        );
+    }
+
+    if (conditions.if_new_file || conditions.if_overwrites_existing) && *action != Action::Write {
+        bail!(
+            "Rule {}: 'if_new_file' and 'if_overwrites_existing' only apply to write actions.",
+            index
+        );
+    }
+    if conditions.if_new_file && conditions.if_overwrites_existing {
+        bail!(
+            "Rule {}: 'if_new_file' and 'if_overwrites_existing' can't both be set; no write matches both.",
+            index
+        );
+    }
+
+    if !conditions.if_codeowners_not.is_empty() && !matches!(action, Action::Write | Action::Delete)
+    {
+        bail!(
+            "Rule {}: 'if_codeowners_not' only applies to write and delete actions.",
+            index
+        );
+    }
+
+    if !conditions.if_diff_touches.is_empty() {
+        if *action != Action::Write {
+            bail!(
+                "Rule {}: 'if_diff_touches' only applies to write actions.",
+                index
+            );
+        }
+        if conditions
+            .if_diff_touches
+            .iter()
+            .any(|s| s.trim().is_empty())
+        {
+            bail!("Rule {}: 'if_diff_touches' entries can't be empty.", index);
+        }
+    }
+
+    if !conditions.if_content_matches.is_empty() && *action != Action::Write {
+        bail!(
+            "Rule {}: 'if_content_matches' only applies to write actions.",
+            index
+        );
+    }
+
+    if !conditions.if_tag.is_empty() {
+        if *action != Action::RunCmd {
+            bail!("Rule {}: 'if_tag' only applies to run_cmd actions.", index);
+        }
+        if conditions.if_tag.iter().any(|t| t.trim().is_empty()) {
+            bail!("Rule {}: 'if_tag' entries can't be empty.", index);
+        }
+    }
+
+    if !conditions.if_threat.is_empty() {
+        if !matches!(action, Action::RunCmd | Action::RemoteExec) {
+            bail!(
+                "Rule {}: 'if_threat' only applies to run_cmd and remote_exec actions.",
+                index
+            );
+        }
+        if let Some(threat) = conditions
+            .if_threat
+            .iter()
+            .find(|t| !Threat::NAMES.contains(&t.to_ascii_lowercase().as_str()))
+        {
+            bail!(
+                "Rule {}: '{}' isn't a threat — use crypto_mining, reverse_shell or resource_abuse.",
+                index,
+                threat
+            );
+        }
+    }
+
+    if (!conditions.if_package_matches.is_empty() || !conditions.unless_package.is_empty())
+        && *action != Action::PackageInstall
+    {
+        bail!(
+            "Rule {}: 'if_package_matches' and 'unless_package' only apply to package_install actions.",
+            index
+        );
+    }
+
+    if (!conditions.if_context_matches.is_empty() || !conditions.if_verb.is_empty())
+        && *action != Action::K8s
+    {
+        bail!(
+            "Rule {}: 'if_context_matches' and 'if_verb' only apply to k8s actions.",
+            index
+        );
+    }
+
+    if (!conditions.if_effect.is_empty() || !conditions.if_account_matches.is_empty())
+        && *action != Action::Cloud
+    {
+        bail!(
+            "Rule {}: 'if_effect' and 'if_account_matches' only apply to cloud actions.",
+            index
+        );
+    }
+    if let Some(effect) = conditions
+        .if_effect
+        .iter()
+        .find(|e| !CloudEffect::NAMES.contains(&e.to_ascii_lowercase().as_str()))
+    {
+        bail!(
+            "Rule {}: '{}' isn't an effect — use read, mutate or destroy.",
+            index,
+            effect
+        );
+    }
+
+    if !conditions.if_branch.is_empty() && *action != Action::GitPush {
+        bail!(
+            "Rule {}: 'if_branch' only applies to git_push actions.",
+            index
+        );
+    }
+
     match action {
         Action::RunCmd => {
             if !conditions.if_path_matches.is_empty() || !conditions.unless_path.is_empty() {
@@ -202,11 +1339,48 @@ fn validate_conditions_for_action(
                 );
             }
         }
+        Action::Any => {
+            // What's left once the conditions every action has are taken out
+            let mut rest = conditions.clone();
+            rest.if_path_matches.clear();
+            rest.unless_path.clear();
+            rest.only_between = None;
+            rest.only_on_days.clear();
+            rest.max_per_minute = None;
+            rest.max_per_session = None;
+            rest.if_plugin.clear();
+            if !rest.is_empty() {
+                bail!(
+                    "Rule {}: 'any' rules cover every action, so they only take conditions \
+                     every action has: 'if_path_matches', 'unless_path', 'only_between', \
+                     'only_on_days', 'max_per_minute', 'max_per_session' and 'if_plugin'.",
+                    index
+                );
+            }
+        }
         Action::GitPush => {
             if !conditions.if_matches.is_empty() {
                 bail!(
                     "Rule {}: 'if_matches' doesn't apply to git_push. \
-                     Use 'if_path_matches' for branch patterns.",
+                     Use 'if_branch' for branch patterns.",
+                    index
+                );
+            }
+        }
+        Action::PackageInstall => {
+            if !conditions.if_matches.is_empty() || !conditions.unless_domain.is_empty() {
+                bail!(
+                    "Rule {}: package_install rules match package names — \
+                     use 'if_package_matches' and 'unless_package'.",
+                    index
+                );
+            }
+        }
+        Action::EnvAccess => {
+            if !conditions.if_matches.is_empty() || !conditions.unless_domain.is_empty() {
+                bail!(
+                    "Rule {}: env_access rules match variable names — \
+                     use 'if_path_matches' and 'unless_path', e.g. [\"AWS_*\", \"*_TOKEN\"].",
                     index
                 );
             }
@@ -220,7 +1394,45 @@ fn validate_conditions_for_action(
                 );
             }
         }
-        Action::Write | Action::Delete => {
+        Action::Cloud => {
+            if !conditions.if_path_matches.is_empty()
+                || !conditions.unless_path.is_empty()
+                || !conditions.unless_domain.is_empty()
+            {
+                bail!(
+                    "Rule {}: cloud rules match accounts and commands — \
+                     use 'if_account_matches', 'if_effect' and 'if_matches'.",
+                    index
+                );
+            }
+        }
+        Action::K8s => {
+            if !conditions.if_path_matches.is_empty()
+                || !conditions.unless_path.is_empty()
+                || !conditions.unless_domain.is_empty()
+            {
+                bail!(
+                    "Rule {}: k8s rules match clusters — use 'if_context_matches' and 'if_verb'.",
+                    index
+                );
+            }
+        }
+        Action::RemoteExec | Action::RemoteCopy => {
+            if !conditions.if_path_matches.is_empty() || !conditions.unless_path.is_empty() {
+                bail!(
+                    "Rule {}: {} rules match hosts — use 'unless_domain' to allow trusted machines.",
+                    index,
+                    action
+                );
+            }
+            if *action == Action::RemoteCopy && !conditions.if_matches.is_empty() {
+                bail!(
+                    "Rule {}: 'if_matches' only applies to the command of remote_exec actions.",
+                    index
+                );
+            }
+        }
+        Action::Write | Action::Read | Action::Delete | Action::Move | Action::Chmod => {
             if !conditions.unless_domain.is_empty() {
                 bail!(
                     "Rule {}: 'unless_domain' only applies to network actions.",
@@ -228,11 +1440,114 @@ fn validate_conditions_for_action(
                 );
             }
         }
+        // Never parsed from a policy; see Action::from_str_loose
+        Action::Rejected | Action::UnknownTool | Action::PlanApproval => {}
     }
 
-    // Validate glob patterns are well-formed
-    for pattern in &conditions.if_path_matches {
+    // Validate glob and regex patterns are well-formed
+    for pattern in &conditions.if_matches {
+        if pattern.starts_with(REGEX_PREFIX) {
+            let regex = command_pattern_to_regex(pattern);
+            if regex.is_empty() {
+                bail!(
+                    "Rule {}: '{}' has no pattern after it.",
+                    index,
+                    REGEX_PREFIX
+                );
+            }
+            regex::Regex::new(&regex)
+                .with_context(|| format!("Rule {}: invalid regex '{}'", index, pattern))?;
+        }
+    }
+    for pattern in conditions
+        .if_package_matches
+        .iter()
+        .chain(&conditions.unless_package)
+    {
+        globset::Glob::new(pattern)
+            .with_context(|| format!("Rule {}: invalid package pattern '{}'", index, pattern))?;
+    }
+    for pattern in &conditions.if_content_matches {
+        if pattern != crate::utils::redact::SECRETS_ALIAS {
+            regex::Regex::new(pattern)
+                .with_context(|| format!("Rule {}: invalid content regex '{}'", index, pattern))?;
+        }
+    }
+    if let Some(ref window) = conditions.only_between {
+        if TimeWindow::parse(window).is_none() {
+            bail!(
+                "Rule {}: 'only_between' must look like \"09:00-18:00\", got '{}'.",
+                index,
+                window
+            );
+        }
+    }
+    for day in &conditions.only_on_days {
+        if schedule::parse_days(day).is_none() {
+            bail!(
+                "Rule {}: '{}' isn't a day — use mon..sun, weekdays or weekends.",
+                index,
+                day
+            );
+        }
+    }
+    for (name, limit) in [
+        ("max_per_minute", conditions.max_per_minute),
+        ("max_per_session", conditions.max_per_session),
+    ] {
+        if limit == Some(0) {
+            bail!(
+                "Rule {}: '{}' must be at least 1 — it counts the action being checked.",
+                index,
+                name
+            );
+        }
+    }
+    for pattern in &conditions.if_account_matches {
+        globset::Glob::new(pattern)
+            .with_context(|| format!("Rule {}: invalid account pattern '{}'", index, pattern))?;
+    }
+    for pattern in &conditions.if_context_matches {
+        globset::Glob::new(pattern)
+            .with_context(|| format!("Rule {}: invalid context pattern '{}'", index, pattern))?;
+    }
+    for (key, patterns) in &conditions.if_context {
+        if key.trim().is_empty() {
+            bail!("Rule {}: 'if_context' keys can't be empty.", index);
+        }
+        if patterns.is_empty() {
+            bail!(
+                "Rule {}: 'if_context' needs at least one pattern for '{}'.",
+                index,
+                key
+            );
+        }
+        for pattern in patterns {
+            globset::Glob::new(pattern).with_context(|| {
+                format!(
+                    "Rule {}: invalid pattern '{}' for '{}' in 'if_context'",
+                    index, pattern, key
+                )
+            })?;
+        }
+    }
+    for pattern in &conditions.if_branch {
+        if pattern.trim().is_empty() {
+            bail!("Rule {}: 'if_branch' entries can't be empty.", index);
+        }
         globset::Glob::new(pattern)
+            .with_context(|| format!("Rule {}: invalid branch pattern '{}'", index, pattern))?;
+    }
+    for pattern in &conditions.if_path_matches {
+        let excluded = pattern.strip_prefix(EXCLUDE_PREFIX);
+        if excluded.is_some_and(|glob| glob.trim().is_empty()) {
+            bail!(
+                "Rule {}: '{}' excludes nothing — put a glob after the '!'.",
+                index,
+                pattern
+            );
+        }
+        globset::Glob::new(excluded.unwrap_or(pattern))
             .with_context(|| format!("Rule {}: invalid glob pattern '{}'", index, pattern))?;
     }
     for pattern in &conditions.unless_path {
@@ -375,6 +1690,100 @@ rules:
         assert!(parse_policy_str(yaml).is_err());
     }
 
+    #[test]
+    fn test_enforce_percentage() {
+        let policy = |rule: &str| parse_policy_str(&format!("law: t\nrules:\n  - {}\n", rule));
+        let enforce = |rule: &str| policy(rule).unwrap().rules[0].conditions().enforce;
+        assert_eq!(enforce("deny: write\n    enforce: 25%"), Some(25));
+        assert_eq!(
+            enforce("require_approval: git_push\n    enforce: 100"),
+            Some(100)
+        );
+        assert_eq!(enforce("deny: write"), None);
+        for bad in [
+            "deny: write\n    enforce: 150%",
+            "deny: write\n    enforce: most",
+            "allow: write\n    enforce: 25%",
+            "log_only: write\n    enforce: 25%",
+        ] {
+            assert!(policy(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_rule_expiry() {
+        let policy = |rule: &str| parse_policy_str(&format!("law: t\nrules:\n  - {}\n", rule));
+        let rule = &policy("allow: write\n    expires: 2025-07-01")
+            .unwrap()
+            .rules[0];
+        assert_eq!(
+            rule.conditions().expires,
+            chrono::NaiveDate::from_ymd_opt(2025, 7, 1)
+        );
+        assert!(rule.describe().ends_with(":expires:2025-07-01"));
+        assert!(rule.is_expired(chrono::NaiveDate::from_ymd_opt(2025, 7, 1).unwrap()));
+        assert!(!rule.is_expired(chrono::NaiveDate::from_ymd_opt(2025, 6, 30).unwrap()));
+        assert!(policy("allow: write\n    expires: next week").is_err());
+        assert!(policy("allow: write\n    expires: 2025-13-01").is_err());
+    }
+
+    #[test]
+    fn test_rule_ids_tags_and_disabling() {
+        let yaml = r#"
+law: test
+rules:
+  - deny: write
+    id: no-env
+    tags: [secrets]
+    if_path_matches: [".env"]
+  - allow: git_push
+    id: temp-allow-deploy
+    tags: [ci, deploy]
+  - deny: delete
+    tags: ci
+  - deny: network
+    enabled: false
+"#;
+        let mut policy = parse_policy_str(yaml).unwrap();
+        assert_eq!(policy.rules.len(), 3);
+        assert!(policy.rules[0]
+            .describe()
+            .starts_with("[no-env] deny:write"));
+        assert_eq!(policy.rules[2].conditions().tags, ["ci"]);
+
+        assert!(policy.disable_rules(&["nope".to_string()]).is_err());
+        assert!(policy.disable_rules(&["tag:nope".to_string()]).is_err());
+        assert_eq!(policy.disable_rules(&["tag:ci".to_string()]).unwrap(), 2);
+        assert_eq!(policy.rules.len(), 1);
+        assert_eq!(policy.disable_rules(&["no-env".to_string()]).unwrap(), 1);
+
+        let policy = |rule: &str| parse_policy_str(&format!("law: t\nrules:\n  - {}\n", rule));
+        for bad in [
+            "deny: write\n    id: \"\"",
+            "deny: write\n    id: has space",
+            "deny: write\n    tags: [\"a,b\"]",
+            "deny: write\n    id: x\n  - deny: delete\n    id: x",
+        ] {
+            assert!(policy(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_log_only_rule() {
+        let policy = parse_policy_str(
+            "law: test\nrules:\n  - log_only: shell\n    if_matches: [\"curl *\"]\n    reason: Soon denied\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            &policy.rules[0],
+            Rule::LogOnly { action: Action::RunCmd, reason: Some(r), .. } if r == "Soon denied"
+        ));
+        assert!(
+            parse_policy_str("law: t\nrules:\n  - log_only: write\n    deny: write\n").is_err()
+        );
+        assert!(parse_policy_str("law: t\nrules:\n  - log_only: teleport\n").is_err());
+    }
+
     #[test]
     fn test_reject_path_conditions_on_run_cmd() {
         let yaml = r#"
@@ -386,6 +1795,667 @@ rules:
         assert!(parse_policy_str(yaml).is_err());
     }
 
+    #[test]
+    fn test_network_request_conditions() {
+        let yaml = r#"
+law: test
+rules:
+  - deny: network
+    if_scheme: http
+    if_method: [POST, PUT]
+  - allow: network
+    max_response_mb: 50
+    max_upload_mb_per_domain: 20
+"#;
+        let policy = parse_policy_str(yaml).unwrap();
+        assert_eq!(policy.rules[0].conditions().if_scheme, vec!["http"]);
+        assert_eq!(policy.rules[0].conditions().if_method, vec!["POST", "PUT"]);
+        assert_eq!(policy.rules[1].conditions().max_response_mb, Some(50));
+        assert_eq!(
+            policy.rules[1].conditions().max_upload_mb_per_domain,
+            Some(20)
+        );
+
+        let yaml = r#"
+law: test
+rules:
+  - deny: write
+    if_scheme: [http]
+"#;
+        assert!(parse_policy_str(yaml).is_err());
+    }
+
+    #[test]
+    fn test_max_file_size() {
+        let policy = |rule: &str| parse_policy_str(&format!("law: t\nrules:\n  - {}\n", rule));
+        let rule = &policy("deny: write\n    max_file_size_kb: 2048")
+            .unwrap()
+            .rules[0];
+        assert_eq!(rule.conditions().max_file_size_kb, Some(2048));
+        assert!(policy("deny: run_cmd\n    max_file_size_kb: 10").is_err());
+    }
+
+    #[test]
+    fn test_require_prior_read() {
+        let policy = |rule: &str| parse_policy_str(&format!("law: t\nrules:\n  - {}\n", rule));
+        let policy = |rule| policy(rule).map(|p| p.rules[0].conditions().require_prior_read);
+        assert!(policy("allow: write\n    require_prior_read: true").unwrap());
+        assert!(!policy("allow: write").unwrap());
+        assert!(policy("deny: write\n    require_prior_read: true").is_err());
+        assert!(policy("allow: delete\n    require_prior_read: true").is_err());
+    }
+
+    #[test]
+    fn test_new_file_conditions() {
+        let policy = |rule: &str| parse_policy_str(&format!("law: t\nrules:\n  - {}\n", rule));
+        let rule = &policy("allow: write\n    if_new_file: true").unwrap().rules[0];
+        assert!(rule.conditions().if_new_file);
+        assert!(!rule.conditions().is_empty());
+        assert!(policy("require_approval: write\n    if_overwrites_existing: true").is_ok());
+        assert!(policy("allow: delete\n    if_new_file: true").is_err());
+        assert!(
+            policy("allow: write\n    if_new_file: true\n    if_overwrites_existing: true")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_any_action() {
+        let policy = |rule: &str| parse_policy_str(&format!("law: t\nrules:\n  - {}\n", rule));
+        let rule = &policy("require_approval: any\n    if_path_matches: [\"infra/**\"]")
+            .unwrap()
+            .rules[0];
+        assert_eq!(*rule.action(), Action::Any);
+        assert!(rule.applies_to(&Action::Delete));
+        assert!(!rule.applies_to(&Action::RunCmd));
+        assert!(policy("deny: \"*\"\n    only_on_days: weekends").is_ok());
+        for bad in [
+            "deny: any\n    if_matches: [\"rm *\"]",
+            "deny: any\n    unless_domain: [github.com]",
+            "allow: any\n    max_diff_lines: 10",
+        ] {
+            assert!(policy(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_diff_budget_conditions() {
+        let yaml = r#"
+law: test
+rules:
+  - require_approval: write
+    max_total_diff_lines: 2000
+    max_total_diff_lines_per_file: 300
+"#;
+        let policy = parse_policy_str(yaml).unwrap();
+        assert_eq!(
+            policy.rules[0].conditions().max_total_diff_lines,
+            Some(2000)
+        );
+        assert_eq!(
+            policy.rules[0].conditions().max_total_diff_lines_per_file,
+            Some(300)
+        );
+
+        let yaml = r#"
+law: test
+rules:
+  - require_approval: delete
+    max_total_diff_lines: 2000
+"#;
+        assert!(parse_policy_str(yaml).is_err());
+    }
+
+    #[test]
+    fn test_codeowners_condition() {
+        let yaml = r#"
+law: test
+rules:
+  - require_approval: write
+    if_codeowners_not: "@me"
+"#;
+        let policy = parse_policy_str(yaml).unwrap();
+        assert_eq!(policy.rules[0].conditions().if_codeowners_not, vec!["@me"]);
+
+        let yaml = r#"
+law: test
+rules:
+  - deny: run_cmd
+    if_codeowners_not: "@me"
+"#;
+        assert!(parse_policy_str(yaml).is_err());
+    }
+
+    #[test]
+    fn test_env_passthrough() {
+        let yaml = r#"
+law: test
+env_passthrough: [NPM_TOKEN, "CARGO_*"]
+rules:
+  - deny: delete
+"#;
+        let policy = parse_policy_str(yaml).unwrap();
+        assert_eq!(policy.env_passthrough, ["NPM_TOKEN", "CARGO_*"]);
+
+        let yaml = r#"
+law: test
+env_passthrough: ["*_KEY"]
+rules:
+  - deny: delete
+"#;
+        assert!(parse_policy_str(yaml).is_err());
+    }
+
+    #[test]
+    fn test_allowed_cwd() {
+        let yaml = r#"
+law: test
+allowed_cwd: [/tmp]
+rules:
+  - deny: delete
+"#;
+        assert_eq!(parse_policy_str(yaml).unwrap().allowed_cwd, ["/tmp"]);
+
+        let yaml = r#"
+law: test
+allowed_cwd: [build]
+rules:
+  - deny: delete
+"#;
+        assert!(parse_policy_str(yaml).is_err());
+    }
+
+    #[test]
+    fn test_shell() {
+        let yaml = r#"
+law: test
+shell: [bash, -lc]
+rules:
+  - deny: delete
+"#;
+        assert_eq!(parse_policy_str(yaml).unwrap().shell, ["bash", "-lc"]);
+        assert!(parse_policy_str("law: test\nshell: ['']\nrules:\n  - deny: delete").is_err());
+    }
+
+    #[test]
+    fn test_regex_if_matches() {
+        let policy = |pattern: &str| {
+            parse_policy_str(&format!(
+                "law: test\nrules:\n  - deny: run_cmd\n    if_matches: ['{}']\n",
+                pattern
+            ))
+        };
+        assert!(policy(r"regex:^rm\s+-\w*[rR]").is_ok());
+        assert!(format!("{:#}", policy("regex:(rm").unwrap_err()).contains("invalid regex"));
+        assert!(policy("regex: ").is_err());
+        // Without the prefix it's a glob, where brackets are literal
+        assert!(policy("(rm").is_ok());
+    }
+
+    #[test]
+    fn test_path_exclusions() {
+        let policy = |patterns: &str| {
+            parse_policy_str(&format!(
+                "law: test\nrules:\n  - allow: write\n    if_path_matches: {}\n",
+                patterns
+            ))
+        };
+        assert!(policy(r#"["src/**", "!src/generated/**"]"#).is_ok());
+        assert!(policy(r#"["!*.lock"]"#).is_ok());
+        assert!(
+            format!("{:#}", policy(r#"["src/**", "!"]"#).unwrap_err()).contains("excludes nothing")
+        );
+        assert!(policy(r#"["!src/[generated"]"#).is_err());
+    }
+
+    #[test]
+    fn test_extends_and_include() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("policies")).unwrap();
+        std::fs::write(
+            dir.path().join("policies/base.yaml"),
+            r#"
+law: org-base
+trusted_packages: [left-pad]
+max_output_kb: 64
+rules:
+  - deny: network
+    unless_domain: [github.com]
+"#,
+        )
+        .unwrap();
+        let policy_path = dir.path().join(".lawctl.yaml");
+        std::fs::write(
+            &policy_path,
+            r#"
+law: project
+extends: safe-dev
+include: ./policies/base.yaml
+trusted_packages: [serde]
+rules:
+  - allow: write
+    if_path_matches: ["generated/**"]
+"#,
+        )
+        .unwrap();
+        let policy = parse_policy_file(&policy_path).unwrap();
+        let safe_dev = parse_policy_str(crate::policy::defaults::SAFE_DEV_YAML).unwrap();
+
+        assert_eq!(policy.law, "project");
+        // Own rules, then the include, then the extended template
+        assert_eq!(policy.rules.len(), 2 + safe_dev.rules.len());
+        assert!(policy.rules[0].describe().contains("generated/**"));
+        assert_eq!(*policy.rules[1].action(), Action::Network);
+        assert_eq!(policy.trusted_packages, ["serde", "left-pad"]);
+        assert_eq!(policy.max_output_kb, Some(64));
+        assert_eq!(policy.included.len(), 2);
+        assert!(policy.included[0].ends_with("base.yaml"));
+        assert_eq!(policy.included[1], "template:safe-dev");
+
+        // A layer-only file needs no rules of its own
+        std::fs::write(&policy_path, "law: project\nextends: safe-dev\n").unwrap();
+        assert_eq!(
+            parse_policy_file(&policy_path).unwrap().rules.len(),
+            safe_dev.rules.len()
+        );
+    }
+
+    #[test]
+    fn test_defaults() {
+        let policy = parse_policy_str(
+            "law: t\ndefaults: {write: deny, shell: require_approval, fetch: allow}\nrules:\n  - allow: read\n",
+        )
+        .unwrap();
+        assert_eq!(
+            policy.defaults.get(&Action::Write),
+            Some(&DefaultDecision::Deny)
+        );
+        assert_eq!(
+            policy.defaults.get(&Action::RunCmd),
+            Some(&DefaultDecision::RequireApproval)
+        );
+        assert_eq!(
+            policy.defaults.get(&Action::Network),
+            Some(&DefaultDecision::Allow)
+        );
+
+        for bad in [
+            "law: t\ndefaults: {teleport: deny}\nrules:\n  - allow: read\n",
+            "law: t\ndefaults: {write: maybe}\nrules:\n  - allow: read\n",
+            "law: t\ndefaults: {write: deny, write_file: allow}\nrules:\n  - allow: read\n",
+            "law: t\ndefaults: {unknown_tool: deny}\nrules:\n  - allow: read\n",
+            "law: t\nunknown_tools: ask\nrules:\n  - allow: read\n",
+        ] {
+            assert!(parse_policy_str(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let schema = json_schema();
+        assert_eq!(schema["title"], "lawctl policy");
+        assert_eq!(schema["required"], serde_json::json!(["law"]));
+        let rule = &schema["$defs"]["Rule"];
+        assert!(rule["properties"]["if_path_matches"].is_object());
+        assert!(rule["properties"]["deny"]["description"]
+            .as_str()
+            .is_some_and(|d| d.starts_with("Block this action")));
+        assert_eq!(rule["oneOf"].as_array().map(Vec::len), Some(4));
+    }
+
+    #[test]
+    fn test_toml_and_json_formats() {
+        let yaml = parse_policy_str(
+            "law: team\nrules:\n  - deny: write\n    if_path_matches: [\"*.env\"]\n  - require_approval: git_push\n    prompt: Push?\n",
+        )
+        .unwrap();
+        let toml = parse_policy_toml(
+            r#"
+law = "team"
+
+[[rules]]
+deny = "write"
+if_path_matches = ["*.env"]
+
+[[rules]]
+require_approval = "git_push"
+prompt = "Push?"
+"#,
+        )
+        .unwrap();
+        let json = parse_policy_json(
+            r#"{"law": "team", "rules": [
+                {"deny": "write", "if_path_matches": "*.env"},
+                {"require_approval": "git_push", "prompt": "Push?"}
+            ]}"#,
+        )
+        .unwrap();
+        for policy in [&toml, &json] {
+            assert_eq!(policy.rules.len(), yaml.rules.len());
+            for (a, b) in policy.rules.iter().zip(&yaml.rules) {
+                assert_eq!(a.describe(), b.describe());
+            }
+        }
+
+        let err = parse_policy_toml("law = \"team\"\nrules = [").unwrap_err();
+        assert!(format!("{:#}", err).contains("Invalid TOML syntax"));
+        let err = parse_policy_json("{\"law\": \"team\", \"rules\": []}").unwrap_err();
+        assert!(format!("{:#}", err).contains("at least one rule"));
+    }
+
+    #[test]
+    fn test_policy_files_by_extension() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let toml_path = dir.path().join(".lawctl.toml");
+        std::fs::write(
+            &toml_path,
+            "law = \"project\"\ninclude = \"base.json\"\n\n[[rules]]\nallow = \"write\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("base.json"),
+            r#"{"law": "base", "rules": [{"deny": "delete"}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(policy_file_in(dir.path()), Some(toml_path.clone()));
+        assert_eq!(locate_policy(&dir.path().join(POLICY_FILE)), toml_path);
+        let policy = parse_policy_file(&toml_path).unwrap();
+        assert_eq!(policy.rules.len(), 2);
+        assert_eq!(*policy.rules[1].action(), Action::Delete);
+
+        // A .lawctl.yaml takes precedence, and explicit paths are kept
+        std::fs::write(
+            dir.path().join(POLICY_FILE),
+            "law: y\nrules:\n  - allow: read\n",
+        )
+        .unwrap();
+        assert_eq!(
+            policy_file_in(dir.path()),
+            Some(dir.path().join(POLICY_FILE))
+        );
+        let other = dir.path().join("other.yaml");
+        assert_eq!(locate_policy(&other), other);
+    }
+
+    #[test]
+    fn test_nested_policies() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let payments = dir.path().join("packages/payments");
+        std::fs::create_dir_all(&payments).unwrap();
+        std::fs::create_dir_all(dir.path().join("node_modules/pkg")).unwrap();
+        std::fs::write(
+            dir.path().join(POLICY_FILE),
+            "law: root\nrules:\n  - allow: write\n",
+        )
+        .unwrap();
+        std::fs::write(
+            payments.join(POLICY_FILE),
+            "law: payments\nrules:\n  - require_approval: write\n",
+        )
+        .unwrap();
+        // Never searched
+        std::fs::write(
+            dir.path().join("node_modules/pkg").join(POLICY_FILE),
+            "law: dep\nrules:\n  - allow: delete\n",
+        )
+        .unwrap();
+
+        let policy = parse_policy_tree(dir.path().join(POLICY_FILE)).unwrap();
+        assert_eq!(policy.law, "root");
+        assert_eq!(policy.rules.len(), 2);
+        assert_eq!(
+            policy.rules[0].conditions().within.as_deref(),
+            Some("packages/payments")
+        );
+        assert_eq!(policy.rules[1].conditions().within, None);
+        assert_eq!(policy.included.len(), 1);
+
+        // Non-file rules can't be confined to a directory, nor can a
+        // nested file loosen anything; a bad file is skipped, not fatal
+        for (rules, problem) in [
+            ("  - deny: git_push\n", "only file rules"),
+            ("  - allow: write\n", "an allow rule"),
+            ("  - deny: [write\n", "Failed to parse"),
+        ] {
+            std::fs::write(
+                payments.join(POLICY_FILE),
+                format!("law: payments\nrules:\n{}", rules),
+            )
+            .unwrap();
+            let policy = parse_policy_tree(dir.path().join(POLICY_FILE)).unwrap();
+            assert_eq!(policy.rules.len(), 1);
+            assert!(policy.included.is_empty());
+            assert_eq!(policy.skipped_layers.len(), 1);
+            assert!(
+                policy.skipped_layers[0].contains(problem),
+                "{}",
+                policy.skipped_layers[0]
+            );
+        }
+    }
+
+    #[test]
+    fn test_include_cycle() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("a.yaml"),
+            "law: a\ninclude: b.yaml\nrules:\n  - deny: delete\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("b.yaml"),
+            "law: b\ninclude: ./a.yaml\nrules:\n  - deny: write\n",
+        )
+        .unwrap();
+        let err = parse_policy_file(dir.path().join("a.yaml")).unwrap_err();
+        assert!(format!("{:#}", err).contains("Include cycle"), "{:#}", err);
+
+        std::fs::write(dir.path().join("c.yaml"), "law: c\ninclude: nowhere.yaml\n").unwrap();
+        let err = parse_policy_file(dir.path().join("c.yaml")).unwrap_err();
+        assert!(format!("{:#}", err).contains("Failed to read included policy"));
+    }
+
+    #[test]
+    fn test_plugins() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let policy_path = dir.path().join(".lawctl.yaml");
+        std::fs::write(
+            &policy_path,
+            r#"
+law: test
+plugins:
+  ticket-open:
+    command: ["./scripts/ticket.sh", "--project", "OPS"]
+    timeout_ms: 500
+  on-path:
+    command: my-checker
+rules:
+  - require_approval: git_push
+    if_plugin: ticket-open
+"#,
+        )
+        .unwrap();
+        let policy = parse_policy_file(&policy_path).unwrap();
+        let ticket = &policy.plugins["ticket-open"];
+        assert_eq!(
+            ticket.command[0],
+            dir.path().join("scripts/ticket.sh").to_string_lossy()
+        );
+        assert_eq!(ticket.timeout_ms, Some(500));
+        // Bare names are looked up on PATH
+        assert_eq!(policy.plugins["on-path"].command, ["my-checker"]);
+        assert_eq!(policy.rules[0].conditions().if_plugin, ["ticket-open"]);
+
+        let unknown = "law: test\nrules:\n  - deny: write\n    if_plugin: nope\n";
+        assert!(parse_policy_str(unknown)
+            .unwrap_err()
+            .to_string()
+            .contains("unknown plugin 'nope'"));
+        let empty = "law: test\nplugins:\n  x:\n    command: []\nrules:\n  - deny: write\n";
+        assert!(parse_policy_str(empty).is_err());
+    }
+
+    #[test]
+    fn test_sub_policies() {
+        let policy = parse_policy_str(
+            r#"
+law: test
+sub_policies:
+  test-only:
+    description: What test runs may do
+    rules:
+      - deny: network
+      - deny: write
+        unless_path: ["tests/**"]
+rules:
+  - allow: run_cmd
+    if_matches: ["pytest *"]
+    sub_policy: test-only
+"#,
+        )
+        .unwrap();
+        assert_eq!(policy.sub_policies["test-only"].rules.len(), 2);
+        assert_eq!(
+            policy.rules[0].conditions().sub_policy.as_deref(),
+            Some("test-only")
+        );
+
+        let unknown = "law: test
+rules:
+  - allow: run_cmd
+    sub_policy: nope
+";
+        assert!(parse_policy_str(unknown)
+            .unwrap_err()
+            .to_string()
+            .contains("unknown sub-policy 'nope'"));
+        let not_allow = "law: test
+sub_policies:
+  x:
+    rules:
+      - deny: network
+rules:
+  - deny: run_cmd
+    sub_policy: x
+";
+        assert!(parse_policy_str(not_allow).is_err());
+        let nested = "law: test
+sub_policies:
+  x:
+    rules:
+      - allow: run_cmd
+        sub_policy: x
+rules:
+  - allow: run_cmd
+";
+        assert!(parse_policy_str(nested).is_err());
+    }
+
+    #[test]
+    fn test_if_context() {
+        let policy = |rule: &str| parse_policy_str(&format!("law: t\nrules:\n  - {}\n", rule));
+        let parsed = policy(
+            "require_approval: network\n    if_context: {tool: WebFetch, user: [\"ci-*\", bot]}",
+        )
+        .unwrap();
+        let if_context = &parsed.rules[0].conditions().if_context;
+        assert_eq!(if_context["tool"], ["WebFetch"]);
+        assert_eq!(if_context["user"], ["ci-*", "bot"]);
+        assert!(policy("deny: write\n    if_context: {tool: []}").is_err());
+        assert!(policy("deny: write\n    if_context: {tool: \"[\"}").is_err());
+    }
+
+    #[test]
+    fn test_classifier() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let policy_path = dir.path().join(".lawctl.yaml");
+        std::fs::write(
+            &policy_path,
+            "law: test\nclassifier: ./scripts/classify.py\nrules:\n  - deny: run_cmd\n    if_tag: [network, crypto-mining]\n",
+        )
+        .unwrap();
+        let policy = parse_policy_file(&policy_path).unwrap();
+        assert_eq!(
+            policy.classifier.unwrap().command,
+            [dir.path().join("scripts/classify.py").to_string_lossy()]
+        );
+        assert_eq!(
+            policy.rules[0].conditions().if_tag,
+            ["network", "crypto-mining"]
+        );
+
+        let no_classifier = "law: test\nrules:\n  - deny: run_cmd\n    if_tag: network\n";
+        assert!(
+            format!("{:#}", parse_policy_str(no_classifier).unwrap_err())
+                .contains("'if_tag' needs a 'classifier'")
+        );
+        let not_a_command =
+            "law: test\nclassifier: c\nrules:\n  - deny: write\n    if_tag: network\n";
+        assert!(
+            format!("{:#}", parse_policy_str(not_a_command).unwrap_err())
+                .contains("only applies to run_cmd")
+        );
+    }
+
+    #[test]
+    fn test_reviewer() {
+        let yaml = r#"
+law: test
+reviewer:
+  url: http://localhost:11434/v1/chat/completions
+  model: llama3
+  timeout_ms: 5000
+rules:
+  - require_approval: run_cmd
+"#;
+        let reviewer = parse_policy_str(yaml).unwrap().reviewer.unwrap();
+        assert_eq!(reviewer.model.as_deref(), Some("llama3"));
+        assert_eq!(reviewer.timeout_ms, Some(5000));
+        assert!(reviewer.api_key_env.is_none());
+
+        let bad_url = "law: test\nreviewer:\n  url: llm.example\nrules:\n  - deny: write\n";
+        assert!(parse_policy_str(bad_url)
+            .unwrap_err()
+            .to_string()
+            .contains("http(s) URL"));
+        let typo = "law: test\nreviewer:\n  url: https://x\n  modle: m\nrules:\n  - deny: write\n";
+        assert!(parse_policy_str(typo).is_err());
+    }
+
+    #[test]
+    fn test_cedar_sources() {
+        let both = r#"
+law: test
+cedar: 'permit(principal, action, resource);'
+cedar_file: policies.cedar
+rules: []
+"#;
+        assert!(parse_policy_str(both)
+            .unwrap_err()
+            .to_string()
+            .contains("not both"));
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let policy_path = dir.path().join(".lawctl.yaml");
+        std::fs::write(
+            &policy_path,
+            "law: test\ncedar_file: policies.cedar\nrules: []\n",
+        )
+        .unwrap();
+        // Relative to the policy file, not the current directory
+        std::fs::write(
+            dir.path().join("policies.cedar"),
+            r#"forbid(principal, action == Action::"write", resource);"#,
+        )
+        .unwrap();
+        let result = parse_policy_file(&policy_path);
+        #[cfg(feature = "cedar")]
+        assert!(result.unwrap().cedar.unwrap().contains("forbid"));
+        #[cfg(not(feature = "cedar"))]
+        assert!(format!("{:#}", result.unwrap_err()).contains("--features cedar"));
+    }
+
     #[test]
     fn test_action_aliases() {
         // Test that various aliases all parse correctly
@@ -398,4 +2468,188 @@ rules:
             assert_eq!(*policy.rules[0].action(), Action::Write);
         }
     }
+
+    #[test]
+    fn test_env_access_conditions() {
+        let policy = parse_policy_str(
+            "law: test\nrules:\n  - deny: env_access\n    if_path_matches: [\"AWS_*\"]\n",
+        )
+        .unwrap();
+        assert_eq!(*policy.rules[0].action(), Action::EnvAccess);
+
+        let err = parse_policy_str(
+            "law: test\nrules:\n  - deny: env_access\n    if_matches: [\"AWS_*\"]\n",
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("variable names"));
+    }
+
+    #[test]
+    fn test_package_conditions() {
+        let policy = parse_policy_str(
+            "law: test\nrules:\n  - deny: package_install\n    unless_package: [react, \"@types/*\"]\n",
+        )
+        .unwrap();
+        assert_eq!(*policy.rules[0].action(), Action::PackageInstall);
+        assert_eq!(
+            policy.rules[0].conditions().unless_package,
+            ["react", "@types/*"]
+        );
+
+        let err = parse_policy_str(
+            "law: test\nrules:\n  - deny: run_cmd\n    if_package_matches: left-pad\n",
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("only apply to package_install"));
+    }
+
+    #[test]
+    fn test_if_content_matches() {
+        let policy = parse_policy_str(
+            "law: test\nrules:\n  - deny: write\n    if_content_matches: [\"@secrets\", \"BEGIN .* KEY\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            policy.rules[0].conditions().if_content_matches,
+            ["@secrets", "BEGIN .* KEY"]
+        );
+
+        let err = parse_policy_str(
+            "law: test\nrules:\n  - deny: run_cmd\n    if_content_matches: \"@secrets\"\n",
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("only applies to write"));
+
+        let err =
+            parse_policy_str("law: test\nrules:\n  - deny: write\n    if_content_matches: \"(\"\n")
+                .unwrap_err();
+        assert!(format!("{:#}", err).contains("invalid content regex"));
+    }
+
+    #[test]
+    fn test_time_conditions() {
+        let policy = parse_policy_str(
+            "law: test\nrules:\n  - require_approval: git_push\n    only_between: \"09:00-18:00\"\n    only_on_days: weekdays\n",
+        )
+        .unwrap();
+        let conditions = policy.rules[0].conditions();
+        assert_eq!(conditions.only_between.as_deref(), Some("09:00-18:00"));
+        assert_eq!(conditions.only_on_days, ["weekdays"]);
+
+        let err =
+            parse_policy_str("law: test\nrules:\n  - deny: write\n    only_between: \"9am-5pm\"\n")
+                .unwrap_err();
+        assert!(format!("{:#}", err).contains("'only_between' must look like"));
+
+        let err = parse_policy_str(
+            "law: test\nrules:\n  - deny: write\n    only_on_days: [mon, funday]\n",
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("'funday' isn't a day"));
+    }
+
+    #[test]
+    fn test_rate_limits() {
+        let policy = parse_policy_str(
+            "law: test\nrules:\n  - require_approval: delete\n    max_per_minute: 30\n    max_per_session: 200\n",
+        )
+        .unwrap();
+        let conditions = policy.rules[0].conditions();
+        assert_eq!(conditions.max_per_minute, Some(30));
+        assert_eq!(conditions.max_per_session, Some(200));
+
+        let err = parse_policy_str("law: test\nrules:\n  - deny: write\n    max_per_minute: 0\n")
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("'max_per_minute' must be at least 1"));
+    }
+
+    #[test]
+    fn test_remote_actions() {
+        let policy = parse_policy_str(
+            "law: test\nrules:\n  - deny: ssh\n    if_matches: [\"rm *\"]\n  - require_approval: rsync\n    unless_domain: [backup.example.com]\n",
+        )
+        .unwrap();
+        assert_eq!(*policy.rules[0].action(), Action::RemoteExec);
+        assert_eq!(*policy.rules[1].action(), Action::RemoteCopy);
+
+        for yaml in [
+            "law: test\nrules:\n  - deny: remote_exec\n    if_path_matches: [\"*\"]\n",
+            "law: test\nrules:\n  - deny: remote_copy\n    if_matches: [\"*\"]\n",
+        ] {
+            assert!(parse_policy_str(yaml).is_err(), "{}", yaml);
+        }
+    }
+
+    #[test]
+    fn test_k8s_conditions() {
+        let policy = parse_policy_str(
+            "law: test\nrules:\n  - deny: kubectl\n    if_context_matches: [\"*prod*\"]\n    if_verb: delete\n",
+        )
+        .unwrap();
+        assert_eq!(*policy.rules[0].action(), Action::K8s);
+        assert_eq!(policy.rules[0].conditions().if_verb, ["delete"]);
+
+        let err = parse_policy_str("law: test\nrules:\n  - deny: run_cmd\n    if_verb: delete\n")
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("only apply to k8s actions"));
+    }
+
+    #[test]
+    fn test_if_threat() {
+        let policy = parse_policy_str(
+            "law: test\nrules:\n  - deny: run_cmd\n    if_threat: [crypto_mining, reverse_shell]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            policy.rules[0].conditions().if_threat,
+            ["crypto_mining", "reverse_shell"]
+        );
+
+        let err =
+            parse_policy_str("law: test\nrules:\n  - deny: run_cmd\n    if_threat: malware\n")
+                .unwrap_err();
+        assert!(format!("{:#}", err).contains("isn't a threat"));
+        let err =
+            parse_policy_str("law: test\nrules:\n  - deny: write\n    if_threat: reverse_shell\n")
+                .unwrap_err();
+        assert!(format!("{:#}", err).contains("only applies to run_cmd and remote_exec"));
+    }
+
+    #[test]
+    fn test_cloud_conditions() {
+        let policy = parse_policy_str(
+            "law: test\nrules:\n  - deny: gcloud\n    if_effect: destroy\n    if_account_matches: \"*-prod\"\n",
+        )
+        .unwrap();
+        assert_eq!(*policy.rules[0].action(), Action::Cloud);
+        assert_eq!(policy.rules[0].conditions().if_effect, ["destroy"]);
+        assert_eq!(policy.rules[0].conditions().if_account_matches, ["*-prod"]);
+
+        let err = parse_policy_str("law: test\nrules:\n  - deny: cloud\n    if_effect: nuke\n")
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("isn't an effect"));
+        let err = parse_policy_str("law: test\nrules:\n  - deny: k8s\n    if_effect: destroy\n")
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("only apply to cloud actions"));
+    }
+
+    #[test]
+    fn test_if_branch() {
+        let policy = parse_policy_str(
+            "law: test\nrules:\n  - require_approval: git_push\n    if_branch: [main, \"release/*\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            policy.rules[0].conditions().if_branch,
+            ["main", "release/*"]
+        );
+
+        let err = parse_policy_str("law: test\nrules:\n  - deny: write\n    if_branch: main\n")
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("only applies to git_push"));
+
+        let err = parse_policy_str("law: test\nrules:\n  - deny: git_push\n    if_branch: \"[\"\n")
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("invalid branch pattern"));
+    }
 }
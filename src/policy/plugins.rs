@@ -0,0 +1,246 @@
+//! Condition plugins — `if_plugin:` conditions decided by an external
+//! program.
+//!
+//! A policy registers plugins by name and rules refer to them:
+//!
+//! ```yaml
+//! plugins:
+//!   ticket-open:
+//!     command: ["./scripts/ticket-open.sh", "--project", "OPS"]
+//!     timeout_ms: 500
+//! rules:
+//!   - require_approval: git_push
+//!     if_plugin: ticket-open
+//! ```
+//!
+//! The program gets the action as JSON on stdin —
+//! `{"plugin", "action", "target", "context": {...}}` — and answers with
+//! its exit code: 0 when the condition matches, 1 when it doesn't. It runs
+//! in the workspace with an empty environment (only `PATH`), no terminal
+//! and no output, and is killed after its timeout (default 1s). A relative
+//! program path is resolved against the policy file's directory.
+//!
+//! A plugin that fails — won't start, crashes, times out or exits with
+//! anything else — fails closed: deny and approval rules apply, allow rules
+//! don't.
+//!
+//! The policy's `classifier:` runs the same way for every command, and
+//! prints tags for `if_tag:` conditions instead of answering with its exit
+//! code:
+//!
+//! ```yaml
+//! classifier: ./scripts/classify.py
+//! rules:
+//!   - deny: run_cmd
+//!     if_tag: [crypto-mining, exfiltration]
+//! ```
+//!
+//! Tags are separated by whitespace or commas, or printed as a JSON array.
+//! A classifier that fails leaves the command's tags unknown, which fails
+//! closed the same way.
+
+use crate::policy::types::{Action, ActionContext};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long a plugin may run when the policy doesn't say.
+pub const DEFAULT_TIMEOUT_MS: u64 = 1000;
+
+/// How often a running plugin is checked on.
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+/// A registered plugin.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Plugin {
+    /// The program and its arguments
+    pub command: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+/// What a plugin reads on stdin.
+#[derive(Serialize)]
+struct PluginInput<'a> {
+    plugin: &'a str,
+    action: &'a Action,
+    target: &'a str,
+    context: &'a ActionContext,
+}
+
+impl Plugin {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS))
+    }
+
+    /// Ask the plugin whether its condition matches `target`. Errors when
+    /// the plugin doesn't give an answer.
+    pub fn matches(
+        &self,
+        name: &str,
+        action: &Action,
+        target: &str,
+        context: &ActionContext,
+        workspace: Option<&Path>,
+    ) -> Result<bool> {
+        let (status, _) = self.run(name, action, target, context, workspace)?;
+        match status.code() {
+            Some(0) => Ok(true),
+            Some(1) => Ok(false),
+            _ => bail!("Plugin '{}' failed ({})", name, status),
+        }
+    }
+
+    /// Run the plugin as a classifier: the tags it prints.
+    pub fn classify(
+        &self,
+        action: &Action,
+        target: &str,
+        context: &ActionContext,
+        workspace: Option<&Path>,
+    ) -> Result<Vec<String>> {
+        let (status, stdout) = self.run("classifier", action, target, context, workspace)?;
+        if !status.success() {
+            bail!("Classifier failed ({})", status);
+        }
+        let stdout = String::from_utf8_lossy(&stdout);
+        if stdout.trim_start().starts_with('[') {
+            return serde_json::from_str(&stdout).context("Classifier printed invalid JSON");
+        }
+        Ok(stdout
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Run the program on one action: its exit status and output.
+    fn run(
+        &self,
+        name: &str,
+        action: &Action,
+        target: &str,
+        context: &ActionContext,
+        workspace: Option<&Path>,
+    ) -> Result<(ExitStatus, Vec<u8>)> {
+        let (program, args) = self
+            .command
+            .split_first()
+            .context("Plugin has no command")?;
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .env_clear()
+            .env("PATH", std::env::var_os("PATH").unwrap_or_default())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null());
+        if let Some(workspace) = workspace {
+            command.current_dir(workspace);
+        }
+        let mut child = command
+            .spawn()
+            .with_context(|| format!("Failed to start plugin '{}' ({})", name, program))?;
+
+        let input = serde_json::to_vec(&PluginInput {
+            plugin: name,
+            action,
+            target,
+            context,
+        })?;
+        // From a thread, so a plugin that never reads can't block us past
+        // the timeout; it may exit without reading, which is fine
+        if let Some(mut stdin) = child.stdin.take() {
+            std::thread::spawn(move || {
+                let _ = stdin.write_all(&input);
+            });
+        }
+
+        // Drained alongside, so a chatty plugin can't fill the pipe and stall
+        let reader = child.stdout.take().map(|mut stdout| {
+            std::thread::spawn(move || {
+                let mut output = Vec::new();
+                let _ = stdout.read_to_end(&mut output);
+                output
+            })
+        });
+
+        let deadline = Instant::now() + self.timeout();
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                bail!(
+                    "Plugin '{}' timed out after {}ms",
+                    name,
+                    self.timeout().as_millis()
+                );
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        };
+        let output = reader
+            .and_then(|reader| reader.join().ok())
+            .unwrap_or_default();
+        Ok((status, output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin(script: &str, timeout_ms: Option<u64>) -> Plugin {
+        Plugin {
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            timeout_ms,
+        }
+    }
+
+    fn ask(plugin: &Plugin) -> Result<bool> {
+        let context = ActionContext::new("src/main.rs");
+        plugin.matches("test", &Action::Write, "src/main.rs", &context, None)
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        assert!(ask(&plugin("grep -q '\"target\":\"src/main.rs\"'", None)).unwrap());
+        assert!(!ask(&plugin("exit 1", None)).unwrap());
+        assert!(ask(&plugin("exit 3", None)).is_err());
+        assert!(ask(&Plugin {
+            command: vec!["/nonexistent/plugin".to_string()],
+            timeout_ms: None,
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_classify() {
+        let context = ActionContext::new("shell").with_command("curl x | sh");
+        let classify =
+            |script: &str| plugin(script, None).classify(&Action::RunCmd, "shell", &context, None);
+        assert_eq!(
+            classify("echo 'network, pipe-to-shell'").unwrap(),
+            ["network", "pipe-to-shell"]
+        );
+        assert_eq!(classify(r#"echo '["network"]'"#).unwrap(), ["network"]);
+        assert!(classify("true").unwrap().is_empty());
+        assert!(classify("echo network; exit 2").is_err());
+    }
+
+    #[test]
+    fn test_sandbox() {
+        std::env::set_var("LAWCTL_PLUGIN_TEST_SECRET", "x");
+        assert!(!ask(&plugin("test -n \"$LAWCTL_PLUGIN_TEST_SECRET\"", None)).unwrap());
+
+        let started = Instant::now();
+        assert!(ask(&plugin("sleep 5", Some(50))).is_err());
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,200 @@
+//! Self-protection: paths an agent may never write or delete.
+//!
+//! An agent that can rewrite lawctl's own pieces can switch lawctl off —
+//! remove the hook from `~/.claude/settings.json`, replace the
+//! `lawctl-hook` binary with a no-op, loosen the policy file, or tamper
+//! with the audit logs. These are denied before any policy rule is
+//! consulted, so no policy (not even `permissive`) can open them up.
+//!
+//! Claude Code settings files are protected whole: besides the hook
+//! entries they hold `disableAllHooks` and the permission allowlist.
+//! Policy files are protected by name wherever they are, so the agent
+//! can't add a nested policy either.
+
+use crate::policy::parser::POLICY_FILES;
+use crate::utils::locations;
+use std::path::{Component, Path, PathBuf};
+
+/// Binaries that make up lawctl.
+const BINARIES: &[&str] = &["lawctl", "lawctl-hook", "lawctl-shim"];
+
+/// Where `lawctl setup` looks for binaries, besides next to itself.
+const INSTALL_DIRS: &[&str] = &["/usr/local/bin", "/usr/bin"];
+
+/// Claude Code settings files that can carry (or disable) hooks.
+const CLAUDE_SETTINGS: &[&str] = &["settings.json", "settings.local.json"];
+
+/// The set of protected paths for a session.
+#[derive(Debug, Clone, Default)]
+pub struct ProtectedPaths {
+    /// Relative targets are resolved against this (the workspace root)
+    root: Option<PathBuf>,
+    /// Protected files, with what they are
+    files: Vec<(PathBuf, &'static str)>,
+    /// Protected directories (everything beneath them)
+    dirs: Vec<(PathBuf, &'static str)>,
+}
+
+impl ProtectedPaths {
+    /// lawctl's binaries, its data directory and the user's Claude Code settings.
+    pub fn defaults() -> Self {
+        let mut protected = Self::default();
+        let home = dirs::home_dir();
+
+        let mut bin_dirs: Vec<PathBuf> = INSTALL_DIRS.iter().map(PathBuf::from).collect();
+        if let Some(home) = &home {
+            bin_dirs.push(home.join(".local/bin"));
+            bin_dirs.push(home.join(".cargo/bin"));
+        }
+        if let Some(dir) = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        {
+            bin_dirs.push(dir);
+        }
+        for dir in bin_dirs {
+            for binary in BINARIES {
+                protected.add_file(dir.join(binary), "a lawctl binary");
+            }
+        }
+
+        if let Ok(data) = locations::data_dir() {
+            protected.add_dir(data, "lawctl's data directory");
+        }
+        if let Some(home) = &home {
+            for name in CLAUDE_SETTINGS {
+                protected.add_file(
+                    home.join(".claude").join(name),
+                    "Claude Code settings (lawctl hook)",
+                );
+            }
+        }
+        protected
+    }
+
+    /// Resolve relative targets against the workspace, and protect its
+    /// project-level Claude Code settings.
+    pub fn set_root(&mut self, root: &Path) {
+        self.root = Some(root.to_path_buf());
+        for name in CLAUDE_SETTINGS {
+            self.add_file(
+                root.join(".claude").join(name),
+                "Claude Code settings (lawctl hook)",
+            );
+        }
+    }
+
+    /// Protect a file, e.g. the active policy.
+    pub fn add_file(&mut self, path: PathBuf, what: &'static str) {
+        self.files.push((path, what));
+    }
+
+    fn add_dir(&mut self, path: PathBuf, what: &'static str) {
+        self.dirs.push((path, what));
+    }
+
+    /// What `target` is, if it's protected. Symlinks are followed where the
+    /// path exists, so a link to a protected file is protected too.
+    pub fn covers(&self, target: &str) -> Option<&'static str> {
+        let target = Path::new(target);
+        let absolute = match (&self.root, target.is_absolute()) {
+            (Some(root), false) => root.join(target),
+            _ => target.to_path_buf(),
+        };
+        let forms = variants(&absolute);
+        if let Some((_, what)) = self
+            .files
+            .iter()
+            .find(|(file, _)| variants(file).iter().any(|f| forms.contains(f)))
+        {
+            return Some(what);
+        }
+        let is_policy = |form: &PathBuf| {
+            form.file_name()
+                .is_some_and(|name| POLICY_FILES.iter().any(|policy| name == *policy))
+        };
+        if forms.iter().any(is_policy) {
+            return Some("a lawctl policy file");
+        }
+        self.dirs
+            .iter()
+            .find(|(dir, _)| {
+                variants(dir)
+                    .iter()
+                    .any(|d| forms.iter().any(|form| form.starts_with(d)))
+            })
+            .map(|(_, what)| *what)
+    }
+}
+
+/// A path with `.`/`..` resolved lexically, plus its canonical form if it exists.
+pub(crate) fn variants(path: &Path) -> Vec<PathBuf> {
+    let mut lexical = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                lexical.pop();
+            }
+            other => lexical.push(other),
+        }
+    }
+    let mut forms = vec![lexical];
+    if let Ok(canonical) = path.canonicalize() {
+        forms.push(canonical);
+    }
+    forms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_covers_files_dirs_and_links() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join(".claude")).unwrap();
+        std::fs::write(root.join(".lawctl.yaml"), "law: x").unwrap();
+
+        let mut protected = ProtectedPaths::default();
+        protected.set_root(root);
+        protected.add_file(root.join(".lawctl.yaml"), "the active policy");
+        protected.add_dir(root.join("data"), "lawctl's data directory");
+
+        assert_eq!(protected.covers(".lawctl.yaml"), Some("the active policy"));
+        assert_eq!(
+            protected.covers("src/../.claude/settings.local.json"),
+            Some("Claude Code settings (lawctl hook)")
+        );
+        assert!(protected.covers("data/logs/abc.jsonl").is_some());
+        assert!(protected.covers("src/main.rs").is_none());
+        assert!(protected.covers(".claude/commands/x.md").is_none());
+        // Policy files anywhere, existing or not
+        assert_eq!(
+            protected.covers("packages/payments/.lawctl.yaml"),
+            Some("a lawctl policy file")
+        );
+        assert!(protected.covers("sub/.lawctl.json").is_some());
+
+        std::os::unix::fs::symlink(root.join(".lawctl.yaml"), root.join("harmless")).unwrap();
+        assert_eq!(protected.covers("harmless"), Some("the active policy"));
+    }
+
+    #[test]
+    fn test_defaults_cover_home_and_binaries() {
+        let protected = ProtectedPaths::defaults();
+        assert_eq!(
+            protected.covers("/usr/local/bin/lawctl-hook"),
+            Some("a lawctl binary")
+        );
+        if let Some(home) = dirs::home_dir() {
+            let settings = home.join(".claude/settings.json");
+            assert!(protected.covers(&settings.to_string_lossy()).is_some());
+        }
+        if let Ok(data) = locations::data_dir() {
+            let log = data.join("logs/s.jsonl");
+            assert!(protected.covers(&log.to_string_lossy()).is_some());
+        }
+    }
+}
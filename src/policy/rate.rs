@@ -0,0 +1,224 @@
+//! Per-action counts for `max_per_minute` and `max_per_session`, running
+//! diff totals for `max_total_diff_lines`, and the files read for
+//! `require_prior_read`.
+//!
+//! Each action is fine on its own, but 500 deletes in ten seconds isn't.
+//! The gateway keeps one counter per session; the hook, which runs once per
+//! tool call, rebuilds it from the session's audit log. Every evaluated
+//! action counts, whatever it was decided. Diff lines and reads only count
+//! when the action went through.
+
+use crate::audit::LogEntry;
+use crate::policy::types::Action;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+/// How many actions of one kind a session has made, this one included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ActionCounts {
+    /// In the last 60 seconds
+    pub per_minute: usize,
+    /// Since the session started
+    pub per_session: usize,
+}
+
+/// Diff lines a session has written, this write included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct DiffTotals {
+    /// To every file
+    pub session: usize,
+    /// To the file being written
+    pub file: usize,
+}
+
+/// Running diff line totals for a session, per file.
+#[derive(Debug, Default)]
+pub struct DiffBudget {
+    per_file: HashMap<String, usize>,
+}
+
+impl DiffBudget {
+    /// Totals holding the writes a session's log records as made.
+    pub fn from_entries(entries: &[LogEntry]) -> Self {
+        let mut budget = Self::default();
+        for entry in entries {
+            if entry.action == Action::Write && went_through(entry) {
+                let lines = entry.diff.as_deref().map_or(0, |d| d.lines().count());
+                budget.add(&entry.target, lines);
+            }
+        }
+        budget
+    }
+
+    /// The totals if a write of `lines` to `file` went ahead.
+    pub fn with(&self, file: &str, lines: usize) -> DiffTotals {
+        let session: usize = self.per_file.values().sum();
+        DiffTotals {
+            session: session.saturating_add(lines),
+            file: self
+                .per_file
+                .get(file)
+                .copied()
+                .unwrap_or(0)
+                .saturating_add(lines),
+        }
+    }
+
+    /// Count a write of `lines` to `file` that went ahead.
+    pub fn add(&mut self, file: &str, lines: usize) {
+        let total = self.per_file.entry(file.to_string()).or_default();
+        *total = total.saturating_add(lines);
+    }
+}
+
+/// Files a session has read, workspace-relative.
+#[derive(Debug, Default)]
+pub struct ReadFiles {
+    paths: HashSet<String>,
+}
+
+impl ReadFiles {
+    /// The files a session's log records it reading.
+    pub fn from_entries(entries: &[LogEntry], workspace: &Path) -> Self {
+        let mut reads = Self::default();
+        for entry in entries {
+            if entry.action == Action::Read && went_through(entry) {
+                // Multi-file reads are logged as one comma-separated target
+                for path in entry.target.split(", ") {
+                    reads.add(path, workspace);
+                }
+            }
+        }
+        reads
+    }
+
+    /// Record a read of `path`.
+    pub fn add(&mut self, path: &str, workspace: &Path) {
+        self.paths.insert(relative(path, workspace));
+    }
+
+    /// Whether the session has read `path`.
+    pub fn contains(&self, path: &str, workspace: &Path) -> bool {
+        self.paths.contains(&relative(path, workspace))
+    }
+}
+
+/// Whether a logged action went ahead: allowed, or approved by a human.
+fn went_through(entry: &LogEntry) -> bool {
+    !entry.is_human()
+        && (entry.decision.is_allowed()
+            || (entry.decision.is_requires_approval() && entry.approved_by.is_some()))
+}
+
+/// `path` relative to `workspace`, so absolute and relative mentions of a
+/// file compare equal.
+fn relative(path: &str, workspace: &Path) -> String {
+    let path = Path::new(path);
+    let path = path.strip_prefix(workspace).unwrap_or(path);
+    let path = path.strip_prefix("./").unwrap_or(path);
+    path.to_string_lossy().into_owned()
+}
+
+/// Running per-action counts for a session.
+#[derive(Debug, Default)]
+pub struct ActionCounter {
+    /// Per action: the session total and the times of the last minute's actions
+    seen: HashMap<Action, (usize, VecDeque<DateTime<Utc>>)>,
+}
+
+impl ActionCounter {
+    /// A counter holding what a session's log already records.
+    pub fn from_entries(entries: &[LogEntry]) -> Self {
+        let mut counter = Self::default();
+        for entry in entries {
+            // Byte counts logged after a transfer, not separate actions
+            if entry.network.as_ref().is_some_and(|n| n.transfer_record) {
+                continue;
+            }
+            counter.record(&entry.action, entry.timestamp);
+        }
+        counter
+    }
+
+    /// Count an action made at `at` and return the counts including it.
+    pub fn record(&mut self, action: &Action, at: DateTime<Utc>) -> ActionCounts {
+        let (total, recent) = self.seen.entry(action.clone()).or_default();
+        *total += 1;
+        recent.push_back(at);
+        let cutoff = at - Duration::seconds(60);
+        while recent.front().is_some_and(|&t| t <= cutoff) {
+            recent.pop_front();
+        }
+        ActionCounts {
+            per_minute: recent.len(),
+            per_session: *total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_files_compare_relative_to_workspace() {
+        let workspace = Path::new("/work");
+        let mut reads = ReadFiles::default();
+        reads.add("/work/src/main.rs", workspace);
+        reads.add("./README.md", workspace);
+        assert!(reads.contains("src/main.rs", workspace));
+        assert!(reads.contains("/work/README.md", workspace));
+        assert!(!reads.contains("/work/src/lib.rs", workspace));
+    }
+
+    #[test]
+    fn test_diff_budget_counts_per_file() {
+        let mut budget = DiffBudget::default();
+        budget.add("a.rs", 10);
+        budget.add("b.rs", 5);
+        budget.add("a.rs", 3);
+        assert_eq!(
+            budget.with("a.rs", 2),
+            DiffTotals {
+                session: 20,
+                file: 15
+            }
+        );
+        assert_eq!(
+            budget.with("c.rs", 1),
+            DiffTotals {
+                session: 19,
+                file: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_counts_per_action_and_window() {
+        let start = Utc::now();
+        let mut counter = ActionCounter::default();
+        for i in 0..3 {
+            counter.record(&Action::Delete, start + Duration::seconds(i));
+        }
+        let counts = counter.record(&Action::Write, start);
+        assert_eq!(
+            counts,
+            ActionCounts {
+                per_minute: 1,
+                per_session: 1
+            }
+        );
+
+        // A minute after the first delete, it drops out of the window
+        let counts = counter.record(&Action::Delete, start + Duration::seconds(60));
+        assert_eq!(
+            counts,
+            ActionCounts {
+                per_minute: 3,
+                per_session: 4
+            }
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! Protection score — how much of the usual risk surface a policy covers.
+//!
+//! `lawctl check` grades a policy out of 100 across six risk categories
+//! (secrets, deletes, pushes, network, package installs, infrastructure as
+//! code) and suggests the next rule to add for each one that isn't fully
+//! covered. It's a rough guide for people who don't want to reason about
+//! rule ordering — the linter covers the finer points.
+
+use crate::policy::types::*;
+use crate::utils::paths::EXCLUDE_PREFIX;
+use crate::utils::term::glyph;
+use colored::Colorize;
+
+/// Paths that hold credentials.
+const SECRET_MARKERS: &[&str] = &[".env", ".ssh", ".pem", ".key", ".p12", ".keystore", ".aws"];
+
+/// Paths that describe infrastructure.
+const IAC_MARKERS: &[&str] = &[
+    ".tf",
+    "terraform",
+    "infra",
+    "k8s",
+    "helm",
+    "Dockerfile",
+    "docker-compose",
+    ".github/workflows",
+    "pulumi",
+];
+
+/// Commands that change infrastructure.
+const IAC_COMMANDS: &[&str] = &["terraform", "kubectl", "helm", "pulumi", "cdk"];
+
+/// The score for one risk category.
+#[derive(Debug)]
+pub struct CategoryScore {
+    pub name: &'static str,
+    pub points: u32,
+    pub max: u32,
+    /// What would raise the score, when it isn't full
+    pub next_step: Option<String>,
+}
+
+/// A policy's protection score and how it breaks down.
+#[derive(Debug)]
+pub struct ProtectionScore {
+    pub categories: Vec<CategoryScore>,
+}
+
+impl ProtectionScore {
+    /// Points out of 100.
+    pub fn total(&self) -> u32 {
+        self.categories.iter().map(|c| c.points).sum()
+    }
+
+    /// A one-word verdict for the total.
+    pub fn grade(&self) -> &'static str {
+        match self.total() {
+            90.. => "strong",
+            70..=89 => "good",
+            40..=69 => "fair",
+            _ => "weak",
+        }
+    }
+
+    /// Format for terminal output.
+    pub fn display(&self) -> String {
+        let total = self.total();
+        let verdict = match total {
+            70.. => format!("{}/100 ({})", total, self.grade()).green(),
+            40..=69 => format!("{}/100 ({})", total, self.grade()).yellow(),
+            _ => format!("{}/100 ({})", total, self.grade()).red(),
+        };
+        let mut out = format!("  Protection score: {}\n", verdict.bold());
+        for category in &self.categories {
+            let line = format!(
+                "    {:<18} {:>2}/{}",
+                category.name, category.points, category.max
+            );
+            if category.points == category.max {
+                out.push_str(&format!("\n{}", line));
+            } else {
+                out.push_str(&format!("\n{}", line.yellow()));
+            }
+        }
+        let steps: Vec<_> = self
+            .categories
+            .iter()
+            .filter_map(|c| c.next_step.as_deref())
+            .collect();
+        if !steps.is_empty() {
+            out.push_str(&format!("\n\n  {}:", "Next steps".green()));
+            for step in steps {
+                out.push_str(&format!("\n    {} {}", glyph("•"), step));
+            }
+        }
+        out
+    }
+}
+
+/// Score a policy's coverage of the common risk categories.
+pub fn score_policy(policy: &Policy) -> ProtectionScore {
+    ProtectionScore {
+        categories: vec![
+            score_secrets(policy),
+            score_deletes(policy),
+            score_pushes(policy),
+            score_network(policy),
+            score_package_installs(policy),
+            score_iac(policy),
+        ],
+    }
+}
+
+/// How the policy treats an action, looking at the first rule that decides
+/// it broadly and at whether any rule narrows it down.
+#[derive(Debug, PartialEq)]
+enum Coverage {
+    /// An unconditional allow comes before any deny or approval rule
+    AllowedOutright,
+    /// A deny or approval rule matching the predicate is reachable
+    Guarded,
+    /// Nothing in the policy guards it
+    Unguarded,
+}
+
+fn coverage(policy: &Policy, action: Action, guards: impl Fn(&Conditions) -> bool) -> Coverage {
+    for rule in &policy.rules {
+        if !rule.applies_to(&action) {
+            continue;
+        }
+        match rule {
+            Rule::Allow { conditions, .. } if conditions.is_empty() => {
+                return Coverage::AllowedOutright
+            }
+            Rule::Deny { conditions, .. } | Rule::RequireApproval { conditions, .. }
+                if guards(conditions) =>
+            {
+                return Coverage::Guarded
+            }
+            _ => {}
+        }
+    }
+    Coverage::Unguarded
+}
+
+fn has_rule_for(policy: &Policy, action: &Action) -> bool {
+    policy.rules.iter().any(|rule| rule.applies_to(action))
+}
+
+fn mentions(patterns: &[String], markers: &[&str]) -> bool {
+    patterns
+        .iter()
+        .any(|p| !p.starts_with(EXCLUDE_PREFIX) && markers.iter().any(|marker| p.contains(marker)))
+}
+
+fn category(
+    name: &'static str,
+    points: u32,
+    max: u32,
+    next_step: impl Into<String>,
+) -> CategoryScore {
+    CategoryScore {
+        name,
+        points,
+        max,
+        next_step: (points < max).then(|| next_step.into()),
+    }
+}
+
+fn score_secrets(policy: &Policy) -> CategoryScore {
+    let secret_paths = |c: &Conditions| mentions(&c.if_path_matches, SECRET_MARKERS);
+    let writes = coverage(policy, Action::Write, secret_paths) == Coverage::Guarded;
+    let reads = coverage(policy, Action::Read, secret_paths) == Coverage::Guarded;
+    let points = if writes { 15 } else { 0 } + if reads { 5 } else { 0 };
+    let step = if writes {
+        "Keep agents from reading secrets too: deny: read, if_path_matches: [\"*.env\", \".ssh/*\"]"
+    } else {
+        "Protect credentials: deny: write, if_path_matches: [\"*.env\", \".ssh/*\", \"*.pem\", \"*.key\"]"
+    };
+    category("Secrets", points, 20, step)
+}
+
+fn score_deletes(policy: &Policy) -> CategoryScore {
+    // Deletes are denied by default, so only an allow rule weakens them
+    let points = match coverage(policy, Action::Delete, |_| true) {
+        Coverage::Guarded => 20,
+        Coverage::AllowedOutright => 0,
+        Coverage::Unguarded if has_rule_for(policy, &Action::Delete) => 10,
+        Coverage::Unguarded => 15,
+    };
+    category(
+        "Deletes",
+        points,
+        20,
+        "Limit deletes to scratch space: deny: delete, unless_path: [\"/tmp\", \"build/\"]",
+    )
+}
+
+fn score_pushes(policy: &Policy) -> CategoryScore {
+    let points = match coverage(policy, Action::GitPush, |_| true) {
+        Coverage::Guarded => 20,
+        Coverage::AllowedOutright => 0,
+        Coverage::Unguarded if has_rule_for(policy, &Action::GitPush) => 10,
+        Coverage::Unguarded => 15,
+    };
+    category(
+        "Pushes",
+        points,
+        20,
+        "Review pushes before they leave: require_approval: git_push",
+    )
+}
+
+fn score_network(policy: &Policy) -> CategoryScore {
+    let allowlisted = |c: &Conditions| !c.unless_domain.is_empty();
+    let points = if coverage(policy, Action::Network, allowlisted) == Coverage::Guarded {
+        15
+    } else if coverage(policy, Action::Network, |_| true) == Coverage::Guarded {
+        8
+    } else {
+        0
+    };
+    category(
+        "Network",
+        points,
+        15,
+        "Allowlist the hosts agents need: deny: network, unless_domain: [\"github.com\", \"registry.npmjs.org\"]",
+    )
+}
+
+fn score_package_installs(policy: &Policy) -> CategoryScore {
+    let points = if coverage(policy, Action::PackageInstall, |_| true) == Coverage::Guarded {
+        15
+    } else if policy.require_approval_on_new_dependency
+        || !policy.deny_new_deps_with_license.is_empty()
+    {
+        5
+    } else {
+        0
+    };
+    category(
+        "Package installs",
+        points,
+        15,
+        "Review new packages: require_approval: package_install, unless_package: [\"<packages you trust>\"]",
+    )
+}
+
+fn score_iac(policy: &Policy) -> CategoryScore {
+    let files = coverage(policy, Action::Write, |c| {
+        mentions(&c.if_path_matches, IAC_MARKERS)
+    }) == Coverage::Guarded;
+    let commands = coverage(policy, Action::RunCmd, |c| {
+        mentions(&c.if_matches, IAC_COMMANDS)
+    }) == Coverage::Guarded;
+    let points = if files { 5 } else { 0 } + if commands { 5 } else { 0 };
+    let step = if files {
+        "Ask before infrastructure changes: require_approval: run_cmd, if_matches: [\"terraform apply*\", \"kubectl apply*\"]"
+    } else {
+        "Ask before infrastructure edits: require_approval: write, if_path_matches: [\"**/*.tf\", \".github/workflows/**\"]"
+    };
+    category("Infra as code", points, 10, step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::parser;
+
+    fn points(score: &ProtectionScore, name: &str) -> u32 {
+        score
+            .categories
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap()
+            .points
+    }
+
+    #[test]
+    fn test_score_permissive_policy_is_weak() {
+        let yaml = r#"
+law: wide-open
+rules:
+  - allow: write
+  - allow: delete
+  - allow: git_push
+"#;
+        let score = score_policy(&parser::parse_policy_str(yaml).unwrap());
+        assert_eq!(score.total(), 0);
+        assert_eq!(score.grade(), "weak");
+        assert!(score.categories.iter().all(|c| c.next_step.is_some()));
+    }
+
+    #[test]
+    fn test_score_safe_dev() {
+        let policy = parser::parse_policy_str(crate::policy::defaults::SAFE_DEV_YAML).unwrap();
+        let score = score_policy(&policy);
+        assert_eq!(points(&score, "Secrets"), 15);
+        assert_eq!(points(&score, "Deletes"), 20);
+        assert_eq!(points(&score, "Pushes"), 20);
+        assert!(score.total() >= 50, "got {}", score.total());
+    }
+
+    #[test]
+    fn test_score_allow_before_deny_doesnt_count() {
+        let yaml = r#"
+law: shadowed
+rules:
+  - allow: write
+  - deny: write
+    if_path_matches: ["*.env"]
+"#;
+        let score = score_policy(&parser::parse_policy_str(yaml).unwrap());
+        assert_eq!(points(&score, "Secrets"), 0);
+    }
+
+    #[test]
+    fn test_score_full_marks() {
+        let yaml = r#"
+law: locked-down
+rules:
+  - deny: write
+    if_path_matches: ["*.env", ".ssh/*"]
+  - deny: read
+    if_path_matches: ["*.env"]
+  - require_approval: write
+    if_path_matches: ["**/*.tf"]
+  - require_approval: run_cmd
+    if_matches: ["terraform apply*"]
+  - deny: delete
+    unless_path: ["/tmp"]
+  - require_approval: git_push
+  - deny: network
+    unless_domain: ["github.com"]
+  - require_approval: package_install
+"#;
+        let score = score_policy(&parser::parse_policy_str(yaml).unwrap());
+        assert_eq!(score.total(), 100);
+        assert_eq!(score.grade(), "strong");
+        assert!(score.categories.iter().all(|c| c.next_step.is_none()));
+    }
+}
@@ -0,0 +1,325 @@
+//! Unit tests for policies: `.lawctl.tests.yaml` and `lawctl test`.
+//!
+//! A test file lists actions and the decision the policy should make on
+//! each, so a policy can be checked before an agent ever runs under it —
+//! and re-checked in CI whenever it changes:
+//!
+//! ```yaml
+//! tests:
+//!   - name: secrets stay untouched
+//!     action: write
+//!     target: .env
+//!     expect: denied
+//!   - action: run_cmd
+//!     command: git push origin main
+//!     expect: requires_approval
+//!   - action: network
+//!     url: https://api.github.com/repos
+//!     expect: allowed
+//! ```
+//!
+//! Each case is evaluated on its own through the real policy engine, with
+//! a context built from the fields given. Nothing is executed or logged.
+
+use crate::policy::{Action, ActionContext, Decision, PolicyEngine};
+use crate::utils::{cloud, kube};
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// The default test file, next to the policy.
+pub const TEST_FILE: &str = ".lawctl.tests.yaml";
+
+/// A parsed `.lawctl.tests.yaml`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TestFile {
+    /// The policy under test, relative to the test file. Defaults to the
+    /// policy in the same directory.
+    #[serde(default)]
+    pub policy: Option<PathBuf>,
+    pub tests: Vec<TestCase>,
+}
+
+/// One action and the decision it should get.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TestCase {
+    /// Shown in the results; defaults to the action and target
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Any action name a rule accepts (`write`, `run_cmd`, `git_push`...)
+    pub action: String,
+    #[serde(default)]
+    pub target: Option<String>,
+    /// Further targets, for multi-target actions like `delete`
+    #[serde(default)]
+    pub targets: Vec<String>,
+    /// For run_cmd, remote_exec, k8s and cloud: the command line
+    #[serde(default)]
+    pub command: Option<String>,
+    /// For writes: the content written
+    #[serde(default)]
+    pub diff: Option<String>,
+    /// For network: the URL requested
+    #[serde(default)]
+    pub url: Option<String>,
+    /// For network: the HTTP method
+    #[serde(default)]
+    pub method: Option<String>,
+    /// For moves: where the targets go
+    #[serde(default)]
+    pub destination: Option<String>,
+    /// For chmod: the requested mode
+    #[serde(default)]
+    pub mode: Option<String>,
+    /// For package_install: the package manager
+    #[serde(default)]
+    pub package_manager: Option<String>,
+    pub expect: Expectation,
+}
+
+/// The decision a case expects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Expectation {
+    #[serde(alias = "allow")]
+    Allowed,
+    #[serde(alias = "deny")]
+    Denied,
+    #[serde(alias = "require_approval", alias = "approval")]
+    RequiresApproval,
+}
+
+impl Expectation {
+    fn matches(&self, decision: &Decision) -> bool {
+        match self {
+            Expectation::Allowed => decision.is_allowed(),
+            Expectation::Denied => decision.is_denied(),
+            Expectation::RequiresApproval => decision.is_requires_approval(),
+        }
+    }
+}
+
+impl fmt::Display for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expectation::Allowed => write!(f, "allowed"),
+            Expectation::Denied => write!(f, "denied"),
+            Expectation::RequiresApproval => write!(f, "requires approval"),
+        }
+    }
+}
+
+/// How one case went.
+#[derive(Debug)]
+pub struct TestResult {
+    pub name: String,
+    pub expected: Expectation,
+    pub decision: Decision,
+}
+
+impl TestResult {
+    pub fn passed(&self) -> bool {
+        self.expected.matches(&self.decision)
+    }
+}
+
+impl TestFile {
+    /// Read and parse a test file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_yaml::from_str(&text)
+            .with_context(|| format!("Failed to parse test file {}", path.display()))
+    }
+}
+
+/// An action as given on the command line or in a test case, to build
+/// the context the engine sees. Shared by `lawctl test` and `lawctl explain`.
+#[derive(Debug, Clone, Default)]
+pub struct ActionSpec {
+    pub action: String,
+    pub target: Option<String>,
+    pub targets: Vec<String>,
+    pub command: Option<String>,
+    pub diff: Option<String>,
+    pub url: Option<String>,
+    pub method: Option<String>,
+    pub destination: Option<String>,
+    pub mode: Option<String>,
+    pub package_manager: Option<String>,
+}
+
+impl TestCase {
+    /// The action the case describes.
+    pub fn spec(&self) -> ActionSpec {
+        ActionSpec {
+            action: self.action.clone(),
+            target: self.target.clone(),
+            targets: self.targets.clone(),
+            command: self.command.clone(),
+            diff: self.diff.clone(),
+            url: self.url.clone(),
+            method: self.method.clone(),
+            destination: self.destination.clone(),
+            mode: self.mode.clone(),
+            package_manager: self.package_manager.clone(),
+        }
+    }
+
+    fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => self.spec().describe(),
+        }
+    }
+}
+
+impl ActionSpec {
+    /// The action and context to evaluate.
+    pub fn action_and_context(&self) -> Result<(Action, ActionContext)> {
+        let Some(action) = Action::from_str_loose(&self.action) else {
+            bail!("Unknown action '{}'", self.action);
+        };
+        let command = self.command.as_deref();
+        let mut context = match (&action, command) {
+            (Action::RunCmd, Some(command)) => {
+                ActionContext::new(self.target.as_deref().unwrap_or("shell")).with_command(command)
+            }
+            (Action::RemoteExec, Some(command)) => match &self.target {
+                Some(host) => ActionContext::new(host.as_str()).with_command(command),
+                None => bail!("A remote_exec test needs the host as its target"),
+            },
+            (Action::K8s, Some(command)) => match kube::invocations(command).first() {
+                Some(invocation) => ActionContext::new(
+                    self.target
+                        .clone()
+                        .or_else(|| invocation.context.clone())
+                        .unwrap_or_default(),
+                )
+                .with_kube(invocation),
+                None => bail!("'{}' isn't a kubectl or helm command", command),
+            },
+            (Action::Cloud, Some(command)) => match cloud::invocations(command).first() {
+                Some(invocation) => ActionContext::new(
+                    self.target
+                        .clone()
+                        .or_else(|| invocation.account.clone())
+                        .unwrap_or_else(|| invocation.provider.clone()),
+                )
+                .with_cloud(invocation),
+                None => bail!("'{}' isn't an aws, gcloud or az command", command),
+            },
+            (_, Some(_)) => bail!("'command' only applies to run_cmd, remote_exec, k8s and cloud"),
+            _ => {
+                let target = self.target.clone().or_else(|| self.url.clone());
+                let Some(target) = target else {
+                    bail!("A '{}' test needs a target", action);
+                };
+                ActionContext::with_targets(
+                    std::iter::once(target).chain(self.targets.iter().cloned()),
+                )
+            }
+        };
+        if let Some(diff) = &self.diff {
+            context = context.with_diff(diff.as_str());
+        }
+        if let Some(url) = &self.url {
+            context = context.with_url(url);
+        }
+        if let Some(method) = &self.method {
+            context = context.with_method(method.to_ascii_uppercase());
+        }
+        if let Some(destination) = &self.destination {
+            context = context.with_destination(destination.as_str());
+        }
+        if let Some(mode) = &self.mode {
+            context = context.with_mode(mode.as_str());
+        }
+        if let Some(manager) = &self.package_manager {
+            context = context.with_package_manager(manager.as_str());
+        }
+        if action == Action::GitPush {
+            let target = context.target.clone();
+            context = context.with_branch(&target);
+        }
+        Ok((action, context))
+    }
+
+    /// The action and what it's on, e.g. "write .env".
+    pub fn describe(&self) -> String {
+        let target = self
+            .command
+            .as_deref()
+            .or(self.url.as_deref())
+            .or(self.target.as_deref())
+            .unwrap_or_default();
+        format!("{} {}", self.action, target)
+    }
+}
+
+/// Evaluate every case. A case that can't be turned into an action (an
+/// unknown action name, a missing target) is an error naming it.
+pub fn run(engine: &PolicyEngine, cases: &[TestCase]) -> Result<Vec<TestResult>> {
+    cases
+        .iter()
+        .enumerate()
+        .map(|(i, case)| {
+            let (action, context) = case
+                .spec()
+                .action_and_context()
+                .with_context(|| format!("Test #{} ({})", i + 1, case.display_name()))?;
+            Ok(TestResult {
+                name: case.display_name(),
+                expected: case.expect,
+                decision: engine.evaluate(&action, &context),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::parser;
+
+    #[test]
+    fn test_run_cases() {
+        let policy = parser::parse_policy_str(
+            "law: t\nrules:\n  - deny: write\n    if_path_matches: [\"*.env\"]\n  - require_approval: git_push\n    if_branch: [main]\n  - deny: run_cmd\n    if_matches: [\"rm -rf *\"]\n  - allow: network\n    if_domain: [api.github.com]\n",
+        )
+        .unwrap();
+        let engine = PolicyEngine::new(policy).unwrap();
+        let file: TestFile = serde_yaml::from_str(
+            "tests:\n  - name: secrets\n    action: write\n    target: .env\n    expect: denied\n  - action: git_push\n    target: main\n    expect: approval\n  - action: run_cmd\n    command: rm -rf build\n    expect: deny\n  - action: network\n    url: https://api.github.com/repos\n    expect: allowed\n  - action: write\n    target: src/lib.rs\n    expect: denied\n",
+        )
+        .unwrap();
+
+        let results = run(&engine, &file.tests).unwrap();
+        let passed: Vec<bool> = results.iter().map(TestResult::passed).collect();
+        assert_eq!(passed, [true, true, true, true, false]);
+        assert_eq!(results[0].name, "secrets");
+        assert_eq!(results[2].name, "run_cmd rm -rf build");
+    }
+
+    #[test]
+    fn test_bad_cases_are_errors() {
+        let engine = PolicyEngine::new(
+            parser::parse_policy_str("law: t\nrules:\n  - allow: write\n").unwrap(),
+        )
+        .unwrap();
+        for case in [
+            "action: teleport\ntarget: x\nexpect: allowed",
+            "action: write\nexpect: allowed",
+            "action: write\ntarget: a\ncommand: ls\nexpect: allowed",
+        ] {
+            let case: TestCase = serde_yaml::from_str(case).unwrap();
+            assert!(run(&engine, &[case]).is_err());
+        }
+        assert!(
+            serde_yaml::from_str::<TestCase>("action: write\ntarget: a\nexpect: maybe").is_err()
+        );
+    }
+}
@@ -3,34 +3,90 @@
 //! These types define the structure of policies, rules, actions, and decisions
 //! that form the heart of Lawctl's security enforcement.
 
+pub use crate::approval::reviewer::Reviewer;
+pub use crate::policy::plugins::Plugin;
+pub use crate::utils::lockfile::Dependency;
+use anyhow::{bail, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
 /// Represents an action an AI agent is attempting to perform.
 /// Every tool call from an agent maps to one of these variants.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Action {
     /// Writing content to a file (includes creating new files)
     Write,
+    /// Reading a file's contents
+    Read,
     /// Deleting a file or directory
     Delete,
+    /// Moving or renaming a file or directory
+    Move,
+    /// Changing a file's or directory's permissions
+    Chmod,
     /// Running a shell command
     RunCmd,
     /// Pushing to a git remote
     GitPush,
     /// Making a network request (future — included for policy completeness)
     Network,
+    /// Reading an environment variable (`$AWS_SECRET_ACCESS_KEY`, `printenv`)
+    EnvAccess,
+    /// Installing registry packages (`npm install`, `pip install`, `cargo add`)
+    PackageInstall,
+    /// Running commands on another machine (`ssh host cmd`); the target is the host
+    RemoteExec,
+    /// Copying files to or from another machine (`scp`, `rsync host:`);
+    /// the target is the host
+    RemoteCopy,
+    /// Working with a Kubernetes cluster (`kubectl`, `helm`); the target is
+    /// the kubeconfig context
+    K8s,
+    /// Using a cloud provider's CLI (`aws`, `gcloud`, `az`); the target is
+    /// the account (AWS profile, GCP project, Azure subscription)
+    Cloud,
+    /// Not an agent action: a gateway request turned away before it could
+    /// be evaluated (malformed JSON, an unknown action, missing fields).
+    /// Logged so probing of the gateway shows up; no rule can name it.
+    #[serde(rename = "rejected_request")]
+    Rejected,
+    /// Not an agent action: a Claude Code tool the hook doesn't know how to
+    /// check (a new built-in, an MCP tool). Decided by `unknown_tools`;
+    /// no rule can name it.
+    UnknownTool,
+    /// Only in rules: every agent action, e.g. `require_approval: any,
+    /// if_path_matches: ["infra/**"]`. With path conditions it covers the
+    /// actions that touch paths (write, read, delete, move, chmod).
+    Any,
+    /// Not an agent action: the human approving a plan's files and
+    /// commands up front (Claude Code leaving plan mode). Logged with the
+    /// plan, which pre-authorizes what it covers; no rule can name it.
+    PlanApproval,
 }
 
 impl fmt::Display for Action {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Action::Write => write!(f, "write"),
+            Action::Read => write!(f, "read"),
             Action::Delete => write!(f, "delete"),
+            Action::Move => write!(f, "move"),
+            Action::Chmod => write!(f, "chmod"),
             Action::RunCmd => write!(f, "run_cmd"),
             Action::GitPush => write!(f, "git_push"),
             Action::Network => write!(f, "network"),
+            Action::EnvAccess => write!(f, "env_access"),
+            Action::PackageInstall => write!(f, "package_install"),
+            Action::RemoteExec => write!(f, "remote_exec"),
+            Action::RemoteCopy => write!(f, "remote_copy"),
+            Action::K8s => write!(f, "k8s"),
+            Action::Cloud => write!(f, "cloud"),
+            Action::Rejected => write!(f, "rejected_request"),
+            Action::UnknownTool => write!(f, "unknown_tool"),
+            Action::Any => write!(f, "any"),
+            Action::PlanApproval => write!(f, "plan_approval"),
         }
     }
 }
@@ -41,10 +97,20 @@ impl Action {
     pub fn from_str_loose(s: &str) -> Option<Action> {
         match s.to_lowercase().trim() {
             "write" | "write_file" | "file_write" => Some(Action::Write),
+            "read" | "read_file" | "file_read" => Some(Action::Read),
             "delete" | "delete_file" | "file_delete" | "rm" => Some(Action::Delete),
+            "move" | "move_file" | "rename" | "mv" => Some(Action::Move),
+            "chmod" | "permissions" | "change_mode" => Some(Action::Chmod),
             "run_cmd" | "shell" | "exec" | "command" | "cmd" => Some(Action::RunCmd),
             "git_push" | "push" | "git" => Some(Action::GitPush),
             "network" | "net" | "http" | "fetch" => Some(Action::Network),
+            "env_access" | "env_var" | "environment" | "getenv" => Some(Action::EnvAccess),
+            "package_install" | "install" | "package" | "packages" => Some(Action::PackageInstall),
+            "remote_exec" | "ssh" | "remote" => Some(Action::RemoteExec),
+            "remote_copy" | "scp" | "rsync" => Some(Action::RemoteCopy),
+            "k8s" | "kubernetes" | "kubectl" | "helm" => Some(Action::K8s),
+            "cloud" | "aws" | "gcloud" | "az" => Some(Action::Cloud),
+            "any" | "all" | "*" => Some(Action::Any),
             _ => None,
         }
     }
@@ -54,6 +120,26 @@ impl Action {
     pub fn is_destructive(&self) -> bool {
         matches!(self, Action::Delete | Action::GitPush | Action::RunCmd)
     }
+
+    /// Whether this action's targets are file paths.
+    pub fn touches_paths(&self) -> bool {
+        matches!(
+            self,
+            Action::Write | Action::Read | Action::Delete | Action::Move | Action::Chmod
+        )
+    }
+
+    /// Whether a rule written for this action covers `action`: the same
+    /// action, or for `any`, every action an agent makes.
+    pub fn covers(&self, action: &Action) -> bool {
+        match self {
+            Action::Any => !matches!(
+                action,
+                Action::Rejected | Action::UnknownTool | Action::PlanApproval
+            ),
+            own => own == action,
+        }
+    }
 }
 
 /// Conditions that narrow when a rule applies.
@@ -61,7 +147,8 @@ impl Action {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Conditions {
     /// Rule applies only when the target path matches these glob patterns.
-    /// Example: ["src/**", "tests/**"]
+    /// Example: ["src/**", "tests/**"]. A `!` prefix excludes, e.g.
+    /// ["src/**", "!src/generated/**"]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub if_path_matches: Vec<String>,
 
@@ -71,7 +158,10 @@ pub struct Conditions {
     pub unless_path: Vec<String>,
 
     /// For run_cmd: rule applies when the command matches these patterns.
-    /// Supports glob-style matching: "rm -rf *", "curl * | bash"
+    /// Supports glob-style matching: "rm -rf *", "curl * | bash", and
+    /// regular expressions with a `regex:` prefix: "regex:^rm\s+-\w*[rR]".
+    /// A glob naming one command is matched against the parsed words, so
+    /// "rm -rf *" also catches "rm --recursive --force .".
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub if_matches: Vec<String>,
 
@@ -80,9 +170,206 @@ pub struct Conditions {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_diff_lines: Option<usize>,
 
+    /// For write rules: rule applies only once the session's writes add up
+    /// to more than this many diff lines (this one included), so a rewrite
+    /// split into many small writes still counts.
+    /// Example: `require_approval: write, max_total_diff_lines: 2000`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_diff_lines: Option<usize>,
+
+    /// For write rules: as `max_total_diff_lines`, counting only the
+    /// session's writes to the file being written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_diff_lines_per_file: Option<usize>,
+
+    /// For write rules: rule applies only when the content written is
+    /// larger than this many kilobytes.
+    /// Example: `require_approval: write, max_file_size_kb: 512`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_file_size_kb: Option<u64>,
+
+    /// For allow-write rules: overwriting an existing file needs the session
+    /// to have read it first; a blind overwrite is denied instead.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub require_prior_read: bool,
+
+    /// For write rules: rule applies only when the write creates a file.
+    /// Example: `allow: write, if_path_matches: ["tests/**"], if_new_file: true`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub if_new_file: bool,
+
+    /// For write rules: rule applies only when the write replaces a file
+    /// that already exists.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub if_overwrites_existing: bool,
+
     /// For network rules: only allow these domains.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub unless_domain: Vec<String>,
+
+    /// For network rules: rule applies only to these URL schemes.
+    /// Example: `deny: network, if_scheme: [http]` blocks plain HTTP.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub if_scheme: Vec<String>,
+
+    /// For network rules: rule applies only to these HTTP methods.
+    /// Only visible for plain HTTP and tool fetches — HTTPS tunnels hide it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub if_method: Vec<String>,
+
+    /// For network rules: cap on the response size, in megabytes.
+    /// Doesn't affect matching — the rule that decides a request sets its cap,
+    /// enforced by the local proxy and recorded in audit entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_mb: Option<u64>,
+
+    /// For network rules: rule applies only once this session has sent more
+    /// than this many megabytes to the request's host (this request included).
+    /// Example: `require_approval: network, max_upload_mb_per_domain: 10`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_upload_mb_per_domain: Option<u64>,
+
+    /// For write/delete rules: rule applies only to files that CODEOWNERS
+    /// assigns to someone other than these owners. `"@me"` stands for the
+    /// workspace's `git config github.user`. Unowned files never match.
+    /// Example: `require_approval: write, if_codeowners_not: "@me"`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub if_codeowners_not: Vec<String>,
+
+    /// For write rules: rule applies only when the written content mentions
+    /// one of these symbols — protects critical code even in allowed paths.
+    /// Example: `deny: write, if_diff_touches: ["fn authenticate"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub if_diff_touches: Vec<String>,
+
+    /// For write rules: rule applies only when the written content matches
+    /// one of these regular expressions, whatever the file. `"@secrets"`
+    /// stands for lawctl's built-in set of secret patterns.
+    /// Example: `deny: write, if_content_matches: ["@secrets"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub if_content_matches: Vec<String>,
+
+    /// Rule applies only when one of these plugins (registered under the
+    /// policy's `plugins:`) says it does.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub if_plugin: Vec<String>,
+
+    /// For run_cmd rules: rule applies only when the policy's `classifier`
+    /// tags the command with one of these.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub if_tag: Vec<String>,
+
+    /// For run_cmd and remote_exec rules: rule applies only to commands the
+    /// built-in detection flags as one of these — `crypto_mining`,
+    /// `reverse_shell`, `resource_abuse`.
+    /// Example: `deny: run_cmd, if_threat: [crypto_mining, reverse_shell]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub if_threat: Vec<String>,
+
+    /// For package_install rules: rule applies only to packages whose names
+    /// match these glob patterns. Example: `deny: package_install,
+    /// if_package_matches: ["*-miner", "@evil/*"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub if_package_matches: Vec<String>,
+
+    /// For package_install rules: packages (name globs) the rule doesn't
+    /// apply to — for deny rules, an allowlist.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unless_package: Vec<String>,
+
+    /// For git_push rules: rule applies only when pushing to a branch that
+    /// matches these glob patterns.
+    /// Example: `require_approval: git_push, if_branch: ["main", "release/*"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub if_branch: Vec<String>,
+
+    /// For k8s rules: rule applies only when the command talks to a
+    /// kubeconfig context matching these glob patterns.
+    /// Example: `deny: k8s, if_context_matches: ["*prod*"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub if_context_matches: Vec<String>,
+
+    /// For k8s rules: rule applies only to these kubectl/helm verbs.
+    /// Example: `require_approval: k8s, if_verb: [delete, apply]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub if_verb: Vec<String>,
+
+    /// For cloud rules: rule applies only to commands with one of these
+    /// effects — `read`, `mutate` or `destroy`.
+    /// Example: `deny: cloud, if_effect: destroy`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub if_effect: Vec<String>,
+
+    /// For cloud rules: rule applies only to accounts (AWS profiles, GCP
+    /// projects, Azure subscriptions) matching these glob patterns.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub if_account_matches: Vec<String>,
+
+    /// Rule applies only when the action's metadata matches: for every key,
+    /// one of its values matches one of the glob patterns. Keys are what
+    /// the integration passed along (`tool`, `model`, `user`, `agent`,
+    /// `process`, `remote`, `packages`...) and the context's own fields by name
+    /// (`branch`, `domain`, `package_manager`...); a key the action doesn't
+    /// have never matches.
+    /// Example: `require_approval: network, if_context: {tool: WebFetch}`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub if_context: BTreeMap<String, Vec<String>>,
+
+    /// Rule applies only during this local-time window, `HH:MM-HH:MM`
+    /// (overnight windows like `22:00-06:00` work too).
+    /// Example: `require_approval: git_push, only_between: "09:00-18:00"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub only_between: Option<String>,
+
+    /// Rule applies only on these days (`mon`..`sun`, `weekdays`, `weekends`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub only_on_days: Vec<String>,
+
+    /// Rule applies only once the session has made more than this many
+    /// actions of the rule's kind in the last minute (this one included).
+    /// Example: `require_approval: delete, max_per_minute: 30`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_per_minute: Option<usize>,
+
+    /// Rule applies only once the session has made more than this many
+    /// actions of the rule's kind in total (this one included).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_per_session: Option<usize>,
+
+    /// Set on rules from a subdirectory's `.lawctl.yaml`: the directory,
+    /// relative to the workspace root, whose files they're confined to.
+    /// Not written in policy files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub within: Option<String>,
+
+    /// Not a condition: for deny and require_approval rules, the
+    /// percentage of sessions the rule is enforced in. In the rest it only
+    /// logs what it would have done, like a `log_only` rule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enforce: Option<u8>,
+
+    /// Not a condition: a stable name for the rule, shown in decisions and
+    /// audit entries and accepted by `lawctl run --disable-rule`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Not a condition: labels for grouping rules, e.g. `[ci, secrets]`.
+    /// `--disable-rule tag:NAME` turns off every rule carrying the tag.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Not a condition: the date the rule stops applying, for temporary
+    /// exceptions. From that day on the engine skips it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<chrono::NaiveDate>,
+
+    /// Not a condition: for allow-run_cmd rules, a sub-policy (from the
+    /// policy's `sub_policies:`) that everything the command starts is held
+    /// to as well, e.g. `allow: run_cmd, if_matches: ["pytest *"],
+    /// sub_policy: test-only`. Enforced by the gateway, which knows which
+    /// process each request comes from (Linux only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub_policy: Option<String>,
 }
 
 impl Conditions {
@@ -91,7 +378,36 @@ impl Conditions {
             && self.unless_path.is_empty()
             && self.if_matches.is_empty()
             && self.max_diff_lines.is_none()
+            && self.max_total_diff_lines.is_none()
+            && self.max_total_diff_lines_per_file.is_none()
+            && self.max_file_size_kb.is_none()
+            && !self.require_prior_read
+            && !self.if_new_file
+            && !self.if_overwrites_existing
             && self.unless_domain.is_empty()
+            && self.if_scheme.is_empty()
+            && self.if_method.is_empty()
+            && self.max_response_mb.is_none()
+            && self.max_upload_mb_per_domain.is_none()
+            && self.if_codeowners_not.is_empty()
+            && self.if_diff_touches.is_empty()
+            && self.if_content_matches.is_empty()
+            && self.if_plugin.is_empty()
+            && self.if_tag.is_empty()
+            && self.if_threat.is_empty()
+            && self.if_package_matches.is_empty()
+            && self.unless_package.is_empty()
+            && self.if_branch.is_empty()
+            && self.if_context_matches.is_empty()
+            && self.if_verb.is_empty()
+            && self.if_effect.is_empty()
+            && self.if_account_matches.is_empty()
+            && self.if_context.is_empty()
+            && self.only_between.is_none()
+            && self.only_on_days.is_empty()
+            && self.max_per_minute.is_none()
+            && self.max_per_session.is_none()
+            && self.within.is_none()
     }
 }
 
@@ -123,6 +439,17 @@ pub enum Rule {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         prompt: Option<String>,
     },
+    /// Log that a deny rule would have blocked the action — for trying a
+    /// stricter rule out before enforcing it. The rules after it still
+    /// decide; when they allow, the entry is marked as a would-deny.
+    LogOnly {
+        action: Action,
+        #[serde(default)]
+        conditions: Conditions,
+        /// Why the action would have been denied.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
 }
 
 impl Rule {
@@ -132,6 +459,7 @@ impl Rule {
             Rule::Deny { action, .. } => action,
             Rule::Allow { action, .. } => action,
             Rule::RequireApproval { action, .. } => action,
+            Rule::LogOnly { action, .. } => action,
         }
     }
 
@@ -141,16 +469,34 @@ impl Rule {
             Rule::Deny { conditions, .. } => conditions,
             Rule::Allow { conditions, .. } => conditions,
             Rule::RequireApproval { conditions, .. } => conditions,
+            Rule::LogOnly { conditions, .. } => conditions,
+        }
+    }
+
+    pub fn conditions_mut(&mut self) -> &mut Conditions {
+        match self {
+            Rule::Deny { conditions, .. } => conditions,
+            Rule::Allow { conditions, .. } => conditions,
+            Rule::RequireApproval { conditions, .. } => conditions,
+            Rule::LogOnly { conditions, .. } => conditions,
         }
     }
 
     /// Human-readable description of this rule (used in logs and approval prompts).
     pub fn describe(&self) -> String {
-        match self {
+        let desc = match self {
             Rule::Deny {
                 action, conditions, ..
+            }
+            | Rule::LogOnly {
+                action, conditions, ..
             } => {
-                let mut desc = format!("deny:{}", action);
+                let kind = if matches!(self, Rule::LogOnly { .. }) {
+                    "log_only"
+                } else {
+                    "deny"
+                };
+                let mut desc = format!("{}:{}", kind, action);
                 if !conditions.if_path_matches.is_empty() {
                     desc.push_str(&format!(
                         ":if_path_matches:{}",
@@ -186,6 +532,48 @@ impl Rule {
             Rule::RequireApproval { action, .. } => {
                 format!("require_approval:{}", action)
             }
+        };
+        let desc = match self.conditions().within {
+            Some(ref dir) => format!("{}:within:{}", desc, dir),
+            None => desc,
+        };
+        let desc = match self.conditions().enforce {
+            Some(percent) => format!("{}:enforce:{}%", desc, percent),
+            None => desc,
+        };
+        let desc = match self.conditions().expires {
+            Some(date) => format!("{}:expires:{}", desc, date),
+            None => desc,
+        };
+        match self.conditions().id {
+            Some(ref id) => format!("[{}] {}", id, desc),
+            None => desc,
+        }
+    }
+
+    /// Whether the rule applies to `action`. An `any` rule with path
+    /// conditions, or from a nested policy, only applies to actions on files.
+    pub fn applies_to(&self, action: &Action) -> bool {
+        let conditions = self.conditions();
+        let path_scoped = !conditions.if_path_matches.is_empty()
+            || !conditions.unless_path.is_empty()
+            || conditions.within.is_some();
+        self.action().covers(action)
+            && !(*self.action() == Action::Any && path_scoped && !action.touches_paths())
+    }
+
+    /// Whether the rule's `expires` date has come by `today`.
+    pub fn is_expired(&self, today: chrono::NaiveDate) -> bool {
+        self.conditions().expires.is_some_and(|date| today >= date)
+    }
+
+    /// Whether `--disable-rule` selector `selector` picks this rule: its id,
+    /// or `tag:NAME` for any rule tagged NAME.
+    pub fn selected_by(&self, selector: &str) -> bool {
+        let conditions = self.conditions();
+        match selector.strip_prefix("tag:") {
+            Some(tag) => conditions.tags.iter().any(|t| t == tag),
+            None => conditions.id.as_deref() == Some(selector),
         }
     }
 }
@@ -200,8 +588,201 @@ pub struct Policy {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    /// The built-in template revision this file was generated from
+    /// (e.g. "safe-dev@2"). Only the file's own; never inherited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+
     /// Ordered list of rules. First match wins.
     pub rules: Vec<Rule>,
+
+    /// What an action gets when no rule matches it, overriding the
+    /// built-in default (destructive actions denied, the rest allowed)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub defaults: BTreeMap<Action, DefaultDecision>,
+
+    /// What the hook does with tools it can't map to an action (MCP tools,
+    /// new Claude Code tools). Unset means allow.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unknown_tools: Option<UnknownTools>,
+
+    /// Record the session and policy on commits pushed through the gateway
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotate_pushes: Option<AnnotatePushes>,
+
+    /// Deny lockfile changes that add a dependency under one of these
+    /// licenses (SPDX ids, e.g. "GPL-3.0")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny_new_deps_with_license: Vec<String>,
+
+    /// Ask a human before a lockfile change adds any dependency
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub require_approval_on_new_dependency: bool,
+
+    /// Package names exempt from typosquat checks on install commands
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_packages: Vec<String>,
+
+    /// Oldest lawctl this policy was written for (e.g. "0.3"). Older hooks
+    /// and gateways still run it, but log a version-skew warning.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requires_lawctl: Option<String>,
+
+    /// Environment variables passed to the agent on top of the default safe
+    /// set (exact names, or prefixes ending in `*`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_passthrough: Vec<String>,
+
+    /// Directories outside the workspace that commands may `cd` into
+    /// (absolute paths, e.g. "/tmp")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_cwd: Vec<String>,
+
+    /// The interpreter gateway-run commands go through, command appended
+    /// (e.g. ["bash", "-lc"]; default `sh -c`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shell: Vec<String>,
+
+    /// Output kept per stream of a gateway-run command, in KB (default 1024)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_kb: Option<u64>,
+
+    /// Keep the full output of truncated commands in a file next to the
+    /// session log
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub spill_output: bool,
+
+    /// Hold a gateway write for approval when the file changed on disk
+    /// since the agent last read or wrote it, e.g. in the human's editor
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub detect_stale_writes: bool,
+
+    /// Also keep the policy's history (see `policy::history`) as a git
+    /// repository, one commit per version
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub commit_policy_history: bool,
+
+    /// Cedar policies consulted before `rules` (lawctl built with the
+    /// `cedar` feature)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cedar: Option<String>,
+
+    /// Programs deciding `if_plugin` conditions, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub plugins: BTreeMap<String, Plugin>,
+
+    /// Tighter rules for the processes of commands allowed with
+    /// `sub_policy`, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sub_policies: BTreeMap<String, SubPolicy>,
+
+    /// Program tagging commands for `if_tag` conditions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classifier: Option<Plugin>,
+
+    /// LLM endpoint giving approval prompts a second opinion
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewer: Option<Reviewer>,
+
+    /// Templates and files merged in through `extends` and `include`, and
+    /// nested directory policies, highest precedence first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub included: Vec<String>,
+
+    /// Nested policy files that were skipped, with why, for the loader's
+    /// caller to report
+    #[serde(skip)]
+    pub skipped_layers: Vec<String>,
+}
+
+impl Policy {
+    /// Drop the rules `--disable-rule` names, by id or `tag:NAME`, and
+    /// return how many went. A selector that picks no rule is an error, so
+    /// a typo doesn't leave the rule quietly in force.
+    pub fn disable_rules(&mut self, selectors: &[String]) -> Result<usize> {
+        if let Some(unknown) = selectors
+            .iter()
+            .find(|selector| !self.rules.iter().any(|rule| rule.selected_by(selector)))
+        {
+            match unknown.strip_prefix("tag:") {
+                Some(tag) => bail!("No rule in the policy is tagged '{}'", tag),
+                None => bail!("No rule in the policy has id '{}'", unknown),
+            }
+        }
+        let before = self.rules.len();
+        self.rules
+            .retain(|rule| !selectors.iter().any(|selector| rule.selected_by(selector)));
+        Ok(before - self.rules.len())
+    }
+}
+
+/// Rules for processes started by a command a `sub_policy` rule allowed.
+/// They only tighten: an action one of them denies or holds for approval
+/// is, whatever the policy's own rules say, and an action none of them
+/// matches is left to the policy.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubPolicy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub rules: Vec<Rule>,
+}
+
+/// What happens to an action no rule matches, per action in `defaults:`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DefaultDecision {
+    Allow,
+    Deny,
+    /// Ask a human
+    #[serde(alias = "approval")]
+    RequireApproval,
+}
+
+impl fmt::Display for DefaultDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DefaultDecision::Allow => write!(f, "allow"),
+            DefaultDecision::Deny => write!(f, "deny"),
+            DefaultDecision::RequireApproval => write!(f, "require_approval"),
+        }
+    }
+}
+
+/// What happens to a tool call the hook can't map to an action. It's
+/// logged, with its input, whichever this is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UnknownTools {
+    Allow,
+    Deny,
+    /// Ask a human
+    #[serde(alias = "approval")]
+    RequireApproval,
+    /// Allow, but flag the log entry with a warning so it stands out
+    LogOnly,
+}
+
+impl fmt::Display for UnknownTools {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnknownTools::Allow => write!(f, "allow"),
+            UnknownTools::Deny => write!(f, "deny"),
+            UnknownTools::RequireApproval => write!(f, "require_approval"),
+            UnknownTools::LogOnly => write!(f, "log_only"),
+        }
+    }
+}
+
+/// How pushed commits are traced back to the session that produced them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnotatePushes {
+    /// Attach a note under `refs/notes/lawctl` and push that ref too.
+    /// Commits themselves are untouched.
+    Notes,
+    /// Add `Lawctl-Session` / `Lawctl-Policy` trailers to the commit
+    /// messages of not-yet-pushed commits (rewrites them) before pushing.
+    Trailer,
 }
 
 /// The result of evaluating an action against a policy.
@@ -230,11 +811,36 @@ pub enum Decision {
         #[serde(skip_serializing_if = "Option::is_none")]
         matched_rule: Option<String>,
     },
+    /// Action is permitted, but a `log_only` rule says strict mode would
+    /// have denied it. Executes like Allowed.
+    Logged {
+        /// Why it would have been denied
+        reason: String,
+        /// Which log_only rule matched
+        #[serde(skip_serializing_if = "Option::is_none")]
+        matched_rule: Option<String>,
+    },
 }
 
 impl Decision {
+    /// Whether the action goes ahead: allowed outright, or log-only.
     pub fn is_allowed(&self) -> bool {
-        matches!(self, Decision::Allowed { .. })
+        matches!(self, Decision::Allowed { .. } | Decision::Logged { .. })
+    }
+
+    /// Whether this was allowed only because the denying rule is log-only.
+    pub fn is_logged(&self) -> bool {
+        matches!(self, Decision::Logged { .. })
+    }
+
+    /// The rule behind the decision, if any.
+    pub fn matched_rule(&self) -> Option<&str> {
+        match self {
+            Decision::Allowed { matched_rule }
+            | Decision::Denied { matched_rule, .. }
+            | Decision::RequiresApproval { matched_rule, .. }
+            | Decision::Logged { matched_rule, .. } => matched_rule.as_deref(),
+        }
     }
 
     pub fn is_denied(&self) -> bool {
@@ -254,23 +860,86 @@ impl fmt::Display for Decision {
             Decision::RequiresApproval { reason, .. } => {
                 write!(f, "requires approval: {}", reason)
             }
+            Decision::Logged { reason, .. } => write!(f, "allowed (would deny: {})", reason),
         }
     }
 }
 
 /// Payload metadata passed alongside an action for richer policy evaluation.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ActionContext {
     /// The target path or resource (file path, git branch, URL, etc.)
     pub target: String,
+    /// Further targets for multi-target operations (e.g. `rm a b c`).
+    /// The engine evaluates every target and the most restrictive decision wins.
+    pub additional_targets: Vec<String>,
     /// For file writes: the diff content
     pub diff: Option<String>,
     /// For run_cmd: the full command string
     pub command: Option<String>,
     /// For network: the target URL/domain
     pub domain: Option<String>,
+    /// For network: the destination port (explicit or the scheme default)
+    pub port: Option<u16>,
+    /// For network: the URL scheme, lowercased (e.g. "https")
+    pub scheme: Option<String>,
+    /// For network: the HTTP method, uppercased, when it's known
+    pub method: Option<String>,
+    /// For network: bytes sent to this host so far in the session,
+    /// including this request, when the caller tracks it
+    pub uploaded_bytes: Option<u64>,
     /// Number of diff lines (computed from diff if provided)
     pub diff_lines: Option<usize>,
+    /// For moves: where the targets go. Ends in `/` when it's a directory
+    /// they move into.
+    pub destination: Option<String>,
+    /// For chmod: the requested mode as given (`755`, `u+x`, `go-w`)
+    pub mode: Option<String>,
+    /// For recursive deletes, moves and chmods of a directory: paths found
+    /// inside it. Checked so a directory delete can't remove protected files
+    /// wholesale.
+    pub contained_paths: Vec<String>,
+    /// Whether the directory held more paths than were collected, so
+    /// `contained_paths` isn't the whole of it
+    pub contents_truncated: bool,
+    /// For lockfile writes: packages the new content adds
+    pub new_dependencies: Vec<Dependency>,
+    /// For package_install: the package manager (`npm`, `pip`, `cargo`...)
+    pub package_manager: Option<String>,
+    /// For git_push: the branch being pushed to on the remote
+    pub branch: Option<String>,
+    /// For k8s: the kubeconfig context, namespace and verb, when known
+    pub k8s_context: Option<String>,
+    pub k8s_namespace: Option<String>,
+    pub k8s_verb: Option<String>,
+    /// For cloud: the provider CLI, the account and what the command does
+    /// (`read`, `mutate`, `destroy`)
+    pub cloud_provider: Option<String>,
+    pub cloud_account: Option<String>,
+    pub cloud_effect: Option<String>,
+    /// When the action happens, in local time, for `only_between` and
+    /// `only_on_days`. `None` means now.
+    pub local_time: Option<chrono::NaiveDateTime>,
+    /// How many actions of this kind the session has made, this one
+    /// included, when the caller counts them (`max_per_minute`,
+    /// `max_per_session`)
+    pub counts: Option<crate::policy::rate::ActionCounts>,
+    /// For writes: diff lines the session has written, in total and to this
+    /// file, this write included, when the caller tracks them
+    /// (`max_total_diff_lines`, `max_total_diff_lines_per_file`)
+    pub diff_totals: Option<crate::policy::rate::DiffTotals>,
+    /// For writes to existing files: whether the session read the file
+    /// first, when the caller tracks reads (`require_prior_read`)
+    pub previously_read: Option<bool>,
+    /// For writes: whether the target already exists, when the caller has
+    /// looked (`if_new_file`, `if_overwrites_existing`)
+    pub file_exists: Option<bool>,
+    /// For run_cmd: tags from the policy's classifier, once it has run.
+    /// `None` when it hasn't or couldn't.
+    pub tags: Option<Vec<String>>,
+    /// Metadata from the integration with no field of its own (the tool
+    /// called, the model, the user...), by name, for `if_context`
+    pub extras: BTreeMap<String, Vec<String>>,
 }
 
 impl ActionContext {
@@ -281,6 +950,46 @@ impl ActionContext {
         }
     }
 
+    /// Create a context covering several targets at once.
+    /// The first target becomes `target`, the rest go to `additional_targets`.
+    pub fn with_targets<I, S>(targets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut iter = targets.into_iter().map(Into::into);
+        Self {
+            target: iter.next().unwrap_or_default(),
+            additional_targets: iter.collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Iterate over every target this action touches.
+    pub fn all_targets(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.target.as_str())
+            .chain(self.additional_targets.iter().map(|t| t.as_str()))
+    }
+
+    /// Whether this context covers more than one target.
+    pub fn is_multi_target(&self) -> bool {
+        !self.additional_targets.is_empty()
+    }
+
+    /// All targets joined for display and audit logging.
+    pub fn display_targets(&self) -> String {
+        self.all_targets().collect::<Vec<_>>().join(", ")
+    }
+
+    /// A copy of this context narrowed to a single target.
+    pub fn for_target(&self, target: &str) -> Self {
+        Self {
+            target: target.to_string(),
+            additional_targets: Vec::new(),
+            ..self.clone()
+        }
+    }
+
     pub fn with_diff(mut self, diff: impl Into<String>) -> Self {
         let d = diff.into();
         self.diff_lines = Some(d.lines().count());
@@ -297,4 +1006,168 @@ impl ActionContext {
         self.domain = Some(domain.into());
         self
     }
+
+    /// Set the network domain, port and scheme from a URL.
+    pub fn with_url(mut self, url: &str) -> Self {
+        if let Some((host, port)) = crate::utils::domain::host_and_port(url) {
+            self.domain = Some(host);
+            self.port = port;
+        }
+        if let Some((scheme, _)) = url.split_once("://") {
+            self.scheme = Some(scheme.to_ascii_lowercase());
+        }
+        self
+    }
+
+    pub fn with_method(mut self, method: impl Into<String>) -> Self {
+        self.method = Some(method.into().to_ascii_uppercase());
+        self
+    }
+
+    pub fn with_uploaded_bytes(mut self, bytes: u64) -> Self {
+        self.uploaded_bytes = Some(bytes);
+        self
+    }
+
+    /// Set the k8s context, namespace and verb from a kubectl or helm
+    /// invocation, whose context the caller has filled in from the
+    /// kubeconfig when it wasn't given.
+    pub fn with_kube(mut self, invocation: &crate::utils::kube::KubeInvocation) -> Self {
+        self.k8s_context = invocation.context.clone();
+        self.k8s_namespace = invocation.namespace.clone();
+        self.k8s_verb = invocation.verb.clone();
+        self
+    }
+
+    /// Set the cloud provider, account and effect from an aws, gcloud or
+    /// az invocation, whose account the caller has filled in from the
+    /// CLI's defaults when it wasn't given.
+    pub fn with_cloud(mut self, invocation: &crate::utils::cloud::CloudInvocation) -> Self {
+        self.cloud_provider = Some(invocation.provider.clone());
+        self.cloud_account = invocation.account.clone();
+        self.cloud_effect = Some(invocation.effect.to_string());
+        self
+    }
+
+    pub fn with_counts(mut self, counts: crate::policy::rate::ActionCounts) -> Self {
+        self.counts = Some(counts);
+        self
+    }
+
+    pub fn with_diff_totals(mut self, totals: crate::policy::rate::DiffTotals) -> Self {
+        self.diff_totals = Some(totals);
+        self
+    }
+
+    pub fn with_previously_read(mut self, read: bool) -> Self {
+        self.previously_read = Some(read);
+        self
+    }
+
+    pub fn with_file_exists(mut self, exists: bool) -> Self {
+        self.file_exists = Some(exists);
+        self
+    }
+
+    pub fn with_destination(mut self, destination: impl Into<String>) -> Self {
+        self.destination = Some(destination.into());
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// For moves: each target and the path it ends up at.
+    pub fn move_destinations(&self) -> Vec<(&str, String)> {
+        let Some(destination) = &self.destination else {
+            return Vec::new();
+        };
+        self.all_targets()
+            .map(|source| {
+                let to = match destination.strip_suffix('/') {
+                    Some(dir) => {
+                        let name = source.trim_end_matches('/').rsplit('/').next();
+                        format!("{}/{}", dir, name.unwrap_or(source))
+                    }
+                    None => destination.clone(),
+                };
+                (source, to)
+            })
+            .collect()
+    }
+
+    /// Add `value` to the metadata under `key`.
+    pub fn with_extra(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extras
+            .entry(key.into())
+            .or_default()
+            .push(value.into());
+        self
+    }
+
+    /// What `if_context` sees under `key`: the metadata passed under it,
+    /// or else the context field of that name.
+    pub fn context_values(&self, key: &str) -> Vec<&str> {
+        if let Some(values) = self.extras.get(key) {
+            return values.iter().map(String::as_str).collect();
+        }
+        let field = match key {
+            "branch" => &self.branch,
+            "domain" => &self.domain,
+            "scheme" => &self.scheme,
+            "method" => &self.method,
+            "command" => &self.command,
+            "package_manager" => &self.package_manager,
+            "k8s_context" => &self.k8s_context,
+            "k8s_namespace" => &self.k8s_namespace,
+            "k8s_verb" => &self.k8s_verb,
+            "cloud_provider" => &self.cloud_provider,
+            "cloud_account" => &self.cloud_account,
+            "cloud_effect" => &self.cloud_effect,
+            _ => return Vec::new(),
+        };
+        field.as_deref().into_iter().collect()
+    }
+
+    /// Set the pushed branch from a refspec: `HEAD:release/1.2` and
+    /// `refs/heads/main` name the remote side, `release/1.2` and `main`.
+    pub fn with_branch(mut self, refspec: &str) -> Self {
+        let remote = refspec.rsplit(':').next().unwrap_or(refspec);
+        let remote = remote.trim_start_matches('+');
+        let branch = remote.strip_prefix("refs/heads/").unwrap_or(remote);
+        self.branch = Some(branch.to_string());
+        self
+    }
+
+    pub fn with_package_manager(mut self, manager: impl Into<String>) -> Self {
+        self.package_manager = Some(manager.into());
+        self
+    }
+
+    pub fn with_local_time(mut self, time: chrono::NaiveDateTime) -> Self {
+        self.local_time = Some(time);
+        self
+    }
+
+    pub fn with_mode(mut self, mode: impl Into<String>) -> Self {
+        self.mode = Some(mode.into());
+        self
+    }
+
+    pub fn with_contained_paths(mut self, paths: Vec<String>) -> Self {
+        self.contained_paths = paths;
+        self
+    }
+
+    pub fn with_contents_truncated(mut self, truncated: bool) -> Self {
+        self.contents_truncated = truncated;
+        self
+    }
+
+    pub fn with_new_dependencies(mut self, dependencies: Vec<Dependency>) -> Self {
+        self.new_dependencies = dependencies;
+        self
+    }
 }
@@ -8,8 +8,8 @@
 
 use anyhow::{Context, Result};
 use bollard::container::{
-    Config, CreateContainerOptions, RemoveContainerOptions, StartContainerOptions,
-    WaitContainerOptions,
+    Config, CreateContainerOptions, ListContainersOptions, RemoveContainerOptions,
+    StartContainerOptions, StopContainerOptions, WaitContainerOptions,
 };
 use bollard::image::CreateImageOptions;
 use bollard::models::{HostConfig, Mount, MountTypeEnum};
@@ -18,6 +18,10 @@ use futures_util::StreamExt;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+/// Label carrying the session a sandbox container belongs to, so
+/// containers a crashed lawctl left behind can be found and removed.
+pub const SESSION_LABEL: &str = "lawctl.session";
+
 /// Configuration for a sandbox container.
 #[derive(Debug, Clone)]
 pub struct SandboxConfig {
@@ -35,6 +39,8 @@ pub struct SandboxConfig {
     pub network_enabled: bool,
     /// Container name (auto-generated if None)
     pub container_name: Option<String>,
+    /// The session this container runs, recorded as [`SESSION_LABEL`]
+    pub session_id: Option<String>,
 }
 
 impl Default for SandboxConfig {
@@ -47,6 +53,7 @@ impl Default for SandboxConfig {
             command: vec![],
             network_enabled: false,
             container_name: None,
+            session_id: None,
         }
     }
 }
@@ -161,6 +168,11 @@ impl DockerSandbox {
             working_dir: Some("/workspace".to_string()),
             env: Some(env),
             host_config: Some(host_config),
+            labels: self
+                .config
+                .session_id
+                .as_ref()
+                .map(|id| HashMap::from([(SESSION_LABEL.to_string(), id.clone())])),
             ..Default::default()
         };
 
@@ -203,6 +215,20 @@ impl DockerSandbox {
         }
     }
 
+    /// Ask the container to stop: SIGTERM, then SIGKILL after `grace`.
+    pub async fn stop(&self, grace: std::time::Duration) -> Result<()> {
+        if let Some(ref container_id) = self.container_id {
+            let opts = StopContainerOptions {
+                t: grace.as_secs() as i64,
+            };
+            self.docker
+                .stop_container(container_id, Some(opts))
+                .await
+                .context("Failed to stop sandbox container")?;
+        }
+        Ok(())
+    }
+
     /// Stop and remove the container.
     pub async fn cleanup(&mut self) -> Result<()> {
         if let Some(ref container_id) = self.container_id {
@@ -228,6 +254,56 @@ impl DockerSandbox {
     }
 }
 
+/// Remove sandbox containers whose session isn't running any more, going
+/// by [`SESSION_LABEL`]. Returns the names removed; an error means Docker
+/// couldn't be reached.
+pub async fn remove_orphans(session_running: impl Fn(&str) -> bool) -> Result<Vec<String>> {
+    let docker = Docker::connect_with_local_defaults().context("Failed to connect to Docker")?;
+    let opts = ListContainersOptions {
+        all: true,
+        filters: HashMap::from([("label".to_string(), vec![SESSION_LABEL.to_string()])]),
+        ..Default::default()
+    };
+    let containers = tokio::time::timeout(
+        std::time::Duration::from_secs(3),
+        docker.list_containers(Some(opts)),
+    )
+    .await
+    .context("Docker didn't answer")?
+    .context("Failed to list containers")?;
+
+    let mut removed = Vec::new();
+    for container in containers {
+        let (Some(id), Some(session)) = (
+            container.id,
+            container
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.get(SESSION_LABEL)),
+        ) else {
+            continue;
+        };
+        if session_running(session) {
+            continue;
+        }
+        let opts = RemoveContainerOptions {
+            force: true,
+            ..Default::default()
+        };
+        docker
+            .remove_container(&id, Some(opts))
+            .await
+            .with_context(|| format!("Failed to remove container {}", id))?;
+        let name = container
+            .names
+            .and_then(|names| names.into_iter().next())
+            .map(|name| name.trim_start_matches('/').to_string())
+            .unwrap_or(id);
+        removed.push(name);
+    }
+    Ok(removed)
+}
+
 impl Drop for DockerSandbox {
     fn drop(&mut self) {
         // Best-effort cleanup on drop
@@ -0,0 +1,162 @@
+//! Which environment variables the agent gets to see.
+//!
+//! A shell's environment is full of credentials — `AWS_SECRET_ACCESS_KEY`,
+//! `GITHUB_TOKEN`, database URLs — and an agent started with all of it can
+//! use them without ever going through the gateway. So the agent starts with
+//! an allowlist: enough for a shell and the agent's own model API to work,
+//! plus whatever the policy adds with `env_passthrough:`.
+//!
+//! ```yaml
+//! env_passthrough: [NPM_CONFIG_REGISTRY, "CARGO_*"]
+//! ```
+//!
+//! Entries are exact names or prefixes ending in `*`; a lone `*` passes the
+//! whole environment through.
+
+/// Passed to every agent. The `ANTHROPIC_*`/`OPENAI_*` entries are the
+/// agents' own model credentials — without them no agent starts; the
+/// `LAWCTL_*` directories keep the hook logging where the session does.
+pub const DEFAULT_PASSTHROUGH: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LOGNAME",
+    "SHELL",
+    "TERM",
+    "COLORTERM",
+    "NO_COLOR",
+    "LANG",
+    "LANGUAGE",
+    "LC_*",
+    "TZ",
+    "TMPDIR",
+    "EDITOR",
+    "VISUAL",
+    "PAGER",
+    "XDG_*",
+    "ANTHROPIC_*",
+    "OPENAI_*",
+    "LAWCTL_DATA_DIR",
+    "LAWCTL_RUNTIME_DIR",
+];
+
+/// Names that describe the host, not the agent's settings, so they don't
+/// make sense inside a container.
+const HOST_ONLY: &[&str] = &[
+    "PATH", "HOME", "USER", "LOGNAME", "SHELL", "TMPDIR", "XDG_*",
+];
+
+fn matches(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => pattern == name,
+    }
+}
+
+/// Whether `name` may be passed to the agent, given the policy's extra
+/// `env_passthrough` entries.
+pub fn is_allowed(name: &str, extra: &[String]) -> bool {
+    DEFAULT_PASSTHROUGH.iter().any(|p| matches(p, name)) || extra.iter().any(|p| matches(p, name))
+}
+
+/// Whether `entry` is something `env_passthrough:` accepts.
+pub fn is_valid_pattern(entry: &str) -> bool {
+    let name = entry.strip_suffix('*').unwrap_or(entry);
+    (entry == "*" || !name.is_empty())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The allowed subset of `vars`, plus how many were dropped.
+pub fn scrub(
+    vars: impl IntoIterator<Item = (String, String)>,
+    extra: &[String],
+) -> (Vec<(String, String)>, usize) {
+    let mut dropped = 0;
+    let kept = vars
+        .into_iter()
+        .filter(|(name, _)| {
+            let allowed = is_allowed(name, extra);
+            if !allowed {
+                dropped += 1;
+            }
+            allowed
+        })
+        .collect();
+    (kept, dropped)
+}
+
+/// This process's environment as the agent should see it. Variables that
+/// aren't valid UTF-8 are dropped along with the disallowed ones.
+pub fn agent_env(extra: &[String]) -> Vec<(String, String)> {
+    let (kept, dropped) = scrub(
+        std::env::vars_os()
+            .filter_map(|(k, v)| Some((k.into_string().ok()?, v.into_string().ok()?))),
+        extra,
+    );
+    tracing::debug!(
+        kept = ?kept.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>(),
+        dropped,
+        "scrubbed agent environment"
+    );
+    kept
+}
+
+/// The part of an agent environment worth forwarding into a container.
+pub fn for_container(env: &[(String, String)]) -> Vec<(String, String)> {
+    env.iter()
+        .filter(|(name, _)| !HOST_ONLY.iter().any(|p| matches(p, name)))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(names: &[&str]) -> Vec<(String, String)> {
+        names
+            .iter()
+            .map(|n| (n.to_string(), "x".to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_scrub_keeps_only_allowed() {
+        let env = vars(&[
+            "PATH",
+            "LC_ALL",
+            "ANTHROPIC_API_KEY",
+            "AWS_SECRET_ACCESS_KEY",
+            "GITHUB_TOKEN",
+            "CARGO_HOME",
+        ]);
+        let (kept, dropped) = scrub(env.clone(), &[]);
+        let names: Vec<_> = kept.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(names, ["PATH", "LC_ALL", "ANTHROPIC_API_KEY"]);
+        assert_eq!(dropped, 3);
+
+        let (kept, _) = scrub(env.clone(), &["CARGO_*".to_string()]);
+        assert!(kept.iter().any(|(k, _)| k == "CARGO_HOME"));
+        assert!(!kept.iter().any(|(k, _)| k == "GITHUB_TOKEN"));
+
+        let (kept, dropped) = scrub(env, &["*".to_string()]);
+        assert_eq!((kept.len(), dropped), (6, 0));
+    }
+
+    #[test]
+    fn test_patterns() {
+        assert!(is_valid_pattern("NPM_TOKEN"));
+        assert!(is_valid_pattern("CARGO_*"));
+        assert!(is_valid_pattern("*"));
+        assert!(!is_valid_pattern(""));
+        assert!(!is_valid_pattern("FOO BAR"));
+        assert!(!is_valid_pattern("*_KEY"));
+    }
+
+    #[test]
+    fn test_for_container_drops_host_paths() {
+        let env = vars(&["PATH", "HOME", "TERM", "XDG_CONFIG_HOME", "OPENAI_API_KEY"]);
+        let names: Vec<_> = for_container(&env).into_iter().map(|(k, _)| k).collect();
+        assert_eq!(names, ["TERM", "OPENAI_API_KEY"]);
+    }
+}
@@ -1,6 +1,9 @@
 pub mod docker;
+pub mod env;
 pub mod mount;
 pub mod namespace;
+pub mod user;
 
 pub use docker::{DockerSandbox, SandboxConfig};
 pub use mount::MountConfig;
+pub use user::AgentUser;
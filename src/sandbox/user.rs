@@ -0,0 +1,170 @@
+//! Running the agent as a dedicated unprivileged user (`--as-user`).
+//!
+//! Without Docker the agent runs on the host, so anything it does outside
+//! lawctl's shims happens with your permissions. Starting it as a separate
+//! account (by default `lawctl-agent`) means a stray `rm` or a write that
+//! bypasses the gateway fails with "permission denied" on most of the host.
+//! Switching user needs root, so this is meant for `sudo lawctl run
+//! --as-user ...`; the gateway keeps running as the invoking user's lawctl.
+
+use anyhow::{bail, Context, Result};
+use std::ffi::{CStr, CString};
+use std::path::{Path, PathBuf};
+
+/// The account `--as-user` uses when no name is given.
+pub const DEFAULT_AGENT_USER: &str = "lawctl-agent";
+
+/// A local account the agent can be started as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentUser {
+    pub name: String,
+    pub uid: u32,
+    pub gid: u32,
+    pub home: PathBuf,
+}
+
+impl AgentUser {
+    /// Look up `name` in the user database. `None` if there's no such user.
+    pub fn lookup(name: &str) -> Result<Option<Self>> {
+        let c_name = CString::new(name)?;
+        let mut buf = vec![0 as libc::c_char; 4096];
+        loop {
+            // SAFETY: all pointers are valid for the duration of the call and
+            // `buf.len()` is the real size of `buf`
+            let mut pwd: libc::passwd = unsafe { std::mem::zeroed() };
+            let mut result: *mut libc::passwd = std::ptr::null_mut();
+            let rc = unsafe {
+                libc::getpwnam_r(
+                    c_name.as_ptr(),
+                    &mut pwd,
+                    buf.as_mut_ptr(),
+                    buf.len(),
+                    &mut result,
+                )
+            };
+            if rc == libc::ERANGE && buf.len() < 1 << 20 {
+                buf.resize(buf.len() * 2, 0);
+                continue;
+            }
+            if rc != 0 {
+                bail!(
+                    "Failed to look up user '{}': {}",
+                    name,
+                    std::io::Error::from_raw_os_error(rc)
+                );
+            }
+            if result.is_null() {
+                return Ok(None);
+            }
+            // SAFETY: on success pw_dir points into `buf`, which is still alive
+            let home = unsafe { CStr::from_ptr(pwd.pw_dir) };
+            return Ok(Some(Self {
+                name: name.to_string(),
+                uid: pwd.pw_uid,
+                gid: pwd.pw_gid,
+                home: PathBuf::from(home.to_string_lossy().into_owned()),
+            }));
+        }
+    }
+
+    /// Resolve the user `--as-user` names and make sure lawctl can actually
+    /// start a process as them. The errors explain how to fix it.
+    pub fn resolve(name: &str) -> Result<Self> {
+        let Some(user) = Self::lookup(name)? else {
+            bail!(
+                "There's no user '{}' to run the agent as.\n{}",
+                name,
+                creation_help(name)
+            );
+        };
+        if user.uid == 0 {
+            bail!(
+                "'{}' is root — --as-user is for running the agent with fewer permissions",
+                name
+            );
+        }
+        // SAFETY: geteuid(2) cannot fail and has no preconditions
+        let euid = unsafe { libc::geteuid() };
+        if euid != 0 && euid != user.uid {
+            bail!(
+                "Starting the agent as '{}' needs root. Run: sudo lawctl run --as-user {} -- <your agent command>",
+                name,
+                name
+            );
+        }
+        Ok(user)
+    }
+
+    /// Whether starting the agent needs a user switch at all.
+    pub fn needs_switch(&self) -> bool {
+        // SAFETY: geteuid(2) cannot fail and has no preconditions
+        unsafe { libc::geteuid() != self.uid }
+    }
+
+    /// The environment a login as this user would have.
+    pub fn env_vars(&self) -> Vec<(String, String)> {
+        vec![
+            ("HOME".to_string(), self.home.to_string_lossy().into_owned()),
+            ("USER".to_string(), self.name.clone()),
+            ("LOGNAME".to_string(), self.name.clone()),
+        ]
+    }
+
+    /// Let this user connect to the gateway socket, which is created with
+    /// the invoking user's umask.
+    pub fn grant_socket(&self, socket_path: &Path) -> Result<()> {
+        std::os::unix::fs::chown(socket_path, Some(self.uid), Some(self.gid)).with_context(|| {
+            format!(
+                "Failed to hand the gateway socket to '{}': {}",
+                self.name,
+                socket_path.display()
+            )
+        })
+    }
+}
+
+/// How to create the agent account on this platform, and give it access to
+/// the project.
+pub fn creation_help(name: &str) -> String {
+    let create = if cfg!(target_os = "macos") {
+        format!(
+            "  sudo sysadminctl -addUser {name} -shell /usr/bin/false -home /var/empty\n  \
+             sudo dscl . -create /Users/{name} IsHidden 1"
+        )
+    } else {
+        format!("  sudo useradd --system --create-home --shell /usr/sbin/nologin {name}")
+    };
+    format!(
+        "Create it with:\n{create}\n\
+         Then let it read the project (writes still go through lawctl's gateway):\n  \
+         chmod -R o+rX .\n\
+         or give it write access to just this project:\n  \
+         sudo chgrp -R {name} . && chmod -R g+rwX ."
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let root = AgentUser::lookup("root").unwrap().unwrap();
+        assert_eq!(root.uid, 0);
+        assert!(AgentUser::lookup("lawctl-no-such-user").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_resolve_explains_missing_user() {
+        let err = AgentUser::resolve("lawctl-no-such-user").unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("There's no user 'lawctl-no-such-user'"));
+        assert!(message.contains("useradd") || message.contains("sysadminctl"));
+        assert!(message.contains("Create it with"));
+    }
+
+    #[test]
+    fn test_resolve_refuses_root() {
+        assert!(AgentUser::resolve("root").is_err());
+    }
+}
@@ -10,6 +10,13 @@
 //! 2. When called, it checks argv[0] to figure out which command was intercepted
 //! 3. It builds a GatewayRequest and sends it over the Unix socket
 //! 4. If the gateway allows it, the shim executes the real command
+//!    (`rm`, `mv` and `chmod` are parsed by the shim and the gateway
+//!    deletes, moves or changes the mode of the approved batch;
+//!    `printenv` and `env` only run once their variables are allowed,
+//!    package managers once the packages they'd install are, `ssh`,
+//!    `scp`, `sftp` and `rsync` once the hosts they reach are, and
+//!    `kubectl` and `helm` once the cluster context and verb are, and
+//!    `aws`, `gcloud` and `az` once the account and operation are)
 //! 5. If denied, it prints the error and exits with code 1
 //!
 //! Usage (automatic — set up by `lawctl run`):
@@ -17,13 +24,25 @@
 //!
 //! Or invoke explicitly:
 //!   lawctl-shim write <path> <content>
-//!   lawctl-shim delete <path>
-//!   lawctl-shim exec <command...>
+//!   lawctl-shim delete <path> [path...]
+//!   lawctl-shim move <source>... <destination>
+//!   lawctl-shim chmod [-R] <mode> <path>...
+//!   lawctl-shim exec [--shell <shell>] <command...>
 //!   lawctl-shim git-push <branch>
 
 use lawctl::gateway::client::GatewayClient;
 use lawctl::gateway::protocol::GatewayResponse;
+use lawctl::policy::Action;
+use lawctl::utils::cloud;
+use lawctl::utils::command::{
+    env_access_targets, env_reads, package_installs_by_manager, parse_chmod_args, parse_mv_args,
+    parse_rm_args, remote_accesses, RemoteAccess, RmInvocation, RmPrompt,
+};
+use lawctl::utils::kube;
+use lawctl::utils::paths::workspace_path;
 use std::env;
+use std::io::Write;
+use std::path::Path;
 use std::process;
 
 fn main() {
@@ -38,6 +57,15 @@ fn main() {
     let result = match invoked_as.as_str() {
         // Symlink-based interception: called as `rm`, `git`, etc.
         "rm" => handle_rm(&args[1..]),
+        "mv" => handle_mv(&args[1..]),
+        "chmod" => handle_chmod(&args[1..]),
+        "printenv" | "env" => handle_env(&invoked_as, &args[1..]),
+        "npm" | "pnpm" | "yarn" | "bun" | "pip" | "pip3" | "uv" | "poetry" | "cargo" => {
+            handle_installer(&invoked_as, &args[1..])
+        }
+        "ssh" | "scp" | "sftp" | "rsync" => handle_remote(&invoked_as, &args[1..]),
+        "kubectl" | "helm" => handle_k8s(&invoked_as, &args[1..]),
+        "aws" | "gcloud" | "az" => handle_cloud(&invoked_as, &args[1..]),
         "git" => handle_git(&args[1..]),
 
         // Direct invocation: lawctl-shim <subcommand> [args...]
@@ -49,12 +77,18 @@ fn main() {
             match args[1].as_str() {
                 "write" => handle_write(&args[2..]),
                 "delete" => handle_delete(&args[2..]),
+                "move" => handle_move(&args[2..]),
+                "chmod" => handle_chmod(&args[2..]),
                 "exec" | "run" => handle_exec(&args[2..]),
                 "git-push" | "push" => handle_git_push(&args[2..]),
                 "help" | "--help" | "-h" => {
                     print_usage();
                     Ok(())
                 }
+                "--version" | "-V" => {
+                    println!("lawctl-shim {}", env!("CARGO_PKG_VERSION"));
+                    Ok(())
+                }
                 other => {
                     eprintln!("[lawctl] Unknown shim command: {}", other);
                     print_usage();
@@ -80,49 +114,292 @@ fn main() {
 }
 
 /// Handle `rm` command interception.
-/// Maps `rm <file>` → delete action, checks with gateway.
+/// Parses rm's flags and applies rm's own checks and prompts locally
+/// (`-f`, `-i`/`-I`, `-r`, `-d`, `--`), then sends the surviving operands
+/// to the gateway as one batch. The gateway decides once for the whole
+/// batch and its Delete handler removes exactly those files — nothing is
+/// deleted outside that decision.
 fn handle_rm(args: &[String]) -> anyhow::Result<()> {
-    if args.is_empty() {
+    let inv = match parse_rm_args(args) {
+        Ok(inv) => inv,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            eprintln!("Try 'rm --help' for more information.");
+            process::exit(1);
+        }
+    };
+
+    if inv.operands.is_empty() {
+        if inv.force {
+            return Ok(());
+        }
         eprintln!("rm: missing operand");
         process::exit(1);
     }
 
-    let client = GatewayClient::from_env()?;
+    if inv.prompt == RmPrompt::Once
+        && (inv.recursive || inv.operands.len() > 3)
+        && !confirm(&format!(
+            "rm: remove {} argument{}{}? ",
+            inv.operands.len(),
+            if inv.operands.len() == 1 { "" } else { "s" },
+            if inv.recursive { " recursively" } else { "" }
+        ))
+    {
+        return Ok(());
+    }
 
-    // Check each file argument with the gateway
-    for arg in args {
-        // Skip flags
-        if arg.starts_with('-') {
-            continue;
+    // Mirror rm: a failing operand is reported but doesn't stop the others
+    let mut failed = false;
+    let mut approved: Vec<(String, bool)> = Vec::new();
+    for operand in &inv.operands {
+        match plan_removal(&inv, operand) {
+            Ok(Some(is_dir)) => approved.push((operand.clone(), is_dir)),
+            Ok(None) => {}
+            Err(msg) => {
+                eprintln!("rm: {}", msg);
+                failed = true;
+            }
         }
+    }
 
-        let response = client.delete_file(arg)?;
+    if !approved.is_empty() {
+        let targets: Vec<String> = approved.iter().map(|(t, _)| gateway_path(t)).collect();
+        let client = GatewayClient::from_env()?;
+        let response = client.delete_files(&targets)?;
         if !response.allowed {
             eprintln!(
                 "[lawctl] BLOCKED: cannot delete '{}' — {}",
-                arg,
+                approved
+                    .iter()
+                    .map(|(t, _)| t.as_str())
+                    .collect::<Vec<_>>()
+                    .join("', '"),
                 response
                     .error
                     .unwrap_or_else(|| "denied by policy".to_string())
             );
             process::exit(1);
         }
+        if inv.verbose {
+            for (target, is_dir) in &approved {
+                if *is_dir {
+                    println!("removed directory '{}'", target);
+                } else {
+                    println!("removed '{}'", target);
+                }
+            }
+        }
     }
 
-    // If all files were approved, execute the real rm
-    let full_command = format!("rm {}", args.join(" "));
-    let response = client.run_cmd(&full_command)?;
-    if response.allowed {
-        if let Some(output) = response.result {
-            if !output.is_empty() {
-                print!("{}", output);
+    if failed {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// An operand as the gateway should see it. The shim checked it from its
+/// own working directory, so it's joined to that: relative to the
+/// workspace (`LAWCTL_WORKSPACE`) when inside it, absolute otherwise.
+fn gateway_path(operand: &str) -> String {
+    let Ok(cwd) = env::current_dir() else {
+        return operand.to_string();
+    };
+    let workspace = env::var_os("LAWCTL_WORKSPACE");
+    workspace_path(workspace.as_deref().map(Path::new), &cwd, operand)
+}
+
+/// Apply rm's local checks and prompts to one operand.
+/// Returns `Some(is_dir)` if it should be removed, `None` if it's skipped
+/// (missing under `-f`, or declined at a prompt), or an rm-style error.
+fn plan_removal(inv: &RmInvocation, operand: &str) -> Result<Option<bool>, String> {
+    let path = Path::new(operand);
+    let trimmed = operand.trim_end_matches('/');
+
+    if inv.preserve_root && operand.starts_with('/') && trimmed.is_empty() {
+        return Err(format!(
+            "it is dangerous to operate recursively on '{}'\nrm: use --no-preserve-root to override this failsafe",
+            operand
+        ));
+    }
+    if matches!(trimmed.rsplit('/').next(), Some(".") | Some("..")) {
+        return Err(format!(
+            "refusing to remove '.' or '..' directory: skipping '{}'",
+            operand
+        ));
+    }
+
+    let meta = match std::fs::symlink_metadata(path) {
+        Ok(m) => m,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && inv.force => return Ok(None),
+        Err(e) => return Err(format!("cannot remove '{}': {}", operand, io_reason(&e))),
+    };
+
+    if meta.is_dir() {
+        if !inv.recursive {
+            if !inv.dir {
+                return Err(format!("cannot remove '{}': Is a directory", operand));
+            }
+            let is_empty = std::fs::read_dir(path)
+                .map(|mut entries| entries.next().is_none())
+                .unwrap_or(false);
+            if !is_empty {
+                return Err(format!("cannot remove '{}': Directory not empty", operand));
             }
         }
+        if inv.prompt == RmPrompt::Always
+            && !confirm(&format!("rm: remove directory '{}'? ", operand))
+        {
+            return Ok(None);
+        }
+        Ok(Some(true))
+    } else {
+        if inv.prompt == RmPrompt::Always && !confirm(&format!("rm: remove '{}'? ", operand)) {
+            return Ok(None);
+        }
+        Ok(Some(false))
     }
+}
+
+/// Handle `mv` command interception.
+/// Parses mv's flags and applies `-n` and `-i` locally, then sends the
+/// remaining sources to the gateway as one batch; its Move handler does
+/// the moving, so nothing moves outside that decision.
+fn handle_mv(args: &[String]) -> anyhow::Result<()> {
+    let inv = match parse_mv_args(args) {
+        Ok(inv) => inv,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            eprintln!("Try 'mv --help' for more information.");
+            process::exit(1);
+        }
+    };
 
+    let into_dir = inv.into_directory || Path::new(&inv.destination).is_dir();
+    let target_of = |source: &str| {
+        if into_dir {
+            Path::new(&inv.destination).join(Path::new(source).file_name().unwrap_or_default())
+        } else {
+            Path::new(&inv.destination).to_path_buf()
+        }
+    };
+    let sources: Vec<String> = inv
+        .sources
+        .iter()
+        .filter(|source| {
+            let target = target_of(source);
+            if !target.exists() {
+                return true;
+            }
+            if inv.no_clobber {
+                return false;
+            }
+            !inv.interactive || confirm(&format!("mv: overwrite '{}'? ", target.display()))
+        })
+        .cloned()
+        .collect();
+    if sources.is_empty() {
+        return Ok(());
+    }
+
+    // The checks above ran from this directory; the gateway gets the same
+    // paths, relative to the workspace
+    let mut destination = gateway_path(&inv.destination);
+    if into_dir && !destination.ends_with('/') {
+        destination.push('/');
+    }
+    let targets: Vec<String> = sources.iter().map(|s| gateway_path(s)).collect();
+    let client = GatewayClient::from_env()?;
+    let response = client.move_files(&targets, &destination)?;
+    if !response.allowed {
+        eprintln!(
+            "[lawctl] BLOCKED: cannot move '{}' — {}",
+            sources.join("', '"),
+            response
+                .error
+                .unwrap_or_else(|| "denied by policy".to_string())
+        );
+        process::exit(1);
+    }
+    if inv.verbose {
+        for source in &sources {
+            println!("renamed '{}' -> '{}'", source, target_of(source).display());
+        }
+    }
+    Ok(())
+}
+
+/// Handle `chmod` command interception (and `lawctl-shim chmod`).
+/// `--reference` is resolved here to the reference file's octal mode; the
+/// gateway's Chmod handler applies the mode, so nothing changes outside
+/// that decision.
+fn handle_chmod(args: &[String]) -> anyhow::Result<()> {
+    let inv = match parse_chmod_args(args) {
+        Ok(inv) => inv,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            eprintln!("Try 'chmod --help' for more information.");
+            process::exit(1);
+        }
+    };
+
+    let mode = match &inv.reference {
+        Some(reference) => {
+            use std::os::unix::fs::PermissionsExt;
+            let metadata = match std::fs::metadata(reference) {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    eprintln!("chmod: failed to get attributes of '{}': {}", reference, e);
+                    process::exit(1);
+                }
+            };
+            format!("{:o}", metadata.permissions().mode() & 0o7777)
+        }
+        None => inv.mode.clone(),
+    };
+
+    let targets: Vec<String> = inv.paths.iter().map(|p| gateway_path(p)).collect();
+    let client = GatewayClient::from_env()?;
+    let response = client.chmod(&targets, &mode, inv.recursive)?;
+    if !response.allowed {
+        eprintln!(
+            "[lawctl] BLOCKED: cannot change permissions of '{}' — {}",
+            inv.paths.join("', '"),
+            response
+                .error
+                .unwrap_or_else(|| "denied by policy".to_string())
+        );
+        process::exit(1);
+    }
+    if inv.verbose || inv.changes {
+        for path in &inv.paths {
+            println!("mode of '{}' changed to {}", path, mode);
+        }
+    }
     Ok(())
 }
 
+/// Ask a yes/no question on stderr and read the answer from stdin.
+/// Anything other than an answer starting with `y` is a no, as with rm.
+fn confirm(question: &str) -> bool {
+    eprint!("{}", question);
+    let _ = std::io::stderr().flush();
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    answer.trim_start().starts_with(['y', 'Y'])
+}
+
+/// An io::Error's message without the "(os error N)" suffix.
+fn io_reason(e: &std::io::Error) -> String {
+    let msg = e.to_string();
+    match msg.find(" (os error") {
+        Some(idx) => msg[..idx].to_string(),
+        None => msg,
+    }
+}
+
 /// Handle `git` command interception.
 /// Only intercepts `git push` — all other git commands pass through.
 fn handle_git(args: &[String]) -> anyhow::Result<()> {
@@ -177,36 +454,72 @@ fn handle_write(args: &[String]) -> anyhow::Result<()> {
     handle_response(&response, "write", path)
 }
 
-/// Handle explicit `lawctl-shim delete <path>`.
+/// Handle explicit `lawctl-shim delete <path> [path...]` as one batch.
 fn handle_delete(args: &[String]) -> anyhow::Result<()> {
     if args.is_empty() {
-        eprintln!("Usage: lawctl-shim delete <path>");
+        eprintln!("Usage: lawctl-shim delete <path> [path...]");
         process::exit(1);
     }
 
-    let path = &args[0];
     let client = GatewayClient::from_env()?;
 
-    let response = client.delete_file(path)?;
-    handle_response(&response, "delete", path)
+    let targets: Vec<String> = args.iter().map(|a| gateway_path(a)).collect();
+    let response = client.delete_files(&targets)?;
+    handle_response(&response, "delete", &args.join("', '"))
+}
+
+/// Handle explicit `lawctl-shim move <source>... <destination>`.
+fn handle_move(args: &[String]) -> anyhow::Result<()> {
+    let Some((destination, sources)) = args.split_last().filter(|(_, s)| !s.is_empty()) else {
+        eprintln!("Usage: lawctl-shim move <source>... <destination>");
+        process::exit(1);
+    };
+
+    let client = GatewayClient::from_env()?;
+
+    let targets: Vec<String> = sources.iter().map(|s| gateway_path(s)).collect();
+    let response = client.move_files(&targets, &gateway_path(destination))?;
+    handle_response(&response, "move", &sources.join("', '"))
 }
 
-/// Handle explicit `lawctl-shim exec <command...>`.
+/// Handle explicit `lawctl-shim exec [--shell <shell>] <command...>`.
+/// `--shell` names the shell the command was written for (e.g. `fish`), so
+/// the gateway runs it with that interpreter instead of `sh`.
 fn handle_exec(args: &[String]) -> anyhow::Result<()> {
+    let (shell, args) = match args {
+        [flag, shell, rest @ ..] if flag == "--shell" => (Some(shell.as_str()), rest),
+        _ => (None, args),
+    };
     if args.is_empty() {
-        eprintln!("Usage: lawctl-shim exec <command...>");
+        eprintln!("Usage: lawctl-shim exec [--shell <shell>] <command...>");
         process::exit(1);
     }
 
     let command = args.join(" ");
+    check_env_access(&command)?;
+    check_package_installs(&command)?;
     let client = GatewayClient::from_env()?;
 
-    let response = client.run_cmd(&command)?;
+    let response = client.run_cmd_in(&command, shell)?;
     if response.allowed {
-        if let Some(output) = response.result {
-            print!("{}", output);
+        // Each stream back where the command wrote it, and its exit code;
+        // older gateways only send the combined `result`
+        match (response.stdout, response.stderr) {
+            (Some(stdout), Some(stderr)) => {
+                print!("{}", stdout);
+                eprint!("{}", stderr);
+            }
+            _ => {
+                if let Some(output) = response.result {
+                    print!("{}", output);
+                }
+            }
+        }
+        std::io::stdout().flush()?;
+        match response.exit_code {
+            Some(0) | None => Ok(()),
+            Some(code) => process::exit(code),
         }
-        Ok(())
     } else {
         eprintln!(
             "[lawctl] BLOCKED: command denied — {}",
@@ -218,6 +531,154 @@ fn handle_exec(args: &[String]) -> anyhow::Result<()> {
     }
 }
 
+/// Handle `printenv` and `env` interception: the variables they would
+/// print go to the gateway as `env_access` first, then the real command
+/// runs. `env` running another command reads nothing and passes through.
+fn handle_env(program: &str, args: &[String]) -> anyhow::Result<()> {
+    let mut command = vec![program.to_string()];
+    command.extend_from_slice(args);
+    check_env_access(&command.join(" "))?;
+    handle_passthrough(program, args)
+}
+
+/// Ask the gateway before a command reads environment variables; exits if
+/// any of them is denied. Dumps of the whole environment are checked as
+/// every variable set here.
+fn check_env_access(command: &str) -> anyhow::Result<()> {
+    let reads = env_reads(command);
+    if reads.is_empty() {
+        return Ok(());
+    }
+    let names = env_access_targets(reads, env::vars().map(|(name, _)| name));
+    let client = GatewayClient::from_env()?;
+    let response = client.env_access(&names)?;
+    if !response.allowed {
+        eprintln!(
+            "[lawctl] BLOCKED: environment access denied — {}",
+            response.error.as_deref().unwrap_or("denied by policy")
+        );
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// Handle package manager interception: packages an install would add go
+/// to the gateway as `package_install` first, then the real package
+/// manager runs. Anything but an install passes straight through.
+fn handle_installer(program: &str, args: &[String]) -> anyhow::Result<()> {
+    let mut command = vec![program.to_string()];
+    command.extend_from_slice(args);
+    check_package_installs(&command.join(" "))?;
+    handle_passthrough(program, args)
+}
+
+/// Ask the gateway before a command installs packages; exits if any of
+/// them is denied.
+fn check_package_installs(command: &str) -> anyhow::Result<()> {
+    let installs = package_installs_by_manager(command);
+    if installs.is_empty() {
+        return Ok(());
+    }
+    let client = GatewayClient::from_env()?;
+    for (manager, packages) in installs {
+        let response = client.package_install(&manager, &packages)?;
+        if !response.allowed {
+            eprintln!(
+                "[lawctl] BLOCKED: {} install of {} — {}",
+                manager,
+                packages.join(", "),
+                response.error.as_deref().unwrap_or("denied by policy")
+            );
+            process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+/// Handle remote access interception: each host an `ssh`, `scp`, `sftp`
+/// or `rsync` reaches goes to the gateway as `remote_exec` or
+/// `remote_copy` first, then the real program runs. Local-only rsyncs pass
+/// straight through.
+fn handle_remote(program: &str, args: &[String]) -> anyhow::Result<()> {
+    let mut command = vec![program.to_string()];
+    command.extend_from_slice(args);
+    let accesses = remote_accesses(&command.join(" "));
+    if !accesses.is_empty() {
+        let client = GatewayClient::from_env()?;
+        for access in accesses {
+            let (action, host, remote_command) = match &access {
+                RemoteAccess::Exec { host, command } => {
+                    (Action::RemoteExec, host, command.as_deref())
+                }
+                RemoteAccess::Copy { host } => (Action::RemoteCopy, host, None),
+            };
+            let response = client.remote(action.clone(), host, remote_command)?;
+            if !response.allowed {
+                eprintln!(
+                    "[lawctl] BLOCKED: {} to {} — {}",
+                    action,
+                    host,
+                    response.error.as_deref().unwrap_or("denied by policy")
+                );
+                process::exit(1);
+            }
+        }
+    }
+    handle_passthrough(program, args)
+}
+
+/// Handle kubectl and helm interception: the command goes to the gateway
+/// as `k8s`, with the context it would use, then the real tool runs.
+fn handle_k8s(program: &str, args: &[String]) -> anyhow::Result<()> {
+    let mut command = vec![program.to_string()];
+    command.extend_from_slice(args);
+    let command = command.join(" ");
+    let context = kube::invocations(&command)
+        .into_iter()
+        .next()
+        .and_then(|invocation| invocation.context)
+        .or_else(kube::current_context)
+        .unwrap_or_default();
+    let client = GatewayClient::from_env()?;
+    let response = client.k8s(&context, &command)?;
+    if !response.allowed {
+        eprintln!(
+            "[lawctl] BLOCKED: {} on context '{}' — {}",
+            program,
+            context,
+            response.error.as_deref().unwrap_or("denied by policy")
+        );
+        process::exit(1);
+    }
+    handle_passthrough(program, args)
+}
+
+/// Handle cloud CLI interception: the command goes to the gateway as
+/// `cloud`, with the account it would use, then the real CLI runs.
+fn handle_cloud(program: &str, args: &[String]) -> anyhow::Result<()> {
+    let mut command = vec![program.to_string()];
+    command.extend_from_slice(args);
+    let command = command.join(" ");
+    let account = cloud::invocations(&command)
+        .into_iter()
+        .next()
+        .and_then(|invocation| invocation.account)
+        .or_else(|| cloud::default_account(program))
+        .unwrap_or_default();
+    let client = GatewayClient::from_env()?;
+    let response = client.cloud(&account, &command)?;
+    if !response.allowed {
+        eprintln!(
+            "[lawctl] BLOCKED: {} on account '{}' — {}",
+            program,
+            account,
+            response.error.as_deref().unwrap_or("denied by policy")
+        );
+        process::exit(1);
+    }
+    handle_passthrough(program, args)
+}
+
 /// Handle explicit `lawctl-shim git-push <branch>`.
 fn handle_git_push(args: &[String]) -> anyhow::Result<()> {
     let branch = args.first().map(|s| s.as_str()).unwrap_or("main");
@@ -249,27 +710,26 @@ fn handle_response(response: &GatewayResponse, action: &str, target: &str) -> an
 
 /// Pass a command through to the real binary (not intercepted).
 fn handle_passthrough(command: &str, args: &[String]) -> anyhow::Result<()> {
-    // Find the real binary by checking PATH, skipping our shim
-    let status = process::Command::new(format!("/usr/bin/{}", command))
-        .args(args)
-        .status();
-
-    match status {
-        Ok(s) => {
-            if !s.success() {
-                process::exit(s.code().unwrap_or(1));
-            }
-            Ok(())
-        }
-        Err(_) => {
-            // Try without the /usr/bin prefix
-            let status = process::Command::new(command).args(args).status()?;
-            if !status.success() {
-                process::exit(status.code().unwrap_or(1));
-            }
-            Ok(())
-        }
+    let real =
+        real_binary(command).ok_or_else(|| anyhow::anyhow!("{}: command not found", command))?;
+    let status = process::Command::new(real).args(args).status()?;
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
     }
+    Ok(())
+}
+
+/// The real `command`: the first one on PATH (then in /usr/bin) that isn't
+/// this shim, so a symlinked shim never runs itself.
+fn real_binary(command: &str) -> Option<std::path::PathBuf> {
+    let shim = env::current_exe().and_then(|exe| exe.canonicalize()).ok();
+    let path = env::var_os("PATH").unwrap_or_default();
+    env::split_paths(&path)
+        .chain(std::iter::once(std::path::PathBuf::from("/usr/bin")))
+        .map(|dir| dir.join(command))
+        .find(|candidate| {
+            candidate.is_file() && candidate.canonicalize().ok().as_ref() != shim.as_ref()
+        })
 }
 
 fn print_usage() {
@@ -278,15 +738,19 @@ fn print_usage() {
 
 Usage:
   lawctl-shim write <path> <content>    Write a file through the gateway
-  lawctl-shim delete <path>             Delete a file through the gateway
+  lawctl-shim delete <path>...          Delete files through the gateway
+  lawctl-shim move <path>... <dest>     Move files through the gateway
+  lawctl-shim chmod [-R] <mode> <path>... Change file modes through the gateway
   lawctl-shim exec <command...>         Run a command through the gateway
+    --shell <shell>                     ...written for this shell (bash, fish, pwsh...)
   lawctl-shim git-push [branch]         Git push through the gateway
   lawctl-shim help                      Show this help
 
 Environment:
-  LAWCTL_SOCKET    Path to the gateway Unix socket (required)
+  LAWCTL_SOCKET    Path to the gateway Unix socket (default: the one running gateway)
 
-The shim can also be symlinked as `rm` or `git` to transparently
-intercept those commands."#
+The shim can also be symlinked as `rm`, `mv`, `chmod`, `printenv`, `env`, `git`
+or a package manager (`npm`, `pnpm`, `yarn`, `bun`, `pip`, `pip3`, `uv`, `poetry`,
+`cargo`) to transparently intercept those commands."#
     );
 }
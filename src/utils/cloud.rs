@@ -0,0 +1,279 @@
+//! Cloud provider CLI analysis (`aws`, `gcloud`, `az`) for `cloud` rules.
+//!
+//! Each command is sorted by what it does to the account: reads (`list`,
+//! `describe`, `aws s3 ls`), mutations (`create`, `aws s3 cp`) and destroys
+//! (`delete`, `terminate-instances`, `aws s3 rm`). Anything not recognized
+//! as a read or a destroy counts as a mutation, so an unfamiliar command
+//! asks rather than slips through. The account is the AWS profile, GCP
+//! project or Azure subscription, from the command line or the CLI's own
+//! defaults.
+
+use crate::utils::command::normalize_command;
+use std::fmt;
+
+/// Options of the cloud CLIs that take a value.
+const VALUE_FLAGS: &[&str] = &[
+    "--profile",
+    "--region",
+    "--output",
+    "--query",
+    "--endpoint-url",
+    "--project",
+    "--zone",
+    "--format",
+    "--configuration",
+    "--account",
+    "--subscription",
+    "--resource-group",
+    "-g",
+    "--name",
+    "-n",
+    "--location",
+    "-l",
+    "-o",
+];
+
+/// What a cloud command does to the account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloudEffect {
+    Read,
+    Mutate,
+    Destroy,
+}
+
+impl CloudEffect {
+    /// The names `if_effect` accepts.
+    pub const NAMES: &'static [&'static str] = &["read", "mutate", "destroy"];
+}
+
+impl fmt::Display for CloudEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CloudEffect::Read => write!(f, "read"),
+            CloudEffect::Mutate => write!(f, "mutate"),
+            CloudEffect::Destroy => write!(f, "destroy"),
+        }
+    }
+}
+
+/// One `aws`, `gcloud` or `az` command in a command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloudInvocation {
+    /// `aws`, `gcloud` or `az`
+    pub provider: String,
+    /// The operation as written: `s3 rm`, `compute instances delete`
+    pub operation: String,
+    pub effect: CloudEffect,
+    /// The `--profile`, `--project` or `--subscription` given, if any
+    pub account: Option<String>,
+}
+
+/// Find the cloud CLI commands in a command line, across `&&`, `||`, `;`
+/// and `|` chains.
+pub fn invocations(command: &str) -> Vec<CloudInvocation> {
+    let normalized = normalize_command(command);
+    let mut found = Vec::new();
+    for segment in normalized.split(['&', '|', ';', '(', ')', '{', '}']) {
+        let words: Vec<&str> = segment
+            .split_whitespace()
+            .skip_while(|w| matches!(*w, "sudo" | "builtin" | "command" | "exec" | "time"))
+            .collect();
+        let Some((program, args)) = words.split_first() else {
+            continue;
+        };
+        let provider = program.rsplit('/').next().unwrap_or(program);
+        if !matches!(provider, "aws" | "gcloud" | "az") {
+            continue;
+        }
+        let mut positionals = Vec::new();
+        let mut account = None;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with('-') => (flag, Some(value.to_string())),
+                _ => (*arg, None),
+            };
+            if VALUE_FLAGS.contains(&flag) {
+                let value = inline.or_else(|| args.next().map(|v| v.to_string()));
+                if matches!(flag, "--profile" | "--project" | "--subscription") {
+                    account = value;
+                }
+            } else if !arg.starts_with('-') {
+                positionals.push(*arg);
+            }
+        }
+        found.push(CloudInvocation {
+            provider: provider.to_string(),
+            effect: classify(provider, &positionals),
+            operation: operation(provider, &positionals),
+            account,
+        });
+    }
+    found
+}
+
+/// The words naming the operation: aws's service and command, and for
+/// gcloud and az the command groups up to the verb.
+fn operation(provider: &str, positionals: &[&str]) -> String {
+    let words: Vec<&str> = if provider == "aws" {
+        positionals.iter().take(2).copied().collect()
+    } else {
+        let end = positionals
+            .iter()
+            .position(|w| verb_effect(w).is_some())
+            .map_or(positionals.len(), |i| i + 1);
+        positionals[..end].to_vec()
+    };
+    words.join(" ")
+}
+
+fn classify(provider: &str, positionals: &[&str]) -> CloudEffect {
+    if provider == "aws" {
+        // aws <service> <command>; s3's commands are short verbs
+        return match positionals {
+            [] => CloudEffect::Read,
+            [_] => CloudEffect::Mutate,
+            [_, command, ..] => verb_effect(command).unwrap_or(CloudEffect::Mutate),
+        };
+    }
+    positionals
+        .iter()
+        .find_map(|w| verb_effect(w))
+        .unwrap_or(CloudEffect::Mutate)
+}
+
+/// The effect a verb (or aws command name) clearly has, if it's one of the
+/// known reads or destroys.
+fn verb_effect(verb: &str) -> Option<CloudEffect> {
+    const READS: &[&str] = &[
+        "list", "ls", "describe", "show", "get", "cat", "read", "info", "wait", "head",
+    ];
+    const READ_PREFIXES: &[&str] = &["list-", "describe-", "get-", "show-", "head-"];
+    const DESTROYS: &[&str] = &[
+        "delete",
+        "rm",
+        "rb",
+        "remove",
+        "destroy",
+        "purge",
+        "terminate",
+    ];
+    const DESTROY_PREFIXES: &[&str] = &[
+        "delete-",
+        "terminate-",
+        "remove-",
+        "purge-",
+        "deregister-",
+        "destroy-",
+    ];
+    if READS.contains(&verb) || READ_PREFIXES.iter().any(|p| verb.starts_with(p)) {
+        Some(CloudEffect::Read)
+    } else if DESTROYS.contains(&verb) || DESTROY_PREFIXES.iter().any(|p| verb.starts_with(p)) {
+        Some(CloudEffect::Destroy)
+    } else {
+        None
+    }
+}
+
+/// The account a provider's CLI uses when the command doesn't say: the
+/// AWS profile from the environment (or `default`), the GCP project from
+/// the environment or the active gcloud configuration, the Azure
+/// subscription from the environment.
+pub fn default_account(provider: &str) -> Option<String> {
+    let env = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+    match provider {
+        "aws" => env("AWS_PROFILE")
+            .or_else(|| env("AWS_DEFAULT_PROFILE"))
+            .or_else(|| Some("default".to_string())),
+        "gcloud" => env("CLOUDSDK_CORE_PROJECT").or_else(gcloud_project),
+        "az" => env("AZURE_SUBSCRIPTION_ID"),
+        _ => None,
+    }
+}
+
+/// `project` from the `[core]` section of gcloud's default configuration.
+fn gcloud_project() -> Option<String> {
+    let dir = match std::env::var_os("CLOUDSDK_CONFIG") {
+        Some(dir) => dir.into(),
+        None => dirs::config_dir()?.join("gcloud"),
+    };
+    let text = std::fs::read_to_string(dir.join("configurations/config_default")).ok()?;
+    ini_value(&text, "core", "project")
+}
+
+fn ini_value(text: &str, section: &str, key: &str) -> Option<String> {
+    let mut current = "";
+    for line in text.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            current = name;
+        } else if current == section {
+            if let Some((k, v)) = line.split_once('=') {
+                if k.trim() == key && !v.trim().is_empty() {
+                    return Some(v.trim().to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn effect(command: &str) -> CloudEffect {
+        invocations(command)[0].effect
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(effect("aws s3 ls s3://bucket"), CloudEffect::Read);
+        assert_eq!(effect("aws ec2 describe-instances"), CloudEffect::Read);
+        assert_eq!(effect("aws s3 cp a.txt s3://bucket/"), CloudEffect::Mutate);
+        assert_eq!(
+            effect("aws s3 rm s3://bucket --recursive"),
+            CloudEffect::Destroy
+        );
+        assert_eq!(
+            effect("aws ec2 terminate-instances --instance-ids i-1"),
+            CloudEffect::Destroy
+        );
+        assert_eq!(
+            effect("gcloud compute instances list --project acme"),
+            CloudEffect::Read
+        );
+        assert_eq!(
+            effect("gcloud compute instances delete web-1 --zone us-east1-b"),
+            CloudEffect::Destroy
+        );
+        assert_eq!(
+            effect("gcloud run deploy api --source ."),
+            CloudEffect::Mutate
+        );
+        assert_eq!(effect("az vm show -g rg -n vm1"), CloudEffect::Read);
+        assert_eq!(effect("az group delete --name rg"), CloudEffect::Destroy);
+    }
+
+    #[test]
+    fn test_invocations() {
+        let found = invocations(
+            "aws --profile prod-admin s3 rm s3://logs/old --recursive && gcloud --project=acme-prod sql instances list",
+        );
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].operation, "s3 rm");
+        assert_eq!(found[0].account.as_deref(), Some("prod-admin"));
+        assert_eq!(found[1].operation, "sql instances list");
+        assert_eq!(found[1].account.as_deref(), Some("acme-prod"));
+        assert!(invocations("echo aws s3 rm").is_empty());
+    }
+
+    #[test]
+    fn test_ini_value() {
+        let text = "[core]\naccount = me@example.com\nproject = acme-dev\n\n[compute]\nzone = x\n";
+        assert_eq!(
+            ini_value(text, "core", "project").as_deref(),
+            Some("acme-dev")
+        );
+        assert_eq!(ini_value(text, "core", "zone"), None);
+    }
+}
@@ -0,0 +1,151 @@
+//! CODEOWNERS parsing for `if_codeowners_not` rules.
+//!
+//! Follows GitHub's rules: the file is looked up in `.github/`, the repo
+//! root, then `docs/`; the **last** matching line wins; a line with no
+//! owners leaves matching paths unowned. Patterns use gitignore syntax:
+//!
+//! - `/build/`    — anchored to the repo root (leading slash)
+//! - `*.rs`       — no inner slash, so it matches at any depth
+//! - `docs/*`     — `*` doesn't cross directories, `**` does
+//! - `apps/`      — a directory covers everything beneath it
+
+use globset::{Glob, GlobBuilder, GlobMatcher};
+use std::path::Path;
+
+/// Where GitHub looks for the file, in order.
+const LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// A parsed CODEOWNERS file.
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    matchers: Vec<GlobMatcher>,
+    owners: Vec<String>,
+}
+
+impl CodeOwners {
+    /// Load the repo's CODEOWNERS, if it has one.
+    pub fn load(repo_root: &Path) -> Option<Self> {
+        LOCATIONS.iter().find_map(|location| {
+            let content = std::fs::read_to_string(repo_root.join(location)).ok()?;
+            Some(Self::parse(&content))
+        })
+    }
+
+    /// Parse CODEOWNERS content. Lines with patterns that can't be compiled
+    /// are skipped, as GitHub does.
+    pub fn parse(content: &str) -> Self {
+        let entries = content
+            .lines()
+            .filter_map(|line| {
+                let line = line.split(" #").next().unwrap_or(line).trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let mut fields = line.split_whitespace();
+                let pattern = fields.next()?;
+                let matchers = compile_pattern(pattern)?;
+                Some(Entry {
+                    matchers,
+                    owners: fields.map(String::from).collect(),
+                })
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Owners of a repo-relative path. Empty if nobody owns it.
+    pub fn owners(&self, path: &str) -> &[String] {
+        let path = path.trim_start_matches("./").trim_start_matches('/');
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.matchers.iter().any(|m| m.is_match(path)))
+            .map(|entry| entry.owners.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+/// Translate one gitignore-style pattern into globs over repo-relative paths.
+fn compile_pattern(pattern: &str) -> Option<Vec<GlobMatcher>> {
+    let anchored = pattern.starts_with('/');
+    let body = pattern.trim_start_matches('/').trim_end_matches('/');
+    if body.is_empty() {
+        // A bare "/" covers the whole repo
+        return Some(vec![Glob::new("**").ok()?.compile_matcher()]);
+    }
+
+    let base = if anchored || body.contains('/') {
+        body.to_string()
+    } else {
+        format!("**/{}", body)
+    };
+    // The pattern itself, or anything beneath a directory it names
+    [base.clone(), format!("{}/**", base)]
+        .iter()
+        .map(|glob| {
+            GlobBuilder::new(glob)
+                .literal_separator(true)
+                .build()
+                .map(|g| g.compile_matcher())
+                .ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+# Default owners
+*                   @org/core
+*.rs                @org/rust   # inline comment
+/docs/              @alice
+apps/web/           @org/frontend @bob
+/vendor/
+build/*.lock        @org/release
+"#;
+
+    #[test]
+    fn test_last_match_wins() {
+        let owners = CodeOwners::parse(SAMPLE);
+        assert_eq!(owners.owners("README.md"), ["@org/core"]);
+        assert_eq!(owners.owners("src/deep/main.rs"), ["@org/rust"]);
+        assert_eq!(owners.owners("docs/guide.md"), ["@alice"]);
+        assert_eq!(
+            owners.owners("apps/web/src/index.ts"),
+            ["@org/frontend", "@bob"]
+        );
+    }
+
+    #[test]
+    fn test_pattern_semantics() {
+        let owners = CodeOwners::parse(SAMPLE);
+        // Anchored: only the top-level docs/
+        assert_eq!(owners.owners("src/docs/notes.md"), ["@org/core"]);
+        // No owners listed → unowned
+        assert!(owners.owners("vendor/lib/x.js").is_empty());
+        // `*` doesn't cross directories
+        assert_eq!(owners.owners("build/Cargo.lock"), ["@org/release"]);
+        assert_eq!(owners.owners("build/sub/Cargo.lock"), ["@org/core"]);
+        // Leading ./ and / are tolerated
+        assert_eq!(owners.owners("./docs/a.md"), ["@alice"]);
+    }
+
+    #[test]
+    fn test_load_prefers_github_dir() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".github")).unwrap();
+        std::fs::write(tmp.path().join(".github/CODEOWNERS"), "* @first").unwrap();
+        std::fs::write(tmp.path().join("CODEOWNERS"), "* @second").unwrap();
+        let owners = CodeOwners::load(tmp.path()).unwrap();
+        assert_eq!(owners.owners("x"), ["@first"]);
+        let empty = tempfile::TempDir::new().unwrap();
+        assert!(CodeOwners::load(empty.path()).is_none());
+    }
+}
@@ -0,0 +1,155 @@
+//! `kubectl` and `helm` command analysis for `k8s` rules.
+//!
+//! An agent with the user's kubeconfig can reach every cluster in it, so
+//! what matters about a kubectl or helm command is which context it talks
+//! to and what it does there. The context comes from `--context` (helm:
+//! `--kube-context`) when it's given, and from the kubeconfig's
+//! `current-context` otherwise.
+
+use crate::utils::command::normalize_command;
+use std::path::Path;
+
+/// Options of kubectl and helm that take a value, before or after the verb.
+const VALUE_FLAGS: &[&str] = &[
+    "--context",
+    "--kube-context",
+    "--namespace",
+    "-n",
+    "--kubeconfig",
+    "--cluster",
+    "--user",
+    "--server",
+    "-s",
+    "--selector",
+    "-l",
+    "--output",
+    "-o",
+    "--filename",
+    "-f",
+    "--container",
+    "-c",
+    "--values",
+    "--set",
+    "--version",
+];
+
+/// One `kubectl` or `helm` command in a command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KubeInvocation {
+    /// `kubectl` or `helm`
+    pub tool: String,
+    /// What it does: `get`, `apply`, `delete`, `install`, `uninstall`...
+    pub verb: Option<String>,
+    /// The `--context` / `--kube-context` given, if any
+    pub context: Option<String>,
+    /// The `--namespace` / `-n` given, if any
+    pub namespace: Option<String>,
+}
+
+/// Find the kubectl and helm commands in a command line, across `&&`,
+/// `||`, `;` and `|` chains.
+pub fn invocations(command: &str) -> Vec<KubeInvocation> {
+    let normalized = normalize_command(command);
+    let mut found = Vec::new();
+    for segment in normalized.split(['&', '|', ';', '(', ')', '{', '}']) {
+        let words: Vec<&str> = segment
+            .split_whitespace()
+            .skip_while(|w| matches!(*w, "sudo" | "builtin" | "command" | "exec" | "time"))
+            .collect();
+        let Some((program, args)) = words.split_first() else {
+            continue;
+        };
+        let tool = program.rsplit('/').next().unwrap_or(program);
+        if !matches!(tool, "kubectl" | "helm") {
+            continue;
+        }
+        let mut invocation = KubeInvocation {
+            tool: tool.to_string(),
+            verb: None,
+            context: None,
+            namespace: None,
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with('-') => (flag, Some(value.to_string())),
+                _ => (*arg, None),
+            };
+            if VALUE_FLAGS.contains(&flag) {
+                let value = inline.or_else(|| args.next().map(|v| v.to_string()));
+                match flag {
+                    "--context" | "--kube-context" => invocation.context = value,
+                    "--namespace" | "-n" => invocation.namespace = value,
+                    _ => {}
+                }
+            } else if !arg.starts_with('-') && invocation.verb.is_none() {
+                invocation.verb = Some(arg.to_string());
+            }
+        }
+        found.push(invocation);
+    }
+    found
+}
+
+/// The kubeconfig's `current-context`: the first file in `$KUBECONFIG`,
+/// or `~/.kube/config`.
+pub fn current_context() -> Option<String> {
+    let path = match std::env::var_os("KUBECONFIG") {
+        Some(paths) => std::env::split_paths(&paths).next()?,
+        None => dirs::home_dir()?.join(".kube").join("config"),
+    };
+    context_in(&path)
+}
+
+fn context_in(path: &Path) -> Option<String> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let config: serde_yaml::Value = serde_yaml::from_str(&text).ok()?;
+    config
+        .get("current-context")?
+        .as_str()
+        .filter(|c| !c.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invocations() {
+        let found = invocations("kubectl --context prod-eu -n payments delete pod api-7f9");
+        assert_eq!(
+            found,
+            [KubeInvocation {
+                tool: "kubectl".to_string(),
+                verb: Some("delete".to_string()),
+                context: Some("prod-eu".to_string()),
+                namespace: Some("payments".to_string()),
+            }]
+        );
+
+        let found = invocations(
+            "helm upgrade --install web ./chart --kube-context=staging && kubectl get pods",
+        );
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].verb.as_deref(), Some("upgrade"));
+        assert_eq!(found[0].context.as_deref(), Some("staging"));
+        assert_eq!(found[1].verb.as_deref(), Some("get"));
+        assert_eq!(found[1].context, None);
+
+        assert!(invocations("echo kubectl delete all").is_empty());
+    }
+
+    #[test]
+    fn test_current_context() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config");
+        std::fs::write(
+            &path,
+            "apiVersion: v1\nkind: Config\ncurrent-context: prod-us\ncontexts: []\n",
+        )
+        .unwrap();
+        assert_eq!(context_in(&path).as_deref(), Some("prod-us"));
+        assert_eq!(context_in(&dir.path().join("missing")), None);
+    }
+}
@@ -0,0 +1,172 @@
+//! Where lawctl keeps its files.
+//!
+//! Two directories, each overridable:
+//!
+//! - the data directory (logs, approvals, shims, locks, debug traces):
+//!   `LAWCTL_DATA_DIR`, else `$XDG_DATA_HOME/lawctl`, else `~/.lawctl`.
+//!   An existing `~/.lawctl` wins over `XDG_DATA_HOME` so setting that
+//!   variable later doesn't orphan the logs and approvals already there.
+//! - the runtime directory (gateway sockets): `LAWCTL_RUNTIME_DIR`, else
+//!   `$XDG_RUNTIME_DIR/lawctl`, else `/tmp`.
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::os::unix::fs::DirBuilderExt;
+use std::path::{Path, PathBuf};
+
+/// Overrides the data directory.
+pub const DATA_DIR_VAR: &str = "LAWCTL_DATA_DIR";
+/// Overrides the runtime (socket) directory.
+pub const RUNTIME_DIR_VAR: &str = "LAWCTL_RUNTIME_DIR";
+
+/// Where sockets go when nothing else is configured.
+const FALLBACK_RUNTIME_DIR: &str = "/tmp";
+
+fn env_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// The data directory for the given environment and home directory.
+fn resolve_data_dir(env: impl Fn(&str) -> Option<PathBuf>, home: Option<&Path>) -> Option<PathBuf> {
+    if let Some(dir) = env(DATA_DIR_VAR) {
+        return Some(dir);
+    }
+    let legacy = home.map(|h| h.join(".lawctl"));
+    if let Some(legacy) = &legacy {
+        if legacy.is_dir() {
+            return Some(legacy.clone());
+        }
+    }
+    env("XDG_DATA_HOME")
+        .filter(|d| d.is_absolute())
+        .map(|d| d.join("lawctl"))
+        .or(legacy)
+}
+
+/// The runtime directory for the given environment.
+fn resolve_runtime_dir(env: impl Fn(&str) -> Option<PathBuf>) -> PathBuf {
+    env(RUNTIME_DIR_VAR)
+        .or_else(|| {
+            env("XDG_RUNTIME_DIR")
+                .filter(|d| d.is_absolute())
+                .map(|d| d.join("lawctl"))
+        })
+        .unwrap_or_else(|| PathBuf::from(FALLBACK_RUNTIME_DIR))
+}
+
+/// Where a socket goes that another user must be able to reach (the agent
+/// under `--as-user`): a per-user runtime directory is private to its owner.
+pub fn shared_runtime_dir() -> PathBuf {
+    env_path(RUNTIME_DIR_VAR).unwrap_or_else(|| PathBuf::from(FALLBACK_RUNTIME_DIR))
+}
+
+/// Every directory gateway sockets may have been created in.
+pub fn socket_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![runtime_dir()];
+    let shared = shared_runtime_dir();
+    if !dirs.contains(&shared) {
+        dirs.push(shared);
+    }
+    dirs
+}
+
+/// lawctl's data directory (not created).
+pub fn data_dir() -> Result<PathBuf> {
+    resolve_data_dir(env_path, dirs::home_dir().as_deref()).with_context(|| {
+        format!(
+            "Could not determine home directory; set {} to choose where lawctl keeps its data",
+            DATA_DIR_VAR
+        )
+    })
+}
+
+/// The directory gateway sockets are created in (not created).
+pub fn runtime_dir() -> PathBuf {
+    resolve_runtime_dir(env_path)
+}
+
+/// A short stable name for a workspace, for per-project files: the first
+/// 16 hex digits of the SHA-256 of its canonical path.
+pub fn workspace_key(workspace: &Path) -> String {
+    let workspace = workspace
+        .canonicalize()
+        .unwrap_or_else(|_| workspace.to_path_buf());
+    let digest = Sha256::digest(workspace.to_string_lossy().as_bytes());
+    format!("{:x}", digest)[..16].to_string()
+}
+
+/// Create a socket directory if needed. Ones lawctl creates are private.
+pub fn ensure_dir(dir: &Path) -> Result<()> {
+    if dir.is_dir() {
+        return Ok(());
+    }
+    std::fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(dir)
+        .with_context(|| format!("Failed to create {}", dir.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<PathBuf> {
+        let vars: HashMap<String, PathBuf> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), PathBuf::from(v)))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_data_dir() {
+        let home = tempfile::TempDir::new().unwrap();
+        let home = home.path();
+
+        assert_eq!(
+            resolve_data_dir(env(&[]), Some(home)),
+            Some(home.join(".lawctl"))
+        );
+        assert_eq!(
+            resolve_data_dir(env(&[("XDG_DATA_HOME", "/xdg")]), Some(home)),
+            Some(PathBuf::from("/xdg/lawctl"))
+        );
+        // Relative XDG paths are invalid and ignored
+        assert_eq!(
+            resolve_data_dir(env(&[("XDG_DATA_HOME", "xdg")]), Some(home)),
+            Some(home.join(".lawctl"))
+        );
+        assert_eq!(resolve_data_dir(env(&[]), None), None);
+
+        // An existing ~/.lawctl is kept
+        std::fs::create_dir(home.join(".lawctl")).unwrap();
+        assert_eq!(
+            resolve_data_dir(env(&[("XDG_DATA_HOME", "/xdg")]), Some(home)),
+            Some(home.join(".lawctl"))
+        );
+        assert_eq!(
+            resolve_data_dir(env(&[(DATA_DIR_VAR, "/data")]), Some(home)),
+            Some(PathBuf::from("/data"))
+        );
+    }
+
+    #[test]
+    fn test_runtime_dir() {
+        assert_eq!(resolve_runtime_dir(env(&[])), PathBuf::from("/tmp"));
+        assert_eq!(
+            resolve_runtime_dir(env(&[("XDG_RUNTIME_DIR", "/run/user/1000")])),
+            PathBuf::from("/run/user/1000/lawctl")
+        );
+        assert_eq!(
+            resolve_runtime_dir(env(&[
+                ("XDG_RUNTIME_DIR", "/run/user/1000"),
+                (RUNTIME_DIR_VAR, "/var/run/lawctl"),
+            ])),
+            PathBuf::from("/var/run/lawctl")
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Lockfile deltas for the dependency policy.
+//!
+//! When the agent rewrites `Cargo.lock` or `package-lock.json`, the old and
+//! new contents are compared to find packages that weren't there before.
+//! Licenses come from the lockfile itself where it records them (npm v2+),
+//! or from the local cargo registry cache for crates — nothing is fetched.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// A package a lockfile change introduces.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dependency {
+    pub name: String,
+    pub version: String,
+    /// SPDX expression, when it could be determined
+    pub license: Option<String>,
+}
+
+impl std::fmt::Display for Dependency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} ({})",
+            self.name,
+            self.version,
+            self.license.as_deref().unwrap_or("license unknown")
+        )
+    }
+}
+
+/// Whether a path is a lockfile we know how to diff.
+pub fn is_lockfile(path: &str) -> bool {
+    matches!(
+        Path::new(path).file_name().and_then(|n| n.to_str()),
+        Some("Cargo.lock" | "package-lock.json" | "npm-shrinkwrap.json")
+    )
+}
+
+/// Packages present in `new` but not `old`. Unparseable content yields
+/// nothing rather than an error — the write itself is still policy-checked.
+pub fn added_dependencies(path: &str, old: &str, new: &str) -> Vec<Dependency> {
+    let parse = if path.ends_with("Cargo.lock") {
+        parse_cargo_lock
+    } else {
+        parse_package_lock
+    };
+    let before: BTreeSet<(String, String)> = parse(old)
+        .into_iter()
+        .map(|d| (d.name, d.version))
+        .collect();
+    let mut added: Vec<Dependency> = parse(new)
+        .into_iter()
+        .filter(|d| !before.contains(&(d.name.clone(), d.version.clone())))
+        .collect();
+    added.dedup_by(|a, b| a.name == b.name && a.version == b.version);
+
+    if path.ends_with("Cargo.lock") {
+        for dep in &mut added {
+            dep.license = cargo_registry_license(&dep.name, &dep.version);
+        }
+    }
+    added
+}
+
+/// Whether a license expression is unacceptable given a deny list: every
+/// `OR` alternative has to include a denied license. `GPL-3.0` also covers
+/// `GPL-3.0-only`, `GPL-3.0-or-later` and `GPL-3.0+`.
+pub fn license_denied(expression: &str, denied: &[String]) -> bool {
+    let base = |id: &str| {
+        id.trim_end_matches('+')
+            .trim_end_matches("-only")
+            .trim_end_matches("-or-later")
+            .to_ascii_lowercase()
+    };
+    let denied: Vec<String> = denied.iter().map(|d| base(d)).collect();
+    // Old npm packages use "MIT/Apache-2.0" to mean either
+    let normalized = expression.replace(['(', ')'], " ").replace('/', " OR ");
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+    if tokens.is_empty() {
+        return false;
+    }
+    tokens
+        .split(|token| token.eq_ignore_ascii_case("OR"))
+        .all(|alternative| alternative.iter().any(|t| denied.contains(&base(t))))
+}
+
+/// `[[package]]` entries that come from a registry or git (not workspace members).
+fn parse_cargo_lock(content: &str) -> Vec<Dependency> {
+    let mut deps = Vec::new();
+    let mut current: Option<(Option<String>, Option<String>, bool)> = None;
+    let mut finish = |entry: Option<(Option<String>, Option<String>, bool)>| {
+        if let Some((Some(name), Some(version), true)) = entry {
+            deps.push(Dependency {
+                name,
+                version,
+                license: None,
+            });
+        }
+    };
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            finish(current.take());
+            if line == "[[package]]" {
+                current = Some((None, None, false));
+            }
+            continue;
+        }
+        let Some(entry) = current.as_mut() else {
+            continue;
+        };
+        if let Some((key, value)) = line.split_once('=') {
+            let value = value.trim().trim_matches('"').to_string();
+            match key.trim() {
+                "name" => entry.0 = Some(value),
+                "version" => entry.1 = Some(value),
+                "source" => entry.2 = true,
+                _ => {}
+            }
+        }
+    }
+    finish(current);
+    deps
+}
+
+/// Packages from npm lockfile v2/v3 (`packages`) or v1 (`dependencies`).
+fn parse_package_lock(content: &str) -> Vec<Dependency> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let mut deps = Vec::new();
+    if let Some(packages) = json.get("packages").and_then(|p| p.as_object()) {
+        for (key, info) in packages {
+            // "" is the project itself; links point at workspace packages
+            let Some((_, name)) = key.rsplit_once("node_modules/") else {
+                continue;
+            };
+            if info.get("link").and_then(|l| l.as_bool()) == Some(true) {
+                continue;
+            }
+            let text = |field: &str| info.get(field).and_then(|v| v.as_str()).map(String::from);
+            if let Some(version) = text("version") {
+                deps.push(Dependency {
+                    name: name.to_string(),
+                    version,
+                    license: text("license"),
+                });
+            }
+        }
+    } else if let Some(dependencies) = json.get("dependencies") {
+        collect_v1(dependencies, &mut deps);
+    }
+    deps
+}
+
+fn collect_v1(dependencies: &serde_json::Value, deps: &mut Vec<Dependency>) {
+    let Some(map) = dependencies.as_object() else {
+        return;
+    };
+    for (name, info) in map {
+        if let Some(version) = info.get("version").and_then(|v| v.as_str()) {
+            deps.push(Dependency {
+                name: name.clone(),
+                version: version.to_string(),
+                license: None,
+            });
+        }
+        if let Some(nested) = info.get("dependencies") {
+            collect_v1(nested, deps);
+        }
+    }
+}
+
+/// A crate's `license` from the local registry cache, if it's been downloaded.
+fn cargo_registry_license(name: &str, version: &str) -> Option<String> {
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|h| h.join(".cargo")))?;
+    let registries = std::fs::read_dir(cargo_home.join("registry/src")).ok()?;
+    registries.flatten().find_map(|registry| {
+        let manifest = registry
+            .path()
+            .join(format!("{}-{}", name, version))
+            .join("Cargo.toml");
+        let content = std::fs::read_to_string(manifest).ok()?;
+        content.lines().find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "license").then(|| value.trim().trim_matches('"').to_string())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARGO_OLD: &str = r#"
+version = 3
+
+[[package]]
+name = "myapp"
+version = "0.1.0"
+dependencies = ["serde"]
+
+[[package]]
+name = "serde"
+version = "1.0.200"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+    #[test]
+    fn test_cargo_lock_delta() {
+        let new = format!(
+            "{}\n[[package]]\nname = \"left-pad\"\nversion = \"0.1.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+            CARGO_OLD.replace("1.0.200", "1.0.201")
+        );
+        let added = added_dependencies("Cargo.lock", CARGO_OLD, &new);
+        let names: Vec<(&str, &str)> = added
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str()))
+            .collect();
+        // Version bumps count as new; the workspace crate never does
+        assert_eq!(names, vec![("serde", "1.0.201"), ("left-pad", "0.1.0")]);
+    }
+
+    #[test]
+    fn test_package_lock_delta() {
+        let old = r#"{"lockfileVersion": 3, "packages": {
+            "": {"name": "app"},
+            "node_modules/react": {"version": "18.2.0", "license": "MIT"}
+        }}"#;
+        let new = r#"{"lockfileVersion": 3, "packages": {
+            "": {"name": "app"},
+            "node_modules/react": {"version": "18.2.0", "license": "MIT"},
+            "node_modules/react/node_modules/gpl-thing": {"version": "1.0.0", "license": "GPL-3.0-only"},
+            "node_modules/local": {"resolved": "packages/local", "link": true}
+        }}"#;
+        let added = added_dependencies("web/package-lock.json", old, new);
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].name, "gpl-thing");
+        assert_eq!(added[0].license.as_deref(), Some("GPL-3.0-only"));
+        assert_eq!(added[0].to_string(), "gpl-thing 1.0.0 (GPL-3.0-only)");
+    }
+
+    #[test]
+    fn test_license_expressions() {
+        let denied = vec!["GPL-3.0".to_string(), "AGPL-3.0".to_string()];
+        assert!(license_denied("GPL-3.0-only", &denied));
+        assert!(license_denied("GPL-3.0+", &denied));
+        assert!(license_denied("(AGPL-3.0-or-later AND MIT)", &denied));
+        // A permissive alternative is available
+        assert!(!license_denied("MIT OR GPL-3.0", &denied));
+        assert!(!license_denied("MIT/Apache-2.0", &denied));
+        assert!(!license_denied("LGPL-3.0", &denied));
+    }
+
+    #[test]
+    fn test_is_lockfile() {
+        assert!(is_lockfile("Cargo.lock"));
+        assert!(is_lockfile("/repo/web/package-lock.json"));
+        assert!(!is_lockfile("Cargo.toml"));
+        assert!(!is_lockfile("yarn.lock"));
+    }
+}
@@ -1 +1,18 @@
+pub mod cloud;
+pub mod codeowners;
+pub mod command;
+pub mod domain;
+pub mod kube;
+pub mod locations;
+pub mod lockfile;
 pub mod paths;
+pub mod process;
+pub mod redact;
+pub mod schedule;
+pub mod session_lock;
+pub mod shell;
+pub mod signals;
+pub mod term;
+pub mod threats;
+pub mod typosquat;
+pub mod version;
@@ -0,0 +1,103 @@
+//! Which process is behind a gateway request.
+//!
+//! An agent that spawns sub-agents or scripts sends all their requests
+//! through the same socket. The gateway asks the kernel who is on the other
+//! end (SO_PEERCRED) and walks up the process tree from there, so each audit
+//! entry says which process asked: the client first, then its parent, and so
+//! on up to the agent lawctl started. Outside Linux there's no /proc to
+//! walk and the chain stays empty.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Longest chain recorded; deeper trees are cut off at the top.
+const MAX_DEPTH: usize = 16;
+
+/// One process in the chain behind a request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    /// The process's command name, as the kernel has it (up to 15 characters)
+    pub name: String,
+}
+
+impl fmt::Display for ProcessInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.pid)
+    }
+}
+
+/// `pid` and its ancestors, stopping below lawctl itself (`stop_at`) or
+/// init.
+pub fn chain(pid: u32, stop_at: u32) -> Vec<ProcessInfo> {
+    let mut chain = Vec::new();
+    let mut pid = pid;
+    while pid > 1 && pid != stop_at && chain.len() < MAX_DEPTH {
+        let Some((name, parent)) = stat(pid) else {
+            break;
+        };
+        chain.push(ProcessInfo { pid, name });
+        pid = parent;
+    }
+    chain
+}
+
+/// The command name and parent pid of a running process.
+fn stat(pid: u32) -> Option<(String, u32)> {
+    parse_stat(&std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?)
+}
+
+/// `pid (name) state ppid ...`, where the name may itself hold spaces and
+/// parentheses.
+fn parse_stat(stat: &str) -> Option<(String, u32)> {
+    let open = stat.find('(')?;
+    let close = stat.rfind(')')?;
+    let name = stat.get(open + 1..close)?.to_string();
+    let parent = stat
+        .get(close + 1..)?
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()?;
+    Some((name, parent))
+}
+
+/// The chain as `requester ← parent ← ...`.
+pub fn describe(chain: &[ProcessInfo]) -> String {
+    chain
+        .iter()
+        .map(ProcessInfo::to_string)
+        .collect::<Vec<_>>()
+        .join(" ← ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stat() {
+        assert_eq!(
+            parse_stat("4312 (python3) S 4301 4312 4100 0 -1"),
+            Some(("python3".to_string(), 4301))
+        );
+        assert_eq!(
+            parse_stat("77 (my (odd) name) R 1 77 77"),
+            Some(("my (odd) name".to_string(), 1))
+        );
+        assert_eq!(parse_stat("garbage"), None);
+    }
+
+    #[test]
+    fn test_chain_of_this_process() {
+        if !std::path::Path::new("/proc/self/stat").exists() {
+            return;
+        }
+        let me = std::process::id();
+        let parent = std::os::unix::process::parent_id();
+        let chain = chain(me, parent);
+        assert_eq!(chain.len(), 1);
+        assert_eq!(chain[0].pid, me);
+        assert!(describe(&chain).contains(&format!("({})", me)));
+    }
+}
@@ -0,0 +1,83 @@
+//! Time windows for `only_between` and `only_on_days` conditions.
+//!
+//! Windows are in local time, `HH:MM-HH:MM`, start inclusive and end
+//! exclusive. A window whose end comes before its start runs overnight —
+//! `22:00-06:00` covers late evening and early morning.
+
+use chrono::{NaiveTime, Weekday};
+
+/// A parsed `only_between` window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeWindow {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl TimeWindow {
+    /// Parse `HH:MM-HH:MM`. Returns None for anything else, or for a window
+    /// that starts and ends at the same minute.
+    pub fn parse(window: &str) -> Option<Self> {
+        let (start, end) = window.split_once('-')?;
+        let time = |s: &str| NaiveTime::parse_from_str(s.trim(), "%H:%M").ok();
+        let (start, end) = (time(start)?, time(end)?);
+        (start != end).then_some(Self { start, end })
+    }
+
+    /// Whether `time` falls inside the window.
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Parse a day name for `only_on_days`: `mon`, `Monday`, and so on, plus
+/// `weekdays` and `weekends` for the usual groups.
+pub fn parse_days(day: &str) -> Option<Vec<Weekday>> {
+    use Weekday::*;
+    match day.trim().to_ascii_lowercase().as_str() {
+        "weekdays" => Some(vec![Mon, Tue, Wed, Thu, Fri]),
+        "weekends" => Some(vec![Sat, Sun]),
+        other => other.parse::<Weekday>().ok().map(|d| vec![d]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_time_window() {
+        let day = TimeWindow::parse("09:00-18:00").unwrap();
+        assert!(day.contains(at(9, 0)));
+        assert!(day.contains(at(17, 59)));
+        assert!(!day.contains(at(18, 0)));
+        assert!(!day.contains(at(3, 0)));
+
+        let night = TimeWindow::parse(" 22:00 - 06:00 ").unwrap();
+        assert!(night.contains(at(23, 30)));
+        assert!(night.contains(at(5, 0)));
+        assert!(!night.contains(at(12, 0)));
+
+        for bad in ["9-5", "09:00", "25:00-26:00", "09:00-09:00", ""] {
+            assert_eq!(TimeWindow::parse(bad), None, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("Mon"), Some(vec![Weekday::Mon]));
+        assert_eq!(parse_days("saturday"), Some(vec![Weekday::Sat]));
+        assert_eq!(
+            parse_days("weekends"),
+            Some(vec![Weekday::Sat, Weekday::Sun])
+        );
+        assert_eq!(parse_days("someday"), None);
+    }
+}
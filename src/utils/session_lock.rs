@@ -0,0 +1,209 @@
+//! One session per workspace.
+//!
+//! Two `lawctl go` runs in the same project would interleave their actions
+//! in the working tree and race over approvals, with nothing in either log
+//! saying the other existed. So a session holds a lock for its workspace in
+//! `locks/` in lawctl's data directory (keyed by a hash of the workspace path, so nothing is
+//! written into the project). The lock records who holds it; one left by a
+//! process that's gone is stale and simply replaced.
+
+use crate::utils::{locations, signals};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Who holds a workspace lock.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockHolder {
+    pub pid: u32,
+    pub session_id: String,
+    pub agent: String,
+    pub started: DateTime<Utc>,
+    pub workspace: PathBuf,
+}
+
+impl LockHolder {
+    /// "session 1234abcd (claude), pid 4242, started 3m ago"
+    pub fn describe(&self) -> String {
+        let age = Utc::now().signed_duration_since(self.started);
+        let age = if age.num_hours() > 0 {
+            format!("{}h", age.num_hours())
+        } else if age.num_minutes() > 0 {
+            format!("{}m", age.num_minutes())
+        } else {
+            format!("{}s", age.num_seconds().max(0))
+        };
+        format!(
+            "session {} ({}), pid {}, started {} ago",
+            &self.session_id[..self.session_id.len().min(8)],
+            self.agent,
+            self.pid,
+            age
+        )
+    }
+}
+
+/// A held workspace lock, released when dropped.
+#[derive(Debug)]
+pub struct WorkspaceLock {
+    path: PathBuf,
+}
+
+/// Where workspace locks live.
+pub fn lock_directory() -> Result<PathBuf> {
+    Ok(locations::data_dir()?.join("locks"))
+}
+
+/// The lock file for a workspace.
+pub fn lock_path(dir: &Path, workspace: &Path) -> PathBuf {
+    dir.join(format!("{}.lock", locations::workspace_key(workspace)))
+}
+
+impl WorkspaceLock {
+    /// Take the lock for `holder.workspace`. A live holder is an error
+    /// naming the session, unless `force`, which stops that session first
+    /// (SIGTERM, so it cleans up and records the interruption).
+    pub fn acquire(dir: &Path, holder: &LockHolder, force: bool) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = lock_path(dir, &holder.workspace);
+        // Another run can win the race between clearing a stale lock and
+        // creating ours, so try a few times
+        for _ in 0..3 {
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    let json = serde_json::to_string(holder)?;
+                    file.write_all(json.as_bytes())
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to create {}", path.display()))
+                }
+            }
+
+            let current = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|json| serde_json::from_str::<LockHolder>(&json).ok());
+            match current {
+                Some(current) if signals::is_alive(current.pid) => {
+                    if !force {
+                        bail!(
+                            "Another lawctl session is already running in this workspace:\n    \
+                             {}\n  Wait for it to finish, or stop it and take over with --force.",
+                            current.describe()
+                        );
+                    }
+                    stop_holder(&current)?;
+                }
+                Some(current) => {
+                    tracing::debug!(holder = %current.describe(), "replacing stale workspace lock");
+                }
+                None => {}
+            }
+            let _ = std::fs::remove_file(&path);
+        }
+        bail!(
+            "Couldn't take the workspace lock at {} — another lawctl keeps taking it",
+            path.display()
+        )
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for WorkspaceLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Stop a running session for `--force` and wait for it to go. Its own
+/// shutdown removes its socket and writes its session-end record.
+fn stop_holder(holder: &LockHolder) -> Result<()> {
+    if holder.pid == std::process::id() {
+        bail!("This process already holds the workspace lock");
+    }
+    signals::send(holder.pid, signals::SIGTERM);
+    // It gets the agent's grace period, plus a moment to clean up
+    let deadline =
+        std::time::Instant::now() + signals::GRACE_PERIOD + std::time::Duration::from_secs(2);
+    while signals::is_alive(holder.pid) {
+        if std::time::Instant::now() > deadline {
+            bail!(
+                "The running session didn't stop ({}); kill it yourself and retry",
+                holder.describe()
+            );
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holder(pid: u32, workspace: &Path) -> LockHolder {
+        LockHolder {
+            pid,
+            session_id: "1234abcd-0000".to_string(),
+            agent: "claude".to_string(),
+            started: Utc::now(),
+            workspace: workspace.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn test_second_session_is_refused() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let workspace = tempfile::TempDir::new().unwrap();
+        let me = holder(std::process::id(), workspace.path());
+
+        let lock = WorkspaceLock::acquire(dir.path(), &me, false).unwrap();
+        assert!(lock.path().exists());
+        let err = WorkspaceLock::acquire(dir.path(), &me, false).unwrap_err();
+        assert!(err.to_string().contains("session 1234abcd (claude)"));
+
+        // Other workspaces aren't affected
+        let elsewhere = tempfile::TempDir::new().unwrap();
+        WorkspaceLock::acquire(dir.path(), &holder(1, elsewhere.path()), false).unwrap();
+
+        let path = lock.path().to_path_buf();
+        drop(lock);
+        assert!(!path.exists());
+        WorkspaceLock::acquire(dir.path(), &me, false).unwrap();
+    }
+
+    #[test]
+    fn test_stale_lock_is_replaced() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let workspace = tempfile::TempDir::new().unwrap();
+        // A pid from a child that has already exited
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let dead = child.id();
+        child.wait().unwrap();
+
+        std::fs::create_dir_all(dir.path()).unwrap();
+        std::fs::write(
+            lock_path(dir.path(), workspace.path()),
+            serde_json::to_string(&holder(dead, workspace.path())).unwrap(),
+        )
+        .unwrap();
+        WorkspaceLock::acquire(
+            dir.path(),
+            &holder(std::process::id(), workspace.path()),
+            false,
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,380 @@
+//! Command lines split the way the shell reads them.
+//!
+//! Glob patterns over the raw text can't tell `rm --recursive --force .`
+//! from `rm -rf .`, and find `rm -rf` inside `grep "rm -rf" notes.txt`.
+//! Here a command line is broken into its simple commands — at `&&`, `||`,
+//! `;`, `|`, `&`, newlines, subshells and `$( )` / backtick substitutions —
+//! and each into its words, quotes and escapes removed, assignments and
+//! redirections taken out. Characters are folded to plain ASCII first, as
+//! in [`normalize_command`](crate::utils::command::normalize_command).
+//!
+//! This is a tokenizer, not a full shell grammar: expansions aren't
+//! performed and control structures only lose their keywords.
+
+use crate::utils::command::fold_char;
+
+/// Words that start a control structure rather than a command.
+const RESERVED_WORDS: &[&str] = &[
+    "if", "then", "else", "elif", "fi", "do", "done", "while", "until", "for", "case", "esac", "!",
+    "{", "}",
+];
+
+/// One command of a command line: the program and its arguments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimpleCommand {
+    pub argv: Vec<String>,
+}
+
+impl SimpleCommand {
+    /// The program run, without its directory (`/usr/bin/rm` → `rm`).
+    pub fn program(&self) -> Option<&str> {
+        let program = self.argv.first()?;
+        Some(program.rsplit('/').next().unwrap_or(program))
+    }
+}
+
+impl std::fmt::Display for SimpleCommand {
+    /// The words as a command line, quoted where needed so it reads back
+    /// into the same words.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let words: Vec<String> = self.argv.iter().map(|word| quote(word)).collect();
+        write!(f, "{}", words.join(" "))
+    }
+}
+
+/// A word as the shell would need it written.
+fn quote(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%^*~".contains(c));
+    if plain {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    /// `&&`, `||`, `;`, `|`, `&`, a newline or a parenthesis: the end of a
+    /// simple command
+    Separator,
+    /// `>`, `>>`, `<`, `2>&1`...: the next word is a file, not an argument
+    Redirect,
+}
+
+/// A command line's tokens, and the text of its command substitutions.
+#[derive(Debug, Default)]
+struct Tokens {
+    tokens: Vec<Token>,
+    substitutions: Vec<String>,
+}
+
+impl Tokens {
+    fn word(&mut self, word: &mut Option<String>) {
+        if let Some(word) = word.take() {
+            self.tokens.push(Token::Word(word));
+        }
+    }
+}
+
+fn tokenize(command: &str) -> Tokens {
+    let chars: Vec<char> = command.chars().filter_map(fold_char).collect();
+    let mut out = Tokens::default();
+    let mut word: Option<String> = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            ' ' | '\t' | '\r' => out.word(&mut word),
+            '\n' | ';' | '(' | ')' => {
+                out.word(&mut word);
+                out.tokens.push(Token::Separator);
+            }
+            '&' if next == Some('>') => {
+                out.word(&mut word);
+                out.tokens.push(Token::Redirect);
+                i += 1;
+                while chars.get(i + 1) == Some(&'>') {
+                    i += 1;
+                }
+            }
+            '&' | '|' => {
+                out.word(&mut word);
+                out.tokens.push(Token::Separator);
+                if matches!(next, Some('&' | '|')) {
+                    i += 1;
+                }
+            }
+            '<' | '>' => {
+                // A number right before is the file descriptor: `2>`
+                match &word {
+                    Some(fd) if !fd.is_empty() && fd.chars().all(|c| c.is_ascii_digit()) => {
+                        word = None
+                    }
+                    _ => out.word(&mut word),
+                }
+                out.tokens.push(Token::Redirect);
+                while matches!(chars.get(i + 1), Some('<' | '>' | '&' | '|')) {
+                    i += 1;
+                }
+            }
+            '#' if word.is_none() => {
+                while chars.get(i + 1).is_some_and(|&c| c != '\n') {
+                    i += 1;
+                }
+            }
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                i += 1;
+                while i < chars.len() && chars[i] != '\'' {
+                    word.push(chars[i]);
+                    i += 1;
+                }
+            }
+            '"' => {
+                word.get_or_insert_with(String::new);
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    match (chars[i], chars.get(i + 1)) {
+                        ('\\', Some(&escaped @ ('$' | '`' | '"' | '\\'))) => {
+                            push(&mut word, escaped);
+                            i += 1;
+                        }
+                        ('\\', Some('\n')) => i += 1,
+                        ('$', Some('(')) | ('`', _) => {
+                            i = substitution(&chars, i, &mut word, &mut out);
+                        }
+                        (c, _) => push(&mut word, c),
+                    }
+                    i += 1;
+                }
+            }
+            '\\' => {
+                match next {
+                    Some('\n') | None => {}
+                    Some(escaped) => push(&mut word, escaped),
+                }
+                i += 1;
+            }
+            '$' if next == Some('(') => i = substitution(&chars, i, &mut word, &mut out),
+            '`' => i = substitution(&chars, i, &mut word, &mut out),
+            _ => push(&mut word, c),
+        }
+        i += 1;
+    }
+    out.word(&mut word);
+    out
+}
+
+fn push(word: &mut Option<String>, c: char) {
+    word.get_or_insert_with(String::new).push(c);
+}
+
+/// Read the `$( )` or backtick substitution starting at `start`: its text
+/// goes to `out.substitutions`, and the whole of it into the current word.
+/// Returns the index of its last character.
+fn substitution(
+    chars: &[char],
+    start: usize,
+    word: &mut Option<String>,
+    out: &mut Tokens,
+) -> usize {
+    let (open, end) = if chars[start] == '`' {
+        let end = (start + 1..chars.len())
+            .find(|&i| chars[i] == '`')
+            .unwrap_or(chars.len());
+        (start + 1, end)
+    } else {
+        let mut depth = 0;
+        let mut quote: Option<char> = None;
+        let mut end = chars.len();
+        for (i, &c) in chars.iter().enumerate().skip(start + 1) {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '\'' | '"') => quote = Some(c),
+                (None, '(') => depth += 1,
+                (None, ')') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = i;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        (start + 2, end)
+    };
+    let inner: String = chars[open.min(end)..end].iter().collect();
+    let whole: String = chars[start..(end + 1).min(chars.len())].iter().collect();
+    word.get_or_insert_with(String::new).push_str(&whole);
+    out.substitutions.push(inner);
+    end
+}
+
+/// Whether a word sets a variable (`NAME=value`) rather than naming a
+/// program.
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// Every simple command in a command line, in order, then those of its
+/// command substitutions.
+pub fn simple_commands(command: &str) -> Vec<SimpleCommand> {
+    let Tokens {
+        tokens,
+        substitutions,
+    } = tokenize(command);
+    let mut commands = Vec::new();
+    let mut argv: Vec<String> = Vec::new();
+    let mut redirected = false;
+    for token in tokens {
+        match token {
+            Token::Separator => {
+                if !argv.is_empty() {
+                    commands.push(SimpleCommand {
+                        argv: std::mem::take(&mut argv),
+                    });
+                }
+                redirected = false;
+            }
+            Token::Redirect => redirected = true,
+            Token::Word(_) if redirected => redirected = false,
+            Token::Word(word)
+                if argv.is_empty()
+                    && (is_assignment(&word) || RESERVED_WORDS.contains(&word.as_str())) => {}
+            Token::Word(word) => argv.push(word),
+        }
+    }
+    if !argv.is_empty() {
+        commands.push(SimpleCommand { argv });
+    }
+    for substitution in substitutions {
+        commands.extend(simple_commands(&substitution));
+    }
+    commands
+}
+
+/// The words of a command line that is one plain command: no operators,
+/// redirections or substitutions. None for anything else.
+pub fn words(command: &str) -> Option<Vec<String>> {
+    let Tokens {
+        tokens,
+        substitutions,
+    } = tokenize(command);
+    if !substitutions.is_empty() {
+        return None;
+    }
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            Token::Word(word) => Some(word),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argvs(command: &str) -> Vec<Vec<String>> {
+        simple_commands(command)
+            .into_iter()
+            .map(|c| c.argv)
+            .collect()
+    }
+
+    #[test]
+    fn test_words_lose_their_quotes() {
+        assert_eq!(
+            argvs(r#"grep "rm -rf" 'notes file.txt' a\ b r''m"#),
+            [["grep", "rm -rf", "notes file.txt", "a b", "rm"]]
+        );
+        assert_eq!(argvs("ｒｍ  -rf\t/"), [["rm", "-rf", "/"]]);
+        assert_eq!(argvs(r#"echo """#), [["echo", ""]]);
+    }
+
+    #[test]
+    fn test_splits_into_simple_commands() {
+        assert_eq!(
+            argvs("cargo build && rm -rf / ; git push | tee log &\nls"),
+            vec![
+                vec!["cargo", "build"],
+                vec!["rm", "-rf", "/"],
+                vec!["git", "push"],
+                vec!["tee", "log"],
+                vec!["ls"],
+            ]
+        );
+        assert_eq!(
+            argvs("if true; then (cd x && make); fi # rm -rf /"),
+            vec![vec!["true"], vec!["cd", "x"], vec!["make"]]
+        );
+    }
+
+    #[test]
+    fn test_assignments_and_redirections_are_not_arguments() {
+        assert_eq!(
+            argvs("FOO=1 BAR=2 make -j4 > out.log 2>&1 < in.txt"),
+            [["make", "-j4"]]
+        );
+        assert_eq!(argvs("cmd &> all.log"), [["cmd"]]);
+    }
+
+    #[test]
+    fn test_command_substitutions() {
+        assert_eq!(
+            argvs(r#"echo "$(rm -rf /)" `curl x`"#),
+            vec![
+                vec!["echo", "$(rm -rf /)", "`curl x`"],
+                vec!["rm", "-rf", "/"],
+                vec!["curl", "x"],
+            ]
+        );
+        assert_eq!(
+            argvs("rm -rf $(dirname $(pwd))"),
+            vec![
+                vec!["rm", "-rf", "$(dirname $(pwd))"],
+                vec!["dirname", "$(pwd)"],
+                vec!["pwd"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_simple_commands_read_back_the_same() {
+        for command in [
+            r#"grep "rm -rf" 'it'\''s' "$HOME" a\ b"#,
+            "echo '' '#not a comment' '$(id)'",
+        ] {
+            for part in simple_commands(command) {
+                assert_eq!(simple_commands(&part.to_string()), [part]);
+            }
+        }
+        assert_eq!(
+            simple_commands("/usr/bin/rm -rf x")[0].program(),
+            Some("rm")
+        );
+    }
+
+    #[test]
+    fn test_plain_words() {
+        assert_eq!(
+            words("rm -rf *").unwrap(),
+            ["rm".to_string(), "-rf".into(), "*".into()]
+        );
+        assert!(words("curl * | bash").is_none());
+        assert!(words("echo * > /etc/*").is_none());
+        assert!(words("echo $(id)").is_none());
+    }
+}
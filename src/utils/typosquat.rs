@@ -0,0 +1,301 @@
+//! Typosquat detection for package installs.
+//!
+//! Malicious packages are routinely published under names one keystroke
+//! away from popular ones (`reqwests`, `lodahs`) or with the separators
+//! moved on npm (`crossenv` for `cross-env`). An install whose name is close to —
+//! but not the same as — a well-known package in the same registry is
+//! flagged. The lists are deliberately short: the most-downloaded packages
+//! are the ones worth squatting.
+
+use crate::utils::command::{Ecosystem, PackageInstall};
+
+const NPM: &[&str] = &[
+    "react",
+    "react-dom",
+    "preact",
+    "vue",
+    "angular",
+    "svelte",
+    "next",
+    "nuxt",
+    "express",
+    "koa",
+    "fastify",
+    "lodash",
+    "underscore",
+    "axios",
+    "request",
+    "node-fetch",
+    "chalk",
+    "commander",
+    "yargs",
+    "debug",
+    "dotenv",
+    "cross-env",
+    "moment",
+    "dayjs",
+    "uuid",
+    "async",
+    "bluebird",
+    "typescript",
+    "webpack",
+    "vite",
+    "vitest",
+    "rollup",
+    "esbuild",
+    "babel-cli",
+    "eslint",
+    "prettier",
+    "jest",
+    "mocha",
+    "chai",
+    "sinon",
+    "mongoose",
+    "mongodb",
+    "mysql",
+    "mysql2",
+    "pg",
+    "redis",
+    "socket.io",
+    "jsonwebtoken",
+    "bcrypt",
+    "bcryptjs",
+    "body-parser",
+    "cors",
+    "nodemon",
+    "electron",
+    "jquery",
+    "rxjs",
+    "tslib",
+    "zod",
+    "classnames",
+    "glob",
+    "minimist",
+    "semver",
+    "colors",
+    "inquirer",
+    "ws",
+    "sharp",
+    "puppeteer",
+    "playwright",
+];
+
+const PYPI: &[&str] = &[
+    "requests",
+    "urllib3",
+    "certifi",
+    "idna",
+    "setuptools",
+    "wheel",
+    "pip",
+    "six",
+    "numpy",
+    "pandas",
+    "scipy",
+    "matplotlib",
+    "seaborn",
+    "scikit-learn",
+    "tensorflow",
+    "torch",
+    "keras",
+    "flask",
+    "django",
+    "fastapi",
+    "uvicorn",
+    "gunicorn",
+    "pydantic",
+    "sqlalchemy",
+    "psycopg2",
+    "pymysql",
+    "redis",
+    "celery",
+    "boto3",
+    "botocore",
+    "pyyaml",
+    "jinja2",
+    "click",
+    "rich",
+    "typer",
+    "pytest",
+    "coverage",
+    "black",
+    "flake8",
+    "mypy",
+    "pylint",
+    "beautifulsoup4",
+    "lxml",
+    "selenium",
+    "pillow",
+    "opencv-python",
+    "cryptography",
+    "pycryptodome",
+    "paramiko",
+    "python-dateutil",
+    "pytz",
+    "httpx",
+    "aiohttp",
+    "openai",
+    "anthropic",
+    "transformers",
+    "colorama",
+    "tqdm",
+    "attrs",
+    "packaging",
+];
+
+const CRATES: &[&str] = &[
+    "serde",
+    "serde_json",
+    "serde_yaml",
+    "tokio",
+    "reqwest",
+    "hyper",
+    "axum",
+    "actix-web",
+    "clap",
+    "anyhow",
+    "thiserror",
+    "log",
+    "env_logger",
+    "tracing",
+    "rand",
+    "regex",
+    "chrono",
+    "time",
+    "uuid",
+    "lazy_static",
+    "once_cell",
+    "futures",
+    "async-trait",
+    "bytes",
+    "itertools",
+    "rayon",
+    "crossbeam",
+    "parking_lot",
+    "libc",
+    "nix",
+    "syn",
+    "quote",
+    "proc-macro2",
+    "sqlx",
+    "diesel",
+    "rusqlite",
+    "base64",
+    "sha2",
+    "hex",
+    "url",
+    "walkdir",
+    "tempfile",
+    "dirs",
+    "toml",
+    "indexmap",
+    "hashbrown",
+    "smallvec",
+    "bitflags",
+    "memchr",
+    "openssl",
+    "rustls",
+];
+
+/// The popular package `install` seems to imitate, if any.
+pub fn resembles_popular(install: &PackageInstall) -> Option<&'static str> {
+    let popular = match install.ecosystem {
+        Ecosystem::Npm => NPM,
+        Ecosystem::PyPI => PYPI,
+        Ecosystem::Crates => CRATES,
+    };
+    // crates.io and PyPI treat `-`, `_` and `.` as the same name; npm doesn't
+    let separators_matter = install.ecosystem == Ecosystem::Npm;
+    let canonical = |name: &str| {
+        let name = name.to_ascii_lowercase();
+        if separators_matter {
+            name
+        } else {
+            name.replace(['_', '.'], "-")
+        }
+    };
+    let name = canonical(&install.name);
+    if popular.iter().any(|known| canonical(known) == name) {
+        return None;
+    }
+    let squashed = squash(&name);
+    popular.iter().copied().find(|known| {
+        // Short names are too close to everything to judge by distance
+        (separators_matter && squash(known) == squashed)
+            || (known.len() >= 5 && edit_distance(&name, &canonical(known)) <= 1)
+    })
+}
+
+/// Drop separators, so `cross_env`, `crossenv` and `cross-env` compare equal.
+fn squash(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '-' | '_' | '.'))
+        .collect()
+}
+
+/// Optimal string alignment distance: insertions, deletions, substitutions
+/// and transpositions of neighbouring characters each cost one.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(ecosystem: Ecosystem, name: &str) -> Option<&'static str> {
+        resembles_popular(&PackageInstall {
+            ecosystem,
+            manager: String::new(),
+            name: name.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_flags_near_misses() {
+        assert_eq!(check(Ecosystem::PyPI, "reqwests"), Some("requests"));
+        assert_eq!(check(Ecosystem::Npm, "lodahs"), Some("lodash"));
+        assert_eq!(check(Ecosystem::Npm, "crossenv"), Some("cross-env"));
+        assert_eq!(check(Ecosystem::Crates, "tokoi"), Some("tokio"));
+    }
+
+    #[test]
+    fn test_leaves_real_packages_alone() {
+        assert_eq!(check(Ecosystem::PyPI, "requests"), None);
+        // Popular in its own registry, even if it's a typo elsewhere
+        assert_eq!(check(Ecosystem::Crates, "reqwest"), None);
+        assert_eq!(check(Ecosystem::Npm, "preact"), None);
+        assert_eq!(check(Ecosystem::Npm, "left-pad"), None);
+        assert_eq!(check(Ecosystem::PyPI, "python_dateutil"), None);
+        // Short names aren't judged by distance
+        assert_eq!(check(Ecosystem::Npm, "ws2"), None);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("lodash", "lodahs"), 1);
+        assert_eq!(edit_distance("requests", "reqwests"), 1);
+        assert_eq!(edit_distance("axios", "axois"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+}
@@ -0,0 +1,73 @@
+//! Version skew between lawctl's pieces.
+//!
+//! The CLI, gateway, hook and shim are meant to run at the same version.
+//! When they don't — a half-finished update, a second copy earlier on PATH,
+//! a container image with an old shim — a newer policy feature can be
+//! silently ignored. These helpers spot the mismatch so it can be logged
+//! and shown by `lawctl status` and `lawctl doctor`.
+
+/// The version of the binary this was compiled into.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// `1.2.3` (or `v1.2.3`, `1.2`) as a comparable tuple.
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split(['-', '+']).next()?;
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+    let patch = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+    Some((major, minor, patch))
+}
+
+/// A warning if `component` reported a version other than ours.
+/// Components that don't report one (older builds) aren't flagged.
+pub fn component_mismatch(component: &str, version: Option<&str>) -> Option<String> {
+    let version = version?;
+    if version == VERSION {
+        return None;
+    }
+    Some(format!(
+        "Version skew: {} {} is talking to lawctl {} — run `lawctl update`",
+        component, version, VERSION
+    ))
+}
+
+/// A warning if a policy asks for a newer lawctl than this one.
+pub fn policy_requirement(required: Option<&str>) -> Option<String> {
+    let required = required?;
+    match (parse_version(required), parse_version(VERSION)) {
+        (Some(needed), Some(have)) if needed > have => Some(format!(
+            "Version skew: the policy needs lawctl {} but this is {} — newer rules may be ignored",
+            required, VERSION
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("v0.2.10"), Some((0, 2, 10)));
+        assert_eq!(parse_version("1.0.0-rc.1"), Some((1, 0, 0)));
+        assert_eq!(parse_version("0.3"), Some((0, 3, 0)));
+        assert!(parse_version("v0.2.10") > parse_version("0.2.9"));
+        assert_eq!(parse_version("latest"), None);
+    }
+
+    #[test]
+    fn test_mismatches() {
+        assert!(component_mismatch("lawctl-shim", Some(VERSION)).is_none());
+        assert!(component_mismatch("lawctl-shim", None).is_none());
+        assert!(component_mismatch("lawctl-shim", Some("0.0.1"))
+            .unwrap()
+            .contains("lawctl-shim 0.0.1"));
+
+        assert!(policy_requirement(Some("0.0.1")).is_none());
+        assert!(policy_requirement(Some("999.0")).is_some());
+        assert!(policy_requirement(None).is_none());
+    }
+}
@@ -83,6 +83,28 @@ async fn setup_gateway() -> (
     (client, workspace, log_dir, handle)
 }
 
+/// Run the shim as `name` (as the symlinks in the sandbox do) from `dir`,
+/// against the gateway behind `client`.
+async fn run_shim_as(
+    client: &Arc<GatewayClient>,
+    workspace: &std::path::Path,
+    dir: &std::path::Path,
+    name: &str,
+    args: &[&str],
+) -> std::process::Output {
+    use std::os::unix::process::CommandExt;
+    let mut command = std::process::Command::new(env!("CARGO_BIN_EXE_lawctl-shim"));
+    command
+        .arg0(name)
+        .args(args)
+        .current_dir(dir)
+        .env("LAWCTL_SOCKET", client.socket_path())
+        .env("LAWCTL_WORKSPACE", workspace);
+    tokio::task::spawn_blocking(move || command.output().unwrap())
+        .await
+        .unwrap()
+}
+
 /// Run a blocking client call without deadlocking the async runtime.
 async fn blocking_write(
     client: &Arc<GatewayClient>,
@@ -108,6 +130,19 @@ async fn blocking_delete(
         .unwrap()
 }
 
+async fn blocking_move(
+    client: &Arc<GatewayClient>,
+    source: &str,
+    destination: &str,
+) -> lawctl::gateway::protocol::GatewayResponse {
+    let c = client.clone();
+    let sources = vec![source.to_string()];
+    let d = destination.to_string();
+    tokio::task::spawn_blocking(move || c.move_files(&sources, &d).unwrap())
+        .await
+        .unwrap()
+}
+
 async fn blocking_run_cmd(
     client: &Arc<GatewayClient>,
     cmd: &str,
@@ -183,6 +218,155 @@ async fn test_e2e_delete_denied_outside_tmp() {
     handle.abort();
 }
 
+#[tokio::test]
+async fn test_e2e_move_paths_judged_relative_to_workspace() {
+    let (client, workspace, _log_dir, handle) = setup_gateway().await;
+    let absolute = workspace.path().join("src/config.rs");
+
+    // Absolute and `..` spellings still hit the `src/**` rule
+    for source in [absolute.to_str().unwrap(), "tmp/../src/config.rs"] {
+        let response = blocking_move(&client, source, "tmp/config.rs").await;
+        assert!(!response.allowed, "Moving {} should be denied", source);
+    }
+    assert!(absolute.exists());
+
+    let scratch = workspace.path().join("tmp/scratch.txt");
+    let response = blocking_move(&client, scratch.to_str().unwrap(), "tmp/moved.txt").await;
+    assert!(response.allowed, "{:?}", response.error);
+    assert!(workspace.path().join("tmp/moved.txt").exists());
+
+    // `mv` run from a subdirectory moves the files named from there
+    let tmp = workspace.path().join("tmp");
+    let output = run_shim_as(
+        &client,
+        workspace.path(),
+        &tmp,
+        "mv",
+        &["moved.txt", "back.txt"],
+    )
+    .await;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(tmp.join("back.txt").exists() && !tmp.join("moved.txt").exists());
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_e2e_chmod_from_a_subdirectory() {
+    use std::os::unix::fs::PermissionsExt;
+    let (client, workspace, _log_dir, handle) = setup_gateway().await;
+    let src = workspace.path().join("src");
+
+    let output = run_shim_as(
+        &client,
+        workspace.path(),
+        &src,
+        "chmod",
+        &["600", "main.rs"],
+    )
+    .await;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let mode = std::fs::metadata(src.join("main.rs"))
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o600);
+
+    handle.abort();
+}
+
+/// Approves plans and turns down everything else, counting what it's asked.
+struct PlansOnly(std::sync::atomic::AtomicUsize);
+
+#[async_trait::async_trait]
+impl lawctl::approval::ApprovalHandler for PlansOnly {
+    async fn request_approval(
+        &self,
+        request: &lawctl::approval::types::ApprovalRequest,
+    ) -> anyhow::Result<lawctl::approval::types::ApprovalResponse> {
+        self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Ok(lawctl::approval::types::ApprovalResponse {
+            approved: request.action == lawctl::policy::Action::PlanApproval,
+            approved_by: Some("test".to_string()),
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_e2e_approved_plan_preauthorizes_what_it_names() {
+    use std::sync::atomic::Ordering;
+    let workspace = TempDir::new().unwrap();
+    let log_dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(workspace.path().join("src")).unwrap();
+    let policy = parser::parse_policy_str(
+        r#"
+law: plan-test
+rules:
+  - require_approval: run_cmd
+  - require_approval: write
+"#,
+    )
+    .unwrap();
+    let socket_path = format!("/tmp/lawctl-test-{}.sock", uuid::Uuid::new_v4());
+    let log_path = log_dir.path().join("plan-session.jsonl");
+    let approvals = Arc::new(PlansOnly(Default::default()));
+    let gateway = GatewayServer::new(
+        &socket_path,
+        PolicyEngine::new(policy).unwrap(),
+        workspace.path(),
+        "plan-session".to_string(),
+        "test-agent".to_string(),
+        AuditLogger::with_path(&log_path).unwrap(),
+        approvals.clone(),
+    );
+    let handle = tokio::spawn(async move {
+        gateway.run().await.ok();
+    });
+    tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+    let client = Arc::new(GatewayClient::new(&socket_path));
+
+    // Without a plan, the human is asked (and says no)
+    let response = blocking_run_cmd(&client, "echo planned").await;
+    assert!(!response.allowed);
+    assert_eq!(approvals.0.load(Ordering::SeqCst), 1);
+
+    let c = client.clone();
+    let response = tokio::task::spawn_blocking(move || {
+        c.approve_plan("Edit `src/lib.rs`, then:\n```bash\necho planned\n```\n")
+            .unwrap()
+    })
+    .await
+    .unwrap();
+    assert!(response.allowed, "{:?}", response.error);
+    assert_eq!(approvals.0.load(Ordering::SeqCst), 2);
+
+    // What the plan names goes ahead without asking
+    let response = blocking_run_cmd(&client, "echo  planned").await;
+    assert!(response.allowed, "{:?}", response.error);
+    let response = blocking_write(&client, "src/lib.rs", "pub fn f() {}").await;
+    assert!(response.allowed, "{:?}", response.error);
+    assert_eq!(approvals.0.load(Ordering::SeqCst), 2);
+
+    // Anything else is still asked about
+    let response = blocking_write(&client, "src/main.rs", "fn main() {}").await;
+    assert!(!response.allowed);
+    assert_eq!(approvals.0.load(Ordering::SeqCst), 3);
+
+    let log = std::fs::read_to_string(&log_path).unwrap();
+    assert!(log.contains("\"plan_approval\""), "{}", log);
+    assert_eq!(log.matches("\"approved_by\":\"plan\"").count(), 3);
+
+    handle.abort();
+}
+
 #[tokio::test]
 async fn test_e2e_safe_command_allowed() {
     let (client, _workspace, _log_dir, handle) = setup_gateway().await;
@@ -199,6 +383,23 @@ async fn test_e2e_safe_command_allowed() {
     handle.abort();
 }
 
+#[tokio::test]
+async fn test_e2e_command_streams_kept_apart() {
+    let (client, _workspace, _log_dir, handle) = setup_gateway().await;
+
+    let response = blocking_run_cmd(&client, "ls src missing-file").await;
+    assert!(response.allowed, "{:?}", response.error);
+    let stdout = response.stdout.unwrap();
+    assert!(stdout.contains("main.rs"));
+    assert!(!stdout.contains("missing-file"));
+    assert!(response.stderr.unwrap().contains("missing-file"));
+    assert_eq!(response.exit_code, Some(2));
+    // The combined form is still there for older shims
+    assert!(response.result.unwrap().contains("[stderr] "));
+
+    handle.abort();
+}
+
 #[tokio::test]
 async fn test_e2e_dangerous_command_denied() {
     let (client, _workspace, _log_dir, handle) = setup_gateway().await;
@@ -293,3 +494,317 @@ async fn test_e2e_path_traversal_blocked() {
 
     handle.abort();
 }
+
+#[tokio::test]
+async fn test_e2e_network_proxy_enforces_policy() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let workspace = TempDir::new().unwrap();
+    let log_dir = TempDir::new().unwrap();
+    let log_path = log_dir.path().join("proxy-session.jsonl");
+
+    let policy = parser::parse_policy_str(
+        "law: proxy-test\nrules:\n  - deny: network\n    unless_domain: [\"127.0.0.1\"]\n    reason: \"Only local traffic\"\n",
+    )
+    .unwrap();
+    let gateway = GatewayServer::new(
+        format!("/tmp/lawctl-test-{}.sock", uuid::Uuid::new_v4()),
+        PolicyEngine::new(policy).unwrap(),
+        workspace.path(),
+        "proxy-session".to_string(),
+        "test-agent".to_string(),
+        AuditLogger::with_path(&log_path).unwrap(),
+        Arc::new(AutoApproval),
+    );
+    let proxy = gateway.start_network_proxy().await.unwrap();
+
+    // A tiny upstream server that answers one request
+    let upstream = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let upstream_addr = upstream.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut stream, _) = upstream.accept().await.unwrap();
+        let mut buf = vec![0u8; 4096];
+        let n = stream.read(&mut buf).await.unwrap();
+        let head = String::from_utf8_lossy(&buf[..n]).to_string();
+        assert!(head.starts_with("GET /hello HTTP/1.1\r\n"));
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nhi")
+            .await
+            .unwrap();
+    });
+
+    // Denied: HTTPS tunnel to a non-allowlisted domain
+    let mut client = tokio::net::TcpStream::connect(proxy.addr()).await.unwrap();
+    client
+        .write_all(b"CONNECT evil.example.com:443 HTTP/1.1\r\nHost: evil.example.com:443\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response = String::new();
+    client.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 403"), "got: {}", response);
+    assert!(response.contains("Only local traffic"));
+
+    // Allowed: plain HTTP to the local upstream is forwarded
+    let mut client = tokio::net::TcpStream::connect(proxy.addr()).await.unwrap();
+    let request = format!(
+        "GET http://{}/hello HTTP/1.1\r\nHost: {}\r\nProxy-Connection: keep-alive\r\n\r\n",
+        upstream_addr, upstream_addr
+    );
+    client.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    client.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "got: {}", response);
+    assert!(response.ends_with("hi"));
+
+    // Both decisions made it into the audit log
+    let log = std::fs::read_to_string(&log_path).unwrap();
+    assert_eq!(log.lines().count(), 2);
+    assert!(log.contains("evil.example.com"));
+}
+
+#[tokio::test]
+async fn test_e2e_network_proxy_caps_response_size() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let workspace = TempDir::new().unwrap();
+    let log_dir = TempDir::new().unwrap();
+    let log_path = log_dir.path().join("cap-session.jsonl");
+
+    let policy = parser::parse_policy_str(
+        "law: cap-test\nrules:\n  - allow: network\n    if_scheme: [http]\n    if_method: [GET]\n    max_response_mb: 1\n",
+    )
+    .unwrap();
+    let gateway = GatewayServer::new(
+        format!("/tmp/lawctl-test-{}.sock", uuid::Uuid::new_v4()),
+        PolicyEngine::new(policy).unwrap(),
+        workspace.path(),
+        "cap-session".to_string(),
+        "test-agent".to_string(),
+        AuditLogger::with_path(&log_path).unwrap(),
+        Arc::new(AutoApproval),
+    );
+    let proxy = gateway.start_network_proxy().await.unwrap();
+
+    // Upstream answers with 2 MB, over the 1 MB cap
+    let upstream = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let upstream_addr = upstream.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut stream, _) = upstream.accept().await.unwrap();
+        let mut buf = vec![0u8; 4096];
+        let _ = stream.read(&mut buf).await.unwrap();
+        let body = vec![b'x'; 2 * 1024 * 1024];
+        let _ = stream
+            .write_all(
+                format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len()).as_bytes(),
+            )
+            .await;
+        let _ = stream.write_all(&body).await;
+    });
+
+    let mut client = tokio::net::TcpStream::connect(proxy.addr()).await.unwrap();
+    let request = format!(
+        "GET http://{}/big HTTP/1.1\r\nHost: {}\r\n\r\n",
+        upstream_addr, upstream_addr
+    );
+    client.write_all(request.as_bytes()).await.unwrap();
+    let mut response = Vec::new();
+    let _ = client.read_to_end(&mut response).await;
+    assert!(response.starts_with(b"HTTP/1.1 200 OK"));
+    assert_eq!(response.len(), 1024 * 1024);
+
+    // The allow decision plus the truncation record
+    let log = std::fs::read_to_string(&log_path).unwrap();
+    assert_eq!(log.lines().count(), 2);
+    assert!(log.contains("\"response_truncated\":true"));
+    assert!(log.contains("\"method\":\"GET\""));
+}
+
+#[tokio::test]
+async fn test_e2e_network_proxy_tracks_upload_volume() {
+    use lawctl::audit::AuditReader;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let workspace = TempDir::new().unwrap();
+    let log_dir = TempDir::new().unwrap();
+    let log_path = log_dir.path().join("upload-session.jsonl");
+
+    let policy = parser::parse_policy_str(
+        "law: upload-test\nrules:\n  - deny: network\n    max_upload_mb_per_domain: 1\n    reason: \"Too much data sent to one host\"\n  - allow: network\n",
+    )
+    .unwrap();
+    let gateway = GatewayServer::new(
+        format!("/tmp/lawctl-test-{}.sock", uuid::Uuid::new_v4()),
+        PolicyEngine::new(policy).unwrap(),
+        workspace.path(),
+        "upload-session".to_string(),
+        "test-agent".to_string(),
+        AuditLogger::with_path(&log_path).unwrap(),
+        Arc::new(AutoApproval),
+    );
+    let proxy = gateway.start_network_proxy().await.unwrap();
+
+    // Upstream swallows whatever the tunnel sends, then closes
+    let upstream = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let upstream_addr = upstream.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut stream, _) = upstream.accept().await.unwrap();
+        let mut sink = Vec::new();
+        let _ = stream.read_to_end(&mut sink).await;
+    });
+
+    // First tunnel is allowed and sends 2 MB
+    let mut client = tokio::net::TcpStream::connect(proxy.addr()).await.unwrap();
+    let connect = format!("CONNECT {} HTTP/1.1\r\n\r\n", upstream_addr);
+    client.write_all(connect.as_bytes()).await.unwrap();
+    let mut established = [0u8; 39];
+    client.read_exact(&mut established).await.unwrap();
+    assert!(established.starts_with(b"HTTP/1.1 200"));
+    client
+        .write_all(&vec![b'x'; 2 * 1024 * 1024])
+        .await
+        .unwrap();
+    client.shutdown().await.unwrap();
+    let mut rest = Vec::new();
+    let _ = client.read_to_end(&mut rest).await;
+
+    // The upload record lands once the pipe closes
+    for _ in 0..50 {
+        if std::fs::read_to_string(&log_path).unwrap().lines().count() >= 2 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+
+    // Now over the threshold for that host
+    let mut client = tokio::net::TcpStream::connect(proxy.addr()).await.unwrap();
+    client.write_all(connect.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    client.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 403"), "got: {}", response);
+    assert!(response.contains("Too much data sent to one host"));
+
+    // Upload records carry bytes but aren't entries or actions
+    let reader = AuditReader::with_dir(log_dir.path());
+    let entries = reader.read_session("upload-session").unwrap();
+    let mut summary = AuditReader::summarize(&entries);
+    summary.add_uploads(&reader.read_session_records("upload-session").unwrap());
+    assert_eq!(entries.len(), 2);
+    assert_eq!(summary.total_actions, 2);
+    assert_eq!(summary.uploads.get("127.0.0.1"), Some(&(2 * 1024 * 1024)));
+    assert!(summary.uploads_line().unwrap().contains("127.0.0.1 2.0 MB"));
+}
+
+#[tokio::test]
+async fn test_e2e_version_skew_logged_once() {
+    use lawctl::gateway::protocol::GatewayRequest;
+    use lawctl::policy::Action;
+
+    let (client, _workspace, log_dir, handle) = setup_gateway().await;
+
+    for _ in 0..2 {
+        let client = client.clone();
+        let response = tokio::task::spawn_blocking(move || {
+            client.send(&GatewayRequest {
+                request_id: uuid::Uuid::new_v4().to_string(),
+                action: Action::RunCmd,
+                target: "shell".to_string(),
+                additional_targets: Vec::new(),
+                method: None,
+                upload_bytes: None,
+                payload: Some("ls".to_string()),
+                client_version: Some("0.0.1".to_string()),
+                shell: None,
+                recursive: false,
+            })
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            response.gateway_version.as_deref(),
+            Some(env!("CARGO_PKG_VERSION"))
+        );
+    }
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let content = std::fs::read_to_string(log_dir.path().join("test-session.jsonl")).unwrap();
+    let warnings: Vec<serde_json::Value> = content
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["warning"].clone())
+        .collect();
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings[0].as_str().unwrap().contains("lawctl-shim 0.0.1"));
+    assert!(warnings[1].is_null());
+
+    handle.abort();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_e2e_sub_policy_holds_what_a_command_starts() {
+    // Requests are traced to the process that sent them through /proc
+    if !std::path::Path::new("/proc/self/stat").exists() {
+        return;
+    }
+    let workspace = TempDir::new().unwrap();
+    let log_dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(workspace.path().join("src")).unwrap();
+    std::fs::create_dir_all(workspace.path().join("tests")).unwrap();
+
+    let policy = parser::parse_policy_str(
+        r#"
+law: delegation-test
+sub_policies:
+  tests-only:
+    rules:
+      - deny: write
+        unless_path: ["tests/**"]
+        reason: "Only test files"
+rules:
+  - allow: run_cmd
+    if_matches: ["lawctl-shim write *"]
+    sub_policy: tests-only
+  - allow: write
+"#,
+    )
+    .unwrap();
+    let socket_path = format!("/tmp/lawctl-test-{}.sock", uuid::Uuid::new_v4());
+    let gateway = GatewayServer::new(
+        &socket_path,
+        PolicyEngine::new(policy).unwrap(),
+        workspace.path(),
+        "delegation-session".to_string(),
+        "test-agent".to_string(),
+        AuditLogger::with_path(log_dir.path().join("delegation-session.jsonl")).unwrap(),
+        Arc::new(AutoApproval),
+    );
+    let handle = tokio::spawn(async move {
+        gateway.run().await.ok();
+    });
+    tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+    let client = Arc::new(GatewayClient::new(&socket_path));
+
+    let shim = format!(
+        "LAWCTL_SOCKET={} {}",
+        socket_path,
+        env!("CARGO_BIN_EXE_lawctl-shim")
+    );
+    let command = format!(
+        "{shim} write src/lib.rs hacked; {shim} write tests/lib_test.rs ok",
+        shim = shim
+    );
+    let response = blocking_run_cmd(&client, &command).await;
+    assert!(response.allowed, "{:?}", response.error);
+    let stderr = response.stderr.unwrap_or_default();
+    assert!(stderr.contains("sub-policy 'tests-only'"), "{}", stderr);
+    assert!(!workspace.path().join("src/lib.rs").exists());
+    assert_eq!(
+        std::fs::read_to_string(workspace.path().join("tests/lib_test.rs")).unwrap(),
+        "ok"
+    );
+
+    // The agent itself isn't held to it
+    let response = blocking_write(&client, "src/lib.rs", "fine").await;
+    assert!(response.allowed, "{:?}", response.error);
+
+    handle.abort();
+}
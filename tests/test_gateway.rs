@@ -10,7 +10,13 @@ fn test_request_serialization() {
         request_id: "req-001".to_string(),
         action: Action::Write,
         target: "src/main.rs".to_string(),
+        additional_targets: vec![],
+        method: None,
+        upload_bytes: None,
         payload: Some("fn main() {}".to_string()),
+        client_version: None,
+        shell: None,
+        recursive: false,
     };
 
     let json = serde_json::to_string(&request).unwrap();
@@ -48,19 +54,78 @@ fn test_response_denied() {
 fn test_all_action_types_serialize() {
     for action in &[
         Action::Write,
+        Action::Read,
         Action::Delete,
+        Action::Move,
+        Action::Chmod,
         Action::RunCmd,
         Action::GitPush,
         Action::Network,
+        Action::EnvAccess,
+        Action::PackageInstall,
     ] {
         let request = GatewayRequest {
             request_id: "test".to_string(),
             action: action.clone(),
             target: "test".to_string(),
+            additional_targets: vec![],
+            method: None,
+            upload_bytes: None,
             payload: None,
+            client_version: None,
+            shell: None,
+            recursive: false,
         };
         let json = serde_json::to_string(&request).unwrap();
         let parsed: GatewayRequest = serde_json::from_str(&json).unwrap();
         assert_eq!(&parsed.action, action);
     }
 }
+
+#[test]
+fn test_multi_target_request_roundtrip() {
+    // Older clients omit additional_targets entirely
+    let json = r#"{"request_id":"r","action":"delete","target":"a.txt"}"#;
+    let parsed: GatewayRequest = serde_json::from_str(json).unwrap();
+    assert!(parsed.additional_targets.is_empty());
+
+    let request = GatewayRequest {
+        request_id: "r".to_string(),
+        action: Action::Delete,
+        target: "a.txt".to_string(),
+        additional_targets: vec!["b.txt".to_string(), "c.txt".to_string()],
+        method: None,
+        upload_bytes: None,
+        payload: None,
+        client_version: None,
+        shell: None,
+        recursive: false,
+    };
+    let json = serde_json::to_string(&request).unwrap();
+    let parsed: GatewayRequest = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.additional_targets, vec!["b.txt", "c.txt"]);
+}
+
+#[test]
+fn test_rejected_requests_say_why() {
+    let ok = GatewayRequest::parse(r#"{"request_id":"r","action":"write","target":"a.txt"}"#);
+    assert_eq!(ok.unwrap().action, Action::Write);
+
+    let malformed = GatewayRequest::parse("{not json").unwrap_err();
+    assert!(malformed.reason.starts_with("Malformed JSON"));
+    assert!(malformed.request_id.is_none());
+
+    for action in ["teleport", "rejected_request"] {
+        let line = format!(
+            r#"{{"request_id":"r2","action":"{}","target":"x"}}"#,
+            action
+        );
+        let unknown = GatewayRequest::parse(&line).unwrap_err();
+        assert_eq!(unknown.reason, format!("Unknown action '{}'", action));
+        assert_eq!(unknown.request_id.as_deref(), Some("r2"));
+    }
+
+    let missing = GatewayRequest::parse(r#"{"request_id":"r3","action":"write"}"#).unwrap_err();
+    assert!(missing.reason.starts_with("Invalid request"));
+    assert!(missing.reason.contains("target"));
+}
@@ -1,7 +1,7 @@
 //! Integration tests for the policy engine.
 //! Tests the full flow: YAML parsing → engine creation → evaluation.
 
-use lawctl::policy::{parser, Action, ActionContext, Decision, PolicyEngine};
+use lawctl::policy::{parser, Action, ActionContext, PolicyEngine};
 
 /// Helper: load the test fixture policy and create an engine.
 fn test_engine() -> PolicyEngine {
@@ -47,6 +47,23 @@ fn test_secrets_protection() {
     }
 }
 
+#[test]
+fn test_secret_reads_denied() {
+    let engine = test_engine();
+
+    for path in &[".env", "config/production.env", ".ssh/id_rsa"] {
+        let ctx = ActionContext::new(*path);
+        let decision = engine.evaluate(&Action::Read, &ctx);
+        assert!(decision.is_denied(), "Reading {} should be denied", path);
+    }
+    // Reads aren't destructive: anything else is allowed by default
+    let ctx = ActionContext::new("server.pem");
+    assert!(engine.evaluate(&Action::Read, &ctx).is_allowed());
+    // `cat README.md .env` is denied as a whole
+    let ctx = ActionContext::with_targets(["README.md", ".env"]);
+    assert!(engine.evaluate(&Action::Read, &ctx).is_denied());
+}
+
 #[test]
 fn test_safe_writes_allowed() {
     let engine = test_engine();
@@ -161,3 +178,43 @@ fn test_policy_evaluation_speed() {
         elapsed
     );
 }
+
+#[test]
+fn test_large_command_denylist() {
+    // A denylist of hundreds of if_matches patterns is compiled into one
+    // matcher; it must agree with matching each pattern on its own.
+    let patterns: Vec<String> = (0..500)
+        .map(|i| format!("dangerous-tool-{} * --force", i))
+        .collect();
+    let mut yaml = String::from("law: big-denylist\nrules:\n  - deny: run_cmd\n    if_matches:\n");
+    for p in &patterns {
+        yaml.push_str(&format!("      - \"{}\"\n", p));
+    }
+    yaml.push_str("  - allow: run_cmd\n");
+
+    let policy = parser::parse_policy_str(&yaml).expect("big denylist should parse");
+    let engine = PolicyEngine::new(policy).expect("big denylist should compile");
+
+    for cmd in [
+        "cargo build --release --all-features",
+        "dangerous-tool-0 wipe --force",
+        "dangerous-tool-250 wipe everything --force",
+        "dangerous-tool-499 x --force",
+        "dangerous-tool-500 x --force",
+        "dangerous-tool-7 x",
+    ] {
+        let ctx = ActionContext::new("shell").with_command(cmd);
+        assert_eq!(
+            engine.evaluate(&Action::RunCmd, &ctx).is_denied(),
+            lawctl::utils::paths::command_matches(cmd, &patterns),
+            "compiled denylist disagrees with per-pattern matching on '{}'",
+            cmd
+        );
+    }
+    assert!(engine
+        .evaluate(
+            &Action::RunCmd,
+            &ActionContext::new("shell").with_command("dangerous-tool-499 x --force")
+        )
+        .is_denied());
+}